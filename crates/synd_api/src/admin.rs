@@ -0,0 +1,655 @@
+//! Administrative operations on the repository such as backup, restore and migration
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{Category, FeedUrl, Requirement};
+
+use crate::{
+    args::{AdminCommand, RepositoryOptions},
+    dependency::Repositories,
+    repository::{
+        types::{
+            ApiToken, EntryReference, FeedSubscription, Folder, FolderId, ReadStates, StarredEntry,
+            Webhook,
+        },
+        RepositoryError,
+    },
+    serve::auth::hash_password,
+};
+
+/// All data of the user. Exported as a line of JSON lines
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UserRecord {
+    pub user_id: String,
+    /// Subscribed feeds in the order defined by the user
+    pub subscriptions: Vec<SubscriptionRecord>,
+    /// Folders in the order of creation
+    #[serde(default)]
+    pub folders: Vec<Folder>,
+    #[serde(default)]
+    pub read_states: ReadStates,
+    /// Starred entries, most recently starred first
+    #[serde(default)]
+    pub starred_entries: Vec<StarredEntry>,
+    /// Api tokens in the order of creation. Only the hashes of the secrets are included
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_tokens: Vec<ApiToken>,
+    /// Webhooks in the order of creation. Delivery logs are not included
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+pub struct SubscriptionRecord {
+    pub url: FeedUrl,
+    pub requirement: Option<Requirement>,
+    pub category: Option<Category<'static>>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub note: Option<String>,
+    #[serde(default)]
+    pub tags: BTreeSet<Category<'static>>,
+    /// Id of the folder in [`UserRecord::folders`]
+    #[serde(default)]
+    pub folder: Option<FolderId>,
+}
+
+impl UserRecord {
+    /// Return true if the user has no data in the repository
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+            && self.folders.is_empty()
+            && self.read_states.feeds.is_empty()
+            && self.starred_entries.is_empty()
+            && self.api_tokens.is_empty()
+            && self.webhooks.is_empty()
+    }
+
+    pub async fn export(
+        repositories: &Repositories,
+        user_id: String,
+    ) -> Result<Self, RepositoryError> {
+        let mut feeds = repositories
+            .subscription
+            .fetch_subscribed_feeds(&user_id)
+            .await?;
+        let subscriptions = feeds
+            .urls
+            .into_iter()
+            .map(|url| {
+                let annotations = feeds
+                    .annotations
+                    .as_mut()
+                    .and_then(|m| m.remove(&url))
+                    .unwrap_or_default();
+                SubscriptionRecord {
+                    folder: feeds.feed_folders.remove(&url),
+                    url,
+                    requirement: annotations.requirement,
+                    category: annotations.category,
+                    title: annotations.title,
+                    note: annotations.note,
+                    tags: annotations.tags,
+                }
+            })
+            .collect();
+        let read_states = repositories.read_state.fetch_read_states(&user_id).await?;
+        let starred_entries = repositories
+            .starred_entry
+            .fetch_starred_entries(&user_id)
+            .await?;
+        let api_tokens = repositories.api_token.fetch_api_tokens(&user_id).await?;
+        let webhooks = repositories.webhook.fetch_webhooks(&user_id).await?;
+
+        Ok(Self {
+            user_id,
+            subscriptions,
+            folders: feeds.folders,
+            read_states,
+            starred_entries,
+            api_tokens,
+            webhooks,
+        })
+    }
+
+    /// Create the folders and return the ids assigned by the repository keyed by the ids in the record.
+    /// Existing folders are reused so that importing the same record again does not duplicate them
+    async fn import_folders(
+        repositories: &Repositories,
+        user_id: &str,
+        folders: Vec<Folder>,
+    ) -> Result<HashMap<FolderId, FolderId>, RepositoryError> {
+        let existing = repositories
+            .subscription
+            .fetch_subscribed_feeds(user_id)
+            .await?
+            .folders;
+        let mut folder_ids = HashMap::with_capacity(folders.len());
+        for Folder { id, name, parent } in folders {
+            // Parent is always created before its children
+            let parent = parent.and_then(|parent| folder_ids.get(&parent).copied());
+            let folder_id = match existing
+                .iter()
+                .find(|folder| folder.name == name && folder.parent == parent)
+            {
+                Some(folder) => folder.id,
+                None => {
+                    repositories
+                        .subscription
+                        .create_folder(user_id, name, parent)
+                        .await?
+                        .id
+                }
+            };
+            folder_ids.insert(id, folder_id);
+        }
+        Ok(folder_ids)
+    }
+
+    /// Write the record to the repository.
+    /// Existing data of the user is merged. Folders which have the same name and parent are reused
+    pub async fn import(self, repositories: &Repositories) -> Result<(), RepositoryError> {
+        let UserRecord {
+            user_id,
+            subscriptions,
+            folders,
+            read_states,
+            starred_entries,
+            api_tokens,
+            webhooks,
+        } = self;
+
+        let folder_ids = Self::import_folders(repositories, &user_id, folders).await?;
+
+        // Repositories put newer one first, so oldest one should be written first
+        let mut moves = Vec::new();
+        for SubscriptionRecord {
+            url,
+            requirement,
+            category,
+            title,
+            note,
+            tags,
+            folder,
+        } in subscriptions.into_iter().rev()
+        {
+            if let Some(folder_id) = folder.and_then(|folder| folder_ids.get(&folder).copied()) {
+                moves.push((url.clone(), folder_id));
+            }
+            repositories
+                .subscription
+                .put_feed_subscription(FeedSubscription {
+                    user_id: user_id.clone(),
+                    url,
+                    requirement,
+                    category,
+                    title,
+                    note,
+                    tags,
+                })
+                .await?;
+        }
+        // Appending to the folder in order keeps the order in the folder
+        for (url, folder_id) in moves.into_iter().rev() {
+            repositories
+                .subscription
+                .move_feed(&user_id, url, Some(folder_id), None)
+                .await?;
+        }
+
+        for (feed_url, read_state) in read_states.feeds {
+            repositories
+                .read_state
+                .mark_feed_read(
+                    &user_id,
+                    feed_url.clone(),
+                    read_state.read_until,
+                    read_state.read.into_iter().collect(),
+                )
+                .await?;
+            if !read_state.unread.is_empty() {
+                let unread = read_state
+                    .unread
+                    .into_iter()
+                    .map(|entry_id| EntryReference {
+                        feed_url: feed_url.clone(),
+                        entry_id,
+                    })
+                    .collect();
+                repositories
+                    .read_state
+                    .mark_entries(&user_id, unread, false)
+                    .await?;
+            }
+        }
+
+        for entry in starred_entries.into_iter().rev() {
+            repositories
+                .starred_entry
+                .star_entry(&user_id, entry)
+                .await?;
+        }
+
+        // Tokens already imported are kept as is
+        let existing = repositories.api_token.fetch_api_tokens(&user_id).await?;
+        for token in api_tokens {
+            if existing.iter().all(|existing| existing.id != token.id) {
+                repositories.api_token.put_api_token(token).await?;
+            }
+        }
+
+        let existing = repositories.webhook.fetch_webhooks(&user_id).await?;
+        for mut webhook in webhooks {
+            if existing.iter().all(|existing| existing.id != webhook.id) {
+                user_id.clone_into(&mut webhook.user_id);
+                repositories.webhook.put_webhook(webhook).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write all users' data as JSON lines and return the number of users
+pub async fn export(repositories: &Repositories, mut writer: impl Write) -> anyhow::Result<usize> {
+    let user_ids = repositories.subscription.fetch_user_ids().await?;
+    let users = user_ids.len();
+
+    for user_id in user_ids {
+        let record = UserRecord::export(repositories, user_id).await?;
+        serde_json::to_writer(&mut writer, &record)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+
+    Ok(users)
+}
+
+/// Read JSON lines written by [`export`] and return the number of users
+pub async fn import(repositories: &Repositories, reader: impl BufRead) -> anyhow::Result<usize> {
+    let mut users = 0;
+
+    for (line_number, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: UserRecord = serde_json::from_str(&line)
+            .with_context(|| format!("invalid record at line {}", line_number + 1))?;
+        record.import(repositories).await?;
+        users += 1;
+    }
+
+    Ok(users)
+}
+
+/// Copy all users' data and return the number of users
+pub async fn migrate(from: &Repositories, to: &Repositories) -> anyhow::Result<usize> {
+    let user_ids = from.subscription.fetch_user_ids().await?;
+    let users = user_ids.len();
+
+    for user_id in user_ids {
+        UserRecord::export(from, user_id).await?.import(to).await?;
+    }
+
+    Ok(users)
+}
+
+/// Data of the user moved by [`move_user`]
+#[derive(Debug)]
+pub struct MovedUser {
+    /// Names of the api tokens deleted instead of moved, since their plaintext contains the user id.
+    /// They include the tokens used for syndication feed urls
+    pub revoked_api_tokens: Vec<String>,
+}
+
+/// Move all data of the user to another user id. Return None if there is no data to move
+pub async fn move_user(
+    repositories: &Repositories,
+    from: &str,
+    to: &str,
+) -> Result<Option<MovedUser>, RepositoryError> {
+    let mut record = UserRecord::export(repositories, from.to_owned()).await?;
+    if record.is_empty() {
+        return Ok(None);
+    }
+    to.clone_into(&mut record.user_id);
+    let revoked_api_tokens = std::mem::take(&mut record.api_tokens)
+        .into_iter()
+        .map(|token| token.name)
+        .collect();
+    record.import(repositories).await?;
+
+    repositories.api_token.delete_api_tokens(from).await?;
+    repositories.webhook.delete_webhooks(from).await?;
+    repositories
+        .starred_entry
+        .delete_starred_entries(from)
+        .await?;
+    repositories.read_state.delete_read_states(from).await?;
+    repositories.subscription.delete_subscriptions(from).await?;
+
+    Ok(Some(MovedUser { revoked_api_tokens }))
+}
+
+pub async fn run(command: AdminCommand) -> anyhow::Result<()> {
+    match command {
+        AdminCommand::Export {
+            repository: RepositoryOptions { repository },
+            kvsd,
+            output,
+        } => {
+            let repositories = Repositories::connect(repository, kvsd).await?;
+            let users = match output {
+                Some(path) => {
+                    let file = File::create(&path)
+                        .with_context(|| format!("create {}", path.display()))?;
+                    export(&repositories, BufWriter::new(file)).await?
+                }
+                None => export(&repositories, io::stdout().lock()).await?,
+            };
+            tracing::info!(users, "Exported");
+        }
+        AdminCommand::Import {
+            repository: RepositoryOptions { repository },
+            kvsd,
+            input,
+        } => {
+            let repositories = Repositories::connect(repository, kvsd).await?;
+            let users = match input {
+                Some(path) => {
+                    let file =
+                        File::open(&path).with_context(|| format!("open {}", path.display()))?;
+                    import(&repositories, BufReader::new(file)).await?
+                }
+                None => import(&repositories, io::stdin().lock()).await?,
+            };
+            tracing::info!(users, "Imported");
+        }
+        AdminCommand::Migrate { from, to, kvsd } => {
+            anyhow::ensure!(from != to, "source and destination repository are same");
+            let source = Repositories::connect(from, kvsd.clone()).await?;
+            let destination = Repositories::connect(to, kvsd).await?;
+            let users = migrate(&source, &destination).await?;
+            tracing::info!(users, "Migrated");
+        }
+        AdminCommand::HashPassword => {
+            let mut password = String::new();
+            io::stdin()
+                .read_line(&mut password)
+                .context("read password")?;
+            let password = password.trim_end_matches(['\r', '\n']);
+            anyhow::ensure!(!password.is_empty(), "password must not be empty");
+            println!("{}", hash_password(password)?);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::repository::{
+        memory::MemoryRepository, sqlite::SqliteRepository, types::ApiTokenScope,
+    };
+
+    use super::*;
+
+    fn url(s: &str) -> FeedUrl {
+        FeedUrl::try_from(s).unwrap()
+    }
+
+    async fn seed(repositories: &Repositories) {
+        for (user_id, feed) in [
+            ("alice", "https://a.ymgyt.io/feed"),
+            ("alice", "https://b.ymgyt.io/feed"),
+            ("bob", "https://a.ymgyt.io/feed"),
+        ] {
+            repositories
+                .subscription
+                .put_feed_subscription(FeedSubscription {
+                    user_id: user_id.into(),
+                    url: url(feed),
+                    requirement: Some(Requirement::Should),
+                    category: Some(Category::new("rust").unwrap()),
+                    title: Some(format!("{user_id}'s feed")),
+                    note: None,
+                    tags: BTreeSet::from([Category::new("blog").unwrap()]),
+                })
+                .await
+                .unwrap();
+        }
+        let blogs = repositories
+            .subscription
+            .create_folder("alice", "blogs".into(), None)
+            .await
+            .unwrap();
+        let rust = repositories
+            .subscription
+            .create_folder("alice", "rust".into(), Some(blogs.id))
+            .await
+            .unwrap();
+        repositories
+            .subscription
+            .move_feed("alice", url("https://a.ymgyt.io/feed"), Some(rust.id), None)
+            .await
+            .unwrap();
+        repositories
+            .read_state
+            .mark_feed_read(
+                "alice",
+                url("https://a.ymgyt.io/feed"),
+                Some(Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap()),
+                vec!["1".into()],
+            )
+            .await
+            .unwrap();
+        repositories
+            .read_state
+            .mark_entries(
+                "alice",
+                vec![EntryReference {
+                    feed_url: url("https://a.ymgyt.io/feed"),
+                    entry_id: "2".into(),
+                }],
+                false,
+            )
+            .await
+            .unwrap();
+        for id in ["1", "2"] {
+            repositories
+                .starred_entry
+                .star_entry(
+                    "bob",
+                    StarredEntry {
+                        feed_url: url("https://a.ymgyt.io/feed"),
+                        entry_id: id.into(),
+                        title: Some(format!("entry {id}")),
+                        website_url: None,
+                        summary: None,
+                        published: None,
+                        updated: None,
+                        feed_title: None,
+                        requirement: None,
+                        category: None,
+                        starred_at: Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap(),
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let (token, _) = ApiToken::issue(
+            "bob",
+            "ci",
+            ApiTokenScope::ReadOnly,
+            Utc.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap(),
+            None,
+        );
+        repositories.api_token.put_api_token(token).await.unwrap();
+    }
+
+    async fn dump(repositories: &Repositories) -> String {
+        let mut buf = Vec::new();
+        export(repositories, &mut buf).await.unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[tokio::test]
+    async fn export_then_import() {
+        let source = Repositories::new(MemoryRepository::new());
+        seed(&source).await;
+
+        let exported = dump(&source).await;
+        assert_eq!(exported.lines().count(), 2);
+
+        let destination = Repositories::new(MemoryRepository::new());
+        let users = import(&destination, exported.as_bytes()).await.unwrap();
+        assert_eq!(users, 2);
+
+        let alice = UserRecord::export(&destination, "alice".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            alice
+                .subscriptions
+                .iter()
+                .map(|s| s.url.as_str())
+                .collect::<Vec<_>>(),
+            vec!["https://b.ymgyt.io/feed", "https://a.ymgyt.io/feed"],
+        );
+        assert_eq!(
+            alice.subscriptions[0].requirement,
+            Some(Requirement::Should)
+        );
+        assert_eq!(
+            alice
+                .folders
+                .iter()
+                .map(|f| (f.name.as_str(), f.parent))
+                .collect::<Vec<_>>(),
+            vec![("blogs", None), ("rust", Some(alice.folders[0].id))],
+        );
+        assert_eq!(alice.subscriptions[0].folder, None);
+        assert_eq!(alice.subscriptions[1].folder, Some(alice.folders[1].id));
+        let read_state = alice
+            .read_states
+            .feed(&url("https://a.ymgyt.io/feed"))
+            .unwrap();
+        assert!(read_state.read.contains("1"));
+        assert!(read_state.unread.contains("2"));
+        assert!(read_state.read_until.is_some());
+
+        let bob = UserRecord::export(&destination, "bob".into())
+            .await
+            .unwrap();
+        assert_eq!(
+            bob.starred_entries
+                .iter()
+                .map(|e| e.entry_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2", "1"],
+        );
+        assert_eq!(bob.api_tokens.len(), 1);
+
+        // Importing again does not duplicate the tokens
+        import(&destination, exported.as_bytes()).await.unwrap();
+        let bob = UserRecord::export(&destination, "bob".into())
+            .await
+            .unwrap();
+        assert_eq!(bob.api_tokens.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn import_invalid_record() {
+        let repositories = Repositories::new(MemoryRepository::new());
+        let err = import(&repositories, "{}\n".as_bytes()).await.unwrap_err();
+        assert!(err.to_string().contains("line 1"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn move_user_data() {
+        let repositories = Repositories::new(MemoryRepository::new());
+        seed(&repositories).await;
+        let expected = UserRecord::export(&repositories, "alice".into())
+            .await
+            .unwrap();
+
+        assert!(move_user(&repositories, "alice", "carol")
+            .await
+            .unwrap()
+            .is_some());
+        assert!(UserRecord::export(&repositories, "alice".into())
+            .await
+            .unwrap()
+            .is_empty());
+        let carol = UserRecord::export(&repositories, "carol".into())
+            .await
+            .unwrap();
+        assert_eq!(carol.subscriptions, expected.subscriptions);
+        assert_eq!(carol.folders.len(), expected.folders.len());
+        assert!(carol
+            .read_states
+            .feed(&url("https://a.ymgyt.io/feed"))
+            .is_some());
+
+        // Tokens are revoked since they contain the old user id
+        let moved = move_user(&repositories, "bob", "dave")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(moved.revoked_api_tokens, vec!["ci".to_owned()]);
+        let dave = UserRecord::export(&repositories, "dave".into())
+            .await
+            .unwrap();
+        assert_eq!(dave.starred_entries.len(), 2);
+        assert!(dave.api_tokens.is_empty());
+        assert!(repositories
+            .api_token
+            .fetch_api_tokens("bob")
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Nothing to move
+        assert!(move_user(&repositories, "alice", "carol")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn migrate_memory_to_sqlite() {
+        let source = Repositories::new(MemoryRepository::new());
+        seed(&source).await;
+        let destination = Repositories::new(SqliteRepository::in_memory().await.unwrap());
+
+        let users = migrate(&source, &destination).await.unwrap();
+        assert_eq!(users, 2);
+
+        // Read states are exported from HashMap, so compare each record instead of whole dump
+        for user_id in ["alice", "bob"] {
+            let expected = UserRecord::export(&source, user_id.into()).await.unwrap();
+            let actual = UserRecord::export(&destination, user_id.into())
+                .await
+                .unwrap();
+            assert_eq!(actual.subscriptions, expected.subscriptions);
+            assert_eq!(actual.folders, expected.folders);
+            assert_eq!(actual.starred_entries.len(), expected.starred_entries.len());
+            assert_eq!(actual.api_tokens, expected.api_tokens);
+            for (feed_url, state) in expected.read_states.feeds {
+                let migrated = actual.read_states.feed(&feed_url).unwrap();
+                assert_eq!(migrated.read_until, state.read_until);
+                assert_eq!(migrated.read, state.read);
+                assert_eq!(migrated.unread, state.unread);
+            }
+        }
+    }
+}
@@ -8,33 +8,135 @@ use crate::{
 };
 
 #[derive(Parser, Debug)]
-#[command(version, propagate_version = true, disable_help_subcommand = true)]
+#[command(
+    version,
+    propagate_version = true,
+    disable_help_subcommand = true,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    #[command(flatten)]
+    pub repository: RepositoryOptions,
     #[command(flatten)]
     pub kvsd: KvsdOptions,
     #[command(flatten)]
     pub bind: BindOptions,
     #[command(flatten)]
     pub serve: ServeOptions,
+    /// Always present unless subcommand is given
     #[command(flatten)]
-    pub tls: TlsOptions,
+    pub tls: Option<TlsOptions>,
     #[command(flatten)]
     pub o11y: ObservabilityOptions,
     #[command(flatten)]
     pub cache: CacheOptions,
+    #[command(flatten)]
+    pub syndication: SyndicationOptions,
+    #[command(flatten)]
+    pub auth: AuthOptions,
+    #[command(flatten)]
+    pub quota: QuotaOptions,
+    #[command(flatten)]
+    pub feed: FeedOptions,
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Administrative operations on the repository
+    #[command(subcommand)]
+    Admin(AdminCommand),
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum AdminCommand {
+    /// Export all users' data as JSON lines
+    Export {
+        #[command(flatten)]
+        repository: RepositoryOptions,
+        #[command(flatten)]
+        kvsd: KvsdOptions,
+        /// Output file path. stdout if not specified
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Import users' data from JSON lines which is exported by `admin export`
+    Import {
+        #[command(flatten)]
+        repository: RepositoryOptions,
+        #[command(flatten)]
+        kvsd: KvsdOptions,
+        /// Input file path. stdin if not specified
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Copy all users' data from one repository to another
+    Migrate {
+        /// Source repository
+        #[arg(long)]
+        from: Repository,
+        /// Destination repository
+        #[arg(long)]
+        to: Repository,
+        #[command(flatten)]
+        kvsd: KvsdOptions,
+    },
+    /// Hash the password read from stdin for the local users file
+    HashPassword,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+#[command(next_help_heading = "Repository options")]
+pub struct RepositoryOptions {
+    /// Repository to persist subscriptions. `kvsd`, `sqlite:///path/to/synd.db` or `memory`
+    #[arg(long, default_value = "kvsd", env = env_key!("REPOSITORY"))]
+    pub repository: Repository,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Repository {
+    Kvsd,
+    Sqlite {
+        path: PathBuf,
+    },
+    /// Data is lost when the process exits
+    Memory,
 }
 
+impl FromStr for Repository {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "kvsd" => Ok(Repository::Kvsd),
+            "memory" => Ok(Repository::Memory),
+            _ => match s.strip_prefix("sqlite://") {
+                Some(path) if !path.is_empty() => Ok(Repository::Sqlite { path: path.into() }),
+                _ => Err(format!(
+                    "invalid repository `{s}`, should be `kvsd`, `sqlite:///path/to/db` or `memory`"
+                )),
+            },
+        }
+    }
+}
+
+/// Kvsd options are required only when kvsd repository is selected
 #[derive(clap::Args, Debug, Clone)]
 #[command(next_help_heading = "Kvsd options")]
 pub struct KvsdOptions {
     #[arg(long = "kvsd-host", env = env_key!("KVSD_HOST"))]
-    pub kvsd_host: String,
+    pub kvsd_host: Option<String>,
     #[arg(long = "kvsd-port", env = env_key!("KVSD_PORT"))]
-    pub kvsd_port: u16,
+    pub kvsd_port: Option<u16>,
     #[arg(long = "kvsd-username", alias = "kvsd-user", env = env_key!("KVSD_USER"))]
-    pub kvsd_username: String,
+    pub kvsd_username: Option<String>,
     #[arg(long = "kvsd-password", alias = "kvsd-pass", env = env_key!("KVSD_PASS"))]
-    pub kvsd_password: String,
+    pub kvsd_password: Option<String>,
+    /// Max number of connections to kvsd
+    #[arg(long = "kvsd-pool-size", default_value_t = config::kvsd::DEFAULT_POOL_SIZE, env = env_key!("KVSD_POOL_SIZE"))]
+    pub kvsd_pool_size: usize,
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -57,6 +159,12 @@ pub struct ServeOptions {
     pub body_limit_bytes: usize,
     #[arg(long, default_value_t = config::serve::DEFAULT_REQUEST_CONCURRENCY_LIMIT)]
     pub concurrency_limit: usize,
+    /// Requests which each user can send in a burst
+    #[arg(long, default_value_t = config::serve::DEFAULT_RATE_LIMIT_BURST, env = env_key!("RATE_LIMIT_BURST"))]
+    pub rate_limit_burst: u32,
+    /// Requests per second which each user can send continuously. 0 disables the rate limit
+    #[arg(long, default_value_t = config::serve::DEFAULT_RATE_LIMIT_PER_SECOND, env = env_key!("RATE_LIMIT_PER_SECOND"))]
+    pub rate_limit_per_second: u32,
 }
 
 #[derive(clap::Args, Debug)]
@@ -102,6 +210,50 @@ pub struct CacheOptions {
     pub feed_cache_refresh_interval: Duration,
 }
 
+#[derive(clap::Args, Debug, Clone)]
+#[command(next_help_heading = "Syndication options")]
+pub struct SyndicationOptions {
+    /// Serve subscribed entries as feeds at `/syndication/{token}` authenticated by read-only personal access tokens
+    #[arg(long, default_value_t = false, env = env_key!("SYNDICATION"))]
+    pub syndication: bool,
+    /// Serve Google Reader compatible api for feed readers authenticated by personal access tokens
+    #[arg(long, default_value_t = false, env = env_key!("READER_API"))]
+    pub reader_api: bool,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+#[command(next_help_heading = "Auth options")]
+pub struct AuthOptions {
+    /// Local users file(toml) to enable `local` authentication without github or google
+    #[arg(long, env = env_key!("AUTH_LOCAL_USERS"), value_name = "PATH")]
+    pub auth_local_users: Option<PathBuf>,
+    /// `OpenID` Connect issuer url to enable `oidc` authentication(e.g. Keycloak, Dex)
+    #[arg(long, env = env_key!("AUTH_OIDC_ISSUER"), value_name = "URL", requires = "auth_oidc_client_id")]
+    pub auth_oidc_issuer: Option<String>,
+    /// `OpenID` Connect client id which is expected as the audience of id tokens
+    #[arg(long, env = env_key!("AUTH_OIDC_CLIENT_ID"), requires = "auth_oidc_issuer")]
+    pub auth_oidc_client_id: Option<String>,
+    /// Comma separated user ids or emails of the users who can run admin queries
+    #[arg(long, env = env_key!("AUTH_ADMINS"), value_name = "USER", value_delimiter = ',')]
+    pub auth_admins: Vec<String>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+#[command(next_help_heading = "Quota options")]
+pub struct QuotaOptions {
+    /// Max number of feeds which each user can subscribe
+    #[arg(long, default_value_t = config::quota::DEFAULT_MAX_SUBSCRIPTIONS, env = env_key!("MAX_SUBSCRIPTIONS_PER_USER"))]
+    pub max_subscriptions_per_user: usize,
+}
+
+#[derive(clap::Args, Debug, Clone)]
+#[command(next_help_heading = "Feed options")]
+pub struct FeedOptions {
+    /// Check robots.txt of the feed origin before fetching
+    #[arg(long, env = env_key!("FEED_RESPECT_ROBOTS_TXT"), default_value_t = false, action = ArgAction::Set)]
+    pub feed_respect_robots_txt: bool,
+}
+
 pub fn try_parse<I, T>(iter: I) -> Result<Args, clap::Error>
 where
     I: IntoIterator<Item = T>,
@@ -122,12 +274,18 @@ impl From<ServeOptions> for serve::ServeOptions {
             timeout,
             body_limit_bytes,
             concurrency_limit,
+            rate_limit_burst,
+            rate_limit_per_second,
         }: ServeOptions,
     ) -> Self {
         Self {
             timeout,
             body_limit_bytes,
             concurrency_limit,
+            rate_limit: (rate_limit_per_second > 0).then_some(serve::RateLimitOptions {
+                burst: rate_limit_burst,
+                per_second: rate_limit_per_second,
+            }),
         }
     }
 }
@@ -160,4 +318,72 @@ mod tests {
         let _ = serve::ServeOptions::from(args.serve.clone());
         insta::assert_debug_snapshot!(args);
     }
+
+    #[test]
+    fn parse_admin_command() {
+        let args = try_parse([
+            "synd-api",
+            "admin",
+            "migrate",
+            "--from=kvsd",
+            "--to=sqlite:///tmp/synd.db",
+            "--kvsd-host=foo",
+            "--kvsd-port=3000",
+            "--kvsd-username=me",
+            "--kvsd-password=secret",
+        ])
+        .unwrap();
+        let Some(Command::Admin(AdminCommand::Migrate { from, to, kvsd })) = args.command else {
+            panic!("unexpected command: {:?}", args.command);
+        };
+        assert_eq!(from, Repository::Kvsd);
+        assert_eq!(
+            to,
+            Repository::Sqlite {
+                path: "/tmp/synd.db".into()
+            }
+        );
+        assert_eq!(kvsd.kvsd_port, Some(3000));
+        assert!(args.tls.is_none());
+
+        let args = try_parse(["synd-api", "admin", "export", "--repository=memory"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Admin(AdminCommand::Export { .. }))
+        ));
+
+        let args = try_parse(["synd-api", "admin", "hash-password"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Command::Admin(AdminCommand::HashPassword))
+        ));
+
+        // tls options are still required to serve
+        assert_eq!(
+            try_parse(["synd-api", "--repository=memory"])
+                .unwrap_err()
+                .kind(),
+            clap::error::ErrorKind::MissingRequiredArgument,
+        );
+    }
+
+    #[test]
+    fn parse_repository() {
+        assert_eq!("kvsd".parse::<Repository>(), Ok(Repository::Kvsd));
+        assert_eq!("memory".parse::<Repository>(), Ok(Repository::Memory));
+        assert_eq!(
+            "sqlite:///var/lib/synd/synd.db".parse::<Repository>(),
+            Ok(Repository::Sqlite {
+                path: "/var/lib/synd/synd.db".into()
+            })
+        );
+        assert_eq!(
+            "sqlite://synd.db".parse::<Repository>(),
+            Ok(Repository::Sqlite {
+                path: "synd.db".into()
+            })
+        );
+        assert!("sqlite://".parse::<Repository>().is_err());
+        assert!("postgres://localhost".parse::<Repository>().is_err());
+    }
 }
@@ -18,10 +18,76 @@ pub mod serve {
     pub const DEFAULT_ADDR: &str = "127.0.0.1";
     pub const DEFAULT_PORT: u16 = 5959;
     pub const DEFAULT_REQUEST_TIMEOUT: &str = "30s";
-    pub const DEFAULT_REQUEST_BODY_LIMIT_BYTES: usize = 1024 * 2;
+    pub const DEFAULT_REQUEST_BODY_LIMIT_BYTES: usize = 1024 * 4;
     pub const DEFAULT_REQUEST_CONCURRENCY_LIMIT: usize = 100;
+    /// Requests which each user can send in a burst
+    pub const DEFAULT_RATE_LIMIT_BURST: u32 = 60;
+    /// Requests per second which each user can send continuously
+    pub const DEFAULT_RATE_LIMIT_PER_SECOND: u32 = 10;
 
     pub const HEALTH_CHECK_PATH: &str = "/health";
+    pub const GRAPHQL_WS_PATH: &str = "/graphql/ws";
+}
+
+pub mod auth {
+    use std::time::Duration;
+
+    /// Login sessions of a local user kept at the same time. The oldest one is revoked on login
+    pub const MAX_SESSIONS_PER_USER: usize = 5;
+    /// Logins of the username are rejected without verifying the password after these failures
+    pub const MAX_FAILED_LOGINS: u32 = 5;
+    /// Failures are forgotten when the username has not failed for this duration
+    pub const FAILED_LOGIN_WINDOW: Duration = Duration::from_secs(15 * 60);
+    /// Usernames whose failures are tracked at the same time
+    pub const FAILED_LOGIN_CAPACITY: u64 = 10_000;
+    /// Argon2 verifications which run at the same time
+    pub const MAX_CONCURRENT_PASSWORD_VERIFICATIONS: usize = 4;
+}
+
+pub mod quota {
+    pub const DEFAULT_MAX_SUBSCRIPTIONS: usize = 1000;
+}
+
+pub mod kvsd {
+    pub const DEFAULT_POOL_SIZE: usize = 8;
+}
+
+pub mod syndication {
+    pub const PATH: &str = "/syndication/:token";
+    pub const PATH_PREFIX: &str = "/syndication/";
+    pub const DEFAULT_ENTRIES: usize = 50;
+    pub const MAX_ENTRIES: usize = 200;
+}
+
+pub mod reader {
+    pub const CLIENT_LOGIN_PATH: &str = "/accounts/ClientLogin";
+    pub const DEFAULT_ITEMS: usize = 20;
+    pub const MAX_ITEMS: usize = 1000;
+    /// Entries fetched at once while filling a page of the stream
+    pub const BATCH_ITEMS: usize = 200;
+    /// Items served recently whose entries are looked up by the item ids
+    pub const ITEM_INDEX_CAPACITY: u64 = 100_000;
+    pub const ITEM_INDEX_TIME_TO_IDLE: std::time::Duration =
+        std::time::Duration::from_secs(60 * 60 * 24);
+}
+
+pub mod webhook {
+    use std::time::Duration;
+
+    pub const MAX_WEBHOOKS_PER_USER: usize = 10;
+    /// Requests per delivery including retries
+    pub const MAX_ATTEMPTS: u32 = 3;
+    /// Backoff before the first retry. Doubled on each retry
+    pub const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+    pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+    /// Webhooks which are delivered at the same time across updates
+    pub const MAX_CONCURRENT_DELIVERIES: usize = 16;
+    /// Index of the users who have webhooks is rebuilt after this duration
+    /// to follow registrations and subscriptions
+    pub const SUBSCRIBER_INDEX_TTL: Duration = Duration::from_secs(60);
+
+    pub const DELIVERY_HEADER: &str = "x-synd-delivery";
+    pub const SIGNATURE_HEADER: &str = "x-synd-signature-256";
 }
 
 pub mod metrics {
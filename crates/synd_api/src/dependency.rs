@@ -2,51 +2,128 @@ use std::{sync::Arc, time::Duration};
 
 use anyhow::Context;
 use axum_server::tls_rustls::RustlsConfig;
+use synd_auth::jwt::oidc::JwtService as OidcJwtService;
 use synd_feed::feed::{
-    cache::{CacheConfig, CacheLayer},
+    cache::{CacheConfig, CacheLayer, SubscribeFeedUpdates},
     service::FeedService,
 };
 
 use crate::{
-    args::{self, CacheOptions, KvsdOptions, TlsOptions},
+    args::{
+        self, AuthOptions, CacheOptions, FeedOptions, KvsdOptions, QuotaOptions, Repository,
+        RepositoryOptions, SyndicationOptions, TlsOptions,
+    },
     config,
     monitor::Monitors,
-    repository::kvsd::KvsdClient,
-    serve::{auth::Authenticator, ServeOptions},
-    usecase::{authorize::Authorizer, MakeUsecase, Runtime},
+    repository::{kvsd::KvsdClient, memory::MemoryRepository, sqlite::SqliteRepository},
+    serve::{
+        auth::{
+            Admins, Authenticator, GoogleLoginBackend, LocalBackend, OidcBackend, UserIdMigration,
+        },
+        ServeOptions,
+    },
+    usecase::{
+        authorize::{Authorizer, Quota},
+        MakeUsecase, Runtime,
+    },
+    webhook::WebhookWorker,
 };
 
+/// Repositories backed by the same storage
+#[derive(Clone)]
+pub struct Repositories {
+    pub subscription: Arc<dyn crate::repository::SubscriptionRepository>,
+    pub read_state: Arc<dyn crate::repository::ReadStateRepository>,
+    pub starred_entry: Arc<dyn crate::repository::StarredEntryRepository>,
+    pub api_token: Arc<dyn crate::repository::ApiTokenRepository>,
+    pub webhook: Arc<dyn crate::repository::WebhookRepository>,
+}
+
+impl Repositories {
+    pub fn new<R>(repository: R) -> Self
+    where
+        R: crate::repository::SubscriptionRepository
+            + crate::repository::ReadStateRepository
+            + crate::repository::StarredEntryRepository
+            + crate::repository::ApiTokenRepository
+            + crate::repository::WebhookRepository
+            + 'static,
+    {
+        let repository = Arc::new(repository);
+        Self {
+            subscription: repository.clone(),
+            read_state: repository.clone(),
+            starred_entry: repository.clone(),
+            api_token: repository.clone(),
+            webhook: repository,
+        }
+    }
+
+    pub async fn connect(repository: Repository, kvsd: KvsdOptions) -> anyhow::Result<Self> {
+        match repository {
+            Repository::Kvsd => {
+                let KvsdOptions {
+                    kvsd_host: Some(kvsd_host),
+                    kvsd_port: Some(kvsd_port),
+                    kvsd_username: Some(kvsd_username),
+                    kvsd_password: Some(kvsd_password),
+                    kvsd_pool_size,
+                } = kvsd
+                else {
+                    anyhow::bail!(
+                        "kvsd options(--kvsd-host, --kvsd-port, --kvsd-username, --kvsd-password) are required for kvsd repository"
+                    );
+                };
+                let kvsd = KvsdClient::connect(
+                    kvsd_host,
+                    kvsd_port,
+                    kvsd_username,
+                    kvsd_password,
+                    kvsd_pool_size,
+                    Duration::from_secs(10),
+                )
+                .await?;
+                Ok(Self::new(kvsd))
+            }
+            Repository::Sqlite { path } => {
+                let sqlite = SqliteRepository::connect(&path).await?;
+                tracing::info!(path = %path.display(), "Sqlite repository opened");
+                Ok(Self::new(sqlite))
+            }
+            Repository::Memory => {
+                tracing::warn!("Memory repository is used, data will be lost on exit");
+                Ok(Self::new(MemoryRepository::new()))
+            }
+        }
+    }
+}
+
 pub struct Dependency {
     pub authenticator: Authenticator,
     pub runtime: Runtime,
+    /// Serve subscribed entries as feeds
+    pub syndication: bool,
+    /// Serve Google Reader compatible api
+    pub reader_api: bool,
     pub tls_config: RustlsConfig,
     pub serve_options: ServeOptions,
     pub monitors: Monitors,
 }
 
 impl Dependency {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
+        repository: RepositoryOptions,
         kvsd: KvsdOptions,
         tls: TlsOptions,
         serve_options: args::ServeOptions,
         cache: CacheOptions,
+        syndication: SyndicationOptions,
+        auth: AuthOptions,
+        quota: QuotaOptions,
+        feed: FeedOptions,
     ) -> anyhow::Result<Self> {
-        let kvsd = {
-            let KvsdOptions {
-                kvsd_host,
-                kvsd_port,
-                kvsd_username,
-                kvsd_password,
-            } = kvsd;
-            KvsdClient::connect(
-                kvsd_host,
-                kvsd_port,
-                kvsd_username,
-                kvsd_password,
-                Duration::from_secs(10),
-            )
-            .await?
-        };
+        let repositories = Repositories::connect(repository.repository, kvsd).await?;
 
         let cache_feed_service = {
             let CacheOptions {
@@ -54,7 +131,11 @@ impl Dependency {
                 feed_cache_ttl,
                 feed_cache_refresh_interval,
             } = cache;
-            let feed_service = FeedService::new(config::USER_AGENT, 10 * 1024 * 1024);
+            let FeedOptions {
+                feed_respect_robots_txt,
+            } = feed;
+            let feed_service = FeedService::new(config::USER_AGENT, 10 * 1024 * 1024)
+                .with_robots_txt(feed_respect_robots_txt);
             let cache_feed_service = CacheLayer::with(
                 feed_service,
                 CacheConfig::default()
@@ -70,12 +151,47 @@ impl Dependency {
             cache_feed_service
         };
 
+        let webhook_worker = WebhookWorker::new(&repositories).context("webhook worker")?;
+        tokio::spawn(webhook_worker.run(cache_feed_service.subscribe()));
+
+        let user_id_migration = UserIdMigration::new(repositories.clone());
+
         let make_usecase = MakeUsecase {
-            subscription_repo: Arc::new(kvsd),
-            fetch_feed: Arc::new(cache_feed_service),
+            subscription_repo: repositories.subscription,
+            read_state_repo: repositories.read_state,
+            starred_entry_repo: repositories.starred_entry,
+            api_token_repo: repositories.api_token.clone(),
+            webhook_repo: repositories.webhook.clone(),
+            fetch_feed: Arc::new(cache_feed_service.clone()),
+            feed_updates: Arc::new(cache_feed_service),
+            quota: Quota {
+                max_subscriptions: quota.max_subscriptions_per_user,
+            },
         };
 
-        let authenticator = Authenticator::new()?;
+        let mut authenticator = Authenticator::new()?
+            .with_api_tokens(repositories.api_token.clone())
+            .with_user_id_migration(user_id_migration);
+        if syndication.reader_api {
+            tracing::info!("Google Reader compatible api enabled");
+            authenticator =
+                authenticator.with_backend(GoogleLoginBackend::new(repositories.api_token));
+        }
+        if let Some(path) = auth.auth_local_users {
+            let local = LocalBackend::load(&path).context("auth options")?;
+            tracing::info!(path = %path.display(), "Local authentication enabled");
+            authenticator = authenticator.with_backend(local);
+        }
+        if let (Some(issuer), Some(client_id)) = (auth.auth_oidc_issuer, auth.auth_oidc_client_id) {
+            tracing::info!(%issuer, "OIDC authentication enabled");
+            authenticator = authenticator
+                .with_backend(OidcBackend::new(OidcJwtService::new(issuer, client_id)));
+        }
+
+        if !auth.auth_admins.is_empty() {
+            tracing::info!(admins = auth.auth_admins.len(), "Admin role enabled");
+            authenticator = authenticator.with_admins(Admins::new(auth.auth_admins));
+        }
 
         let authorizer = Authorizer::new();
 
@@ -90,6 +206,8 @@ impl Dependency {
         Ok(Dependency {
             authenticator,
             runtime,
+            syndication: syndication.syndication,
+            reader_api: syndication.reader_api,
             tls_config,
             serve_options: serve_options.into(),
             monitors,
@@ -0,0 +1,138 @@
+//! Fixtures shared by unit tests
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Write as _,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use synd_feed::{
+    feed::{
+        cache::FetchCachedFeed,
+        service::{FetchFeed, FetchFeedError, FetchFeedResult},
+    },
+    types::{Feed, FeedUrl},
+};
+
+use crate::{
+    principal::{Principal, User},
+    repository::types::FeedSubscription,
+    usecase::{authorize::Authorized, Input},
+};
+
+pub(crate) fn url(s: &str) -> FeedUrl {
+    FeedUrl::try_from(s).unwrap()
+}
+
+/// Subscription of the feed without annotations
+pub(crate) fn subscription(user_id: &str, url: &FeedUrl) -> FeedSubscription {
+    FeedSubscription {
+        user_id: user_id.into(),
+        url: url.clone(),
+        requirement: None,
+        category: None,
+        title: None,
+        note: None,
+        tags: BTreeSet::new(),
+    }
+}
+
+pub(crate) fn principal() -> Principal {
+    Principal::User(User::from_id("alice"))
+}
+
+/// Usecase input of alice which skips authorization
+pub(crate) fn input<T>(input: T) -> Input<T> {
+    Input {
+        principal: Authorized::unchecked(principal()),
+        input,
+    }
+}
+
+/// Build atom feed from (entry id, published day of June 2024). Entry titles are same as ids.
+/// Entries of day 0 have no published time
+pub(crate) fn feed(url: &FeedUrl, entries: &[(&str, u32)]) -> Feed {
+    let entries = entries.iter().fold(String::new(), |mut xml, (id, day)| {
+        let published = if *day == 0 {
+            String::new()
+        } else {
+            format!("<published>2024-06-{day:02}T00:00:00Z</published>")
+        };
+        let _ = write!(
+            xml,
+            "<entry><id>{id}</id><title>{id}</title>{published}</entry>"
+        );
+        xml
+    });
+    let atom = format!(
+        r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>test</id><title>test</title>{entries}</feed>"#
+    );
+    Feed::from((
+        url.clone(),
+        feed_rs::parser::parse(atom.as_bytes()).unwrap(),
+    ))
+}
+
+/// Serve the feeds which can be replaced between requests instead of fetching them
+#[derive(Default)]
+pub(crate) struct StaticFeeds {
+    feeds: Mutex<HashMap<FeedUrl, Arc<Feed>>>,
+    fetched: AtomicUsize,
+}
+
+impl StaticFeeds {
+    pub(crate) fn insert(&self, feed: Feed) {
+        self.feeds
+            .lock()
+            .unwrap()
+            .insert(feed.meta().url().clone(), Arc::new(feed));
+    }
+
+    /// Number of feeds fetched so far
+    pub(crate) fn fetched(&self) -> usize {
+        self.fetched.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl FetchCachedFeed for StaticFeeds {
+    async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<Feed>> {
+        self.fetched.fetch_add(1, Ordering::Relaxed);
+        self.feeds
+            .lock()
+            .unwrap()
+            .get(&url)
+            .cloned()
+            .ok_or_else(|| FetchFeedError::Other(anyhow::anyhow!("feed not found: {url}")))
+    }
+
+    async fn fetch_feeds_parallel(&self, urls: &[FeedUrl]) -> Vec<FetchFeedResult<Arc<Feed>>> {
+        let mut feeds = Vec::with_capacity(urls.len());
+        for url in urls {
+            feeds.push(FetchCachedFeed::fetch_feed(self, url.clone()).await);
+        }
+        feeds
+    }
+}
+
+/// Publish the inserted feeds so that feed updates can be caused by refreshing the cache
+#[async_trait]
+impl FetchFeed for StaticFeeds {
+    async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
+        FetchCachedFeed::fetch_feed(self, url)
+            .await
+            .map(|feed| Feed::clone(&feed))
+    }
+
+    async fn fetch_feeds_parallel(&self, urls: &[FeedUrl]) -> FetchFeedResult<Vec<Feed>> {
+        let mut feeds = Vec::with_capacity(urls.len());
+        for url in urls {
+            feeds.push(FetchFeed::fetch_feed(self, url.clone()).await?);
+        }
+        Ok(feeds)
+    }
+}
@@ -0,0 +1,126 @@
+use async_graphql::{Context, Object, Result, SimpleObject};
+use synd_feed::{
+    feed::cache,
+    types::{Category, FeedUrl, Requirement},
+};
+
+use crate::{
+    gql::run_usecase,
+    repository::types::SubscribedFeeds,
+    usecase::{
+        AdminError, FetchCacheStats, FetchCacheStatsInput, FetchCacheStatsOutput,
+        FetchUserSubscriptions, FetchUserSubscriptionsInput, FetchUserSubscriptionsOutput,
+        FetchUsers, FetchUsersInput, FetchUsersOutput, Output,
+    },
+};
+
+/// Operations for the operators to support users.
+/// Only principals with the admin role can query these fields
+pub(crate) struct Admin;
+
+#[Object]
+impl Admin {
+    /// Return ids of the users who have subscriptions
+    async fn users(&self, cx: &Context<'_>) -> Result<Vec<String>> {
+        let Output {
+            output: FetchUsersOutput { user_ids },
+        } = run_usecase!(FetchUsers, cx, FetchUsersInput {}, |err: AdminError| Err(
+            async_graphql::ErrorExtensions::extend(&err)
+        ))?;
+
+        Ok(user_ids)
+    }
+
+    /// Return the subscriptions of the user in the order defined by the user
+    async fn user_subscriptions(
+        &self,
+        cx: &Context<'_>,
+        user_id: String,
+    ) -> Result<Vec<UserSubscription>> {
+        let Output {
+            output:
+                FetchUserSubscriptionsOutput {
+                    feeds:
+                        SubscribedFeeds {
+                            urls,
+                            mut annotations,
+                            ..
+                        },
+                },
+        } = run_usecase!(
+            FetchUserSubscriptions,
+            cx,
+            FetchUserSubscriptionsInput { user_id },
+            |err: AdminError| Err(async_graphql::ErrorExtensions::extend(&err))
+        )?;
+
+        Ok(urls
+            .into_iter()
+            .map(|url| {
+                let annotations = annotations
+                    .as_mut()
+                    .and_then(|annotations| annotations.remove(&url))
+                    .unwrap_or_default();
+                UserSubscription {
+                    url,
+                    requirement: annotations.requirement,
+                    category: annotations.category,
+                    title: annotations.title,
+                    tags: annotations.tags.into_iter().collect(),
+                }
+            })
+            .collect())
+    }
+
+    /// Return the statistics of the feed cache
+    async fn cache_stats(&self, cx: &Context<'_>) -> Result<CacheStats> {
+        let Output {
+            output: FetchCacheStatsOutput { stats },
+        } = run_usecase!(
+            FetchCacheStats,
+            cx,
+            FetchCacheStatsInput {},
+            |err: AdminError| Err(async_graphql::ErrorExtensions::extend(&err))
+        )?;
+
+        Ok(stats.into())
+    }
+}
+
+/// Feed subscribed by the user
+#[derive(SimpleObject)]
+pub(crate) struct UserSubscription {
+    url: FeedUrl,
+    requirement: Option<Requirement>,
+    category: Option<Category<'static>>,
+    /// Title given by the user
+    title: Option<String>,
+    tags: Vec<Category<'static>>,
+}
+
+/// Statistics of the feed cache
+#[derive(SimpleObject)]
+pub(crate) struct CacheStats {
+    /// Number of the cached feeds
+    entry_count: u64,
+    /// Approximate size of the cached feeds in bytes
+    weighted_size: u64,
+    /// Max size of the cache in bytes
+    max_capacity: u64,
+}
+
+impl From<cache::CacheStats> for CacheStats {
+    fn from(
+        cache::CacheStats {
+            entry_count,
+            weighted_size,
+            max_capacity,
+        }: cache::CacheStats,
+    ) -> Self {
+        Self {
+            entry_count,
+            weighted_size,
+            max_capacity,
+        }
+    }
+}
@@ -1,35 +1,57 @@
 mod query;
 pub(crate) use query::Query;
 
+mod admin;
+
 mod mutation;
-use async_graphql::{EmptySubscription, Schema, SchemaBuilder};
+use async_graphql::{Schema, SchemaBuilder};
 pub(crate) use mutation::Mutation;
 
-use crate::{gql::mutation::ResponseCode, principal::Principal, usecase};
+mod subscription;
+pub(crate) use subscription::SubscriptionRoot;
+
+use crate::{
+    gql::mutation::ResponseCode,
+    principal::Principal,
+    usecase::{self, authorize::Unauthorized},
+};
 
 pub(crate) mod object;
 pub(crate) mod scalar;
 
-pub(crate) type SyndSchema = Schema<Query, Mutation, EmptySubscription>;
+pub(crate) type SyndSchema = Schema<Query, Mutation, SubscriptionRoot>;
 
 pub(crate) mod handler {
-    use async_graphql::http::GraphiQLSource;
-    use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-    use axum::{response::IntoResponse, Extension};
+    use async_graphql::{
+        http::{GraphiQLSource, ALL_WEBSOCKET_PROTOCOLS},
+        Data,
+    };
+    use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+    use axum::{extract::WebSocketUpgrade, response::IntoResponse, Extension};
     use synd_o11y::audit_span;
     use tokio_metrics::TaskMonitor;
     use tracing::Instrument;
 
-    use crate::{principal::Principal, serve::Context};
+    use crate::{
+        config,
+        principal::Principal,
+        serve::{auth::Authenticator, Context},
+    };
 
     pub(crate) async fn graphiql() -> impl IntoResponse {
-        axum::response::Html(GraphiQLSource::build().endpoint("/graphql").finish())
+        axum::response::Html(
+            GraphiQLSource::build()
+                .endpoint("/graphql")
+                .subscription_endpoint(config::serve::GRAPHQL_WS_PATH)
+                .finish(),
+        )
     }
 
     pub(crate) async fn graphql(
         Extension(Context {
             schema,
             gql_monitor,
+            ..
         }): Extension<Context>,
         Extension(principal): Extension<Principal>,
         req: GraphQLRequest,
@@ -40,17 +62,251 @@ pub(crate) mod handler {
             .await
             .into()
     }
+
+    /// Serve graphql subscriptions over websocket.
+    /// Browsers cannot set headers on websocket, so the connection is authenticated in the init payload
+    pub(crate) async fn graphql_ws(
+        Extension(Context {
+            schema,
+            authenticator,
+            ..
+        }): Extension<Context>,
+        protocol: GraphQLProtocol,
+        upgrade: WebSocketUpgrade,
+    ) -> impl IntoResponse {
+        upgrade
+            .protocols(ALL_WEBSOCKET_PROTOCOLS)
+            .on_upgrade(move |stream| {
+                GraphQLWebSocket::new(stream, schema, protocol)
+                    .on_connection_init(move |payload| connection_init(authenticator, payload))
+                    .serve()
+                    .instrument(audit_span!())
+            })
+    }
+
+    /// Authenticate the `authorization` of the payload in the same way as the `Authorization` header.
+    /// e.g. `{ "authorization": "github xxx" }`
+    async fn connection_init(
+        authenticator: Authenticator,
+        payload: serde_json::Value,
+    ) -> async_graphql::Result<Data> {
+        let token = ["authorization", "Authorization"]
+            .into_iter()
+            .find_map(|key| payload.get(key))
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| async_graphql::Error::new("authorization is required"))?;
+
+        let principal = authenticator
+            .authenticate(token)
+            .await
+            .map_err(|()| async_graphql::Error::new("unauthorized"))?;
+
+        let mut data = Data::default();
+        data.insert(principal);
+        Ok(data)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::{sync::Arc, time::Duration};
+
+        use async_trait::async_trait;
+        use axum::{routing::get, Router};
+        use futures_util::{SinkExt, StreamExt};
+        use serde_json::json;
+        use synd_feed::feed::cache::{CacheLayer, FetchCachedFeed};
+        use tokio::net::TcpListener;
+        use tokio_tungstenite::tungstenite::{client::IntoClientRequest, Message};
+
+        use crate::{
+            dependency::Repositories,
+            fixture::{feed, subscription, url, StaticFeeds},
+            gql,
+            principal::User,
+            repository::memory::MemoryRepository,
+            serve::auth::AuthBackend,
+            usecase::{
+                authorize::{Authorizer, Quota},
+                MakeUsecase, Runtime,
+            },
+        };
+
+        use super::*;
+
+        struct Fixed;
+
+        #[async_trait]
+        impl AuthBackend for Fixed {
+            fn scheme(&self) -> &'static str {
+                "test"
+            }
+
+            async fn authenticate(&self, credential: &str) -> anyhow::Result<Principal> {
+                anyhow::ensure!(credential == "valid", "invalid credential");
+                Ok(Principal::User(User::from_id("alice")))
+            }
+        }
+
+        fn authenticator() -> Authenticator {
+            Authenticator::new().unwrap().with_backend(Fixed)
+        }
+
+        #[tokio::test]
+        async fn connection_init_authenticates_payload() {
+            for (payload, authenticated) in [
+                (json!({}), false),
+                (json!({ "authorization": "test invalid" }), false),
+                (json!({ "authorization": "unknown valid" }), false),
+                (json!({ "authorization": "test valid" }), true),
+                (json!({ "Authorization": "test valid" }), true),
+            ] {
+                assert_eq!(
+                    connection_init(authenticator(), payload.clone())
+                        .await
+                        .is_ok(),
+                    authenticated,
+                    "{payload}"
+                );
+            }
+        }
+
+        type WebSocket = tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >;
+
+        async fn connect(port: u16) -> WebSocket {
+            let mut request = format!("ws://localhost:{port}{}", config::serve::GRAPHQL_WS_PATH)
+                .into_client_request()
+                .unwrap();
+            request.headers_mut().insert(
+                "sec-websocket-protocol",
+                "graphql-transport-ws".parse().unwrap(),
+            );
+            tokio_tungstenite::connect_async(request).await.unwrap().0
+        }
+
+        async fn send(ws: &mut WebSocket, message: serde_json::Value) {
+            ws.send(Message::Text(message.to_string())).await.unwrap();
+        }
+
+        async fn recv(ws: &mut WebSocket) -> Message {
+            tokio::time::timeout(Duration::from_secs(5), ws.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap()
+        }
+
+        #[tokio::test]
+        async fn serve_new_entries_over_websocket() {
+            let repositories = Repositories::new(MemoryRepository::new());
+            let publisher = Arc::new(StaticFeeds::default());
+            let cache = CacheLayer::new(publisher.clone());
+            let make = MakeUsecase {
+                subscription_repo: repositories.subscription.clone(),
+                read_state_repo: repositories.read_state,
+                starred_entry_repo: repositories.starred_entry,
+                api_token_repo: repositories.api_token,
+                webhook_repo: repositories.webhook,
+                fetch_feed: Arc::new(cache.clone()),
+                feed_updates: Arc::new(cache.clone()),
+                quota: Quota {
+                    max_subscriptions: 10,
+                },
+            };
+            let cx = Context {
+                gql_monitor: TaskMonitor::new(),
+                schema: gql::schema_builder()
+                    .data(Arc::new(Runtime::new(make, Authorizer::new())))
+                    .finish(),
+                authenticator: authenticator(),
+            };
+            let router = Router::new().route(
+                config::serve::GRAPHQL_WS_PATH,
+                get(graphql_ws).layer(Extension(cx)),
+            );
+            let listener = TcpListener::bind("localhost:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            tokio::spawn(
+                axum_server::from_tcp(listener.into_std().unwrap())
+                    .serve(router.into_make_service()),
+            );
+
+            // Connection is closed if the payload is not authenticated
+            let mut ws = connect(port).await;
+            send(
+                &mut ws,
+                json!({ "type": "connection_init", "payload": { "authorization": "test invalid" } }),
+            )
+            .await;
+            assert!(matches!(recv(&mut ws).await, Message::Close(_)));
+
+            let url = url("https://blog.ymgyt.io/atom.xml");
+            repositories
+                .subscription
+                .put_feed_subscription(subscription("alice", &url))
+                .await
+                .unwrap();
+            publisher.insert(feed(&url, &[("1", 1)]));
+            cache.refresh_feed(url.clone()).await.unwrap();
+
+            let mut ws = connect(port).await;
+            send(
+                &mut ws,
+                json!({ "type": "connection_init", "payload": { "authorization": "test valid" } }),
+            )
+            .await;
+            assert_eq!(
+                recv(&mut ws).await,
+                Message::Text(json!({ "type": "connection_ack" }).to_string())
+            );
+            send(
+                &mut ws,
+                json!({
+                    "id": "1",
+                    "type": "subscribe",
+                    "payload": { "query": "subscription { newEntries { title } }" },
+                }),
+            )
+            .await;
+
+            // Keep publishing new entries since the subscription is started asynchronously
+            let publishing = tokio::spawn(async move {
+                loop {
+                    publisher.insert(feed(&url, &[("new", 2), ("1", 1)]));
+                    cache.refresh_feed(url.clone()).await.unwrap();
+                    publisher.insert(feed(&url, &[("1", 1)]));
+                    cache.refresh_feed(url.clone()).await.unwrap();
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+            });
+            let Message::Text(message) = recv(&mut ws).await else {
+                panic!("unexpected message");
+            };
+            publishing.abort();
+            assert_eq!(
+                serde_json::from_str::<serde_json::Value>(&message).unwrap(),
+                json!({
+                    "id": "1",
+                    "type": "next",
+                    "payload": { "data": { "newEntries": [{ "title": "new" }] } },
+                })
+            );
+        }
+    }
 }
 
 #[must_use]
-pub(crate) fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    let schema = Schema::build(Query, Mutation, EmptySubscription);
+pub(crate) fn schema_builder() -> SchemaBuilder<Query, Mutation, SubscriptionRoot> {
+    let schema = Schema::build(Query, Mutation, SubscriptionRoot);
 
     if cfg!(not(feature = "introspection")) {
+        // Complexity is calculated over the whole document and synd_term sends
+        // all operations defined in a gql file with each request
         schema
             .disable_introspection()
             .limit_depth(10)
-            .limit_complexity(60)
+            .limit_complexity(100)
     } else {
         schema.limit_depth(20).limit_complexity(300)
     }
@@ -58,6 +314,15 @@ pub(crate) fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscripti
     // schema.extension(Tracing)
 }
 
+/// Response for the requests rejected by the rate limit
+pub(crate) fn rate_limited_response() -> async_graphql::Response {
+    let err = async_graphql::ErrorExtensions::extend_with(
+        async_graphql::Error::new("rate limit exceeded"),
+        |_, ext| ext.set("code", ResponseCode::RateLimited),
+    );
+    async_graphql::Response::from_errors(vec![err.into_server_error(async_graphql::Pos::default())])
+}
+
 impl<'a> usecase::Context for &async_graphql::Context<'a> {
     fn principal(&self) -> Principal {
         self.data_unchecked::<Principal>().clone()
@@ -71,7 +336,12 @@ where
     fn extend(&self) -> async_graphql::Error {
         async_graphql::Error::new(format!("{self}")).extend_with(|_, ext| match self {
             usecase::Error::Usecase(_) => unreachable!(),
-            usecase::Error::Unauthorized(_) => ext.set("code", ResponseCode::Unauthorized),
+            usecase::Error::Unauthorized(Unauthorized::Forbidden) => {
+                ext.set("code", ResponseCode::Unauthorized);
+            }
+            usecase::Error::Unauthorized(Unauthorized::QuotaExceeded(_)) => {
+                ext.set("code", ResponseCode::QuotaExceeded);
+            }
             usecase::Error::Repository(_) => ext.set("code", ResponseCode::InternalError),
         })
     }
@@ -91,9 +361,51 @@ impl async_graphql::ErrorExtensions for usecase::FetchSubscribedFeedsError {
     }
 }
 
+impl async_graphql::ErrorExtensions for usecase::WatchNewEntriesError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(format!("{self}"))
+            .extend_with(|_, ext| ext.set("code", ResponseCode::InternalError))
+    }
+}
+
+impl async_graphql::ErrorExtensions for usecase::AdminError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(format!("{self}"))
+            .extend_with(|_, ext| ext.set("code", ResponseCode::InternalError))
+    }
+}
+
+impl async_graphql::ErrorExtensions for usecase::FetchStarredEntriesError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(format!("{self}"))
+            .extend_with(|_, ext| ext.set("code", ResponseCode::InternalError))
+    }
+}
+
+impl async_graphql::ErrorExtensions for usecase::FetchApiTokensError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(format!("{self}"))
+            .extend_with(|_, ext| ext.set("code", ResponseCode::InternalError))
+    }
+}
+
+impl async_graphql::ErrorExtensions for usecase::FetchWebhooksError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(format!("{self}"))
+            .extend_with(|_, ext| ext.set("code", ResponseCode::InternalError))
+    }
+}
+
+impl async_graphql::ErrorExtensions for usecase::FetchWebhookDeliveriesError {
+    fn extend(&self) -> async_graphql::Error {
+        async_graphql::Error::new(format!("{self}"))
+            .extend_with(|_, ext| ext.set("code", ResponseCode::InternalError))
+    }
+}
+
 macro_rules! run_usecase {
     ($usecase:ty, $cx:expr, $input:expr,$err_handle:expr) => {{
-        let runtime = $cx.data_unchecked::<crate::usecase::Runtime>();
+        let runtime = $cx.data_unchecked::<std::sync::Arc<crate::usecase::Runtime>>();
         let err_handle = $err_handle;
 
         match runtime.run::<$usecase, _, _>($cx, $input).await {
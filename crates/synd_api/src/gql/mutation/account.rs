@@ -0,0 +1,101 @@
+use async_graphql::{Object, Union};
+
+use crate::{
+    gql::mutation::ResponseStatus,
+    usecase::{self, AccountError as UsecaseAccountError},
+};
+
+#[derive(Union)]
+pub(crate) enum ExportMyDataResponse {
+    Success(ExportMyDataSuccess),
+    Error(AccountError),
+}
+
+pub(crate) struct ExportMyDataSuccess {
+    pub status: ResponseStatus,
+    pub data: String,
+}
+
+#[Object]
+impl ExportMyDataSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Subscriptions, annotations, folders, read states and starred entries as JSON
+    pub async fn data(&self) -> &str {
+        self.data.as_str()
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum DeleteMyAccountResponse {
+    Success(DeleteMyAccountSuccess),
+    Error(AccountError),
+}
+
+pub(crate) struct DeleteMyAccountSuccess {
+    pub status: ResponseStatus,
+}
+
+#[Object]
+impl DeleteMyAccountSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+}
+
+pub(crate) struct AccountError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl AccountError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<UsecaseAccountError> for AccountError {
+    fn from(err: UsecaseAccountError) -> Self {
+        Self {
+            status: ResponseStatus::internal(),
+            message: format!("{err}"),
+        }
+    }
+}
+
+impl From<usecase::Output<usecase::ExportMyDataOutput>> for ExportMyDataResponse {
+    fn from(output: usecase::Output<usecase::ExportMyDataOutput>) -> Self {
+        ExportMyDataResponse::Success(ExportMyDataSuccess {
+            status: ResponseStatus::ok(),
+            data: output.output.data,
+        })
+    }
+}
+
+impl From<UsecaseAccountError> for ExportMyDataResponse {
+    fn from(err: UsecaseAccountError) -> Self {
+        ExportMyDataResponse::Error(err.into())
+    }
+}
+
+impl From<usecase::Output<usecase::DeleteMyAccountOutput>> for DeleteMyAccountResponse {
+    fn from(_output: usecase::Output<usecase::DeleteMyAccountOutput>) -> Self {
+        DeleteMyAccountResponse::Success(DeleteMyAccountSuccess {
+            status: ResponseStatus::ok(),
+        })
+    }
+}
+
+impl From<UsecaseAccountError> for DeleteMyAccountResponse {
+    fn from(err: UsecaseAccountError) -> Self {
+        DeleteMyAccountResponse::Error(err.into())
+    }
+}
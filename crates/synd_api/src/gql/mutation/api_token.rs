@@ -0,0 +1,179 @@
+use async_graphql::{InputObject, Object, Union};
+
+use crate::{
+    gql::{mutation::ResponseStatus, object, scalar::Rfc3339Time},
+    repository::types::ApiTokenScope,
+    usecase::{self, ApiTokenError as UsecaseApiTokenError},
+};
+
+#[derive(InputObject, Debug)]
+pub(crate) struct CreateApiTokenInput {
+    /// Name to identify the token
+    pub name: String,
+    /// Allow only queries with the token
+    #[graphql(default = false)]
+    pub read_only: bool,
+    /// The time at which the token expires. The token never expires if omitted
+    pub expires_at: Option<Rfc3339Time>,
+}
+
+impl From<CreateApiTokenInput> for usecase::CreateApiTokenInput {
+    fn from(value: CreateApiTokenInput) -> Self {
+        usecase::CreateApiTokenInput {
+            name: value.name,
+            scope: if value.read_only {
+                ApiTokenScope::ReadOnly
+            } else {
+                ApiTokenScope::ReadWrite
+            },
+            expires_at: value.expires_at.map(Into::into),
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub(crate) struct RevokeApiTokenInput {
+    /// Id of the token to revoke
+    pub id: String,
+}
+
+impl From<RevokeApiTokenInput> for usecase::RevokeApiTokenInput {
+    fn from(value: RevokeApiTokenInput) -> Self {
+        usecase::RevokeApiTokenInput { id: value.id }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum CreateApiTokenResponse {
+    Success(CreateApiTokenSuccess),
+    Error(ApiTokenError),
+}
+
+pub(crate) struct CreateApiTokenSuccess {
+    pub status: ResponseStatus,
+    pub api_token: object::ApiToken,
+    pub token: String,
+}
+
+#[Object]
+impl CreateApiTokenSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Created token
+    pub async fn api_token(&self) -> &object::ApiToken {
+        &self.api_token
+    }
+
+    /// Token to be sent as `Authorization: synd <token>`.
+    /// This cannot be retrieved again
+    pub async fn token(&self) -> &str {
+        self.token.as_str()
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum RevokeApiTokenResponse {
+    Success(RevokeApiTokenSuccess),
+    Error(ApiTokenError),
+}
+
+pub(crate) struct RevokeApiTokenSuccess {
+    pub status: ResponseStatus,
+    pub id: String,
+}
+
+#[Object]
+impl RevokeApiTokenSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Id of the revoked token
+    pub async fn id(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+pub(crate) struct ApiTokenError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl ApiTokenError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for CreateApiTokenResponse {
+    fn from(status: ResponseStatus) -> Self {
+        CreateApiTokenResponse::Error(ApiTokenError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::CreateApiTokenOutput>> for CreateApiTokenResponse {
+    fn from(output: usecase::Output<usecase::CreateApiTokenOutput>) -> Self {
+        let usecase::CreateApiTokenOutput { token, plaintext } = output.output;
+        CreateApiTokenResponse::Success(CreateApiTokenSuccess {
+            status: ResponseStatus::ok(),
+            api_token: token.into(),
+            token: plaintext,
+        })
+    }
+}
+
+impl From<UsecaseApiTokenError> for CreateApiTokenResponse {
+    fn from(err: UsecaseApiTokenError) -> Self {
+        CreateApiTokenResponse::Error(err.into())
+    }
+}
+
+impl From<ResponseStatus> for RevokeApiTokenResponse {
+    fn from(status: ResponseStatus) -> Self {
+        RevokeApiTokenResponse::Error(ApiTokenError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::RevokeApiTokenOutput>> for RevokeApiTokenResponse {
+    fn from(output: usecase::Output<usecase::RevokeApiTokenOutput>) -> Self {
+        RevokeApiTokenResponse::Success(RevokeApiTokenSuccess {
+            status: ResponseStatus::ok(),
+            id: output.output.id,
+        })
+    }
+}
+
+impl From<UsecaseApiTokenError> for RevokeApiTokenResponse {
+    fn from(err: UsecaseApiTokenError) -> Self {
+        RevokeApiTokenResponse::Error(err.into())
+    }
+}
+
+impl From<UsecaseApiTokenError> for ApiTokenError {
+    fn from(err: UsecaseApiTokenError) -> Self {
+        let status = match err {
+            UsecaseApiTokenError::EmptyName | UsecaseApiTokenError::AlreadyExpired => {
+                ResponseStatus::invalid_api_token()
+            }
+            UsecaseApiTokenError::NotFound => ResponseStatus::api_token_not_found(),
+        };
+        ApiTokenError {
+            status,
+            message: format!("{err}"),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use async_graphql::{InputObject, Object, Union};
+use synd_feed::types::FeedUrl;
+
+use crate::{
+    gql::{mutation::ResponseStatus, object},
+    repository::types::FolderId,
+    usecase::{self, FolderError as UsecaseFolderError},
+};
+
+#[derive(InputObject, Debug)]
+pub(crate) struct CreateFolderInput {
+    /// Folder name
+    pub name: String,
+    /// Create the folder in this folder. The folder is created at the top level if omitted
+    pub parent_id: Option<FolderId>,
+}
+
+impl From<CreateFolderInput> for usecase::CreateFolderInput {
+    fn from(value: CreateFolderInput) -> Self {
+        usecase::CreateFolderInput {
+            name: value.name,
+            parent_id: value.parent_id,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub(crate) struct RenameFolderInput {
+    /// Folder to rename
+    pub folder_id: FolderId,
+    /// New folder name
+    pub name: String,
+}
+
+impl From<RenameFolderInput> for usecase::RenameFolderInput {
+    fn from(value: RenameFolderInput) -> Self {
+        usecase::RenameFolderInput {
+            folder_id: value.folder_id,
+            name: value.name,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub(crate) struct MoveFeedInput {
+    /// Url of the subscribed feed
+    pub url: FeedUrl,
+    /// Destination folder. The feed is moved to the top level if omitted
+    pub folder_id: Option<FolderId>,
+    /// Zero based position among the feeds in the destination folder.
+    /// The feed is placed at the end if omitted or out of range
+    pub position: Option<u32>,
+}
+
+impl From<MoveFeedInput> for usecase::MoveFeedInput {
+    fn from(value: MoveFeedInput) -> Self {
+        usecase::MoveFeedInput {
+            url: value.url,
+            folder_id: value.folder_id,
+            position: value
+                .position
+                .and_then(|position| usize::try_from(position).ok()),
+        }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum FolderResponse {
+    Success(FolderSuccess),
+    Error(FolderError),
+}
+
+pub(crate) struct FolderSuccess {
+    pub status: ResponseStatus,
+    pub folder: object::Folder,
+}
+
+#[Object]
+impl FolderSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Created or renamed folder
+    pub async fn folder(&self) -> &object::Folder {
+        &self.folder
+    }
+}
+
+pub(crate) struct FolderError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl FolderError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for FolderResponse {
+    fn from(status: ResponseStatus) -> Self {
+        FolderResponse::Error(FolderError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::CreateFolderOutput>> for FolderResponse {
+    fn from(output: usecase::Output<usecase::CreateFolderOutput>) -> Self {
+        FolderResponse::Success(FolderSuccess {
+            status: ResponseStatus::ok(),
+            folder: output.output.folder.into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::RenameFolderOutput>> for FolderResponse {
+    fn from(output: usecase::Output<usecase::RenameFolderOutput>) -> Self {
+        FolderResponse::Success(FolderSuccess {
+            status: ResponseStatus::ok(),
+            folder: output.output.folder.into(),
+        })
+    }
+}
+
+impl From<UsecaseFolderError> for FolderResponse {
+    fn from(err: UsecaseFolderError) -> Self {
+        FolderResponse::Error(FolderError {
+            status: status(&err),
+            message: format!("{err}"),
+        })
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum MoveFeedResponse {
+    Success(MoveFeedSuccess),
+    Error(MoveFeedError),
+}
+
+pub(crate) struct MoveFeedSuccess {
+    pub status: ResponseStatus,
+    pub url: FeedUrl,
+    pub folder_id: Option<FolderId>,
+}
+
+#[Object]
+impl MoveFeedSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Url of the moved feed
+    pub async fn url(&self) -> &FeedUrl {
+        &self.url
+    }
+
+    /// Folder which the feed belongs to. null if the feed is at the top level
+    pub async fn folder_id(&self) -> Option<FolderId> {
+        self.folder_id
+    }
+}
+
+pub(crate) struct MoveFeedError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl MoveFeedError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for MoveFeedResponse {
+    fn from(status: ResponseStatus) -> Self {
+        MoveFeedResponse::Error(MoveFeedError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::MoveFeedOutput>> for MoveFeedResponse {
+    fn from(output: usecase::Output<usecase::MoveFeedOutput>) -> Self {
+        let usecase::MoveFeedOutput { url, folder_id } = output.output;
+        MoveFeedResponse::Success(MoveFeedSuccess {
+            status: ResponseStatus::ok(),
+            url,
+            folder_id,
+        })
+    }
+}
+
+impl From<UsecaseFolderError> for MoveFeedResponse {
+    fn from(err: UsecaseFolderError) -> Self {
+        MoveFeedResponse::Error(MoveFeedError {
+            status: status(&err),
+            message: format!("{err}"),
+        })
+    }
+}
+
+fn status(err: &UsecaseFolderError) -> ResponseStatus {
+    match err {
+        UsecaseFolderError::NotSubscribed => ResponseStatus::feed_not_subscribed(),
+        UsecaseFolderError::FolderNotFound => ResponseStatus::folder_not_found(),
+        UsecaseFolderError::EmptyFolderName => ResponseStatus::invalid_folder_name(),
+    }
+}
@@ -0,0 +1,179 @@
+use async_graphql::{InputObject, Object, Union};
+use synd_feed::{feed::service::FetchFeedError, types::FeedUrl};
+
+use crate::{
+    gql::mutation::ResponseStatus,
+    repository::types::EntryReference,
+    usecase::{self, MarkFeedReadError as UsecaseMarkFeedReadError},
+};
+
+#[derive(InputObject)]
+pub struct EntryReferenceInput {
+    /// Feed url of the entry
+    pub feed_url: FeedUrl,
+    /// Entry id
+    pub entry_id: String,
+}
+
+#[derive(InputObject)]
+pub struct MarkEntriesInput {
+    /// Entries to mark
+    pub entries: Vec<EntryReferenceInput>,
+}
+
+impl From<EntryReferenceInput> for EntryReference {
+    fn from(value: EntryReferenceInput) -> Self {
+        EntryReference {
+            feed_url: value.feed_url,
+            entry_id: value.entry_id,
+        }
+    }
+}
+
+impl From<MarkEntriesInput> for usecase::MarkEntriesInput {
+    fn from(value: MarkEntriesInput) -> Self {
+        usecase::MarkEntriesInput {
+            entries: value.entries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum MarkEntriesResponse {
+    Success(MarkEntriesSuccess),
+    Error(MarkEntriesError),
+}
+
+pub(crate) struct MarkEntriesSuccess {
+    pub status: ResponseStatus,
+}
+
+#[Object]
+impl MarkEntriesSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+}
+
+pub(crate) struct MarkEntriesError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl MarkEntriesError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for MarkEntriesResponse {
+    fn from(status: ResponseStatus) -> Self {
+        MarkEntriesResponse::Error(MarkEntriesError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<anyhow::Error> for MarkEntriesResponse {
+    fn from(err: anyhow::Error) -> Self {
+        MarkEntriesResponse::Error(MarkEntriesError {
+            status: ResponseStatus::internal(),
+            message: format!("{err}"),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::MarkEntriesOutput>> for MarkEntriesResponse {
+    fn from(_output: usecase::Output<usecase::MarkEntriesOutput>) -> Self {
+        MarkEntriesResponse::Success(MarkEntriesSuccess {
+            status: ResponseStatus::ok(),
+        })
+    }
+}
+
+#[derive(InputObject)]
+pub struct MarkFeedReadInput {
+    /// Feed url to mark all entries as read
+    pub url: FeedUrl,
+}
+
+impl From<MarkFeedReadInput> for usecase::MarkFeedReadInput {
+    fn from(value: MarkFeedReadInput) -> Self {
+        usecase::MarkFeedReadInput { url: value.url }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum MarkFeedReadResponse {
+    Success(MarkFeedReadSuccess),
+    Error(MarkFeedReadError),
+}
+
+pub(crate) struct MarkFeedReadSuccess {
+    pub status: ResponseStatus,
+}
+
+#[Object]
+impl MarkFeedReadSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+}
+
+pub(crate) struct MarkFeedReadError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl MarkFeedReadError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for MarkFeedReadResponse {
+    fn from(status: ResponseStatus) -> Self {
+        MarkFeedReadResponse::Error(MarkFeedReadError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::MarkFeedReadOutput>> for MarkFeedReadResponse {
+    fn from(_output: usecase::Output<usecase::MarkFeedReadOutput>) -> Self {
+        MarkFeedReadResponse::Success(MarkFeedReadSuccess {
+            status: ResponseStatus::ok(),
+        })
+    }
+}
+
+impl From<UsecaseMarkFeedReadError> for MarkFeedReadResponse {
+    fn from(err: UsecaseMarkFeedReadError) -> Self {
+        let UsecaseMarkFeedReadError::FetchFeed(fetch_err) = err;
+        let status = match fetch_err {
+            FetchFeedError::InvalidFeed(_) => ResponseStatus::invalid_feed_url(),
+            FetchFeedError::DisallowedByRobotsTxt | FetchFeedError::RetryAfter(_) => {
+                ResponseStatus::feed_unavailable()
+            }
+            _ => ResponseStatus::internal(),
+        };
+        MarkFeedReadResponse::Error(MarkFeedReadError {
+            status,
+            message: format!("{fetch_err}"),
+        })
+    }
+}
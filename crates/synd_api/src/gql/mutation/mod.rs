@@ -2,11 +2,25 @@ use async_graphql::{Context, Enum, Interface, Object, SimpleObject};
 
 use crate::{
     gql::run_usecase,
-    usecase::{SubscribeFeed, SubscribeFeedError, UnsubscribeFeed},
+    usecase::{
+        self, AccountError, AdminError, ApiTokenError, CreateApiToken, CreateFolder, CreateWebhook,
+        DeleteMyAccount, DeleteWebhook, ExportMyData, FolderError, MarkEntriesRead,
+        MarkEntriesUnread, MarkFeedRead, MarkFeedReadError, MoveFeed, RefreshFeed, RenameFolder,
+        RevokeApiToken, StarEntry, StarEntryError, SubscribeFeed, SubscribeFeedError, UnstarEntry,
+        UnsubscribeFeed, UpdateFeedAnnotations, UpdateFeedAnnotationsError, WebhookError,
+    },
 };
 
+pub mod account;
+pub mod api_token;
+pub mod folder;
+pub mod mark_read;
+pub mod refresh_feed;
+pub mod star_entry;
 pub mod subscribe_feed;
 pub mod unsubscribe_feed;
+pub mod update_feed_annotations;
+pub mod webhook;
 
 #[derive(Enum, PartialEq, Eq, Clone, Copy)]
 pub(crate) enum ResponseCode {
@@ -16,6 +30,28 @@ pub(crate) enum ResponseCode {
     Unauthorized,
     /// Given url is not valid feed url
     InvalidFeedUrl,
+    /// Feed is not available due to the policy of the feed server(e.g. robots.txt, rate limit)
+    FeedUnavailable,
+    /// Given entry is not found in the feed
+    EntryNotFound,
+    /// Given feed is not subscribed
+    FeedNotSubscribed,
+    /// Given folder is not found
+    FolderNotFound,
+    /// Given folder name is not valid
+    InvalidFolderName,
+    /// Given api token is not found
+    ApiTokenNotFound,
+    /// Given api token parameters are not valid
+    InvalidApiToken,
+    /// Given webhook is not found
+    WebhookNotFound,
+    /// Given webhook parameters are not valid
+    InvalidWebhook,
+    /// Principal reached the quota(e.g. max subscriptions)
+    QuotaExceeded,
+    /// Principal sent too many requests
+    RateLimited,
     /// Something went wrong
     InternalError,
 }
@@ -45,6 +81,60 @@ impl ResponseStatus {
         }
     }
 
+    fn feed_unavailable() -> Self {
+        Self {
+            code: ResponseCode::FeedUnavailable,
+        }
+    }
+
+    fn entry_not_found() -> Self {
+        Self {
+            code: ResponseCode::EntryNotFound,
+        }
+    }
+
+    fn feed_not_subscribed() -> Self {
+        Self {
+            code: ResponseCode::FeedNotSubscribed,
+        }
+    }
+
+    fn folder_not_found() -> Self {
+        Self {
+            code: ResponseCode::FolderNotFound,
+        }
+    }
+
+    fn invalid_folder_name() -> Self {
+        Self {
+            code: ResponseCode::InvalidFolderName,
+        }
+    }
+
+    fn api_token_not_found() -> Self {
+        Self {
+            code: ResponseCode::ApiTokenNotFound,
+        }
+    }
+
+    fn invalid_api_token() -> Self {
+        Self {
+            code: ResponseCode::InvalidApiToken,
+        }
+    }
+
+    fn webhook_not_found() -> Self {
+        Self {
+            code: ResponseCode::WebhookNotFound,
+        }
+    }
+
+    fn invalid_webhook() -> Self {
+        Self {
+            code: ResponseCode::InvalidWebhook,
+        }
+    }
+
     fn internal() -> Self {
         Self {
             code: ResponseCode::InternalError,
@@ -58,6 +148,20 @@ impl ResponseStatus {
 enum MutationResponse {
     SubscribeFeed(subscribe_feed::SubscribeFeedSuccess),
     UnsubscribeFeed(unsubscribe_feed::UnsubscribeFeedSuccess),
+    UpdateFeedAnnotations(update_feed_annotations::UpdateFeedAnnotationsSuccess),
+    Folder(folder::FolderSuccess),
+    MoveFeed(folder::MoveFeedSuccess),
+    MarkEntries(mark_read::MarkEntriesSuccess),
+    MarkFeedRead(mark_read::MarkFeedReadSuccess),
+    StarEntry(star_entry::StarEntrySuccess),
+    UnstarEntry(star_entry::UnstarEntrySuccess),
+    CreateApiToken(api_token::CreateApiTokenSuccess),
+    RevokeApiToken(api_token::RevokeApiTokenSuccess),
+    CreateWebhook(webhook::CreateWebhookSuccess),
+    DeleteWebhook(webhook::DeleteWebhookSuccess),
+    RefreshFeed(refresh_feed::RefreshFeedSuccess),
+    ExportMyData(account::ExportMyDataSuccess),
+    DeleteMyAccount(account::DeleteMyAccountSuccess),
 }
 
 #[derive(Interface)]
@@ -68,6 +172,17 @@ enum MutationResponse {
 enum ErrorResponse {
     SubscribeFeed(subscribe_feed::SubscribeFeedError),
     UnsubscribeFeed(unsubscribe_feed::UnsubscribeFeedError),
+    UpdateFeedAnnotations(update_feed_annotations::UpdateFeedAnnotationsError),
+    Folder(folder::FolderError),
+    MoveFeed(folder::MoveFeedError),
+    MarkEntries(mark_read::MarkEntriesError),
+    MarkFeedRead(mark_read::MarkFeedReadError),
+    StarEntry(star_entry::StarEntryError),
+    UnstarEntry(star_entry::UnstarEntryError),
+    ApiToken(api_token::ApiTokenError),
+    Webhook(webhook::WebhookError),
+    RefreshFeed(refresh_feed::RefreshFeedError),
+    Account(account::AccountError),
 }
 
 pub(crate) struct Mutation;
@@ -75,6 +190,7 @@ pub(crate) struct Mutation;
 #[Object]
 impl Mutation {
     /// Subscribe feed
+    /// If the feed is already subscribed, its annotations are replaced and omitted ones are cleared
     async fn subscribe_feed(
         &self,
         cx: &Context<'_>,
@@ -96,4 +212,175 @@ impl Mutation {
             err.into()
         ))
     }
+
+    /// Update annotations of the subscribed feed
+    async fn update_feed_annotations(
+        &self,
+        cx: &Context<'_>,
+        input: update_feed_annotations::UpdateFeedAnnotationsInput,
+    ) -> async_graphql::Result<update_feed_annotations::UpdateFeedAnnotationsResponse> {
+        run_usecase!(
+            UpdateFeedAnnotations,
+            cx,
+            input,
+            |err: UpdateFeedAnnotationsError| Ok(err.into())
+        )
+    }
+
+    /// Create a folder to organize subscribed feeds
+    async fn create_folder(
+        &self,
+        cx: &Context<'_>,
+        input: folder::CreateFolderInput,
+    ) -> async_graphql::Result<folder::FolderResponse> {
+        run_usecase!(CreateFolder, cx, input, |err: FolderError| Ok(err.into()))
+    }
+
+    /// Rename the folder
+    async fn rename_folder(
+        &self,
+        cx: &Context<'_>,
+        input: folder::RenameFolderInput,
+    ) -> async_graphql::Result<folder::FolderResponse> {
+        run_usecase!(RenameFolder, cx, input, |err: FolderError| Ok(err.into()))
+    }
+
+    /// Move the subscribed feed into the folder at the position
+    async fn move_feed(
+        &self,
+        cx: &Context<'_>,
+        input: folder::MoveFeedInput,
+    ) -> async_graphql::Result<folder::MoveFeedResponse> {
+        run_usecase!(MoveFeed, cx, input, |err: FolderError| Ok(err.into()))
+    }
+
+    /// Mark given entries as read
+    async fn mark_entries_read(
+        &self,
+        cx: &Context<'_>,
+        input: mark_read::MarkEntriesInput,
+    ) -> async_graphql::Result<mark_read::MarkEntriesResponse> {
+        run_usecase!(MarkEntriesRead, cx, input, |err: anyhow::Error| Ok(
+            err.into()
+        ))
+    }
+
+    /// Mark given entries as unread
+    async fn mark_entries_unread(
+        &self,
+        cx: &Context<'_>,
+        input: mark_read::MarkEntriesInput,
+    ) -> async_graphql::Result<mark_read::MarkEntriesResponse> {
+        run_usecase!(MarkEntriesUnread, cx, input, |err: anyhow::Error| Ok(
+            err.into()
+        ))
+    }
+
+    /// Mark all current entries of given feed as read
+    async fn mark_feed_read(
+        &self,
+        cx: &Context<'_>,
+        input: mark_read::MarkFeedReadInput,
+    ) -> async_graphql::Result<mark_read::MarkFeedReadResponse> {
+        run_usecase!(MarkFeedRead, cx, input, |err: MarkFeedReadError| Ok(
+            err.into()
+        ))
+    }
+
+    /// Star given entry to keep it even after evicted from the feed
+    async fn star_entry(
+        &self,
+        cx: &Context<'_>,
+        input: star_entry::StarEntryInput,
+    ) -> async_graphql::Result<star_entry::StarEntryResponse> {
+        run_usecase!(StarEntry, cx, input, |err: StarEntryError| Ok(err.into()))
+    }
+
+    /// Unstar given entry
+    /// If given entry is not starred, this mutation will succeed
+    async fn unstar_entry(
+        &self,
+        cx: &Context<'_>,
+        input: star_entry::StarEntryInput,
+    ) -> async_graphql::Result<star_entry::UnstarEntryResponse> {
+        run_usecase!(UnstarEntry, cx, input, |err: anyhow::Error| Ok(err.into()))
+    }
+
+    /// Create a personal access token to call the api from scripts.
+    /// The token is returned only in this response
+    async fn create_api_token(
+        &self,
+        cx: &Context<'_>,
+        input: api_token::CreateApiTokenInput,
+    ) -> async_graphql::Result<api_token::CreateApiTokenResponse> {
+        run_usecase!(CreateApiToken, cx, input, |err: ApiTokenError| Ok(
+            err.into()
+        ))
+    }
+
+    /// Revoke the personal access token
+    async fn revoke_api_token(
+        &self,
+        cx: &Context<'_>,
+        input: api_token::RevokeApiTokenInput,
+    ) -> async_graphql::Result<api_token::RevokeApiTokenResponse> {
+        run_usecase!(RevokeApiToken, cx, input, |err: ApiTokenError| Ok(
+            err.into()
+        ))
+    }
+
+    /// Register a webhook to which new entries of the subscribed feeds are posted.
+    /// The secret is returned only in this response
+    async fn create_webhook(
+        &self,
+        cx: &Context<'_>,
+        input: webhook::CreateWebhookInput,
+    ) -> async_graphql::Result<webhook::CreateWebhookResponse> {
+        run_usecase!(CreateWebhook, cx, input, |err: WebhookError| Ok(err.into()))
+    }
+
+    /// Delete the webhook. Its delivery log is kept
+    async fn delete_webhook(
+        &self,
+        cx: &Context<'_>,
+        input: webhook::DeleteWebhookInput,
+    ) -> async_graphql::Result<webhook::DeleteWebhookResponse> {
+        run_usecase!(DeleteWebhook, cx, input, |err: WebhookError| Ok(err.into()))
+    }
+
+    /// Export all data of the user as JSON
+    async fn export_my_data(
+        &self,
+        cx: &Context<'_>,
+    ) -> async_graphql::Result<account::ExportMyDataResponse> {
+        run_usecase!(
+            ExportMyData,
+            cx,
+            usecase::ExportMyDataInput {},
+            |err: AccountError| Ok(err.into())
+        )
+    }
+
+    /// Delete all data of the user including api tokens. This cannot be undone
+    async fn delete_my_account(
+        &self,
+        cx: &Context<'_>,
+    ) -> async_graphql::Result<account::DeleteMyAccountResponse> {
+        run_usecase!(
+            DeleteMyAccount,
+            cx,
+            usecase::DeleteMyAccountInput {},
+            |err: AccountError| Ok(err.into())
+        )
+    }
+
+    /// Fetch the feed bypassing the cache and replace the cached one.
+    /// Only principals with the admin role can run this mutation
+    async fn refresh_feed(
+        &self,
+        cx: &Context<'_>,
+        input: refresh_feed::RefreshFeedInput,
+    ) -> async_graphql::Result<refresh_feed::RefreshFeedResponse> {
+        run_usecase!(RefreshFeed, cx, input, |err: AdminError| Ok(err.into()))
+    }
 }
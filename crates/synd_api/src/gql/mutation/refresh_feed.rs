@@ -0,0 +1,87 @@
+use async_graphql::{InputObject, Object, Union};
+use synd_feed::{feed::service::FetchFeedError, types::FeedUrl};
+
+use crate::{
+    gql::{mutation::ResponseStatus, object},
+    usecase::{self, AdminError},
+};
+
+#[derive(InputObject)]
+pub struct RefreshFeedInput {
+    /// Feed url to refresh
+    pub url: FeedUrl,
+}
+
+impl From<RefreshFeedInput> for usecase::RefreshFeedInput {
+    fn from(value: RefreshFeedInput) -> Self {
+        usecase::RefreshFeedInput { url: value.url }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Union)]
+pub(crate) enum RefreshFeedResponse {
+    Success(RefreshFeedSuccess),
+    Error(RefreshFeedError),
+}
+
+pub(crate) struct RefreshFeedSuccess {
+    pub status: ResponseStatus,
+    /// Refreshed feed
+    pub feed: object::Feed,
+}
+
+#[Object]
+impl RefreshFeedSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    pub async fn feed(&self) -> &object::Feed {
+        &self.feed
+    }
+}
+
+pub(crate) struct RefreshFeedError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl RefreshFeedError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<usecase::Output<usecase::RefreshFeedOutput>> for RefreshFeedResponse {
+    fn from(output: usecase::Output<usecase::RefreshFeedOutput>) -> Self {
+        RefreshFeedResponse::Success(RefreshFeedSuccess {
+            status: ResponseStatus::ok(),
+            feed: object::Feed::from(synd_feed::types::Annotated::new(output.output.feed)),
+        })
+    }
+}
+
+impl From<AdminError> for RefreshFeedResponse {
+    fn from(err: AdminError) -> Self {
+        let status = match &err {
+            AdminError::FetchFeed(FetchFeedError::InvalidFeed(_)) => {
+                ResponseStatus::invalid_feed_url()
+            }
+            AdminError::FetchFeed(
+                FetchFeedError::DisallowedByRobotsTxt | FetchFeedError::RetryAfter(_),
+            ) => ResponseStatus::feed_unavailable(),
+            AdminError::FetchFeed(_) => ResponseStatus::internal(),
+        };
+        RefreshFeedResponse::Error(RefreshFeedError {
+            status,
+            message: format!("{err}"),
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use async_graphql::{Object, Union};
+use synd_feed::feed::service::FetchFeedError;
+
+use crate::{
+    gql::{
+        mutation::{mark_read::EntryReferenceInput, ResponseStatus},
+        object,
+    },
+    repository::types::EntryReference,
+    usecase::{self, StarEntryError as UsecaseStarEntryError},
+};
+
+#[derive(async_graphql::InputObject)]
+pub struct StarEntryInput {
+    /// Entry to star or unstar
+    pub entry: EntryReferenceInput,
+}
+
+impl From<StarEntryInput> for usecase::StarEntryInput {
+    fn from(value: StarEntryInput) -> Self {
+        usecase::StarEntryInput {
+            entry: EntryReference::from(value.entry),
+        }
+    }
+}
+
+impl From<StarEntryInput> for usecase::UnstarEntryInput {
+    fn from(value: StarEntryInput) -> Self {
+        usecase::UnstarEntryInput {
+            entry: EntryReference::from(value.entry),
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Union)]
+pub(crate) enum StarEntryResponse {
+    Success(StarEntrySuccess),
+    Error(StarEntryError),
+}
+
+pub(crate) struct StarEntrySuccess {
+    pub status: ResponseStatus,
+    /// Starred entry
+    pub entry: object::StarredEntry,
+}
+
+#[Object]
+impl StarEntrySuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    pub async fn entry(&self) -> &object::StarredEntry {
+        &self.entry
+    }
+}
+
+pub(crate) struct StarEntryError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl StarEntryError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for StarEntryResponse {
+    fn from(status: ResponseStatus) -> Self {
+        StarEntryResponse::Error(StarEntryError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::StarEntryOutput>> for StarEntryResponse {
+    fn from(output: usecase::Output<usecase::StarEntryOutput>) -> Self {
+        StarEntryResponse::Success(StarEntrySuccess {
+            status: ResponseStatus::ok(),
+            entry: output.output.entry.into(),
+        })
+    }
+}
+
+impl From<UsecaseStarEntryError> for StarEntryResponse {
+    fn from(err: UsecaseStarEntryError) -> Self {
+        let status = match &err {
+            UsecaseStarEntryError::FetchFeed(FetchFeedError::InvalidFeed(_)) => {
+                ResponseStatus::invalid_feed_url()
+            }
+            UsecaseStarEntryError::FetchFeed(
+                FetchFeedError::DisallowedByRobotsTxt | FetchFeedError::RetryAfter(_),
+            ) => ResponseStatus::feed_unavailable(),
+            UsecaseStarEntryError::EntryNotFound => ResponseStatus::entry_not_found(),
+            UsecaseStarEntryError::FeedNotSubscribed => ResponseStatus::feed_not_subscribed(),
+            UsecaseStarEntryError::FetchFeed(_) => ResponseStatus::internal(),
+        };
+        StarEntryResponse::Error(StarEntryError {
+            status,
+            message: format!("{err}"),
+        })
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum UnstarEntryResponse {
+    Success(UnstarEntrySuccess),
+    Error(UnstarEntryError),
+}
+
+pub(crate) struct UnstarEntrySuccess {
+    pub status: ResponseStatus,
+}
+
+#[Object]
+impl UnstarEntrySuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+}
+
+pub(crate) struct UnstarEntryError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl UnstarEntryError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for UnstarEntryResponse {
+    fn from(status: ResponseStatus) -> Self {
+        UnstarEntryResponse::Error(UnstarEntryError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<anyhow::Error> for UnstarEntryResponse {
+    fn from(err: anyhow::Error) -> Self {
+        UnstarEntryResponse::Error(UnstarEntryError {
+            status: ResponseStatus::internal(),
+            message: format!("{err}"),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::UnstarEntryOutput>> for UnstarEntryResponse {
+    fn from(_output: usecase::Output<usecase::UnstarEntryOutput>) -> Self {
+        UnstarEntryResponse::Success(UnstarEntrySuccess {
+            status: ResponseStatus::ok(),
+        })
+    }
+}
@@ -20,6 +20,12 @@ pub(crate) struct SubscribeFeedInput {
     pub requirement: Option<Requirement>,
     /// Feed category
     pub category: Option<Category<'static>>,
+    /// Title which overrides the feed title
+    pub title: Option<String>,
+    /// Note for the feed
+    pub note: Option<String>,
+    /// Tags in addition to the category
+    pub tags: Option<Vec<Category<'static>>>,
 }
 
 impl From<SubscribeFeedInput> for usecase::SubscribeFeedInput {
@@ -28,6 +34,9 @@ impl From<SubscribeFeedInput> for usecase::SubscribeFeedInput {
             url: value.url,
             requirement: value.requirement,
             category: value.category,
+            title: value.title,
+            note: value.note,
+            tags: value.tags.into_iter().flatten().collect(),
         }
     }
 }
@@ -105,6 +114,11 @@ impl From<UsecaseSubscribeFeedError> for SubscribeFeedError {
                     status: ResponseStatus::invalid_feed_url(),
                     message: format!("{kind}"),
                 },
+                fetch_err @ (FetchFeedError::DisallowedByRobotsTxt
+                | FetchFeedError::RetryAfter(_)) => Self {
+                    status: ResponseStatus::feed_unavailable(),
+                    message: format!("{fetch_err}"),
+                },
                 fetch_err => Self {
                     status: ResponseStatus::internal(),
                     message: format!("{fetch_err}"),
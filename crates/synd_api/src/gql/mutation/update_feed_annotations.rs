@@ -0,0 +1,152 @@
+use std::collections::BTreeSet;
+
+use async_graphql::{InputObject, MaybeUndefined, Object, Union};
+use synd_feed::types::{Category, FeedUrl, Requirement};
+
+use crate::{
+    gql::mutation::ResponseStatus,
+    repository::types::FeedAnnotations,
+    usecase::{self, Patch, UpdateFeedAnnotationsError as UsecaseUpdateFeedAnnotationsError},
+};
+
+/// Annotations to update. Omitted fields are kept and null clears the annotation
+#[derive(InputObject, Debug)]
+pub(crate) struct UpdateFeedAnnotationsInput {
+    /// Url of the subscribed feed
+    pub url: FeedUrl,
+    /// Requirement level for feed
+    pub requirement: MaybeUndefined<Requirement>,
+    /// Feed category
+    pub category: MaybeUndefined<Category<'static>>,
+    /// Title which overrides the feed title
+    pub title: MaybeUndefined<String>,
+    /// Note for the feed
+    pub note: MaybeUndefined<String>,
+    /// Replace all tags
+    pub tags: MaybeUndefined<Vec<Category<'static>>>,
+}
+
+fn patch<T>(value: MaybeUndefined<T>) -> Patch<T> {
+    match value {
+        MaybeUndefined::Undefined => Patch::Keep,
+        MaybeUndefined::Null => Patch::Clear,
+        MaybeUndefined::Value(value) => Patch::Set(value),
+    }
+}
+
+impl From<UpdateFeedAnnotationsInput> for usecase::UpdateFeedAnnotationsInput {
+    fn from(value: UpdateFeedAnnotationsInput) -> Self {
+        usecase::UpdateFeedAnnotationsInput {
+            url: value.url,
+            requirement: patch(value.requirement),
+            category: patch(value.category),
+            title: patch(value.title),
+            note: patch(value.note),
+            tags: match value.tags {
+                MaybeUndefined::Undefined => None,
+                MaybeUndefined::Null => Some(BTreeSet::new()),
+                MaybeUndefined::Value(tags) => Some(tags.into_iter().collect()),
+            },
+        }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum UpdateFeedAnnotationsResponse {
+    Success(UpdateFeedAnnotationsSuccess),
+    Error(UpdateFeedAnnotationsError),
+}
+
+pub(crate) struct UpdateFeedAnnotationsSuccess {
+    pub status: ResponseStatus,
+    pub url: FeedUrl,
+    pub annotations: FeedAnnotations,
+}
+
+#[Object]
+impl UpdateFeedAnnotationsSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Url of the updated feed
+    pub async fn url(&self) -> &FeedUrl {
+        &self.url
+    }
+
+    /// Requirement level for feed
+    pub async fn requirement(&self) -> Option<Requirement> {
+        self.annotations.requirement
+    }
+
+    /// Feed category
+    pub async fn category(&self) -> Option<&Category<'static>> {
+        self.annotations.category.as_ref()
+    }
+
+    /// Title which overrides the feed title
+    pub async fn custom_title(&self) -> Option<&str> {
+        self.annotations.title.as_deref()
+    }
+
+    /// Note for the feed
+    pub async fn note(&self) -> Option<&str> {
+        self.annotations.note.as_deref()
+    }
+
+    /// Tags in addition to the category
+    pub async fn tags(&self) -> Vec<&Category<'static>> {
+        self.annotations.tags.iter().collect()
+    }
+}
+
+pub(crate) struct UpdateFeedAnnotationsError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl UpdateFeedAnnotationsError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for UpdateFeedAnnotationsResponse {
+    fn from(status: ResponseStatus) -> Self {
+        UpdateFeedAnnotationsResponse::Error(UpdateFeedAnnotationsError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::UpdateFeedAnnotationsOutput>> for UpdateFeedAnnotationsResponse {
+    fn from(output: usecase::Output<usecase::UpdateFeedAnnotationsOutput>) -> Self {
+        let usecase::UpdateFeedAnnotationsOutput { url, annotations } = output.output;
+        UpdateFeedAnnotationsResponse::Success(UpdateFeedAnnotationsSuccess {
+            status: ResponseStatus::ok(),
+            url,
+            annotations,
+        })
+    }
+}
+
+impl From<UsecaseUpdateFeedAnnotationsError> for UpdateFeedAnnotationsResponse {
+    fn from(err: UsecaseUpdateFeedAnnotationsError) -> Self {
+        let status = match err {
+            UsecaseUpdateFeedAnnotationsError::NotSubscribed => {
+                ResponseStatus::feed_not_subscribed()
+            }
+        };
+        UpdateFeedAnnotationsResponse::Error(UpdateFeedAnnotationsError {
+            status,
+            message: format!("{err}"),
+        })
+    }
+}
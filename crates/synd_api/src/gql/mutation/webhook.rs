@@ -0,0 +1,185 @@
+use async_graphql::{InputObject, Object, Union};
+use synd_feed::types::{Category, Requirement};
+
+use crate::{
+    gql::{mutation::ResponseStatus, object},
+    usecase::{self, WebhookError as UsecaseWebhookError},
+};
+
+#[derive(InputObject, Debug)]
+pub(crate) struct CreateWebhookInput {
+    /// Url to which new entries are posted
+    pub url: String,
+    /// Key to sign payloads with HMAC-SHA256. Random one is generated if omitted
+    pub secret: Option<String>,
+    /// Only entries of the feeds which satisfy the requirement are delivered.
+    /// Feeds without requirement are treated as SHOULD
+    #[graphql(default_with = "Requirement::Must")]
+    pub requirement: Requirement,
+    /// Only entries of the feeds which belong to any of the categories are delivered.
+    /// All categories if empty
+    #[graphql(default)]
+    pub categories: Vec<Category<'static>>,
+    /// JSON payload whose string values can contain placeholders like `{{title}}`.
+    /// Slack compatible `{"text": ...}` payload is used if omitted
+    pub template: Option<String>,
+}
+
+impl From<CreateWebhookInput> for usecase::CreateWebhookInput {
+    fn from(value: CreateWebhookInput) -> Self {
+        usecase::CreateWebhookInput {
+            url: value.url,
+            secret: value.secret,
+            requirement: value.requirement,
+            categories: value.categories,
+            template: value.template,
+        }
+    }
+}
+
+#[derive(InputObject, Debug)]
+pub(crate) struct DeleteWebhookInput {
+    /// Id of the webhook to delete
+    pub id: String,
+}
+
+impl From<DeleteWebhookInput> for usecase::DeleteWebhookInput {
+    fn from(value: DeleteWebhookInput) -> Self {
+        usecase::DeleteWebhookInput { id: value.id }
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum CreateWebhookResponse {
+    Success(CreateWebhookSuccess),
+    Error(WebhookError),
+}
+
+pub(crate) struct CreateWebhookSuccess {
+    pub status: ResponseStatus,
+    pub webhook: object::Webhook,
+    pub secret: String,
+}
+
+#[Object]
+impl CreateWebhookSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Created webhook
+    pub async fn webhook(&self) -> &object::Webhook {
+        &self.webhook
+    }
+
+    /// Key to verify `X-Synd-Signature-256` header.
+    /// This cannot be retrieved again
+    pub async fn secret(&self) -> &str {
+        self.secret.as_str()
+    }
+}
+
+#[derive(Union)]
+pub(crate) enum DeleteWebhookResponse {
+    Success(DeleteWebhookSuccess),
+    Error(WebhookError),
+}
+
+pub(crate) struct DeleteWebhookSuccess {
+    pub status: ResponseStatus,
+    pub id: String,
+}
+
+#[Object]
+impl DeleteWebhookSuccess {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Id of the deleted webhook
+    pub async fn id(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+pub(crate) struct WebhookError {
+    pub status: ResponseStatus,
+    pub message: String,
+}
+
+#[Object]
+impl WebhookError {
+    pub async fn status(&self) -> ResponseStatus {
+        self.status.clone()
+    }
+
+    /// Error message
+    pub async fn message(&self) -> String {
+        self.message.clone()
+    }
+}
+
+impl From<ResponseStatus> for CreateWebhookResponse {
+    fn from(status: ResponseStatus) -> Self {
+        CreateWebhookResponse::Error(WebhookError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::CreateWebhookOutput>> for CreateWebhookResponse {
+    fn from(output: usecase::Output<usecase::CreateWebhookOutput>) -> Self {
+        let webhook = output.output.webhook;
+        CreateWebhookResponse::Success(CreateWebhookSuccess {
+            status: ResponseStatus::ok(),
+            secret: webhook.secret.clone(),
+            webhook: webhook.into(),
+        })
+    }
+}
+
+impl From<UsecaseWebhookError> for CreateWebhookResponse {
+    fn from(err: UsecaseWebhookError) -> Self {
+        CreateWebhookResponse::Error(err.into())
+    }
+}
+
+impl From<ResponseStatus> for DeleteWebhookResponse {
+    fn from(status: ResponseStatus) -> Self {
+        DeleteWebhookResponse::Error(WebhookError {
+            status,
+            message: "Unauthorized".into(),
+        })
+    }
+}
+
+impl From<usecase::Output<usecase::DeleteWebhookOutput>> for DeleteWebhookResponse {
+    fn from(output: usecase::Output<usecase::DeleteWebhookOutput>) -> Self {
+        DeleteWebhookResponse::Success(DeleteWebhookSuccess {
+            status: ResponseStatus::ok(),
+            id: output.output.id,
+        })
+    }
+}
+
+impl From<UsecaseWebhookError> for DeleteWebhookResponse {
+    fn from(err: UsecaseWebhookError) -> Self {
+        DeleteWebhookResponse::Error(err.into())
+    }
+}
+
+impl From<UsecaseWebhookError> for WebhookError {
+    fn from(err: UsecaseWebhookError) -> Self {
+        let status = match err {
+            UsecaseWebhookError::InvalidUrl(_)
+            | UsecaseWebhookError::LocalAddress(_)
+            | UsecaseWebhookError::InvalidTemplate(_) => ResponseStatus::invalid_webhook(),
+            UsecaseWebhookError::NotFound => ResponseStatus::webhook_not_found(),
+        };
+        WebhookError {
+            status,
+            message: format!("{err}"),
+        }
+    }
+}
@@ -1,7 +1,6 @@
-use std::convert::Infallible;
-
 use async_graphql::connection::CursorType;
-use synd_feed::types;
+
+use crate::usecase;
 
 pub(crate) struct FeedIdV1(String);
 
@@ -18,23 +17,23 @@ impl From<FeedIdV1> for async_graphql::ID {
     }
 }
 
-pub(in crate::gql) struct EntryId<'a>(types::EntryId<'a>);
+/// Opaque cursor of entries
+pub(in crate::gql) struct EntryCursor(usecase::EntryCursor);
 
-impl<'a> CursorType for EntryId<'a> {
-    type Error = Infallible;
+impl CursorType for EntryCursor {
+    type Error = usecase::InvalidCursor;
 
     fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
-        let s = s.to_string();
-        Ok(EntryId(s.into()))
+        usecase::EntryCursor::decode(s).map(EntryCursor)
     }
 
     fn encode_cursor(&self) -> String {
-        self.0.to_string()
+        self.0.encode()
     }
 }
 
-impl<'a> From<types::EntryId<'a>> for EntryId<'a> {
-    fn from(value: types::EntryId<'a>) -> Self {
+impl From<usecase::EntryCursor> for EntryCursor {
+    fn from(value: usecase::EntryCursor) -> Self {
         Self(value)
     }
 }
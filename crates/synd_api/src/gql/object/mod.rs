@@ -1,13 +1,19 @@
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, collections::HashMap, sync::Arc};
 
 use async_graphql::{
     connection::{Connection, ConnectionNameType, Edge, EdgeNameType, EmptyFields},
     Object, SimpleObject, ID,
 };
 use feed_rs::model as feedrs;
-use synd_feed::types::{self, Annotated, Category, FeedType, FeedUrl, Requirement};
+use synd_feed::{
+    feed::health::{self, FetchErrorKind},
+    types::{self, Annotated, Category, FeedType, FeedUrl, Requirement},
+};
 
-use crate::gql::scalar;
+use crate::{
+    gql::scalar,
+    repository::types::{self as repository, FeedReadState, FolderId, ReadStates},
+};
 
 use self::id::FeedIdV1;
 
@@ -34,13 +40,20 @@ impl From<feedrs::Link> for Link {
     }
 }
 
+#[allow(clippy::struct_field_names)]
 pub(crate) struct Entry<'a> {
     meta: Cow<'a, Annotated<types::FeedMeta>>,
     entry: types::Entry,
+    read: bool,
 }
 
 #[Object]
 impl<'a> Entry<'a> {
+    /// Entry id
+    async fn id(&self) -> String {
+        self.entry.id_ref().to_string()
+    }
+
     /// Feed of this entry
     async fn feed(&'a self) -> FeedMeta {
         self.meta.clone().into()
@@ -69,41 +82,151 @@ impl<'a> Entry<'a> {
     async fn website_url(&self) -> Option<&str> {
         self.entry.website_url(self.meta.feed.r#type())
     }
+
+    /// Whether the entry is already read
+    async fn read(&self) -> bool {
+        self.read
+    }
 }
 
 impl<'a> Entry<'a> {
     pub fn new(meta: Cow<'a, Annotated<types::FeedMeta>>, entry: types::Entry) -> Self {
-        Self { meta, entry }
+        Self {
+            meta,
+            entry,
+            read: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_read(self, read: bool) -> Self {
+        Self { read, ..self }
+    }
+}
+
+/// Snapshot of the entry taken when it was starred
+pub(crate) struct StarredEntry(repository::StarredEntry);
+
+#[Object]
+impl StarredEntry {
+    /// Entry id
+    async fn id(&self) -> &str {
+        self.0.entry_id.as_str()
+    }
+
+    /// Url of the feed to which the entry belongs
+    async fn feed_url(&self) -> &FeedUrl {
+        &self.0.feed_url
+    }
+
+    /// Title of the feed to which the entry belongs
+    async fn feed_title(&self) -> Option<&str> {
+        self.0.feed_title.as_deref()
+    }
+
+    /// Requirement level of the feed when the entry was starred
+    async fn requirement(&self) -> Option<Requirement> {
+        self.0.requirement
+    }
+
+    /// Category of the feed when the entry was starred
+    async fn category(&self) -> Option<&Category<'static>> {
+        self.0.category.as_ref()
+    }
+
+    /// Entry title
+    async fn title(&self) -> Option<&str> {
+        self.0.title.as_deref()
+    }
+
+    /// Time at which the entry was last modified
+    async fn updated(&self) -> Option<scalar::Rfc3339Time> {
+        self.0.updated.map(Into::into)
+    }
+
+    /// The time at which the entry published
+    async fn published(&self) -> Option<scalar::Rfc3339Time> {
+        self.0.published.map(Into::into)
+    }
+
+    /// Entry summary
+    async fn summary(&self) -> Option<&str> {
+        self.0.summary.as_deref()
+    }
+
+    /// Link to websiteurl at which this entry is published
+    async fn website_url(&self) -> Option<&str> {
+        self.0.website_url.as_deref()
+    }
+
+    /// The time at which the entry was starred
+    async fn starred_at(&self) -> scalar::Rfc3339Time {
+        self.0.starred_at.into()
     }
 }
 
-pub struct Feed(Annotated<Arc<types::Feed>>);
+impl From<repository::StarredEntry> for StarredEntry {
+    fn from(value: repository::StarredEntry) -> Self {
+        Self(value)
+    }
+}
+
+#[allow(clippy::struct_field_names)]
+pub struct Feed {
+    feed: Annotated<Arc<types::Feed>>,
+    read_state: Option<FeedReadState>,
+    folder_id: Option<FolderId>,
+    health: Option<health::FeedHealth>,
+}
+
+impl Feed {
+    /// Attach the read state of the feed from given read states
+    #[must_use]
+    pub fn with_read_states(mut self, read_states: &ReadStates) -> Self {
+        self.read_state = read_states.feed(self.feed.feed.meta().url()).cloned();
+        self
+    }
+
+    /// Attach the folder which the feed belongs to
+    #[must_use]
+    pub fn with_folders(mut self, feed_folders: &HashMap<FeedUrl, FolderId>) -> Self {
+        self.folder_id = feed_folders.get(self.feed.feed.meta().url()).copied();
+        self
+    }
+
+    /// Attach the health of the feed from given healths
+    #[must_use]
+    pub fn with_healths(mut self, healths: &HashMap<FeedUrl, health::FeedHealth>) -> Self {
+        self.health = healths.get(self.feed.feed.meta().url()).cloned();
+        self
+    }
+}
 
 #[Object]
 impl Feed {
     /// Feed Id
     async fn id(&self) -> ID {
-        FeedIdV1::new(self.0.feed.meta().url()).into()
+        FeedIdV1::new(self.feed.feed.meta().url()).into()
     }
 
     /// Undering feed specification
     async fn r#type(&self) -> FeedType {
-        self.0.feed.meta().r#type()
+        self.feed.feed.meta().r#type()
     }
 
     /// Feed title
     async fn title(&self) -> Option<&str> {
-        self.0.feed.meta().title()
+        self.feed.feed.meta().title()
     }
 
     /// Feed URL
     async fn url(&self) -> &FeedUrl {
-        self.0.feed.meta().url()
+        self.feed.feed.meta().url()
     }
 
     /// The time at which the feed was last modified
     async fn updated(&self) -> Option<scalar::Rfc3339Time> {
-        self.0.feed.meta().updated().map(Into::into)
+        self.feed.feed.meta().updated().map(Into::into)
     }
 
     /// Feed entries
@@ -121,12 +244,18 @@ impl Feed {
     > {
         #[allow(clippy::cast_sign_loss)]
         let first = first.unwrap_or(5).max(0) as usize;
-        let meta = self.0.project(|feed| feed.meta().clone());
+        let meta = self.feed.project(|feed| feed.meta().clone());
         let entries = self
-            .0
+            .feed
             .feed
             .entries()
-            .map(move |entry| Entry::new(Cow::Owned(meta.clone()), entry.clone()))
+            .map(move |entry| {
+                let read = self
+                    .read_state
+                    .as_ref()
+                    .is_some_and(|read_state| read_state.is_read(entry));
+                Entry::new(Cow::Owned(meta.clone()), entry.clone()).with_read(read)
+            })
             .take(first)
             .collect::<Vec<_>>();
 
@@ -145,7 +274,7 @@ impl Feed {
     async fn authors(&self) -> Connection<usize, String> {
         let mut c = Connection::new(false, false);
         c.edges.extend(
-            self.0
+            self.feed
                 .feed
                 .meta()
                 .authors()
@@ -158,13 +287,13 @@ impl Feed {
 
     /// Description of feed
     async fn description(&self) -> Option<&str> {
-        self.0.feed.meta().description()
+        self.feed.feed.meta().description()
     }
 
     async fn links(&self) -> Connection<usize, Link> {
         let mut c = Connection::new(false, false);
         c.edges.extend(
-            self.0
+            self.feed
                 .feed
                 .meta()
                 .links()
@@ -177,21 +306,257 @@ impl Feed {
     }
 
     async fn website_url(&self) -> Option<&str> {
-        self.0.feed.meta().website_url()
+        self.feed.feed.meta().website_url()
     }
 
     async fn generator(&self) -> Option<&str> {
-        self.0.feed.meta().generator()
+        self.feed.feed.meta().generator()
     }
 
     /// Requirement level for feed
     async fn requirement(&self) -> Option<Requirement> {
-        self.0.requirement
+        self.feed.requirement
     }
 
     /// Feed category
     async fn category(&self) -> Option<&Category<'static>> {
-        self.0.category.as_ref()
+        self.feed.category.as_ref()
+    }
+
+    /// Title given by the user which overrides the feed title
+    async fn custom_title(&self) -> Option<&str> {
+        self.feed.title.as_deref()
+    }
+
+    /// Note by the user
+    async fn note(&self) -> Option<&str> {
+        self.feed.note.as_deref()
+    }
+
+    /// Tags in addition to the category
+    async fn tags(&self) -> Vec<&Category<'static>> {
+        self.feed.tags.iter().collect()
+    }
+
+    /// Number of entries which are not read
+    async fn unread_count(&self) -> usize {
+        FeedReadState::count_unread(self.read_state.as_ref(), self.feed.feed.entries())
+    }
+
+    /// Id of the folder which the feed belongs to. null if the feed is at the top level
+    async fn folder_id(&self) -> Option<FolderId> {
+        self.folder_id
+    }
+
+    /// Outcomes of fetching the feed since the server started.
+    /// null if the feed has not been fetched by the server
+    async fn health(&self) -> Option<FeedHealth<'_>> {
+        self.health.as_ref().map(FeedHealth)
+    }
+}
+
+/// Accumulated outcomes of fetching the feed
+pub(crate) struct FeedHealth<'a>(&'a health::FeedHealth);
+
+#[Object]
+impl<'a> FeedHealth<'a> {
+    /// Time of the last successful fetch
+    async fn last_success(&self) -> Option<scalar::Rfc3339Time> {
+        self.0.last_success.map(Into::into)
+    }
+
+    /// Time of the last failed fetch
+    async fn last_failure(&self) -> Option<scalar::Rfc3339Time> {
+        self.0.last_failure.map(Into::into)
+    }
+
+    /// Kind of the last error. null if the last fetch succeeded
+    async fn last_error(&self) -> Option<FetchErrorKind> {
+        self.0.last_error
+    }
+
+    /// Number of failures since the last success
+    async fn consecutive_failures(&self) -> u32 {
+        self.0.consecutive_failures
+    }
+
+    /// HTTP status of the last response
+    async fn last_http_status(&self) -> Option<u16> {
+        self.0.last_http_status
+    }
+
+    /// Average latency of the requests in milliseconds
+    async fn average_latency_ms(&self) -> Option<u64> {
+        self.0
+            .average_latency
+            .map(|latency| latency.as_millis().try_into().unwrap_or(u64::MAX))
+    }
+}
+
+/// Named group of subscribed feeds
+pub(crate) struct Folder(repository::Folder);
+
+#[Object]
+impl Folder {
+    /// Folder id
+    async fn id(&self) -> FolderId {
+        self.0.id
+    }
+
+    /// Folder name
+    async fn name(&self) -> &str {
+        self.0.name.as_str()
+    }
+
+    /// Id of the folder which contains this folder. null if the folder is at the top level
+    async fn parent_id(&self) -> Option<FolderId> {
+        self.0.parent
+    }
+}
+
+impl From<repository::Folder> for Folder {
+    fn from(value: repository::Folder) -> Self {
+        Self(value)
+    }
+}
+
+/// Personal access token. The secret is never returned after creation
+pub(crate) struct ApiToken(repository::ApiToken);
+
+#[Object]
+impl ApiToken {
+    /// Token id
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    /// Name given to identify the token
+    async fn name(&self) -> &str {
+        self.0.name.as_str()
+    }
+
+    /// Whether the token can only be used for queries
+    async fn read_only(&self) -> bool {
+        self.0.scope == repository::ApiTokenScope::ReadOnly
+    }
+
+    /// The time at which the token was created
+    async fn created_at(&self) -> scalar::Rfc3339Time {
+        self.0.created_at.into()
+    }
+
+    /// The time at which the token expires. null if the token never expires
+    async fn expires_at(&self) -> Option<scalar::Rfc3339Time> {
+        self.0.expires_at.map(Into::into)
+    }
+}
+
+impl From<repository::ApiToken> for ApiToken {
+    fn from(value: repository::ApiToken) -> Self {
+        Self(value)
+    }
+}
+
+pub(crate) struct Webhook(repository::Webhook);
+
+#[Object]
+impl Webhook {
+    /// Webhook id
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    /// Url to which new entries are posted
+    async fn url(&self) -> &str {
+        self.0.url.as_str()
+    }
+
+    /// Only entries of the feeds which satisfy the requirement are delivered
+    async fn requirement(&self) -> Requirement {
+        self.0.requirement
+    }
+
+    /// Only entries of the feeds which belong to any of the categories are delivered.
+    /// Empty means all categories
+    async fn categories(&self) -> &[Category<'static>] {
+        self.0.categories.as_slice()
+    }
+
+    /// JSON payload template. null if the default payload is used
+    async fn template(&self) -> Option<String> {
+        self.0.template.as_ref().map(ToString::to_string)
+    }
+
+    /// The time at which the webhook was created
+    async fn created_at(&self) -> scalar::Rfc3339Time {
+        self.0.created_at.into()
+    }
+}
+
+impl From<repository::Webhook> for Webhook {
+    fn from(value: repository::Webhook) -> Self {
+        Self(value)
+    }
+}
+
+pub(crate) struct WebhookDelivery(repository::WebhookDelivery);
+
+#[Object]
+impl WebhookDelivery {
+    /// Delivery id which is sent in `X-Synd-Delivery` header
+    async fn id(&self) -> &str {
+        self.0.id.as_str()
+    }
+
+    /// Id of the webhook
+    async fn webhook_id(&self) -> &str {
+        self.0.webhook_id.as_str()
+    }
+
+    /// Url of the feed of the delivered entry
+    async fn feed_url(&self) -> &FeedUrl {
+        &self.0.feed_url
+    }
+
+    /// Id of the delivered entry
+    async fn entry_id(&self) -> &str {
+        self.0.entry_id.as_str()
+    }
+
+    /// Title of the delivered entry
+    async fn entry_title(&self) -> Option<&str> {
+        self.0.entry_title.as_deref()
+    }
+
+    /// Whether the receiver accepted the payload
+    async fn succeeded(&self) -> bool {
+        self.0.succeeded()
+    }
+
+    /// Number of requests including retries
+    async fn attempts(&self) -> u32 {
+        self.0.attempts
+    }
+
+    /// Http status of the last response. null if no response was received
+    async fn status_code(&self) -> Option<u16> {
+        self.0.status_code
+    }
+
+    /// Error of the last attempt. null if the delivery succeeded
+    async fn error(&self) -> Option<&str> {
+        self.0.error.as_deref()
+    }
+
+    /// The time at which the delivery finished
+    async fn delivered_at(&self) -> scalar::Rfc3339Time {
+        self.0.delivered_at.into()
+    }
+}
+
+impl From<repository::WebhookDelivery> for WebhookDelivery {
+    fn from(value: repository::WebhookDelivery) -> Self {
+        Self(value)
     }
 }
 
@@ -213,7 +578,12 @@ impl EdgeNameType for FeedEntryEdgeName {
 
 impl From<Annotated<Arc<types::Feed>>> for Feed {
     fn from(value: Annotated<Arc<types::Feed>>) -> Self {
-        Self(value)
+        Self {
+            feed: value,
+            read_state: None,
+            folder_id: None,
+            health: None,
+        }
     }
 }
 
@@ -240,6 +610,16 @@ impl<'a> FeedMeta<'a> {
     async fn category(&self) -> Option<&Category<'static>> {
         self.0.category.as_ref()
     }
+
+    /// Title given by the user which overrides the feed title
+    async fn custom_title(&self) -> Option<&str> {
+        self.0.title.as_deref()
+    }
+
+    /// Tags of the feed in addition to the category
+    async fn tags(&self) -> Vec<&Category<'static>> {
+        self.0.tags.iter().collect()
+    }
 }
 
 impl<'a> From<Cow<'a, Annotated<types::FeedMeta>>> for FeedMeta<'a> {
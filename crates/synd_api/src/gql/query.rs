@@ -4,23 +4,32 @@ use async_graphql::{
     connection::{Connection, Edge},
     Context, Object, Result, SimpleObject,
 };
-use synd_feed::types::FeedUrl;
+use synd_feed::types::{Category, FeedUrl, Requirement};
 
 use crate::{
     gql::{
-        object::{self, id, Entry},
+        admin::Admin,
+        object::{self, id, ApiToken, Entry, StarredEntry, Webhook, WebhookDelivery},
         run_usecase,
+        scalar::Rfc3339Time,
     },
     usecase::{
-        FetchEntries, FetchEntriesError, FetchEntriesInput, FetchEntriesOutput,
-        FetchSubscribedFeeds, FetchSubscribedFeedsError, FetchSubscribedFeedsInput,
-        FetchSubscribedFeedsOutput, Output,
+        EntryCursor, FetchApiTokens, FetchApiTokensError, FetchApiTokensInput,
+        FetchApiTokensOutput, FetchEntries, FetchEntriesError, FetchEntriesInput,
+        FetchEntriesOutput, FetchStarredEntries, FetchStarredEntriesError,
+        FetchStarredEntriesInput, FetchStarredEntriesOutput, FetchSubscribedFeeds,
+        FetchSubscribedFeedsError, FetchSubscribedFeedsInput, FetchSubscribedFeedsOutput,
+        FetchWebhookDeliveries, FetchWebhookDeliveriesError, FetchWebhookDeliveriesInput,
+        FetchWebhookDeliveriesOutput, FetchWebhooks, FetchWebhooksError, FetchWebhooksInput,
+        FetchWebhooksOutput, Output, StarredEntryCursor, SubscriptionFilter,
     },
 };
 
 #[derive(SimpleObject)]
 struct FeedsConnectionFields {
     errors: Vec<FetchFeedError>,
+    /// All folders of the user regardless of pagination
+    folders: Vec<object::Folder>,
 }
 
 #[derive(SimpleObject)]
@@ -29,16 +38,53 @@ struct FetchFeedError {
     error_message: String,
 }
 
+/// Build filter from the arguments of `Subscription.feeds` and `Subscription.entries`
+fn subscription_filter(
+    requirement: Option<Requirement>,
+    categories: Option<Vec<Category<'static>>>,
+    feed_urls: Option<Vec<FeedUrl>>,
+    since: Option<Rfc3339Time>,
+    until: Option<Rfc3339Time>,
+    query: Option<String>,
+) -> SubscriptionFilter {
+    SubscriptionFilter {
+        requirement,
+        categories,
+        feed_urls,
+        since: since.map(Into::into),
+        until: until.map(Into::into),
+        query,
+    }
+}
+
 struct Subscription;
 
 #[Object]
 impl Subscription {
-    /// Return Subscribed feeds
+    /// Return Subscribed feeds in the order defined by the user.
+    /// Filters are applied before pagination
+    #[allow(clippy::too_many_arguments)]
     async fn feeds(
         &self,
         cx: &Context<'_>,
         after: Option<String>,
         #[graphql(default = 20)] first: Option<i32>,
+        #[graphql(
+            desc = "Only feeds which satisfy the requirement. Feeds without requirement are treated as SHOULD"
+        )]
+        requirement: Option<Requirement>,
+        #[graphql(desc = "Only feeds which belong to any of the categories")] categories: Option<
+            Vec<Category<'static>>,
+        >,
+        #[graphql(desc = "Only the feeds of the urls")] feed_urls: Option<Vec<FeedUrl>>,
+        #[graphql(desc = "Only feeds which have entries published at or after the time")]
+        since: Option<Rfc3339Time>,
+        #[graphql(desc = "Only feeds which have entries published at or before the time")]
+        until: Option<Rfc3339Time>,
+        #[graphql(
+            desc = "Whitespace separated keywords which titles should contain, case insensitive"
+        )]
+        query: Option<String>,
     ) -> Result<Connection<String, object::Feed, FeedsConnectionFields>> {
         #[allow(clippy::cast_sign_loss)]
         let first = first.unwrap_or(10).min(100) as usize;
@@ -46,9 +92,17 @@ impl Subscription {
         let input = FetchSubscribedFeedsInput {
             after,
             first: first + 1,
+            filter: subscription_filter(requirement, categories, feed_urls, since, until, query),
         };
         let Output {
-            output: FetchSubscribedFeedsOutput { feeds },
+            output:
+                FetchSubscribedFeedsOutput {
+                    feeds,
+                    read_states,
+                    folders,
+                    feed_folders,
+                    healths,
+                },
         } = run_usecase!(
             FetchSubscribedFeeds,
             cx,
@@ -70,6 +124,7 @@ impl Subscription {
                     }
                 })
                 .collect::<Vec<_>>(),
+            folders: folders.into_iter().map(object::Folder::from).collect(),
         };
         let mut connection = Connection::with_additional_fields(has_prev, has_next, fields);
 
@@ -78,7 +133,15 @@ impl Subscription {
             .take(first)
             .map(Result::unwrap)
             .map(|feed| (feed.feed.meta().url().as_str().to_owned(), feed))
-            .map(|(cursor, feed)| (cursor, object::Feed::from(feed)))
+            .map(|(cursor, feed)| {
+                (
+                    cursor,
+                    object::Feed::from(feed)
+                        .with_read_states(&read_states)
+                        .with_folders(&feed_folders)
+                        .with_healths(&healths),
+                )
+            })
             .map(|(cursor, feed)| Edge::new(cursor, feed));
 
         connection.edges.extend(edges);
@@ -86,41 +149,171 @@ impl Subscription {
         Ok(connection)
     }
 
+    /// Return personal access tokens in the order of creation
+    async fn api_tokens(&self, cx: &Context<'_>) -> Result<Vec<ApiToken>> {
+        let Output {
+            output: FetchApiTokensOutput { tokens },
+        } = run_usecase!(
+            FetchApiTokens,
+            cx,
+            FetchApiTokensInput {},
+            |err: FetchApiTokensError| Err(async_graphql::ErrorExtensions::extend(&err))
+        )?;
+
+        Ok(tokens.into_iter().map(ApiToken::from).collect())
+    }
+
+    /// Return webhooks in the order of creation
+    async fn webhooks(&self, cx: &Context<'_>) -> Result<Vec<Webhook>> {
+        let Output {
+            output: FetchWebhooksOutput { webhooks },
+        } = run_usecase!(
+            FetchWebhooks,
+            cx,
+            FetchWebhooksInput {},
+            |err: FetchWebhooksError| Err(async_graphql::ErrorExtensions::extend(&err))
+        )?;
+
+        Ok(webhooks.into_iter().map(Webhook::from).collect())
+    }
+
+    /// Return recent webhook deliveries, most recent first
+    async fn webhook_deliveries(
+        &self,
+        cx: &Context<'_>,
+        #[graphql(desc = "Only deliveries of the webhook")] webhook_id: Option<String>,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let Output {
+            output: FetchWebhookDeliveriesOutput { deliveries },
+        } = run_usecase!(
+            FetchWebhookDeliveries,
+            cx,
+            FetchWebhookDeliveriesInput { webhook_id },
+            |err: FetchWebhookDeliveriesError| Err(async_graphql::ErrorExtensions::extend(&err))
+        )?;
+
+        Ok(deliveries.into_iter().map(WebhookDelivery::from).collect())
+    }
+
     /// Return subscribed latest entries order by published time.
+    /// Filters are applied before pagination
+    #[allow(clippy::too_many_arguments)]
     async fn entries<'cx>(
+        &self,
+        cx: &Context<'_>,
+        #[graphql(desc = "Return entries after the cursor")] after: Option<String>,
+        #[graphql(desc = "Return entries before the cursor")] before: Option<String>,
+        #[graphql(desc = "Return the first n entries. 20 if neither first nor last is given")]
+        first: Option<i32>,
+        #[graphql(desc = "Return the last n entries")] last: Option<i32>,
+        #[graphql(default = false, desc = "Return only entries which are not read")]
+        unread_only: bool,
+        #[graphql(
+            desc = "Only feeds which satisfy the requirement. Feeds without requirement are treated as SHOULD"
+        )]
+        requirement: Option<Requirement>,
+        #[graphql(desc = "Only feeds which belong to any of the categories")] categories: Option<
+            Vec<Category<'static>>,
+        >,
+        #[graphql(desc = "Only the feeds of the urls")] feed_urls: Option<Vec<FeedUrl>>,
+        #[graphql(desc = "Only entries published at or after the time")] since: Option<Rfc3339Time>,
+        #[graphql(desc = "Only entries published at or before the time")] until: Option<
+            Rfc3339Time,
+        >,
+        #[graphql(
+            desc = "Whitespace separated keywords which titles should contain, case insensitive"
+        )]
+        query: Option<String>,
+    ) -> Result<Connection<id::EntryCursor, Entry<'cx>>> {
+        #[allow(clippy::cast_sign_loss)]
+        let limit = |n: i32| n.clamp(0, 200) as usize;
+        let decode = |cursor: Option<String>| {
+            cursor
+                .as_deref()
+                .map(EntryCursor::decode)
+                .transpose()
+                .map_err(|err| async_graphql::Error::new(err.to_string()))
+        };
+        let first = match (first, last) {
+            (None, None) => Some(20),
+            (first, _) => first.map(limit),
+        };
+        let input = FetchEntriesInput {
+            after: decode(after)?,
+            before: decode(before)?,
+            first,
+            last: last.map(limit),
+            filter: subscription_filter(requirement, categories, feed_urls, since, until, query),
+            unread_only,
+        };
+        let Output {
+            output:
+                FetchEntriesOutput {
+                    entries,
+                    feeds,
+                    read_states,
+                    has_previous_page,
+                    has_next_page,
+                },
+        } = run_usecase!(FetchEntries, cx, input, |err: FetchEntriesError| Err(
+            async_graphql::ErrorExtensions::extend(&err)
+        ))?;
+
+        let mut connection = Connection::new(has_previous_page, has_next_page);
+
+        let edges = entries.into_iter().map(move |(entry, feed_url)| {
+            let meta = feeds
+                .get(&feed_url)
+                .expect("FeedMeta not found. this is a bug")
+                .clone();
+            let cursor = EntryCursor::new(&entry, &feed_url).into();
+            let read = read_states.is_read(&feed_url, &entry);
+            let node = Entry::new(Cow::Owned(meta), entry).with_read(read);
+            Edge::new(cursor, node)
+        });
+
+        connection.edges.extend(edges);
+
+        Ok(connection)
+    }
+
+    /// Return starred entries order by the time they were starred.
+    async fn starred_entries(
         &self,
         cx: &Context<'_>,
         after: Option<String>,
         #[graphql(default = 20)] first: Option<i32>,
-    ) -> Result<Connection<id::EntryId, Entry<'cx>>> {
+    ) -> Result<Connection<String, StarredEntry>> {
         #[allow(clippy::cast_sign_loss)]
         let first = first.unwrap_or(20).min(200) as usize;
         let has_prev = after.is_some();
-        let input = FetchEntriesInput {
-            after: after.map(Into::into),
+        let after = after
+            .as_deref()
+            .map(StarredEntryCursor::decode)
+            .transpose()
+            .map_err(|err| async_graphql::Error::new(err.to_string()))?;
+        let input = FetchStarredEntriesInput {
+            after,
             first: first + 1,
         };
         let Output {
-            output: FetchEntriesOutput { entries, feeds },
-        } = run_usecase!(FetchEntries, cx, input, |err: FetchEntriesError| Err(
-            async_graphql::ErrorExtensions::extend(&err)
-        ))?;
+            output: FetchStarredEntriesOutput { entries },
+        } = run_usecase!(
+            FetchStarredEntries,
+            cx,
+            input,
+            |err: FetchStarredEntriesError| Err(async_graphql::ErrorExtensions::extend(&err))
+        )?;
 
         let has_next = entries.len() > first;
         let mut connection = Connection::new(has_prev, has_next);
 
-        let edges = entries
-            .into_iter()
-            .take(first)
-            .map(move |(entry, feed_url)| {
-                let meta = feeds
-                    .get(&feed_url)
-                    .expect("FeedMeta not found. this is a bug")
-                    .clone();
-                let cursor = entry.id().into();
-                let node = Entry::new(Cow::Owned(meta), entry);
-                Edge::new(cursor, node)
-            });
+        let edges = entries.into_iter().take(first).map(|entry| {
+            Edge::new(
+                StarredEntryCursor::new(&entry).encode(),
+                StarredEntry::from(entry),
+            )
+        });
 
         connection.edges.extend(edges);
 
@@ -135,4 +328,9 @@ impl Query {
     async fn subscription(&self) -> Subscription {
         Subscription {}
     }
+
+    /// Administrative queries. Fields respond with the unauthorized error unless the principal is an admin
+    async fn admin(&self) -> Admin {
+        Admin {}
+    }
 }
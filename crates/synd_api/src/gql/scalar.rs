@@ -2,6 +2,7 @@ use async_graphql::{InputValueError, Scalar, ScalarType, Value};
 use chrono::Utc;
 
 /// RFC3339 Time
+#[derive(Debug)]
 pub struct Rfc3339Time(synd_feed::types::Time);
 
 #[Scalar]
@@ -27,3 +28,9 @@ impl From<synd_feed::types::Time> for Rfc3339Time {
         Self(value)
     }
 }
+
+impl From<Rfc3339Time> for synd_feed::types::Time {
+    fn from(value: Rfc3339Time) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,41 @@
+use std::borrow::Cow;
+
+use async_graphql::{Context, Result, Subscription};
+use futures_util::{Stream, StreamExt};
+
+use crate::{
+    gql::{object::Entry, run_usecase},
+    usecase::{
+        NewEntries, Output, WatchNewEntries, WatchNewEntriesError, WatchNewEntriesInput,
+        WatchNewEntriesOutput,
+    },
+};
+
+// `Subscription` is already used for the subscribed feeds of the user
+pub(crate) struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Emit new entries whenever a subscribed feed is updated.
+    /// Entries are emitted per feed, so all entries in an event belong to the same feed
+    async fn new_entries(
+        &self,
+        cx: &Context<'_>,
+    ) -> Result<impl Stream<Item = Vec<Entry<'static>>>> {
+        let Output {
+            output: WatchNewEntriesOutput { new_entries },
+        } = run_usecase!(
+            WatchNewEntries,
+            cx,
+            WatchNewEntriesInput,
+            |err: WatchNewEntriesError| Err(async_graphql::ErrorExtensions::extend(&err))
+        )?;
+
+        Ok(new_entries.map(|NewEntries { feed, entries }| {
+            entries
+                .into_iter()
+                .map(|entry| Entry::new(Cow::Owned(feed.clone()), entry))
+                .collect()
+        }))
+    }
+}
@@ -2,10 +2,13 @@
 #![allow(clippy::new_without_default)]
 #![warn(rustdoc::broken_intra_doc_links)]
 
+pub mod admin;
 pub mod args;
 pub mod client;
 pub mod config;
 pub mod dependency;
+#[cfg(test)]
+mod fixture;
 pub(crate) mod gql;
 pub mod monitor;
 pub(crate) mod principal;
@@ -13,3 +16,4 @@ pub mod repository;
 pub mod serve;
 pub mod shutdown;
 pub mod usecase;
+pub mod webhook;
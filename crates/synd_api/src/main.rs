@@ -1,5 +1,6 @@
 use std::env;
 
+use anyhow::Context as _;
 use fdlimit::Outcome;
 use synd_o11y::{
     opentelemetry::OpenTelemetryGuard, tracing_subscriber::otel_metrics::metrics_event_filter,
@@ -7,7 +8,8 @@ use synd_o11y::{
 use tracing::{error, info};
 
 use synd_api::{
-    args::{self, Args, ObservabilityOptions},
+    admin,
+    args::{self, Args, Command, ObservabilityOptions},
     config,
     dependency::Dependency,
     repository::kvsd::ConnectKvsdFailed,
@@ -70,18 +72,46 @@ fn init_tracing(options: &ObservabilityOptions) -> Option<OpenTelemetryGuard> {
     otel_guard
 }
 
+/// Admin commands may write their output to stdout, so logs are written to stderr
+fn init_admin_tracing() {
+    use tracing_subscriber::filter::EnvFilter;
+
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(EnvFilter::try_from_env("SYND_LOG").unwrap_or_else(|_| "info".into()))
+        .init();
+}
+
 async fn run(
     Args {
+        command: _,
+        repository,
         kvsd,
         bind,
         serve,
         tls,
         o11y,
         cache,
+        syndication,
+        auth,
+        quota,
+        feed,
     }: Args,
     shutdown: Shutdown,
 ) -> anyhow::Result<()> {
-    let dep = Dependency::new(kvsd, tls, serve, cache.clone()).await?;
+    let tls = tls.context("tls options are required")?;
+    let dep = Dependency::new(
+        repository,
+        kvsd,
+        tls,
+        serve,
+        cache.clone(),
+        syndication,
+        auth,
+        quota,
+        feed,
+    )
+    .await?;
 
     info!(
         version = config::VERSION,
@@ -89,6 +119,7 @@ async fn run(
         request_timeout=?dep.serve_options.timeout,
         request_body_limit_bytes=dep.serve_options.body_limit_bytes,
         concurrency_limit=?dep.serve_options.concurrency_limit,
+        rate_limit=?dep.serve_options.rate_limit,
         feed_cache_ttl_minutes=?cache.feed_cache_ttl.as_secs() / 60,
         feed_cache_refresh_interval_minutes=?cache.feed_cache_refresh_interval.as_secs() / 60,
         "Runinng...",
@@ -117,6 +148,14 @@ async fn main() {
         Ok(args) => args,
         Err(err) => err.exit(),
     };
+    if let Some(Command::Admin(command)) = args.command {
+        init_admin_tracing();
+        if let Err(err) = admin::run(command).await {
+            error!("{err:?}");
+            std::process::exit(1);
+        }
+        return;
+    }
     let _guard = init_tracing(&args.o11y);
     let shutdown = Shutdown::watch_signal();
 
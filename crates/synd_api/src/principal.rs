@@ -1,7 +1,6 @@
-use std::{
-    collections::hash_map::DefaultHasher,
-    hash::{Hash, Hasher},
-};
+use sha2::{Digest, Sha256};
+
+use crate::serve::auth::{ApiTokenBackend, GoogleLoginBackend};
 
 #[derive(Clone, Debug)]
 pub enum Principal {
@@ -15,30 +14,224 @@ impl Principal {
             Principal::User(User { id, .. }) => Some(id.as_str()),
         }
     }
+
+    /// Return true if the principal is not allowed to modify the data(e.g. read only api token)
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            Principal::User(User { read_only, .. }) => *read_only,
+        }
+    }
+
+    #[must_use]
+    pub fn with_role(self, role: Role) -> Self {
+        match self {
+            Principal::User(user) => Principal::User(user.with_role(role)),
+        }
+    }
+
+    /// Return true if the principal can run administrative usecases
+    pub fn is_admin(&self) -> bool {
+        match self {
+            Principal::User(User { role, .. }) => *role == Role::Admin,
+        }
+    }
+
+    #[must_use]
+    pub fn with_auth_scheme(self, scheme: &'static str) -> Self {
+        match self {
+            Principal::User(user) => Principal::User(user.with_auth_scheme(scheme)),
+        }
+    }
+
+    /// `Authorization` scheme by which the principal is authenticated.
+    /// None if authenticated by other means(e.g. syndication token)
+    pub fn auth_scheme(&self) -> Option<&'static str> {
+        match self {
+            Principal::User(User { auth_scheme, .. }) => *auth_scheme,
+        }
+    }
+
+    /// Return true if the principal is authenticated by a personal access token.
+    /// Session tokens issued on login are not personal access tokens
+    pub fn is_api_token(&self) -> bool {
+        match self {
+            Principal::User(User { session, .. }) => {
+                !*session
+                    && matches!(
+                        self.auth_scheme(),
+                        Some(ApiTokenBackend::SCHEME | GoogleLoginBackend::SCHEME)
+                    )
+            }
+        }
+    }
+}
+
+/// Role of the user which determines runnable usecases
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Role {
+    #[default]
+    User,
+    /// Operators who support users(e.g. inspect subscriptions, refresh feeds)
+    Admin,
 }
 
 #[derive(Clone, Debug)]
 pub struct User {
     id: String,
-    #[allow(dead_code)]
-    email: String,
+    /// Id derived by the hash before the migration to sha256.
+    /// Data stored under this id is moved to `id` on authentication
+    legacy_id: Option<String>,
+    email: Option<String>,
+    read_only: bool,
+    /// Authenticated by the session token issued in exchange for the password
+    session: bool,
+    role: Role,
+    auth_scheme: Option<&'static str>,
 }
 
 impl User {
     pub fn from_email(email: impl Into<String>) -> Self {
-        let mut s = DefaultHasher::new();
         let email = email.into();
 
-        email.hash(&mut s);
-        let id = s.finish();
-        let id = format!("{id:016x}");
+        User {
+            id: hash_id(&email),
+            legacy_id: Some(legacy_hash_id(&email)),
+            email: Some(email),
+            read_only: false,
+            session: false,
+            role: Role::User,
+            auth_scheme: None,
+        }
+    }
+
+    /// Construct user authenticated by the local provider without email.
+    /// Username is namespaced so that it never collides with emails
+    pub fn from_local_username(username: &str) -> Self {
+        let username = format!("local:{username}");
+        User {
+            id: hash_id(&username),
+            legacy_id: Some(legacy_hash_id(&username)),
+            email: None,
+            read_only: false,
+            session: false,
+            role: Role::User,
+            auth_scheme: None,
+        }
+    }
+
+    /// Construct user from already resolved id.
+    /// Used when the request is authenticated without email(e.g. syndication token)
+    pub fn from_id(id: impl Into<String>) -> Self {
+        User {
+            id: id.into(),
+            legacy_id: None,
+            email: None,
+            read_only: false,
+            session: false,
+            role: Role::User,
+            auth_scheme: None,
+        }
+    }
+
+    /// Restrict the user to read only usecases
+    #[must_use]
+    pub fn with_read_only(self, read_only: bool) -> Self {
+        Self { read_only, ..self }
+    }
+
+    #[must_use]
+    pub fn with_session(self, session: bool) -> Self {
+        Self { session, ..self }
+    }
+
+    #[must_use]
+    pub fn with_role(self, role: Role) -> Self {
+        Self { role, ..self }
+    }
 
-        User { id, email }
+    #[must_use]
+    pub fn with_auth_scheme(self, scheme: &'static str) -> Self {
+        Self {
+            auth_scheme: Some(scheme),
+            ..self
+        }
     }
 
     pub fn id(&self) -> &str {
         self.id.as_str()
     }
+
+    pub fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+
+    pub fn legacy_id(&self) -> Option<&str> {
+        self.legacy_id.as_deref()
+    }
+}
+
+/// Derive the user id from the first 8 bytes of sha256 so that the id is stable across toolchains
+fn hash_id(value: &str) -> String {
+    let digest = Sha256::digest(value.as_bytes());
+    hex::encode(&digest[..8])
+}
+
+/// Derive the user id in the way before the migration to [`hash_id`].
+/// Ids were derived by `DefaultHasher` which is not guaranteed to be stable across Rust releases,
+/// so the algorithm at that time is reproduced to find the data to be migrated
+fn legacy_hash_id(value: &str) -> String {
+    // `str` is hashed with the trailing 0xff to be prefix free
+    let mut message = Vec::with_capacity(value.len() + 1);
+    message.extend_from_slice(value.as_bytes());
+    message.push(0xff);
+    format!("{:016x}", sip_hash_1_3(&message))
+}
+
+/// SipHash-1-3 with zero keys, which `DefaultHasher::new()` was
+fn sip_hash_1_3(message: &[u8]) -> u64 {
+    fn round(v: &mut [u64; 4]) {
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    }
+    let compress = |v: &mut [u64; 4], m: u64| {
+        v[3] ^= m;
+        round(v);
+        v[0] ^= m;
+    };
+
+    let mut v = [
+        0x736f_6d65_7073_6575,
+        0x646f_7261_6e64_6f6d,
+        0x6c79_6765_6e65_7261,
+        0x7465_6462_7974_6573,
+    ];
+    let chunks = message.chunks_exact(8);
+    let tail = chunks.remainder();
+    for chunk in chunks {
+        compress(&mut v, u64::from_le_bytes(chunk.try_into().unwrap()));
+    }
+    // The last block holds the remaining bytes and the lowest byte of the length
+    let last = tail
+        .iter()
+        .enumerate()
+        .fold((message.len() as u64) << 56, |last, (i, byte)| {
+            last | u64::from(*byte) << (8 * i)
+        });
+    compress(&mut v, last);
+
+    v[2] ^= 0xff;
+    for _ in 0..3 {
+        round(&mut v);
+    }
+    v[0] ^ v[1] ^ v[2] ^ v[3]
 }
 
 #[cfg(test)]
@@ -49,6 +242,22 @@ mod tests {
     fn user_from_email() {
         let u = User::from_email("foo@ymgyt.io");
         assert_eq!(u.id.len(), 16);
-        assert_eq!(u.id, "585779d8c9b2e06d");
+        assert_eq!(u.id, "cc80eb522a51b6b3");
+        assert_eq!(u.legacy_id(), Some("585779d8c9b2e06d"));
+    }
+
+    #[test]
+    fn user_from_local_username() {
+        let u = User::from_local_username("foo");
+        assert_eq!(u.id.len(), 16);
+        assert_eq!(u.id, "5bb2d627bc08ebcf");
+        assert_ne!(u.id, User::from_email("foo").id);
+    }
+
+    #[test]
+    fn user_from_id() {
+        let u = User::from_id("cc80eb522a51b6b3");
+        assert_eq!(u.id(), "cc80eb522a51b6b3");
+        assert_eq!(u.legacy_id(), None);
     }
 }
@@ -0,0 +1,42 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::repository::{subscription::RepositoryResult, types::ApiToken};
+
+#[async_trait]
+pub trait ApiTokenRepository: Send + Sync {
+    /// Store the issued token
+    async fn put_api_token(&self, token: ApiToken) -> RepositoryResult<()>;
+
+    /// Fetch api tokens of the user in the order of creation
+    async fn fetch_api_tokens(&self, user_id: &str) -> RepositoryResult<Vec<ApiToken>>;
+
+    /// Delete the token. Return false if the token does not exist
+    async fn delete_api_token(&self, user_id: &str, token_id: &str) -> RepositoryResult<bool>;
+
+    /// Delete all tokens of the user
+    async fn delete_api_tokens(&self, user_id: &str) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl<T> ApiTokenRepository for Arc<T>
+where
+    T: ApiTokenRepository,
+{
+    async fn put_api_token(&self, token: ApiToken) -> RepositoryResult<()> {
+        (**self).put_api_token(token).await
+    }
+
+    async fn fetch_api_tokens(&self, user_id: &str) -> RepositoryResult<Vec<ApiToken>> {
+        (**self).fetch_api_tokens(user_id).await
+    }
+
+    async fn delete_api_token(&self, user_id: &str, token_id: &str) -> RepositoryResult<bool> {
+        (**self).delete_api_token(user_id, token_id).await
+    }
+
+    async fn delete_api_tokens(&self, user_id: &str) -> RepositoryResult<()> {
+        (**self).delete_api_tokens(user_id).await
+    }
+}
@@ -0,0 +1,723 @@
+//! Behaviors which every repository backend should satisfy
+
+use std::{
+    collections::{BTreeSet, HashSet},
+    future::{pending, Future},
+    path::PathBuf,
+    time::Duration,
+};
+
+use chrono::{TimeZone, Utc};
+use synd_feed::types::{Category, FeedUrl, Requirement};
+use tokio::net::TcpListener;
+
+use crate::repository::{
+    kvsd::KvsdClient,
+    memory::MemoryRepository,
+    sqlite::SqliteRepository,
+    types::{
+        ApiToken, ApiTokenScope, EntryReference, FeedSubscription, Folder, StarredEntry, Webhook,
+        WebhookDelivery, Webhooks,
+    },
+    ApiTokenRepository, ReadStateRepository, StarredEntryRepository, SubscriptionRepository,
+    WebhookRepository,
+};
+
+trait Repository:
+    SubscriptionRepository
+    + ReadStateRepository
+    + StarredEntryRepository
+    + ApiTokenRepository
+    + WebhookRepository
+{
+}
+
+impl<T> Repository for T where
+    T: SubscriptionRepository
+        + ReadStateRepository
+        + StarredEntryRepository
+        + ApiTokenRepository
+        + WebhookRepository
+{
+}
+
+macro_rules! contract_tests {
+    ($backend:ident, $repository:expr) => {
+        mod $backend {
+            use super::*;
+
+            #[tokio::test]
+            async fn subscriptions() {
+                super::subscriptions(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn read_states() {
+                super::read_states(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn starred_entries() {
+                super::starred_entries(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn api_tokens() {
+                super::api_tokens(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn webhooks() {
+                super::webhooks(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn user_ids() {
+                super::user_ids(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn delete_user_data() {
+                super::delete_user_data(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn folders() {
+                super::folders(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn concurrent_subscriptions() {
+                super::concurrent_subscriptions(&$repository.await).await;
+            }
+
+            #[tokio::test]
+            async fn subscriptions_within_limit() {
+                super::subscriptions_within_limit(&$repository.await).await;
+            }
+        }
+    };
+}
+
+contract_tests!(memory, async { MemoryRepository::new() });
+contract_tests!(sqlite, async {
+    SqliteRepository::in_memory().await.unwrap()
+});
+contract_tests!(kvsd, run_kvsd());
+
+async fn run_kvsd() -> KvsdClient {
+    let port = spawn_kvsd().await;
+    connect_kvsd(port).await
+}
+
+/// Run kvsd server in background and return the port
+pub(super) async fn spawn_kvsd() -> u16 {
+    let root_dir = tempfile::TempDir::new().unwrap().into_path();
+    let listener = TcpListener::bind(("localhost", 0)).await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(serve_kvsd(root_dir, listener, pending::<()>()));
+    port
+}
+
+/// Run kvsd server which stores data in `root_dir` until `shutdown` completes
+pub(super) async fn serve_kvsd(
+    root_dir: PathBuf,
+    listener: TcpListener,
+    shutdown: impl Future + Send,
+) {
+    let mut config = ::kvsd::config::Config::default();
+    config.kvsd.users = vec![::kvsd::core::UserEntry {
+        username: "test".into(),
+        password: "test".into(),
+    }];
+    config.server.set_disable_tls(&mut Some(true));
+
+    let mut initializer = ::kvsd::config::Initializer::from_config(config);
+    initializer.set_root_dir(root_dir);
+    initializer.set_listener(listener);
+    initializer.init_dir().await.unwrap();
+    initializer.run_kvsd(shutdown).await.unwrap();
+}
+
+pub(super) async fn connect_kvsd(port: u16) -> KvsdClient {
+    KvsdClient::connect(
+        "localhost",
+        port,
+        "test".into(),
+        "test".into(),
+        4,
+        Duration::from_secs(5),
+    )
+    .await
+    .unwrap()
+}
+
+fn url(s: &str) -> FeedUrl {
+    FeedUrl::try_from(s).unwrap()
+}
+
+fn subscription(url: &str, requirement: Option<Requirement>) -> FeedSubscription {
+    FeedSubscription {
+        user_id: "me".into(),
+        url: self::url(url),
+        requirement,
+        category: Some(Category::new("rust").unwrap()),
+        title: None,
+        note: None,
+        tags: BTreeSet::new(),
+    }
+}
+
+// Writes issued concurrently should not be lost
+async fn concurrent_subscriptions(repo: &impl SubscriptionRepository) {
+    let urls = (0..16)
+        .map(|i| format!("https://{i}.ymgyt.io/feed"))
+        .collect::<Vec<_>>();
+    futures_util::future::try_join_all(
+        urls.iter()
+            .map(|url| repo.put_feed_subscription(subscription(url, None))),
+    )
+    .await
+    .unwrap();
+
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert_eq!(feeds.urls.len(), urls.len());
+    for url in urls {
+        assert!(feeds.urls.contains(&self::url(&url)), "{url} is lost");
+    }
+}
+
+// Concurrent subscriptions should not exceed the limit
+async fn subscriptions_within_limit(repo: &impl SubscriptionRepository) {
+    let urls = (0..16)
+        .map(|i| format!("https://{i}.ymgyt.io/feed"))
+        .collect::<Vec<_>>();
+    let subscribed = futures_util::future::try_join_all(
+        urls.iter()
+            .map(|url| repo.put_feed_subscription_within(subscription(url, None), 4)),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        subscribed.iter().filter(|subscribed| **subscribed).count(),
+        4
+    );
+
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert_eq!(feeds.urls.len(), 4);
+
+    // Annotations of the subscribed feed can be updated at the limit
+    let subscribed_url = feeds.urls[0].clone();
+    assert!(repo
+        .put_feed_subscription_within(
+            FeedSubscription {
+                title: Some("updated".into()),
+                ..subscription(subscribed_url.as_str(), None)
+            },
+            4,
+        )
+        .await
+        .unwrap());
+    assert_eq!(
+        repo.fetch_subscribed_feeds("me")
+            .await
+            .unwrap()
+            .annotations
+            .unwrap()[&subscribed_url]
+            .title,
+        Some("updated".into())
+    );
+}
+
+async fn subscriptions(repo: &impl SubscriptionRepository) {
+    repo.put_feed_subscription(subscription("https://a.ymgyt.io/feed", None))
+        .await
+        .unwrap();
+    repo.put_feed_subscription(subscription("https://b.ymgyt.io/feed", None))
+        .await
+        .unwrap();
+    // Updating annotations keep the order
+    repo.put_feed_subscription(FeedSubscription {
+        title: Some("ymgyt blog".into()),
+        note: Some("weekly".into()),
+        tags: BTreeSet::from([
+            Category::new("blog").unwrap(),
+            Category::new("tui").unwrap(),
+        ]),
+        ..subscription("https://a.ymgyt.io/feed", Some(Requirement::Must))
+    })
+    .await
+    .unwrap();
+
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert_eq!(
+        feeds.urls,
+        vec![
+            url("https://b.ymgyt.io/feed"),
+            url("https://a.ymgyt.io/feed")
+        ]
+    );
+    let annotations = feeds.annotations.unwrap();
+    let a = &annotations[&url("https://a.ymgyt.io/feed")];
+    assert_eq!(a.requirement, Some(Requirement::Must));
+    assert_eq!(a.category, Some(Category::new("rust").unwrap()));
+    assert_eq!(a.title.as_deref(), Some("ymgyt blog"));
+    assert_eq!(a.note.as_deref(), Some("weekly"));
+    assert_eq!(
+        a.tags.iter().map(Category::as_str).collect::<Vec<_>>(),
+        vec!["blog", "tui"]
+    );
+    let b = &annotations[&url("https://b.ymgyt.io/feed")];
+    assert_eq!(b.title, None);
+    assert!(b.tags.is_empty());
+
+    repo.delete_feed_subscription(subscription("https://a.ymgyt.io/feed", None))
+        .await
+        .unwrap();
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert_eq!(feeds.urls, vec![url("https://b.ymgyt.io/feed")]);
+    assert_eq!(feeds.annotations.unwrap().len(), 1);
+
+    // Deleting unknown subscription is not an error
+    repo.delete_feed_subscription(subscription("https://c.ymgyt.io/feed", None))
+        .await
+        .unwrap();
+
+    let feeds = repo.fetch_subscribed_feeds("other").await.unwrap();
+    assert!(feeds.urls.is_empty());
+}
+
+async fn folders(repo: &impl SubscriptionRepository) {
+    for feed in ["a", "b", "c", "d"] {
+        repo.put_feed_subscription(subscription(&format!("https://{feed}.ymgyt.io/feed"), None))
+            .await
+            .unwrap();
+    }
+    let blogs = repo
+        .create_folder("me", "blogs".into(), None)
+        .await
+        .unwrap();
+    let rust = repo
+        .create_folder("me", "rust".into(), Some(blogs.id))
+        .await
+        .unwrap();
+    repo.rename_folder("me", rust.id, "rustacean".into())
+        .await
+        .unwrap();
+
+    // d c b a => c d [blogs: a b]
+    let feed = |name: &str| url(&format!("https://{name}.ymgyt.io/feed"));
+    repo.move_feed("me", feed("b"), Some(blogs.id), None)
+        .await
+        .unwrap();
+    repo.move_feed("me", feed("a"), Some(blogs.id), Some(0))
+        .await
+        .unwrap();
+    repo.move_feed("me", feed("d"), None, None).await.unwrap();
+    // Moving unknown feed is ignored
+    repo.move_feed("me", feed("x"), None, Some(0))
+        .await
+        .unwrap();
+
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert_eq!(feeds.urls, vec![feed("c"), feed("d"), feed("a"), feed("b")]);
+    assert_eq!(feeds.feed_folders.get(&feed("a")), Some(&blogs.id));
+    assert_eq!(feeds.feed_folders.get(&feed("b")), Some(&blogs.id));
+    assert_eq!(feeds.feed_folders.get(&feed("c")), None);
+    assert_eq!(
+        feeds.folders,
+        vec![
+            blogs.clone(),
+            Folder {
+                name: "rustacean".into(),
+                ..rust.clone()
+            }
+        ]
+    );
+
+    // Move to nested folder and back to the top
+    repo.move_feed("me", feed("a"), Some(rust.id), None)
+        .await
+        .unwrap();
+    repo.move_feed("me", feed("b"), None, Some(0))
+        .await
+        .unwrap();
+    // Annotations update keeps the place
+    repo.put_feed_subscription(subscription(
+        "https://a.ymgyt.io/feed",
+        Some(Requirement::May),
+    ))
+    .await
+    .unwrap();
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert_eq!(feeds.urls, vec![feed("b"), feed("c"), feed("d"), feed("a")]);
+    assert_eq!(feeds.feed_folders.get(&feed("a")), Some(&rust.id));
+    assert_eq!(feeds.feed_folders.get(&feed("b")), None);
+
+    // Unsubscribing removes the feed from the folder
+    repo.delete_feed_subscription(subscription("https://a.ymgyt.io/feed", None))
+        .await
+        .unwrap();
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert!(feeds.feed_folders.is_empty());
+
+    assert!(repo
+        .fetch_subscribed_feeds("other")
+        .await
+        .unwrap()
+        .folders
+        .is_empty());
+}
+
+async fn read_states(repo: &impl ReadStateRepository) {
+    let feed_url = url("https://a.ymgyt.io/feed");
+    let entry = |id: &str| EntryReference {
+        feed_url: feed_url.clone(),
+        entry_id: id.into(),
+    };
+
+    repo.mark_entries("me", vec![entry("1"), entry("2")], true)
+        .await
+        .unwrap();
+    repo.mark_entries("me", vec![entry("2")], false)
+        .await
+        .unwrap();
+
+    let states = repo.fetch_read_states("me").await.unwrap();
+    let state = states.feed(&feed_url).unwrap();
+    assert!(state.read.contains("1"));
+    assert!(!state.read.contains("2"));
+    assert!(state.unread.contains("2"));
+    assert_eq!(state.read_until, None);
+
+    let t1 = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let t0 = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    repo.mark_feed_read("me", feed_url.clone(), Some(t1), vec!["3".into()])
+        .await
+        .unwrap();
+    // read_until never goes back
+    repo.mark_feed_read("me", feed_url.clone(), Some(t0), vec!["3".into()])
+        .await
+        .unwrap();
+
+    let states = repo.fetch_read_states("me").await.unwrap();
+    let state = states.feed(&feed_url).unwrap();
+    assert_eq!(state.read_until, Some(t1));
+    assert_eq!(state.read.iter().collect::<Vec<_>>(), vec!["3"]);
+    assert!(state.unread.is_empty());
+
+    let states = repo.fetch_read_states("other").await.unwrap();
+    assert!(states.feeds.is_empty());
+
+    // Entries which are no longer in the feed are pruned
+    repo.mark_entries("me", vec![entry("4"), entry("5")], false)
+        .await
+        .unwrap();
+    repo.prune_read_states(
+        "me",
+        feed_url.clone(),
+        HashSet::from(["3".into(), "5".into()]),
+    )
+    .await
+    .unwrap();
+    let states = repo.fetch_read_states("me").await.unwrap();
+    let state = states.feed(&feed_url).unwrap();
+    assert_eq!(state.read.iter().collect::<Vec<_>>(), vec!["3"]);
+    assert_eq!(state.unread.iter().collect::<Vec<_>>(), vec!["5"]);
+    assert_eq!(state.read_until, Some(t1));
+    // Pruning the user who has no read states does not create them
+    repo.prune_read_states("other", feed_url.clone(), HashSet::new())
+        .await
+        .unwrap();
+    assert!(repo
+        .fetch_read_states("other")
+        .await
+        .unwrap()
+        .feeds
+        .is_empty());
+}
+
+async fn starred_entries(repo: &impl StarredEntryRepository) {
+    let starred = |id: &str| StarredEntry {
+        feed_url: url("https://a.ymgyt.io/feed"),
+        entry_id: id.into(),
+        title: Some(format!("entry {id}")),
+        website_url: None,
+        summary: None,
+        published: Some(Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap()),
+        updated: None,
+        feed_title: Some("ymgyt".into()),
+        requirement: Some(Requirement::Should),
+        category: Some(Category::new("rust").unwrap()),
+        starred_at: Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap(),
+    };
+
+    repo.star_entry("me", starred("1")).await.unwrap();
+    repo.star_entry("me", starred("2")).await.unwrap();
+    // Starring again moves the entry to the front
+    repo.star_entry("me", starred("1")).await.unwrap();
+
+    let entries = repo.fetch_starred_entries("me").await.unwrap();
+    let ids = entries
+        .iter()
+        .map(|e| e.entry_id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["1", "2"]);
+    assert_eq!(entries[0].requirement, Some(Requirement::Should));
+    assert_eq!(entries[0].title.as_deref(), Some("entry 1"));
+    assert_eq!(entries[0].published, starred("1").published);
+    assert_eq!(entries[0].starred_at, starred("1").starred_at);
+
+    repo.unstar_entry(
+        "me",
+        EntryReference {
+            feed_url: url("https://a.ymgyt.io/feed"),
+            entry_id: "1".into(),
+        },
+    )
+    .await
+    .unwrap();
+    let entries = repo.fetch_starred_entries("me").await.unwrap();
+    assert_eq!(entries.len(), 1);
+
+    assert!(repo
+        .fetch_starred_entries("other")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn api_tokens(repo: &impl ApiTokenRepository) {
+    let issued_at = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let (ci, ci_token) = ApiToken::issue("me", "ci", ApiTokenScope::ReadOnly, issued_at, None);
+    let (cron, _) = ApiToken::issue(
+        "me",
+        "cron",
+        ApiTokenScope::ReadWrite,
+        issued_at,
+        Some(Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap()),
+    );
+    repo.put_api_token(ci.clone()).await.unwrap();
+    repo.put_api_token(cron.clone()).await.unwrap();
+
+    // Tokens are listed in the order of creation
+    let tokens = repo.fetch_api_tokens("me").await.unwrap();
+    assert_eq!(tokens, vec![ci.clone(), cron.clone()]);
+    assert!(repo.fetch_api_tokens("other").await.unwrap().is_empty());
+
+    // Only the hash of the secret is stored
+    let (_, token_id, secret) = ApiToken::parse_plaintext(&ci_token).unwrap();
+    assert_eq!(token_id, ci.id);
+    assert!(!tokens[0].secret_hash.contains(secret));
+    assert!(tokens[0].verify(secret, issued_at));
+
+    // Other user can not revoke the token
+    assert!(!repo.delete_api_token("other", &ci.id).await.unwrap());
+    assert!(repo.delete_api_token("me", &ci.id).await.unwrap());
+    assert!(!repo.delete_api_token("me", &ci.id).await.unwrap());
+
+    assert_eq!(repo.fetch_api_tokens("me").await.unwrap(), vec![cron]);
+}
+
+async fn webhooks(repo: &impl WebhookRepository) {
+    let created_at = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let chat = Webhook::register(
+        "me",
+        "https://chat.ymgyt.io/hook",
+        Some("secret".into()),
+        Requirement::Must,
+        vec![Category::new("rust").unwrap()],
+        Some(serde_json::json!({ "text": "{{title}}" })),
+        created_at,
+    );
+    let all = Webhook::register(
+        "me",
+        "https://all.ymgyt.io/hook",
+        None,
+        Requirement::May,
+        Vec::new(),
+        None,
+        created_at,
+    );
+    repo.put_webhook(chat.clone()).await.unwrap();
+    repo.put_webhook(all.clone()).await.unwrap();
+
+    // Webhooks are listed in the order of creation
+    assert_eq!(
+        repo.fetch_webhooks("me").await.unwrap(),
+        vec![chat.clone(), all.clone()]
+    );
+    assert!(repo.fetch_webhooks("other").await.unwrap().is_empty());
+
+    // Deliveries are listed most recent first and old ones are discarded
+    for n in 0..=Webhooks::MAX_DELIVERIES {
+        repo.put_webhook_delivery(
+            "me",
+            WebhookDelivery {
+                id: n.to_string(),
+                webhook_id: chat.id.clone(),
+                feed_url: url("https://a.ymgyt.io/feed"),
+                entry_id: n.to_string(),
+                entry_title: Some("title".into()),
+                attempts: 2,
+                status_code: (n % 2 == 0).then_some(200),
+                error: (n % 2 == 1).then(|| "timeout".to_owned()),
+                delivered_at: created_at,
+            },
+        )
+        .await
+        .unwrap();
+    }
+    let deliveries = repo.fetch_webhook_deliveries("me").await.unwrap();
+    assert_eq!(deliveries.len(), Webhooks::MAX_DELIVERIES);
+    assert_eq!(deliveries[0].id, Webhooks::MAX_DELIVERIES.to_string());
+    assert!(deliveries[0].succeeded());
+    assert_eq!(deliveries.last().unwrap().id, "1");
+    assert!(repo
+        .fetch_webhook_deliveries("other")
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Other user can not delete the webhook
+    assert!(!repo.delete_webhook("other", &chat.id).await.unwrap());
+    assert!(repo.delete_webhook("me", &chat.id).await.unwrap());
+    assert!(!repo.delete_webhook("me", &chat.id).await.unwrap());
+    assert_eq!(repo.fetch_webhooks("me").await.unwrap(), vec![all]);
+
+    repo.delete_webhooks("me").await.unwrap();
+    assert!(repo.fetch_webhooks("me").await.unwrap().is_empty());
+    assert!(repo
+        .fetch_webhook_deliveries("me")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn user_ids(repo: &impl Repository) {
+    assert!(repo.fetch_user_ids().await.unwrap().is_empty());
+
+    repo.put_feed_subscription(FeedSubscription {
+        user_id: "b".into(),
+        ..subscription("https://a.ymgyt.io/feed", None)
+    })
+    .await
+    .unwrap();
+    repo.mark_entries(
+        "a",
+        vec![EntryReference {
+            feed_url: url("https://a.ymgyt.io/feed"),
+            entry_id: "1".into(),
+        }],
+        true,
+    )
+    .await
+    .unwrap();
+    // Indexing same user twice does not duplicate
+    repo.put_feed_subscription(FeedSubscription {
+        user_id: "b".into(),
+        ..subscription("https://b.ymgyt.io/feed", None)
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(
+        repo.fetch_user_ids().await.unwrap(),
+        vec!["a".to_owned(), "b".to_owned()]
+    );
+}
+
+async fn delete_user_data(repo: &impl Repository) {
+    let issued_at = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+    let entry = EntryReference {
+        feed_url: url("https://a.ymgyt.io/feed"),
+        entry_id: "1".into(),
+    };
+
+    for user_id in ["me", "other"] {
+        repo.put_feed_subscription(FeedSubscription {
+            user_id: user_id.into(),
+            ..subscription("https://a.ymgyt.io/feed", None)
+        })
+        .await
+        .unwrap();
+        let folder = repo
+            .create_folder(user_id, "blog".into(), None)
+            .await
+            .unwrap();
+        repo.create_folder(user_id, "rust".into(), Some(folder.id))
+            .await
+            .unwrap();
+        repo.move_feed(
+            user_id,
+            url("https://a.ymgyt.io/feed"),
+            Some(folder.id),
+            None,
+        )
+        .await
+        .unwrap();
+        repo.mark_entries(user_id, vec![entry.clone()], true)
+            .await
+            .unwrap();
+        repo.star_entry(
+            user_id,
+            StarredEntry {
+                feed_url: entry.feed_url.clone(),
+                entry_id: entry.entry_id.clone(),
+                title: None,
+                website_url: None,
+                summary: None,
+                published: None,
+                updated: None,
+                feed_title: None,
+                requirement: None,
+                category: None,
+                starred_at: issued_at,
+            },
+        )
+        .await
+        .unwrap();
+        let (token, _) = ApiToken::issue(user_id, "ci", ApiTokenScope::ReadOnly, issued_at, None);
+        repo.put_api_token(token).await.unwrap();
+        repo.put_webhook(Webhook::register(
+            user_id,
+            "https://chat.ymgyt.io/hook",
+            None,
+            Requirement::Must,
+            Vec::new(),
+            None,
+            issued_at,
+        ))
+        .await
+        .unwrap();
+    }
+
+    repo.delete_subscriptions("me").await.unwrap();
+    repo.delete_read_states("me").await.unwrap();
+    repo.delete_starred_entries("me").await.unwrap();
+    repo.delete_api_tokens("me").await.unwrap();
+    repo.delete_webhooks("me").await.unwrap();
+
+    let feeds = repo.fetch_subscribed_feeds("me").await.unwrap();
+    assert!(feeds.urls.is_empty());
+    assert!(feeds.folders.is_empty());
+    assert!(repo.fetch_read_states("me").await.unwrap().feeds.is_empty());
+    assert!(repo.fetch_starred_entries("me").await.unwrap().is_empty());
+    assert!(repo.fetch_api_tokens("me").await.unwrap().is_empty());
+    assert!(repo.fetch_webhooks("me").await.unwrap().is_empty());
+    assert_eq!(
+        repo.fetch_user_ids().await.unwrap(),
+        vec!["other".to_owned()]
+    );
+
+    // Data of other users are kept
+    let feeds = repo.fetch_subscribed_feeds("other").await.unwrap();
+    assert_eq!(feeds.urls.len(), 1);
+    assert_eq!(feeds.folders.len(), 2);
+    assert_eq!(repo.fetch_starred_entries("other").await.unwrap().len(), 1);
+    assert_eq!(repo.fetch_api_tokens("other").await.unwrap().len(), 1);
+    assert_eq!(repo.fetch_webhooks("other").await.unwrap().len(), 1);
+
+    // Deleting the user without data succeeds
+    repo.delete_subscriptions("me").await.unwrap();
+}
@@ -1,101 +1,197 @@
-use std::{collections::HashMap, io::ErrorKind, time::Duration};
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
-use anyhow::Context;
 use async_trait::async_trait;
-use futures_util::TryFutureExt;
-use kvsd::{
-    client::{tcp::Client, Api},
-    Key, Value,
-};
+use futures_util::future::BoxFuture;
+use kvsd::{Key, Value};
+use synd_o11y::metric;
 use thiserror::Error;
-use tokio::sync::Mutex;
-use tokio::{net::TcpStream, sync::MutexGuard};
+
+use synd_feed::types::{FeedUrl, Time};
 
 use crate::repository::{
     self,
     subscription::RepositoryResult,
-    types::{FeedAnnotations, SubscribedFeeds},
-    RepositoryError, SubscriptionRepository,
+    types::{
+        ApiToken, ApiTokens, EntryReference, Folder, FolderId, ReadStates, StarredEntries,
+        StarredEntry, SubscribedFeeds, UserIds, Webhook, WebhookDelivery, Webhooks,
+    },
+    ApiTokenRepository, ReadStateRepository, RepositoryError, StarredEntryRepository,
+    SubscriptionRepository, WebhookRepository,
 };
 
+mod lock;
+mod pool;
+use pool::{Connection, Connector, Pool};
+
 #[derive(Error, Debug)]
 #[error("connect kvsd failed")]
 pub struct ConnectKvsdFailed;
 
 pub struct KvsdClient {
-    #[allow(dead_code)]
-    client: Mutex<Client<TcpStream>>,
+    pool: Arc<Pool>,
+    /// Users which are known to be in the users index
+    indexed_users: std::sync::Mutex<HashSet<String>>,
+    /// Release the lock held by other instances forcibly if it is held longer than this duration
+    lock_timeout: Duration,
 }
 
 impl KvsdClient {
-    pub fn new(client: Client<TcpStream>) -> Self {
-        Self {
-            client: Mutex::new(client),
-        }
-    }
+    /// Give up the update when the lock is lost this many times in a row
+    const MAX_CONFLICTS: usize = 32;
 
     pub async fn connect(
-        host: impl AsRef<str>,
+        host: impl Into<String>,
         port: u16,
         username: String,
         password: String,
+        pool_size: usize,
         timeout: Duration,
     ) -> anyhow::Result<Self> {
-        let handshake = async {
-            let mut retry = 0;
-            loop {
-                match kvsd::client::tcp::UnauthenticatedClient::insecure_from_addr(&host, port)
-                    .and_then(|client| client.authenticate(&username, &password))
-                    .await
-                    .map(Self::new)
-                {
-                    Ok(client) => break Ok(client),
-                    Err(kvsd::KvsdError::Io(io)) if io.kind() == ErrorKind::ConnectionRefused => {
-                        tracing::info!(retry, "Kvsd connection refused");
-                    }
-                    err => break err,
-                }
-                retry += 1;
-                tokio::time::sleep(Duration::from_millis(1000)).await;
-            }
+        let connector = Connector {
+            host: host.into(),
+            port,
+            username,
+            password,
         };
+        let connection = connector.connect(timeout).await?;
+        tracing::info!(pool_size, "Kvsd handshake successfully completed");
 
-        tokio::time::timeout(timeout, handshake)
-            .await
-            .map_err(anyhow::Error::from)
-            .context(ConnectKvsdFailed)?
-            .map_err(anyhow::Error::from)
-            .inspect(|_| tracing::info!("Kvsd handshake successfully completed"))
+        let client = Self {
+            pool: Arc::new(Pool::new(connector, pool_size, connection, timeout)),
+            indexed_users: std::sync::Mutex::new(HashSet::new()),
+            lock_timeout: Self::LOCK_TIMEOUT,
+        };
+        Ok(client)
+    }
+
+    async fn call<F, T>(&self, op: F) -> RepositoryResult<T>
+    where
+        F: for<'c> Fn(&'c mut Connection) -> BoxFuture<'c, kvsd::Result<T>> + Send,
+        T: Send,
+    {
+        self.pool.call(op).await
     }
 
-    async fn get<'a, T>(
-        client: &mut MutexGuard<'a, Client<TcpStream>>,
-        key: Key,
-    ) -> RepositoryResult<Option<T>>
+    async fn get<T>(&self, key: Key) -> RepositoryResult<Option<T>>
     where
         T: TryFrom<Value>,
         T::Error: Into<RepositoryError>,
     {
-        let Some(value) = client.get(key).await.map_err(RepositoryError::internal)? else {
+        let Some(value) = self
+            .call(|connection| kvsd::client::Api::get(connection, key.clone()))
+            .await?
+        else {
             return Ok(None);
         };
         Ok(Some(value.try_into().map_err(Into::into)?))
     }
 
-    async fn set<'a, T>(
-        client: &mut MutexGuard<'a, Client<TcpStream>>,
-        key: Key,
-        value: T,
-    ) -> RepositoryResult<()>
+    /// Read-modify-write the value under the lock of the key.
+    /// `modify` returns `None` if there is nothing to write.
+    /// If the lock is released forcibly before writing, `modify` is applied again to the latest value
+    async fn update<T, F>(&self, key: Key, mut modify: F) -> RepositoryResult<()>
     where
-        T: TryInto<Value>,
-        T::Error: Into<RepositoryError>,
+        T: TryFrom<Value, Error = RepositoryError> + TryInto<Value, Error = RepositoryError>,
+        F: FnMut(Option<T>) -> Option<T> + Send,
     {
-        let value = value.try_into().map_err(Into::into)?;
-        client.set(key, value).await?;
+        for _ in 0..Self::MAX_CONFLICTS {
+            let lock = self.acquire_lock(&key).await?;
+            let written = async {
+                let Some(modified) = modify(self.get::<T>(key.clone()).await?) else {
+                    return Ok(true);
+                };
+                lock.set(&key, modified.try_into()?).await
+            }
+            .await;
+            lock.release().await?;
+
+            if written? {
+                return Ok(());
+            }
+            tracing::debug!(?key, "Lost the lock while updating, retrying");
+            metric!(monotonic_counter.kvsd.update.conflict = 1);
+        }
+        Err(RepositoryError::internal(anyhow::anyhow!(
+            "too many conflicts on updating {key:?}"
+        )))
+    }
+
+    /// Delete the value under the lock of the key so that
+    /// concurrent updates of other writers do not resurrect the deleted value
+    async fn delete(&self, key: Key) -> RepositoryResult<()> {
+        for _ in 0..Self::MAX_CONFLICTS {
+            let lock = self.acquire_lock(&key).await?;
+            let deleted = lock.delete(&key).await;
+            lock.release().await?;
+
+            if deleted? {
+                return Ok(());
+            }
+            metric!(monotonic_counter.kvsd.update.conflict = 1);
+        }
+        Err(RepositoryError::internal(anyhow::anyhow!(
+            "too many conflicts on deleting {key:?}"
+        )))
+    }
+
+    /// Remove the user from the users index if the user has no data anymore
+    async fn unindex_user_if_empty(&self, user_id: &str) -> RepositoryResult<()> {
+        for key in [
+            Self::feed_subscription_key(user_id),
+            Self::read_state_key(user_id),
+            Self::starred_entries_key(user_id),
+            Self::api_tokens_key(user_id),
+            Self::webhooks_key(user_id),
+        ] {
+            if self
+                .call(|connection| kvsd::client::Api::get(connection, key.clone()))
+                .await?
+                .is_some()
+            {
+                return Ok(());
+            }
+        }
+
+        self.update(Self::users_key(), |users: Option<UserIds>| {
+            let mut users = users?;
+            users.ids.remove(user_id).then_some(users)
+        })
+        .await?;
+
+        self.indexed_users.lock().unwrap().remove(user_id);
+        Ok(())
+    }
+
+    /// Add the user to the users index if not yet.
+    /// Users are indexed on write and on fetching subscriptions so that
+    /// the users who have data before the index was introduced are also indexed
+    async fn index_user(&self, user_id: &str) -> RepositoryResult<()> {
+        if self.is_indexed(user_id) {
+            return Ok(());
+        }
+
+        self.update(Self::users_key(), |users: Option<UserIds>| {
+            let mut users = users.unwrap_or_default();
+            users.ids.insert(user_id.to_owned()).then_some(users)
+        })
+        .await?;
+
+        self.indexed_users
+            .lock()
+            .unwrap()
+            .insert(user_id.to_owned());
         Ok(())
     }
 
+    fn is_indexed(&self, user_id: &str) -> bool {
+        self.indexed_users.lock().unwrap().contains(user_id)
+    }
+
+    fn users_key() -> Key {
+        let key = format!("{prefix}/users", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
     fn feed_subscription_key(user_id: &str) -> Key {
         let key = format!(
             "{prefix}/subscription/{user_id}",
@@ -104,6 +200,26 @@ impl KvsdClient {
         Key::new(key).expect("Invalid key")
     }
 
+    fn read_state_key(user_id: &str) -> Key {
+        let key = format!("{prefix}/read_state/{user_id}", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
+    fn starred_entries_key(user_id: &str) -> Key {
+        let key = format!("{prefix}/starred/{user_id}", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
+    fn api_tokens_key(user_id: &str) -> Key {
+        let key = format!("{prefix}/api_token/{user_id}", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
+    fn webhooks_key(user_id: &str) -> Key {
+        let key = format!("{prefix}/webhook/{user_id}", prefix = Self::key_prefix());
+        Key::new(key).expect("Invalid key")
+    }
+
     fn key_prefix() -> &'static str {
         "/synd_api/v1"
     }
@@ -117,38 +233,41 @@ impl SubscriptionRepository for KvsdClient {
         feed: repository::types::FeedSubscription,
     ) -> RepositoryResult<()> {
         let key = Self::feed_subscription_key(&feed.user_id);
+        let annotations = feed.annotations();
 
-        let mut client = self.client.lock().await;
-        let annotations = FeedAnnotations {
-            requirement: feed.requirement,
-            category: feed.category,
-        };
+        self.update(key, |feeds: Option<SubscribedFeeds>| {
+            let mut feeds = feeds.unwrap_or_default();
+            feeds.put(feed.url.clone(), annotations.clone());
+            Some(feeds)
+        })
+        .await?;
+        self.index_user(&feed.user_id).await
+    }
 
-        let feeds = if let Some(mut feeds) =
-            Self::get::<SubscribedFeeds>(&mut client, key.clone()).await?
-        {
-            // Create case
-            if !feeds.urls.contains(&feed.url) {
-                feeds.urls.insert(0, feed.url.clone());
-            };
-            if feeds.annotations.is_none() {
-                feeds.annotations = Some(HashMap::new());
-            };
-            feeds
-                .annotations
-                .as_mut()
-                .map(|m| m.insert(feed.url, annotations));
-            feeds
-        } else {
-            let mut metadata = HashMap::new();
-            metadata.insert(feed.url.clone(), annotations);
-            SubscribedFeeds {
-                urls: vec![feed.url.clone()],
-                annotations: Some(metadata),
-            }
-        };
+    #[tracing::instrument(name = "repo::put_feed_subscription_within", skip_all)]
+    async fn put_feed_subscription_within(
+        &self,
+        feed: repository::types::FeedSubscription,
+        max_subscriptions: usize,
+    ) -> RepositoryResult<bool> {
+        let key = Self::feed_subscription_key(&feed.user_id);
+        let annotations = feed.annotations();
 
-        Self::set(&mut client, key, feeds).await
+        let mut subscribed = false;
+        self.update(key, |feeds: Option<SubscribedFeeds>| {
+            let mut feeds = feeds.unwrap_or_default();
+            subscribed = feeds.can_subscribe(&feed.url, max_subscriptions);
+            if !subscribed {
+                return None;
+            }
+            feeds.put(feed.url.clone(), annotations.clone());
+            Some(feeds)
+        })
+        .await?;
+        if subscribed {
+            self.index_user(&feed.user_id).await?;
+        }
+        Ok(subscribed)
     }
 
     #[tracing::instrument(name = "repo::delete_feed_subscription", skip_all)]
@@ -158,26 +277,485 @@ impl SubscriptionRepository for KvsdClient {
     ) -> RepositoryResult<()> {
         let key = Self::feed_subscription_key(&feed.user_id);
 
-        let mut client = self.client.lock().await;
-
-        let Some(mut feeds) = Self::get::<SubscribedFeeds>(&mut client, key.clone()).await? else {
-            return Ok(());
-        };
-
-        feeds.urls.retain(|url| url != &feed.url);
-        feeds.annotations.as_mut().map(|m| m.remove(&feed.url));
-
-        Self::set(&mut client, key, feeds).await
+        self.update(key, |feeds: Option<SubscribedFeeds>| {
+            let mut feeds = feeds?;
+            feeds.remove(&feed.url);
+            Some(feeds)
+        })
+        .await
     }
 
     #[tracing::instrument(name = "repo::fetch_subscribed_feed_urls", skip_all)]
     async fn fetch_subscribed_feeds(&self, user_id: &str) -> RepositoryResult<SubscribedFeeds> {
         let key = Self::feed_subscription_key(user_id);
 
-        let mut client = self.client.lock().await;
-        let Some(feeds) = Self::get::<SubscribedFeeds>(&mut client, key).await? else {
+        let Some(feeds) = self.get::<SubscribedFeeds>(key).await? else {
             return Ok(SubscribedFeeds::default());
         };
+        self.index_user(user_id).await?;
         Ok(feeds)
     }
+
+    #[tracing::instrument(name = "repo::fetch_user_ids", skip_all)]
+    async fn fetch_user_ids(&self) -> RepositoryResult<Vec<String>> {
+        Ok(self
+            .get::<UserIds>(Self::users_key())
+            .await?
+            .map(|users| users.ids.into_iter().collect())
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_subscriptions", skip_all)]
+    async fn delete_subscriptions(&self, user_id: &str) -> RepositoryResult<()> {
+        self.delete(Self::feed_subscription_key(user_id)).await?;
+        self.unindex_user_if_empty(user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::create_folder", skip_all)]
+    async fn create_folder(
+        &self,
+        user_id: &str,
+        name: String,
+        parent: Option<FolderId>,
+    ) -> RepositoryResult<Folder> {
+        let key = Self::feed_subscription_key(user_id);
+
+        let mut created = None;
+        self.update(key, |feeds: Option<SubscribedFeeds>| {
+            let mut feeds = feeds.unwrap_or_default();
+            created = Some(feeds.create_folder(name.clone(), parent));
+            Some(feeds)
+        })
+        .await?;
+        self.index_user(user_id).await?;
+        Ok(created.expect("folder is created on update"))
+    }
+
+    #[tracing::instrument(name = "repo::rename_folder", skip_all)]
+    async fn rename_folder(
+        &self,
+        user_id: &str,
+        folder_id: FolderId,
+        name: String,
+    ) -> RepositoryResult<()> {
+        let key = Self::feed_subscription_key(user_id);
+
+        self.update(key, |feeds: Option<SubscribedFeeds>| {
+            let mut feeds = feeds?;
+            feeds.rename_folder(folder_id, name.clone());
+            Some(feeds)
+        })
+        .await
+    }
+
+    #[tracing::instrument(name = "repo::move_feed", skip_all)]
+    async fn move_feed(
+        &self,
+        user_id: &str,
+        url: FeedUrl,
+        folder_id: Option<FolderId>,
+        position: Option<usize>,
+    ) -> RepositoryResult<()> {
+        let key = Self::feed_subscription_key(user_id);
+
+        self.update(key, |feeds: Option<SubscribedFeeds>| {
+            let mut feeds = feeds?;
+            feeds.move_feed(&url, folder_id, position);
+            Some(feeds)
+        })
+        .await
+    }
+}
+
+#[async_trait]
+impl ReadStateRepository for KvsdClient {
+    #[tracing::instrument(name = "repo::mark_entries", skip_all)]
+    async fn mark_entries(
+        &self,
+        user_id: &str,
+        entries: Vec<EntryReference>,
+        read: bool,
+    ) -> RepositoryResult<()> {
+        let key = Self::read_state_key(user_id);
+
+        self.update(key, |read_states: Option<ReadStates>| {
+            let mut read_states = read_states.unwrap_or_default();
+            read_states.mark_entries(entries.clone(), read);
+            Some(read_states)
+        })
+        .await?;
+        self.index_user(user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::mark_feed_read", skip_all)]
+    async fn mark_feed_read(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        read_until: Option<Time>,
+        entry_ids: Vec<String>,
+    ) -> RepositoryResult<()> {
+        let key = Self::read_state_key(user_id);
+
+        self.update(key, |read_states: Option<ReadStates>| {
+            let mut read_states = read_states.unwrap_or_default();
+            read_states.mark_feed_read(feed_url.clone(), read_until, entry_ids.clone());
+            Some(read_states)
+        })
+        .await?;
+        self.index_user(user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::prune_read_states", skip_all)]
+    async fn prune_read_states(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        retained: HashSet<String>,
+    ) -> RepositoryResult<()> {
+        let key = Self::read_state_key(user_id);
+
+        self.update(key, |read_states: Option<ReadStates>| {
+            let mut read_states = read_states?;
+            read_states.prune(&feed_url, &retained);
+            Some(read_states)
+        })
+        .await
+    }
+
+    #[tracing::instrument(name = "repo::fetch_read_states", skip_all)]
+    async fn fetch_read_states(&self, user_id: &str) -> RepositoryResult<ReadStates> {
+        let key = Self::read_state_key(user_id);
+
+        Ok(self.get::<ReadStates>(key).await?.unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_read_states", skip_all)]
+    async fn delete_read_states(&self, user_id: &str) -> RepositoryResult<()> {
+        self.delete(Self::read_state_key(user_id)).await?;
+        self.unindex_user_if_empty(user_id).await
+    }
+}
+
+#[async_trait]
+impl StarredEntryRepository for KvsdClient {
+    #[tracing::instrument(name = "repo::star_entry", skip_all)]
+    async fn star_entry(&self, user_id: &str, entry: StarredEntry) -> RepositoryResult<()> {
+        let key = Self::starred_entries_key(user_id);
+
+        self.update(key, |starred: Option<StarredEntries>| {
+            let mut starred = starred.unwrap_or_default();
+            starred.star(entry.clone());
+            Some(starred)
+        })
+        .await?;
+        self.index_user(user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::unstar_entry", skip_all)]
+    async fn unstar_entry(&self, user_id: &str, entry: EntryReference) -> RepositoryResult<()> {
+        let key = Self::starred_entries_key(user_id);
+
+        self.update(key, |starred: Option<StarredEntries>| {
+            let mut starred = starred?;
+            starred.unstar(&entry);
+            Some(starred)
+        })
+        .await
+    }
+
+    #[tracing::instrument(name = "repo::fetch_starred_entries", skip_all)]
+    async fn fetch_starred_entries(&self, user_id: &str) -> RepositoryResult<Vec<StarredEntry>> {
+        let key = Self::starred_entries_key(user_id);
+
+        Ok(self
+            .get::<StarredEntries>(key)
+            .await?
+            .map(|starred| starred.entries)
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_starred_entries", skip_all)]
+    async fn delete_starred_entries(&self, user_id: &str) -> RepositoryResult<()> {
+        self.delete(Self::starred_entries_key(user_id)).await?;
+        self.unindex_user_if_empty(user_id).await
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for KvsdClient {
+    #[tracing::instrument(name = "repo::put_api_token", skip_all)]
+    async fn put_api_token(&self, token: ApiToken) -> RepositoryResult<()> {
+        let user_id = token.user_id.clone();
+        let key = Self::api_tokens_key(&user_id);
+
+        self.update(key, |tokens: Option<ApiTokens>| {
+            let mut tokens = tokens.unwrap_or_default();
+            tokens.tokens.push(token.clone());
+            Some(tokens)
+        })
+        .await?;
+        self.index_user(&user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::fetch_api_tokens", skip_all)]
+    async fn fetch_api_tokens(&self, user_id: &str) -> RepositoryResult<Vec<ApiToken>> {
+        let key = Self::api_tokens_key(user_id);
+
+        Ok(self
+            .get::<ApiTokens>(key)
+            .await?
+            .map(|tokens| tokens.tokens)
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_api_token", skip_all)]
+    async fn delete_api_token(&self, user_id: &str, token_id: &str) -> RepositoryResult<bool> {
+        let key = Self::api_tokens_key(user_id);
+        let mut deleted = false;
+
+        self.update(key, |tokens: Option<ApiTokens>| {
+            let mut tokens = tokens?;
+            let len = tokens.tokens.len();
+            tokens.tokens.retain(|token| token.id != token_id);
+            deleted = tokens.tokens.len() != len;
+            deleted.then_some(tokens)
+        })
+        .await?;
+        Ok(deleted)
+    }
+
+    #[tracing::instrument(name = "repo::delete_api_tokens", skip_all)]
+    async fn delete_api_tokens(&self, user_id: &str) -> RepositoryResult<()> {
+        self.delete(Self::api_tokens_key(user_id)).await?;
+        self.unindex_user_if_empty(user_id).await
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for KvsdClient {
+    #[tracing::instrument(name = "repo::put_webhook", skip_all)]
+    async fn put_webhook(&self, webhook: Webhook) -> RepositoryResult<()> {
+        let user_id = webhook.user_id.clone();
+        let key = Self::webhooks_key(&user_id);
+
+        self.update(key, |webhooks: Option<Webhooks>| {
+            let mut webhooks = webhooks.unwrap_or_default();
+            webhooks.webhooks.push(webhook.clone());
+            Some(webhooks)
+        })
+        .await?;
+        self.index_user(&user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::fetch_webhooks", skip_all)]
+    async fn fetch_webhooks(&self, user_id: &str) -> RepositoryResult<Vec<Webhook>> {
+        let key = Self::webhooks_key(user_id);
+
+        Ok(self
+            .get::<Webhooks>(key)
+            .await?
+            .map(|webhooks| webhooks.webhooks)
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_webhook", skip_all)]
+    async fn delete_webhook(&self, user_id: &str, webhook_id: &str) -> RepositoryResult<bool> {
+        let key = Self::webhooks_key(user_id);
+        let mut deleted = false;
+
+        self.update(key, |webhooks: Option<Webhooks>| {
+            let mut webhooks = webhooks?;
+            let len = webhooks.webhooks.len();
+            webhooks.webhooks.retain(|webhook| webhook.id != webhook_id);
+            deleted = webhooks.webhooks.len() != len;
+            deleted.then_some(webhooks)
+        })
+        .await?;
+        Ok(deleted)
+    }
+
+    #[tracing::instrument(name = "repo::delete_webhooks", skip_all)]
+    async fn delete_webhooks(&self, user_id: &str) -> RepositoryResult<()> {
+        self.delete(Self::webhooks_key(user_id)).await?;
+        self.unindex_user_if_empty(user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::put_webhook_delivery", skip_all)]
+    async fn put_webhook_delivery(
+        &self,
+        user_id: &str,
+        delivery: WebhookDelivery,
+    ) -> RepositoryResult<()> {
+        let key = Self::webhooks_key(user_id);
+
+        self.update(key, |webhooks: Option<Webhooks>| {
+            let mut webhooks = webhooks.unwrap_or_default();
+            webhooks.push_delivery(delivery.clone());
+            Some(webhooks)
+        })
+        .await?;
+        self.index_user(user_id).await
+    }
+
+    #[tracing::instrument(name = "repo::fetch_webhook_deliveries", skip_all)]
+    async fn fetch_webhook_deliveries(
+        &self,
+        user_id: &str,
+    ) -> RepositoryResult<Vec<WebhookDelivery>> {
+        let key = Self::webhooks_key(user_id);
+
+        Ok(self
+            .get::<Webhooks>(key)
+            .await?
+            .map(|webhooks| webhooks.deliveries)
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use kvsd::Value;
+    use tokio::{net::TcpListener, sync::oneshot, time::timeout};
+
+    use crate::{
+        fixture::{subscription, url},
+        repository::{
+            contract::{connect_kvsd, serve_kvsd, spawn_kvsd},
+            kvsd::KvsdClient,
+            types::SubscribedFeeds,
+            SubscriptionRepository,
+        },
+    };
+
+    // Emulate multiple synd-api instances subscribing feeds of the same user at the same time
+    #[tokio::test]
+    async fn parallel_writers_do_not_clobber() {
+        let port = spawn_kvsd().await;
+        let mut instances = Vec::new();
+        for _ in 0..4 {
+            instances.push(Arc::new(connect_kvsd(port).await));
+        }
+
+        let handles = (0..32)
+            .map(|i| {
+                let instance = Arc::clone(&instances[i % instances.len()]);
+                tokio::spawn(async move {
+                    instance
+                        .put_feed_subscription(subscription(
+                            "me",
+                            &url(&format!("https://{i}.ymgyt.io/feed")),
+                        ))
+                        .await
+                })
+            })
+            .collect::<Vec<_>>();
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        for instance in &instances {
+            let feeds = instance.fetch_subscribed_feeds("me").await.unwrap();
+            assert_eq!(feeds.urls.len(), 32);
+        }
+        assert_eq!(
+            instances[0].fetch_user_ids().await.unwrap(),
+            vec!["me".to_owned()]
+        );
+    }
+
+    // Pooled connections are closed by kvsd restart, the next call should reconnect
+    #[tokio::test]
+    async fn reconnect_after_kvsd_restart() {
+        let root_dir = tempfile::TempDir::new().unwrap().into_path();
+        let listener = TcpListener::bind(("localhost", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
+        let kvsd = tokio::spawn(serve_kvsd(root_dir.clone(), listener, shutdown_rx));
+
+        let client = connect_kvsd(port).await;
+        let feed = url("https://blog.ymgyt.io/atom.xml");
+        client
+            .put_feed_subscription(subscription("me", &feed))
+            .await
+            .unwrap();
+
+        shutdown_tx.send(()).unwrap();
+        kvsd.await.unwrap();
+        let listener = TcpListener::bind(("localhost", port)).await.unwrap();
+        tokio::spawn(serve_kvsd(root_dir, listener, std::future::pending::<()>()));
+
+        let feeds = client.fetch_subscribed_feeds("me").await.unwrap();
+        assert_eq!(feeds.urls, vec![feed]);
+    }
+
+    // Waiters release the lock forcibly after the timeout.
+    // Then the former holder must neither write nor release the lock acquired by the waiter
+    #[tokio::test]
+    async fn lock_held_longer_than_timeout() {
+        let port = spawn_kvsd().await;
+        let holder = connect_kvsd(port).await;
+        let mut waiter = connect_kvsd(port).await;
+        waiter.lock_timeout = Duration::from_millis(200);
+        let key = KvsdClient::feed_subscription_key("me");
+
+        let held = holder.acquire_lock(&key).await.unwrap();
+        let started = Instant::now();
+        let taken = waiter.acquire_lock(&key).await.unwrap();
+        assert!(started.elapsed() >= waiter.lock_timeout);
+        assert!(taken.fencing_token > held.fencing_token);
+
+        let value = Value::new(b"stale".as_slice()).unwrap();
+        assert!(!held.set(&key, value).await.unwrap());
+        held.release().await.unwrap();
+        assert!(holder
+            .get::<SubscribedFeeds>(key.clone())
+            .await
+            .unwrap()
+            .is_none());
+
+        let other = connect_kvsd(port).await;
+        assert!(
+            timeout(Duration::from_millis(500), other.acquire_lock(&key))
+                .await
+                .is_err(),
+            "lock acquired by the waiter was released"
+        );
+
+        // Lock is released on drop
+        drop(taken);
+        let other = connect_kvsd(port).await;
+        timeout(Duration::from_secs(1), other.acquire_lock(&key))
+            .await
+            .unwrap()
+            .unwrap()
+            .release()
+            .await
+            .unwrap();
+    }
+
+    // Writes of other records are not blocked by the lock
+    #[tokio::test]
+    async fn lock_per_record() {
+        let port = spawn_kvsd().await;
+        let client = connect_kvsd(port).await;
+
+        let _held = client
+            .acquire_lock(&KvsdClient::feed_subscription_key("me"))
+            .await
+            .unwrap();
+        timeout(
+            Duration::from_secs(1),
+            client
+                .put_feed_subscription(subscription("you", &url("https://blog.ymgyt.io/atom.xml"))),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    }
 }
@@ -0,0 +1,305 @@
+//! Locks of each record shared by synd-api instances to serialize read-modify-write on kvsd.
+//!
+//! kvsd has no compare-and-set, but `delete` atomically removes the value and returns it.
+//! Each record has its own lock key which holds the fencing token of the last holder while the lock is free,
+//! and whoever deletes the key acquires the lock with the next token.
+//! The holder records its lease so that waiters can tell whether the holder has crashed.
+//! Since waiters release the lock of a stalled holder forcibly, the holder checks that the lease is
+//! still its own right before writing, so that a stalled holder does not clobber the writes of the next one.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use kvsd::{client::Api, Key, Value};
+use serde::{Deserialize, Serialize};
+use synd_o11y::metric;
+
+use crate::repository::{subscription::RepositoryResult, RepositoryError};
+
+use super::{pool::Pool, KvsdClient};
+
+/// Holder of the lock
+#[derive(Serialize, Deserialize, Debug)]
+struct Lease {
+    /// Random token to distinguish holders
+    owner: String,
+    /// Incremented on every acquisition of the lock
+    fencing_token: u64,
+    acquired_at: DateTime<Utc>,
+}
+
+impl Lease {
+    fn new(fencing_token: u64) -> Self {
+        Self {
+            owner: hex::encode(rand::random::<[u8; 16]>()),
+            fencing_token,
+            acquired_at: Utc::now(),
+        }
+    }
+}
+
+impl TryFrom<Value> for Lease {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<Lease> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: Lease) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Keys of the lock of a record
+#[derive(Clone)]
+struct LockKeys {
+    /// Exists while the lock is free and holds the fencing token of the last holder
+    lock: Key,
+    lease: Key,
+}
+
+impl LockKeys {
+    fn of(record: &Key) -> Self {
+        let path = record
+            .strip_prefix(KvsdClient::key_prefix())
+            .unwrap_or(record);
+        let key = |kind: &str| {
+            Key::new(format!(
+                "{prefix}/{kind}{path}",
+                prefix = KvsdClient::key_prefix()
+            ))
+            .expect("Invalid key")
+        };
+        Self {
+            lock: key("lock"),
+            lease: key("lock_lease"),
+        }
+    }
+
+    fn free(fencing_token: u64) -> Value {
+        Value::new(fencing_token.to_string().into_bytes()).expect("Invalid value")
+    }
+
+    /// Lock keys written before fencing tokens were introduced have no token
+    fn fencing_token(free: &Value) -> u64 {
+        std::str::from_utf8(free)
+            .ok()
+            .and_then(|token| token.parse().ok())
+            .unwrap_or(0)
+    }
+}
+
+/// Acquired lock of a record. The lock is released on drop unless released explicitly
+#[must_use]
+pub(super) struct LockGuard {
+    pool: Option<Arc<Pool>>,
+    keys: LockKeys,
+    lease: Value,
+    pub(super) fencing_token: u64,
+}
+
+impl LockGuard {
+    /// Set the value of the record only if the lock is still held.
+    /// Return false if the lock has been released forcibly and the value is not written
+    pub(super) async fn set(&self, key: &Key, value: Value) -> RepositoryResult<bool> {
+        if !self.is_held().await? {
+            return Ok(false);
+        }
+        self.pool()
+            .call(|connection| connection.set(key.clone(), value.clone()))
+            .await?;
+        Ok(true)
+    }
+
+    /// Delete the record only if the lock is still held.
+    /// Return false if the lock has been released forcibly and the value is not deleted
+    pub(super) async fn delete(&self, key: &Key) -> RepositoryResult<bool> {
+        if !self.is_held().await? {
+            return Ok(false);
+        }
+        self.pool()
+            .call(|connection| connection.delete(key.clone()))
+            .await?;
+        Ok(true)
+    }
+
+    async fn is_held(&self) -> RepositoryResult<bool> {
+        let lease_key = self.keys.lease.clone();
+        let current = self
+            .pool()
+            .call(|connection| connection.get(lease_key.clone()))
+            .await?;
+        if current.as_ref() == Some(&self.lease) {
+            return Ok(true);
+        }
+        tracing::warn!(
+            fencing_token = self.fencing_token,
+            "Kvsd lock has been released forcibly while held"
+        );
+        metric!(monotonic_counter.kvsd.lock.lost = 1);
+        Ok(false)
+    }
+
+    fn pool(&self) -> &Pool {
+        self.pool.as_ref().expect("lock is not released yet")
+    }
+
+    pub(super) async fn release(mut self) -> RepositoryResult<()> {
+        let pool = self.pool.take().expect("lock is not released yet");
+        Self::release_lease(&pool, &self.keys, &self.lease, self.fencing_token).await
+    }
+
+    /// Free the lock only if the lease is still ours.
+    /// If the lease has been released forcibly, the lock may be held by another instance now
+    async fn release_lease(
+        pool: &Pool,
+        keys: &LockKeys,
+        lease: &Value,
+        fencing_token: u64,
+    ) -> RepositoryResult<()> {
+        let current = pool
+            .call_once(|connection| connection.delete(keys.lease.clone()))
+            .await?;
+        if current.as_ref() == Some(lease) {
+            return pool
+                .call(|connection| connection.set(keys.lock.clone(), LockKeys::free(fencing_token)))
+                .await
+                .map_err(|RepositoryError::Internal(err)| {
+                    RepositoryError::Internal(err.context("failed to release kvsd lock"))
+                });
+        }
+
+        if let Some(current) = current {
+            pool.call(|connection| connection.set(keys.lease.clone(), current.clone()))
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        let Some(pool) = self.pool.take() else {
+            return;
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let (keys, lease, fencing_token) =
+            (self.keys.clone(), self.lease.clone(), self.fencing_token);
+        runtime.spawn(async move {
+            if let Err(err) = Self::release_lease(&pool, &keys, &lease, fencing_token).await {
+                tracing::warn!("Failed to release kvsd lock: {err}");
+            }
+        });
+    }
+}
+
+impl KvsdClient {
+    /// The lock is held only while read-modify-write of a record.
+    /// If the same lease is held longer than this duration, the holder is considered to have crashed
+    pub(super) const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+    /// If neither the lock nor its lease exists for this duration, the lock is created.
+    /// The lease does not exist while the holder is about to write it
+    const LOCK_CREATION_GRACE: Duration = Duration::from_millis(200);
+    const LOCK_MAX_BACKOFF: Duration = Duration::from_millis(100);
+
+    /// Acquire the lock of the record
+    pub(super) async fn acquire_lock(&self, record: &Key) -> RepositoryResult<LockGuard> {
+        let keys = LockKeys::of(record);
+        let mut backoff = Duration::from_millis(1);
+        // Lease of the holder and when it was first observed
+        let mut observed: Option<(Option<Value>, Instant)> = None;
+
+        loop {
+            if let Some(free) = self
+                .pool
+                .call_once(|connection| connection.delete(keys.lock.clone()))
+                .await?
+            {
+                let fencing_token = LockKeys::fencing_token(&free) + 1;
+                let lease: Value = Lease::new(fencing_token).try_into()?;
+                if let Err(err) = self
+                    .call(|connection| connection.set(keys.lease.clone(), lease.clone()))
+                    .await
+                {
+                    self.call(|connection| connection.set(keys.lock.clone(), free.clone()))
+                        .await?;
+                    return Err(err);
+                }
+                return Ok(LockGuard {
+                    pool: Some(Arc::clone(&self.pool)),
+                    keys,
+                    lease,
+                    fencing_token,
+                });
+            }
+
+            let lease = self
+                .call(|connection| connection.get(keys.lease.clone()))
+                .await?;
+            match observed {
+                Some((ref stale, since)) if *stale == lease => {
+                    let timeout = if lease.is_some() {
+                        self.lock_timeout
+                    } else {
+                        Self::LOCK_CREATION_GRACE
+                    };
+                    if since.elapsed() > timeout {
+                        self.release_stale_lock(&keys, lease).await?;
+                        observed = None;
+                        continue;
+                    }
+                }
+                _ => observed = Some((lease, Instant::now())),
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(Self::LOCK_MAX_BACKOFF);
+        }
+    }
+
+    /// Release the lock only if the stale lease is still present.
+    /// If another waiter has released it and acquired the lock in the meantime, its lease is restored.
+    /// The lock which has never been acquired is created in the same way since it has no lease
+    async fn release_stale_lock(
+        &self,
+        keys: &LockKeys,
+        stale: Option<Value>,
+    ) -> RepositoryResult<()> {
+        let current = self
+            .pool
+            .call_once(|connection| connection.delete(keys.lease.clone()))
+            .await?;
+        if current != stale {
+            if let Some(current) = current {
+                self.call(|connection| connection.set(keys.lease.clone(), current.clone()))
+                    .await?;
+            }
+            return Ok(());
+        }
+
+        let fencing_token = match stale.map(Lease::try_from).transpose()? {
+            Some(lease) => {
+                tracing::warn!(
+                    timeout = ?self.lock_timeout,
+                    ?lease,
+                    "Kvsd lock is not released, releasing it forcibly"
+                );
+                metric!(monotonic_counter.kvsd.lock.expired = 1);
+                lease.fencing_token
+            }
+            None => 0,
+        };
+        self.call(|connection| connection.set(keys.lock.clone(), LockKeys::free(fencing_token)))
+            .await
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    io::ErrorKind,
+    ops::{Deref, DerefMut},
+    panic::AssertUnwindSafe,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use futures_util::{future::BoxFuture, FutureExt, TryFutureExt};
+use kvsd::{
+    client::{
+        tcp::{Client, UnauthenticatedClient},
+        Api,
+    },
+    KvsdError,
+};
+use synd_o11y::metric;
+use tokio::{
+    net::TcpStream,
+    sync::{Semaphore, SemaphorePermit},
+};
+
+use crate::repository::{subscription::RepositoryResult, RepositoryError};
+
+use super::ConnectKvsdFailed;
+
+pub(super) type Connection = Client<TcpStream>;
+
+/// Address and credential to establish kvsd connections
+#[derive(Clone)]
+pub(super) struct Connector {
+    pub(super) host: String,
+    pub(super) port: u16,
+    pub(super) username: String,
+    pub(super) password: String,
+}
+
+impl Connector {
+    const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+    const MAX_BACKOFF: Duration = Duration::from_secs(3);
+
+    /// Establish an authenticated connection.
+    /// While kvsd refuses connections(e.g. restarting), retry with exponential backoff until timeout
+    pub(super) async fn connect(&self, timeout: Duration) -> anyhow::Result<Connection> {
+        let handshake = async {
+            let mut retry = 0;
+            let mut backoff = Self::INITIAL_BACKOFF;
+            loop {
+                match UnauthenticatedClient::insecure_from_addr(&self.host, self.port)
+                    .and_then(|client| client.authenticate(&self.username, &self.password))
+                    .await
+                {
+                    Ok(client) => break Ok(client),
+                    Err(KvsdError::Io(io)) if io.kind() == ErrorKind::ConnectionRefused => {
+                        tracing::info!(retry, ?backoff, "Kvsd connection refused");
+                    }
+                    Err(err) => break Err(err),
+                }
+                retry += 1;
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(Self::MAX_BACKOFF);
+            }
+        };
+
+        tokio::time::timeout(timeout, handshake)
+            .await
+            .map_err(anyhow::Error::from)
+            .context(ConnectKvsdFailed)?
+            .map_err(anyhow::Error::from)
+    }
+}
+
+struct IdleConnection {
+    connection: Connection,
+    since: Instant,
+}
+
+/// Fixed size pool of kvsd connections.
+/// Connections are established lazily and idle connections are health checked before reuse
+pub(super) struct Pool {
+    connector: Connector,
+    idle: Mutex<Vec<IdleConnection>>,
+    permits: Semaphore,
+    reconnect_timeout: Duration,
+}
+
+impl Pool {
+    /// Connections idle longer than this are pinged before reuse
+    const HEALTH_CHECK_IDLE: Duration = Duration::from_secs(10);
+
+    pub(super) fn new(
+        connector: Connector,
+        size: usize,
+        connection: Connection,
+        reconnect_timeout: Duration,
+    ) -> Self {
+        metric!(counter.kvsd.pool.connections = 1);
+        Self {
+            connector,
+            idle: Mutex::new(vec![IdleConnection {
+                connection,
+                since: Instant::now(),
+            }]),
+            permits: Semaphore::new(size.max(1)),
+            reconnect_timeout,
+        }
+    }
+
+    pub(super) async fn get(&self) -> anyhow::Result<PooledConnection<'_>> {
+        let started = Instant::now();
+        let permit = self.permits.acquire().await?;
+        metric!(histogram.kvsd.pool.wait = started.elapsed().as_secs_f64());
+
+        loop {
+            let idle = self.idle.lock().unwrap().pop();
+            let Some(IdleConnection {
+                mut connection,
+                since,
+            }) = idle
+            else {
+                break;
+            };
+            if since.elapsed() < Self::HEALTH_CHECK_IDLE || connection.ping().await.is_ok() {
+                return Ok(PooledConnection {
+                    connection: Some(connection),
+                    pool: self,
+                    _permit: permit,
+                });
+            }
+            tracing::info!("Discard unhealthy kvsd connection");
+            metric!(counter.kvsd.pool.connections = -1);
+        }
+
+        let connection = self.connector.connect(self.reconnect_timeout).await?;
+        metric!(counter.kvsd.pool.connections = 1);
+
+        Ok(PooledConnection {
+            connection: Some(connection),
+            pool: self,
+            _permit: permit,
+        })
+    }
+
+    /// Run the operation with a pooled connection.
+    /// If the connection is lost, reconnect and retry once. operations should be idempotent
+    pub(super) async fn call<F, T>(&self, op: F) -> RepositoryResult<T>
+    where
+        F: for<'c> Fn(&'c mut Connection) -> BoxFuture<'c, kvsd::Result<T>> + Send,
+        T: Send,
+    {
+        self.run(op, true).await
+    }
+
+    /// Run the operation without retry.
+    /// `delete` returns the removed value only to the first request, so if the response is lost,
+    /// retrying it would report that the value did not exist although it has been deleted
+    pub(super) async fn call_once<F, T>(&self, op: F) -> RepositoryResult<T>
+    where
+        F: for<'c> Fn(&'c mut Connection) -> BoxFuture<'c, kvsd::Result<T>> + Send,
+        T: Send,
+    {
+        self.run(op, false).await
+    }
+
+    async fn run<F, T>(&self, op: F, retry: bool) -> RepositoryResult<T>
+    where
+        F: for<'c> Fn(&'c mut Connection) -> BoxFuture<'c, kvsd::Result<T>> + Send,
+        T: Send,
+    {
+        let mut reconnected = false;
+        loop {
+            let mut connection = self.get().await?;
+            // kvsd client `get` and `delete` hit `unreachable!()` instead of returning error
+            // when the server closes the connection while waiting response(`read_message` returns `None`).
+            // Catch the panic so that it is handled as a lost connection rather than aborting the request
+            let err = match AssertUnwindSafe(op(&mut connection)).catch_unwind().await {
+                Ok(Ok(value)) => return Ok(value),
+                Ok(Err(err @ (KvsdError::Io(_) | KvsdError::Internal(_)))) => {
+                    anyhow::Error::from(err)
+                }
+                Ok(Err(err)) => return Err(err.into()),
+                Err(_) => anyhow::anyhow!("connection closed"),
+            };
+
+            connection.discard();
+            self.clear();
+            if !retry || reconnected {
+                return Err(RepositoryError::internal(
+                    err.context("kvsd connection lost"),
+                ));
+            }
+            tracing::warn!("Kvsd connection lost, reconnecting: {err}");
+            metric!(monotonic_counter.kvsd.pool.reconnect = 1);
+            reconnected = true;
+        }
+    }
+
+    /// Discard all idle connections.
+    /// When a connection is lost, kvsd is likely to be restarted and other connections are also stale
+    pub(super) fn clear(&self) {
+        let discarded = self.idle.lock().unwrap().drain(..).count();
+        metric!(counter.kvsd.pool.connections = -i64::try_from(discarded).unwrap_or(0));
+    }
+}
+
+/// Connection which returns to the pool on drop
+pub(super) struct PooledConnection<'a> {
+    connection: Option<Connection>,
+    pool: &'a Pool,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl<'a> PooledConnection<'a> {
+    /// Close the connection instead of returning it to the pool
+    pub(super) fn discard(mut self) {
+        self.connection.take();
+        metric!(counter.kvsd.pool.connections = -1);
+    }
+}
+
+impl<'a> Deref for PooledConnection<'a> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.connection.as_ref().unwrap()
+    }
+}
+
+impl<'a> DerefMut for PooledConnection<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.connection.as_mut().unwrap()
+    }
+}
+
+impl<'a> Drop for PooledConnection<'a> {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            self.pool.idle.lock().unwrap().push(IdleConnection {
+                connection,
+                since: Instant::now(),
+            });
+        }
+    }
+}
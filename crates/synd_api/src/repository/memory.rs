@@ -0,0 +1,374 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use synd_feed::types::{FeedUrl, Time};
+
+use crate::repository::{
+    self,
+    subscription::RepositoryResult,
+    types::{
+        ApiToken, ApiTokens, EntryReference, Folder, FolderId, ReadStates, StarredEntries,
+        StarredEntry, SubscribedFeeds, Webhook, WebhookDelivery, Webhooks,
+    },
+    ApiTokenRepository, ReadStateRepository, StarredEntryRepository, SubscriptionRepository,
+    WebhookRepository,
+};
+
+/// Repository which holds everything in memory.
+/// All data is lost when the process exits, so this is intended for tests and demos
+#[derive(Default)]
+pub struct MemoryRepository {
+    subscriptions: RwLock<HashMap<String, SubscribedFeeds>>,
+    read_states: RwLock<HashMap<String, ReadStates>>,
+    starred_entries: RwLock<HashMap<String, StarredEntries>>,
+    api_tokens: RwLock<HashMap<String, ApiTokens>>,
+    webhooks: RwLock<HashMap<String, Webhooks>>,
+}
+
+impl MemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SubscriptionRepository for MemoryRepository {
+    #[tracing::instrument(name = "repo::put_feed_subscription", skip_all)]
+    async fn put_feed_subscription(
+        &self,
+        feed: repository::types::FeedSubscription,
+    ) -> RepositoryResult<()> {
+        let annotations = feed.annotations();
+
+        self.subscriptions
+            .write()
+            .await
+            .entry(feed.user_id)
+            .or_default()
+            .put(feed.url, annotations);
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::put_feed_subscription_within", skip_all)]
+    async fn put_feed_subscription_within(
+        &self,
+        feed: repository::types::FeedSubscription,
+        max_subscriptions: usize,
+    ) -> RepositoryResult<bool> {
+        let annotations = feed.annotations();
+
+        let mut subscriptions = self.subscriptions.write().await;
+        let feeds = subscriptions.entry(feed.user_id).or_default();
+        if !feeds.can_subscribe(&feed.url, max_subscriptions) {
+            return Ok(false);
+        }
+        feeds.put(feed.url, annotations);
+
+        Ok(true)
+    }
+
+    #[tracing::instrument(name = "repo::delete_feed_subscription", skip_all)]
+    async fn delete_feed_subscription(
+        &self,
+        feed: repository::types::FeedSubscription,
+    ) -> RepositoryResult<()> {
+        if let Some(feeds) = self.subscriptions.write().await.get_mut(&feed.user_id) {
+            feeds.remove(&feed.url);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_subscribed_feed_urls", skip_all)]
+    async fn fetch_subscribed_feeds(&self, user_id: &str) -> RepositoryResult<SubscribedFeeds> {
+        Ok(self
+            .subscriptions
+            .read()
+            .await
+            .get(user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_user_ids", skip_all)]
+    async fn fetch_user_ids(&self) -> RepositoryResult<Vec<String>> {
+        let mut user_ids = BTreeSet::new();
+        user_ids.extend(self.subscriptions.read().await.keys().cloned());
+        user_ids.extend(self.read_states.read().await.keys().cloned());
+        user_ids.extend(self.starred_entries.read().await.keys().cloned());
+        user_ids.extend(self.api_tokens.read().await.keys().cloned());
+        user_ids.extend(self.webhooks.read().await.keys().cloned());
+        Ok(user_ids.into_iter().collect())
+    }
+
+    #[tracing::instrument(name = "repo::delete_subscriptions", skip_all)]
+    async fn delete_subscriptions(&self, user_id: &str) -> RepositoryResult<()> {
+        self.subscriptions.write().await.remove(user_id);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::create_folder", skip_all)]
+    async fn create_folder(
+        &self,
+        user_id: &str,
+        name: String,
+        parent: Option<FolderId>,
+    ) -> RepositoryResult<Folder> {
+        Ok(self
+            .subscriptions
+            .write()
+            .await
+            .entry(user_id.to_owned())
+            .or_default()
+            .create_folder(name, parent))
+    }
+
+    #[tracing::instrument(name = "repo::rename_folder", skip_all)]
+    async fn rename_folder(
+        &self,
+        user_id: &str,
+        folder_id: FolderId,
+        name: String,
+    ) -> RepositoryResult<()> {
+        if let Some(feeds) = self.subscriptions.write().await.get_mut(user_id) {
+            feeds.rename_folder(folder_id, name);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::move_feed", skip_all)]
+    async fn move_feed(
+        &self,
+        user_id: &str,
+        url: FeedUrl,
+        folder_id: Option<FolderId>,
+        position: Option<usize>,
+    ) -> RepositoryResult<()> {
+        if let Some(feeds) = self.subscriptions.write().await.get_mut(user_id) {
+            feeds.move_feed(&url, folder_id, position);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReadStateRepository for MemoryRepository {
+    #[tracing::instrument(name = "repo::mark_entries", skip_all)]
+    async fn mark_entries(
+        &self,
+        user_id: &str,
+        entries: Vec<EntryReference>,
+        read: bool,
+    ) -> RepositoryResult<()> {
+        self.read_states
+            .write()
+            .await
+            .entry(user_id.to_owned())
+            .or_default()
+            .mark_entries(entries, read);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::mark_feed_read", skip_all)]
+    async fn mark_feed_read(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        read_until: Option<Time>,
+        entry_ids: Vec<String>,
+    ) -> RepositoryResult<()> {
+        self.read_states
+            .write()
+            .await
+            .entry(user_id.to_owned())
+            .or_default()
+            .mark_feed_read(feed_url, read_until, entry_ids);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::prune_read_states", skip_all)]
+    async fn prune_read_states(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        retained: HashSet<String>,
+    ) -> RepositoryResult<()> {
+        if let Some(read_states) = self.read_states.write().await.get_mut(user_id) {
+            read_states.prune(&feed_url, &retained);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_read_states", skip_all)]
+    async fn fetch_read_states(&self, user_id: &str) -> RepositoryResult<ReadStates> {
+        Ok(self
+            .read_states
+            .read()
+            .await
+            .get(user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_read_states", skip_all)]
+    async fn delete_read_states(&self, user_id: &str) -> RepositoryResult<()> {
+        self.read_states.write().await.remove(user_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StarredEntryRepository for MemoryRepository {
+    #[tracing::instrument(name = "repo::star_entry", skip_all)]
+    async fn star_entry(&self, user_id: &str, entry: StarredEntry) -> RepositoryResult<()> {
+        self.starred_entries
+            .write()
+            .await
+            .entry(user_id.to_owned())
+            .or_default()
+            .star(entry);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::unstar_entry", skip_all)]
+    async fn unstar_entry(&self, user_id: &str, entry: EntryReference) -> RepositoryResult<()> {
+        if let Some(starred) = self.starred_entries.write().await.get_mut(user_id) {
+            starred.unstar(&entry);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_starred_entries", skip_all)]
+    async fn fetch_starred_entries(&self, user_id: &str) -> RepositoryResult<Vec<StarredEntry>> {
+        Ok(self
+            .starred_entries
+            .read()
+            .await
+            .get(user_id)
+            .map(|starred| starred.entries.clone())
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_starred_entries", skip_all)]
+    async fn delete_starred_entries(&self, user_id: &str) -> RepositoryResult<()> {
+        self.starred_entries.write().await.remove(user_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for MemoryRepository {
+    #[tracing::instrument(name = "repo::put_api_token", skip_all)]
+    async fn put_api_token(&self, token: ApiToken) -> RepositoryResult<()> {
+        self.api_tokens
+            .write()
+            .await
+            .entry(token.user_id.clone())
+            .or_default()
+            .tokens
+            .push(token);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_api_tokens", skip_all)]
+    async fn fetch_api_tokens(&self, user_id: &str) -> RepositoryResult<Vec<ApiToken>> {
+        Ok(self
+            .api_tokens
+            .read()
+            .await
+            .get(user_id)
+            .map(|tokens| tokens.tokens.clone())
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_api_token", skip_all)]
+    async fn delete_api_token(&self, user_id: &str, token_id: &str) -> RepositoryResult<bool> {
+        let mut api_tokens = self.api_tokens.write().await;
+        let Some(tokens) = api_tokens.get_mut(user_id) else {
+            return Ok(false);
+        };
+        let len = tokens.tokens.len();
+        tokens.tokens.retain(|token| token.id != token_id);
+        Ok(tokens.tokens.len() != len)
+    }
+
+    #[tracing::instrument(name = "repo::delete_api_tokens", skip_all)]
+    async fn delete_api_tokens(&self, user_id: &str) -> RepositoryResult<()> {
+        self.api_tokens.write().await.remove(user_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for MemoryRepository {
+    #[tracing::instrument(name = "repo::put_webhook", skip_all)]
+    async fn put_webhook(&self, webhook: Webhook) -> RepositoryResult<()> {
+        self.webhooks
+            .write()
+            .await
+            .entry(webhook.user_id.clone())
+            .or_default()
+            .webhooks
+            .push(webhook);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_webhooks", skip_all)]
+    async fn fetch_webhooks(&self, user_id: &str) -> RepositoryResult<Vec<Webhook>> {
+        Ok(self
+            .webhooks
+            .read()
+            .await
+            .get(user_id)
+            .map(|webhooks| webhooks.webhooks.clone())
+            .unwrap_or_default())
+    }
+
+    #[tracing::instrument(name = "repo::delete_webhook", skip_all)]
+    async fn delete_webhook(&self, user_id: &str, webhook_id: &str) -> RepositoryResult<bool> {
+        let mut webhooks = self.webhooks.write().await;
+        let Some(webhooks) = webhooks.get_mut(user_id) else {
+            return Ok(false);
+        };
+        let len = webhooks.webhooks.len();
+        webhooks.webhooks.retain(|webhook| webhook.id != webhook_id);
+        Ok(webhooks.webhooks.len() != len)
+    }
+
+    #[tracing::instrument(name = "repo::delete_webhooks", skip_all)]
+    async fn delete_webhooks(&self, user_id: &str) -> RepositoryResult<()> {
+        self.webhooks.write().await.remove(user_id);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::put_webhook_delivery", skip_all)]
+    async fn put_webhook_delivery(
+        &self,
+        user_id: &str,
+        delivery: WebhookDelivery,
+    ) -> RepositoryResult<()> {
+        self.webhooks
+            .write()
+            .await
+            .entry(user_id.to_owned())
+            .or_default()
+            .push_delivery(delivery);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_webhook_deliveries", skip_all)]
+    async fn fetch_webhook_deliveries(
+        &self,
+        user_id: &str,
+    ) -> RepositoryResult<Vec<WebhookDelivery>> {
+        Ok(self
+            .webhooks
+            .read()
+            .await
+            .get(user_id)
+            .map(|webhooks| webhooks.deliveries.clone())
+            .unwrap_or_default())
+    }
+}
@@ -2,9 +2,26 @@ mod subscription;
 use ::kvsd::KvsdError;
 pub use subscription::SubscriptionRepository;
 
+mod read_state;
+pub use read_state::ReadStateRepository;
+
+mod starred;
+pub use starred::StarredEntryRepository;
+
+mod api_token;
+pub use api_token::ApiTokenRepository;
+
+mod webhook;
+pub use webhook::WebhookRepository;
+
 pub mod kvsd;
+pub mod memory;
+pub mod sqlite;
 pub mod types;
 
+#[cfg(test)]
+mod contract;
+
 #[derive(thiserror::Error, Debug)]
 pub enum RepositoryError {
     #[error("internal error: {0}")]
@@ -0,0 +1,90 @@
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use synd_feed::types::{FeedUrl, Time};
+
+use crate::repository::{
+    subscription::RepositoryResult,
+    types::{EntryReference, ReadStates},
+};
+
+#[async_trait]
+pub trait ReadStateRepository: Send + Sync {
+    /// Mark given entries as read or unread
+    async fn mark_entries(
+        &self,
+        user_id: &str,
+        entries: Vec<EntryReference>,
+        read: bool,
+    ) -> RepositoryResult<()>;
+
+    /// Mark all entries of the feed as read.
+    /// Entries published at or before `read_until` and entries of `entry_ids` are regarded as read
+    async fn mark_feed_read(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        read_until: Option<Time>,
+        entry_ids: Vec<String>,
+    ) -> RepositoryResult<()>;
+
+    /// Prune explicit read states of the feed except for `retained` entry ids
+    /// so that they do not grow as the feed is updated
+    async fn prune_read_states(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        retained: HashSet<String>,
+    ) -> RepositoryResult<()>;
+
+    async fn fetch_read_states(&self, user_id: &str) -> RepositoryResult<ReadStates>;
+
+    /// Delete all read states of the user
+    async fn delete_read_states(&self, user_id: &str) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl<T> ReadStateRepository for Arc<T>
+where
+    T: ReadStateRepository,
+{
+    async fn mark_entries(
+        &self,
+        user_id: &str,
+        entries: Vec<EntryReference>,
+        read: bool,
+    ) -> RepositoryResult<()> {
+        (**self).mark_entries(user_id, entries, read).await
+    }
+
+    async fn mark_feed_read(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        read_until: Option<Time>,
+        entry_ids: Vec<String>,
+    ) -> RepositoryResult<()> {
+        (**self)
+            .mark_feed_read(user_id, feed_url, read_until, entry_ids)
+            .await
+    }
+
+    async fn prune_read_states(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        retained: HashSet<String>,
+    ) -> RepositoryResult<()> {
+        (**self)
+            .prune_read_states(user_id, feed_url, retained)
+            .await
+    }
+
+    async fn fetch_read_states(&self, user_id: &str) -> RepositoryResult<ReadStates> {
+        (**self).fetch_read_states(user_id).await
+    }
+
+    async fn delete_read_states(&self, user_id: &str) -> RepositoryResult<()> {
+        (**self).delete_read_states(user_id).await
+    }
+}
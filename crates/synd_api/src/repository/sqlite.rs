@@ -0,0 +1,905 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    Executor, Row, SqlitePool,
+};
+use synd_feed::types::{Category, FeedUrl, Requirement, Time};
+
+use crate::repository::{
+    self,
+    subscription::RepositoryResult,
+    types::{
+        ApiToken, ApiTokenScope, EntryReference, FeedAnnotations, Folder, FolderId, ReadStates,
+        StarredEntry, SubscribedFeeds, Webhook, WebhookDelivery, Webhooks,
+    },
+    ApiTokenRepository, ReadStateRepository, RepositoryError, StarredEntryRepository,
+    SubscriptionRepository, WebhookRepository,
+};
+
+/// Schema migrations applied in order.
+/// The number of applied migrations is tracked by `PRAGMA user_version`
+const MIGRATIONS: &[&str] = &[
+    include_str!("sqlite/migrations/0001_init.sql"),
+    include_str!("sqlite/migrations/0002_feed_annotations.sql"),
+    include_str!("sqlite/migrations/0003_folders.sql"),
+    include_str!("sqlite/migrations/0004_api_tokens.sql"),
+    include_str!("sqlite/migrations/0005_webhooks.sql"),
+];
+
+impl From<sqlx::Error> for RepositoryError {
+    fn from(value: sqlx::Error) -> Self {
+        RepositoryError::Internal(value.into())
+    }
+}
+
+pub struct SqliteRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRepository {
+    /// Open the database file, creating it if it does not exist, and apply pending migrations
+    pub async fn connect(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true)
+            .foreign_keys(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options)
+            .await
+            .with_context(|| format!("open sqlite database {}", path.display()))?;
+
+        Self::with_pool(pool).await
+    }
+
+    /// Open an in-memory database which is discarded when dropped
+    pub async fn in_memory() -> anyhow::Result<Self> {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?.foreign_keys(true);
+        // Each connection of in-memory database has its own database
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+
+        Self::with_pool(pool).await
+    }
+
+    async fn with_pool(pool: SqlitePool) -> anyhow::Result<Self> {
+        let repository = Self { pool };
+        repository.migrate().await.context("sqlite migration")?;
+        Ok(repository)
+    }
+
+    async fn migrate(&self) -> anyhow::Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let applied: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&mut *tx)
+            .await?;
+        let applied = usize::try_from(applied)?;
+
+        for (version, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+            let version = version + 1;
+            tx.execute(*migration)
+                .await
+                .with_context(|| format!("apply migration {version}"))?;
+            // PRAGMA does not support parameter binding
+            tx.execute(format!("PRAGMA user_version = {version}").as_str())
+                .await?;
+            tracing::info!(version, "Sqlite migration applied");
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    fn feed_url(url: &str) -> RepositoryResult<FeedUrl> {
+        FeedUrl::try_from(url).map_err(RepositoryError::internal)
+    }
+
+    fn requirement(requirement: Option<String>) -> RepositoryResult<Option<Requirement>> {
+        requirement
+            .map(|r| Requirement::from_str(&r).map_err(|err| anyhow::anyhow!(err)))
+            .transpose()
+            .map_err(RepositoryError::internal)
+    }
+
+    fn category(category: Option<String>) -> RepositoryResult<Option<Category<'static>>> {
+        category
+            .map(Category::new)
+            .transpose()
+            .map_err(RepositoryError::internal)
+    }
+
+    fn tags(tags: Option<String>) -> RepositoryResult<BTreeSet<Category<'static>>> {
+        tags.map(|tags| serde_json::from_str(&tags))
+            .transpose()
+            .map(Option::unwrap_or_default)
+            .map_err(RepositoryError::internal)
+    }
+
+    /// Subscribe the feed unless the user has `max_subscriptions` feeds without it.
+    /// The limit is checked in the same statement as the insertion so that concurrent subscriptions cannot exceed it
+    async fn upsert_feed_subscription(
+        &self,
+        feed: repository::types::FeedSubscription,
+        max_subscriptions: i64,
+    ) -> RepositoryResult<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        // Newly subscribed feed comes first. Keep the position on conflict
+        let Some(subscription_id): Option<i64> = sqlx::query_scalar(
+            "INSERT INTO subscriptions (user_id, feed_url, position)
+             SELECT ?1, ?2, (SELECT COALESCE(MIN(position), 0) - 1 FROM subscriptions WHERE user_id = ?1)
+             WHERE (SELECT COUNT(*) FROM subscriptions WHERE user_id = ?1) < ?3
+                OR EXISTS (SELECT 1 FROM subscriptions WHERE user_id = ?1 AND feed_url = ?2)
+             ON CONFLICT (user_id, feed_url) DO UPDATE SET feed_url = excluded.feed_url
+             RETURNING id",
+        )
+        .bind(&feed.user_id)
+        .bind(feed.url.as_str())
+        .bind(max_subscriptions)
+        .fetch_optional(&mut *tx)
+        .await?
+        else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            "INSERT INTO subscription_annotations
+                 (subscription_id, requirement, category, title, note, tags)
+             VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT (subscription_id) DO UPDATE
+             SET requirement = excluded.requirement, category = excluded.category,
+                 title = excluded.title, note = excluded.note, tags = excluded.tags",
+        )
+        .bind(subscription_id)
+        .bind(feed.requirement.map(|r| r.to_string()))
+        .bind(feed.category.as_ref().map(Category::as_str))
+        .bind(feed.title.as_deref())
+        .bind(feed.note.as_deref())
+        .bind(if feed.tags.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&feed.tags).map_err(RepositoryError::internal)?)
+        })
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl SubscriptionRepository for SqliteRepository {
+    #[tracing::instrument(name = "repo::put_feed_subscription", skip_all)]
+    async fn put_feed_subscription(
+        &self,
+        feed: repository::types::FeedSubscription,
+    ) -> RepositoryResult<()> {
+        self.upsert_feed_subscription(feed, i64::MAX)
+            .await
+            .map(|_| ())
+    }
+
+    #[tracing::instrument(name = "repo::put_feed_subscription_within", skip_all)]
+    async fn put_feed_subscription_within(
+        &self,
+        feed: repository::types::FeedSubscription,
+        max_subscriptions: usize,
+    ) -> RepositoryResult<bool> {
+        self.upsert_feed_subscription(feed, i64::try_from(max_subscriptions).unwrap_or(i64::MAX))
+            .await
+    }
+
+    #[tracing::instrument(name = "repo::delete_feed_subscription", skip_all)]
+    async fn delete_feed_subscription(
+        &self,
+        feed: repository::types::FeedSubscription,
+    ) -> RepositoryResult<()> {
+        // Annotations are deleted by cascade
+        sqlx::query("DELETE FROM subscriptions WHERE user_id = ? AND feed_url = ?")
+            .bind(&feed.user_id)
+            .bind(feed.url.as_str())
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_subscribed_feed_urls", skip_all)]
+    async fn fetch_subscribed_feeds(&self, user_id: &str) -> RepositoryResult<SubscribedFeeds> {
+        let rows = sqlx::query(
+            "SELECT s.feed_url, s.folder_id, a.requirement, a.category, a.title, a.note, a.tags
+             FROM subscriptions s
+             LEFT JOIN subscription_annotations a ON a.subscription_id = s.id
+             WHERE s.user_id = ?
+             ORDER BY s.position, s.id DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut urls = Vec::with_capacity(rows.len());
+        let mut annotations = HashMap::with_capacity(rows.len());
+        let mut feed_folders = HashMap::new();
+        for row in rows {
+            let url = Self::feed_url(row.try_get("feed_url")?)?;
+            if let Some(folder_id) = row.try_get::<Option<FolderId>, _>("folder_id")? {
+                feed_folders.insert(url.clone(), folder_id);
+            }
+            annotations.insert(
+                url.clone(),
+                FeedAnnotations {
+                    requirement: Self::requirement(row.try_get("requirement")?)?,
+                    category: Self::category(row.try_get("category")?)?,
+                    title: row.try_get("title")?,
+                    note: row.try_get("note")?,
+                    tags: Self::tags(row.try_get("tags")?)?,
+                },
+            );
+            urls.push(url);
+        }
+
+        let folders =
+            sqlx::query("SELECT id, name, parent_id FROM folders WHERE user_id = ? ORDER BY id")
+                .bind(user_id)
+                .fetch_all(&self.pool)
+                .await?
+                .into_iter()
+                .map(|row| {
+                    Ok(Folder {
+                        id: row.try_get("id")?,
+                        name: row.try_get("name")?,
+                        parent: row.try_get("parent_id")?,
+                    })
+                })
+                .collect::<RepositoryResult<Vec<_>>>()?;
+
+        Ok(SubscribedFeeds {
+            urls,
+            annotations: Some(annotations),
+            folders,
+            feed_folders,
+        })
+    }
+
+    #[tracing::instrument(name = "repo::fetch_user_ids", skip_all)]
+    async fn fetch_user_ids(&self) -> RepositoryResult<Vec<String>> {
+        Ok(sqlx::query_scalar(
+            "SELECT user_id FROM subscriptions
+             UNION SELECT user_id FROM feed_read_states
+             UNION SELECT user_id FROM entry_read_states
+             UNION SELECT user_id FROM starred_entries
+             UNION SELECT user_id FROM folders
+             UNION SELECT user_id FROM api_tokens
+             UNION SELECT user_id FROM webhooks
+             UNION SELECT user_id FROM webhook_deliveries
+             ORDER BY user_id",
+        )
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    #[tracing::instrument(name = "repo::delete_subscriptions", skip_all)]
+    async fn delete_subscriptions(&self, user_id: &str) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // Annotations are deleted by cascade
+        sqlx::query("DELETE FROM subscriptions WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM folders WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::create_folder", skip_all)]
+    async fn create_folder(
+        &self,
+        user_id: &str,
+        name: String,
+        parent: Option<FolderId>,
+    ) -> RepositoryResult<Folder> {
+        let id = sqlx::query_scalar(
+            "INSERT INTO folders (user_id, name, parent_id) VALUES (?, ?, ?) RETURNING id",
+        )
+        .bind(user_id)
+        .bind(&name)
+        .bind(parent)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Folder { id, name, parent })
+    }
+
+    #[tracing::instrument(name = "repo::rename_folder", skip_all)]
+    async fn rename_folder(
+        &self,
+        user_id: &str,
+        folder_id: FolderId,
+        name: String,
+    ) -> RepositoryResult<()> {
+        sqlx::query("UPDATE folders SET name = ? WHERE id = ? AND user_id = ?")
+            .bind(name)
+            .bind(folder_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::move_feed", skip_all)]
+    async fn move_feed(
+        &self,
+        user_id: &str,
+        url: FeedUrl,
+        folder_id: Option<FolderId>,
+        position: Option<usize>,
+    ) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // Write first to acquire the write lock before reading current order
+        let moved = sqlx::query(
+            "UPDATE subscriptions SET folder_id = ? WHERE user_id = ? AND feed_url = ?",
+        )
+        .bind(folder_id)
+        .bind(user_id)
+        .bind(url.as_str())
+        .execute(&mut *tx)
+        .await?;
+        if moved.rows_affected() == 0 {
+            return Ok(());
+        }
+
+        let rows = sqlx::query(
+            "SELECT feed_url, folder_id FROM subscriptions
+             WHERE user_id = ?
+             ORDER BY position, id DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let mut feeds = SubscribedFeeds::default();
+        for row in rows {
+            let feed_url = Self::feed_url(row.try_get("feed_url")?)?;
+            if let Some(folder_id) = row.try_get::<Option<FolderId>, _>("folder_id")? {
+                feeds.feed_folders.insert(feed_url.clone(), folder_id);
+            }
+            feeds.urls.push(feed_url);
+        }
+
+        // Reorder with the same rule as other repositories
+        feeds.move_feed(&url, folder_id, position);
+        for (position, feed_url) in feeds.urls.iter().enumerate() {
+            sqlx::query("UPDATE subscriptions SET position = ? WHERE user_id = ? AND feed_url = ?")
+                .bind(i64::try_from(position).map_err(RepositoryError::internal)?)
+                .bind(user_id)
+                .bind(feed_url.as_str())
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ReadStateRepository for SqliteRepository {
+    #[tracing::instrument(name = "repo::mark_entries", skip_all)]
+    async fn mark_entries(
+        &self,
+        user_id: &str,
+        entries: Vec<EntryReference>,
+        read: bool,
+    ) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for EntryReference { feed_url, entry_id } in entries {
+            sqlx::query(
+                "INSERT INTO entry_read_states (user_id, feed_url, entry_id, read)
+                 VALUES (?, ?, ?, ?)
+                 ON CONFLICT (user_id, feed_url, entry_id) DO UPDATE SET read = excluded.read",
+            )
+            .bind(user_id)
+            .bind(feed_url.as_str())
+            .bind(entry_id)
+            .bind(read)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::mark_feed_read", skip_all)]
+    async fn mark_feed_read(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        read_until: Option<Time>,
+        entry_ids: Vec<String>,
+    ) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // Write first to acquire the write lock before reading current read_until
+        // Replacing explicit states also prune the entries which are no longer in the feed
+        sqlx::query("DELETE FROM entry_read_states WHERE user_id = ? AND feed_url = ?")
+            .bind(user_id)
+            .bind(feed_url.as_str())
+            .execute(&mut *tx)
+            .await?;
+
+        for entry_id in entry_ids {
+            sqlx::query(
+                "INSERT INTO entry_read_states (user_id, feed_url, entry_id, read)
+                 VALUES (?, ?, ?, TRUE)",
+            )
+            .bind(user_id)
+            .bind(feed_url.as_str())
+            .bind(entry_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let current: Option<Option<Time>> = sqlx::query_scalar(
+            "SELECT read_until FROM feed_read_states WHERE user_id = ? AND feed_url = ?",
+        )
+        .bind(user_id)
+        .bind(feed_url.as_str())
+        .fetch_optional(&mut *tx)
+        .await?;
+        let read_until = current.flatten().max(read_until);
+
+        sqlx::query(
+            "INSERT INTO feed_read_states (user_id, feed_url, read_until) VALUES (?, ?, ?)
+             ON CONFLICT (user_id, feed_url) DO UPDATE SET read_until = excluded.read_until",
+        )
+        .bind(user_id)
+        .bind(feed_url.as_str())
+        .bind(read_until)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::prune_read_states", skip_all)]
+    async fn prune_read_states(
+        &self,
+        user_id: &str,
+        feed_url: FeedUrl,
+        retained: HashSet<String>,
+    ) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let entry_ids: Vec<String> = sqlx::query_scalar(
+            "SELECT entry_id FROM entry_read_states WHERE user_id = ? AND feed_url = ?",
+        )
+        .bind(user_id)
+        .bind(feed_url.as_str())
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for entry_id in entry_ids
+            .into_iter()
+            .filter(|entry_id| !retained.contains(entry_id))
+        {
+            sqlx::query(
+                "DELETE FROM entry_read_states WHERE user_id = ? AND feed_url = ? AND entry_id = ?",
+            )
+            .bind(user_id)
+            .bind(feed_url.as_str())
+            .bind(entry_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_read_states", skip_all)]
+    async fn fetch_read_states(&self, user_id: &str) -> RepositoryResult<ReadStates> {
+        let mut read_states = ReadStates::default();
+
+        let feeds =
+            sqlx::query("SELECT feed_url, read_until FROM feed_read_states WHERE user_id = ?")
+                .bind(user_id)
+                .fetch_all(&self.pool)
+                .await?;
+        for row in feeds {
+            let feed_url = Self::feed_url(row.try_get("feed_url")?)?;
+            read_states.feeds.entry(feed_url).or_default().read_until =
+                row.try_get("read_until")?;
+        }
+
+        let entries =
+            sqlx::query("SELECT feed_url, entry_id, read FROM entry_read_states WHERE user_id = ?")
+                .bind(user_id)
+                .fetch_all(&self.pool)
+                .await?;
+        for row in entries {
+            let feed_url = Self::feed_url(row.try_get("feed_url")?)?;
+            let entry_id: String = row.try_get("entry_id")?;
+            let read_state = read_states.feeds.entry(feed_url).or_default();
+            if row.try_get("read")? {
+                read_state.read.insert(entry_id);
+            } else {
+                read_state.unread.insert(entry_id);
+            }
+        }
+
+        Ok(read_states)
+    }
+
+    #[tracing::instrument(name = "repo::delete_read_states", skip_all)]
+    async fn delete_read_states(&self, user_id: &str) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM feed_read_states WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM entry_read_states WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StarredEntryRepository for SqliteRepository {
+    #[tracing::instrument(name = "repo::star_entry", skip_all)]
+    async fn star_entry(&self, user_id: &str, entry: StarredEntry) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        // Starring again moves the entry to the front
+        sqlx::query(
+            "DELETE FROM starred_entries WHERE user_id = ? AND feed_url = ? AND entry_id = ?",
+        )
+        .bind(user_id)
+        .bind(entry.feed_url.as_str())
+        .bind(&entry.entry_id)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO starred_entries (
+                user_id, feed_url, entry_id, title, website_url, summary, published, updated,
+                feed_title, requirement, category, starred_at
+             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(user_id)
+        .bind(entry.feed_url.as_str())
+        .bind(&entry.entry_id)
+        .bind(&entry.title)
+        .bind(&entry.website_url)
+        .bind(&entry.summary)
+        .bind(entry.published)
+        .bind(entry.updated)
+        .bind(&entry.feed_title)
+        .bind(entry.requirement.map(|r| r.to_string()))
+        .bind(entry.category.as_ref().map(Category::as_str))
+        .bind(entry.starred_at)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::unstar_entry", skip_all)]
+    async fn unstar_entry(&self, user_id: &str, entry: EntryReference) -> RepositoryResult<()> {
+        sqlx::query(
+            "DELETE FROM starred_entries WHERE user_id = ? AND feed_url = ? AND entry_id = ?",
+        )
+        .bind(user_id)
+        .bind(entry.feed_url.as_str())
+        .bind(&entry.entry_id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_starred_entries", skip_all)]
+    async fn fetch_starred_entries(&self, user_id: &str) -> RepositoryResult<Vec<StarredEntry>> {
+        let rows = sqlx::query(
+            "SELECT feed_url, entry_id, title, website_url, summary, published, updated,
+                    feed_title, requirement, category, starred_at
+             FROM starred_entries
+             WHERE user_id = ?
+             ORDER BY id DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(StarredEntry {
+                    feed_url: Self::feed_url(row.try_get("feed_url")?)?,
+                    entry_id: row.try_get("entry_id")?,
+                    title: row.try_get("title")?,
+                    website_url: row.try_get("website_url")?,
+                    summary: row.try_get("summary")?,
+                    published: row.try_get("published")?,
+                    updated: row.try_get("updated")?,
+                    feed_title: row.try_get("feed_title")?,
+                    requirement: Self::requirement(row.try_get("requirement")?)?,
+                    category: Self::category(row.try_get("category")?)?,
+                    starred_at: row.try_get("starred_at")?,
+                })
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(name = "repo::delete_starred_entries", skip_all)]
+    async fn delete_starred_entries(&self, user_id: &str) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM starred_entries WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ApiTokenRepository for SqliteRepository {
+    #[tracing::instrument(name = "repo::put_api_token", skip_all)]
+    async fn put_api_token(&self, token: ApiToken) -> RepositoryResult<()> {
+        sqlx::query(
+            "INSERT INTO api_tokens (id, user_id, name, secret_hash, scope, created_at, expires_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&token.id)
+        .bind(&token.user_id)
+        .bind(&token.name)
+        .bind(&token.secret_hash)
+        .bind(token.scope.as_str())
+        .bind(token.created_at)
+        .bind(token.expires_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_api_tokens", skip_all)]
+    async fn fetch_api_tokens(&self, user_id: &str) -> RepositoryResult<Vec<ApiToken>> {
+        let rows = sqlx::query(
+            "SELECT id, user_id, name, secret_hash, scope, created_at, expires_at
+             FROM api_tokens
+             WHERE user_id = ?
+             ORDER BY rowid",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(ApiToken {
+                    id: row.try_get("id")?,
+                    user_id: row.try_get("user_id")?,
+                    name: row.try_get("name")?,
+                    secret_hash: row.try_get("secret_hash")?,
+                    scope: ApiTokenScope::from_str(row.try_get("scope")?)?,
+                    created_at: row.try_get("created_at")?,
+                    expires_at: row.try_get("expires_at")?,
+                })
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(name = "repo::delete_api_token", skip_all)]
+    async fn delete_api_token(&self, user_id: &str, token_id: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query("DELETE FROM api_tokens WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(token_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "repo::delete_api_tokens", skip_all)]
+    async fn delete_api_tokens(&self, user_id: &str) -> RepositoryResult<()> {
+        sqlx::query("DELETE FROM api_tokens WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for SqliteRepository {
+    #[tracing::instrument(name = "repo::put_webhook", skip_all)]
+    async fn put_webhook(&self, webhook: Webhook) -> RepositoryResult<()> {
+        sqlx::query(
+            "INSERT INTO webhooks (id, user_id, url, secret, requirement, categories, template, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&webhook.id)
+        .bind(&webhook.user_id)
+        .bind(&webhook.url)
+        .bind(&webhook.secret)
+        .bind(webhook.requirement.to_string())
+        .bind(serde_json::to_string(&webhook.categories).map_err(RepositoryError::internal)?)
+        .bind(webhook.template.as_ref().map(ToString::to_string))
+        .bind(webhook.created_at)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_webhooks", skip_all)]
+    async fn fetch_webhooks(&self, user_id: &str) -> RepositoryResult<Vec<Webhook>> {
+        let rows = sqlx::query(
+            "SELECT id, user_id, url, secret, requirement, categories, template, created_at
+             FROM webhooks
+             WHERE user_id = ?
+             ORDER BY rowid",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let template: Option<String> = row.try_get("template")?;
+                Ok(Webhook {
+                    id: row.try_get("id")?,
+                    user_id: row.try_get("user_id")?,
+                    url: row.try_get("url")?,
+                    secret: row.try_get("secret")?,
+                    requirement: Self::requirement(row.try_get("requirement")?)?
+                        .unwrap_or(Requirement::Must),
+                    categories: serde_json::from_str(row.try_get("categories")?)
+                        .map_err(RepositoryError::internal)?,
+                    template: template
+                        .map(|template| serde_json::from_str(&template))
+                        .transpose()
+                        .map_err(RepositoryError::internal)?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect()
+    }
+
+    #[tracing::instrument(name = "repo::delete_webhook", skip_all)]
+    async fn delete_webhook(&self, user_id: &str, webhook_id: &str) -> RepositoryResult<bool> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE user_id = ? AND id = ?")
+            .bind(user_id)
+            .bind(webhook_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(name = "repo::delete_webhooks", skip_all)]
+    async fn delete_webhooks(&self, user_id: &str) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+        for query in [
+            "DELETE FROM webhook_deliveries WHERE user_id = ?",
+            "DELETE FROM webhooks WHERE user_id = ?",
+        ] {
+            sqlx::query(query).bind(user_id).execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::put_webhook_delivery", skip_all)]
+    async fn put_webhook_delivery(
+        &self,
+        user_id: &str,
+        delivery: WebhookDelivery,
+    ) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "INSERT INTO webhook_deliveries (
+                id, user_id, webhook_id, feed_url, entry_id, entry_title, attempts, status_code,
+                error, delivered_at
+             ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&delivery.id)
+        .bind(user_id)
+        .bind(&delivery.webhook_id)
+        .bind(delivery.feed_url.as_str())
+        .bind(&delivery.entry_id)
+        .bind(&delivery.entry_title)
+        .bind(delivery.attempts)
+        .bind(delivery.status_code)
+        .bind(&delivery.error)
+        .bind(delivery.delivered_at)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM webhook_deliveries
+             WHERE user_id = ? AND rowid NOT IN (
+                SELECT rowid FROM webhook_deliveries WHERE user_id = ? ORDER BY rowid DESC LIMIT ?
+             )",
+        )
+        .bind(user_id)
+        .bind(user_id)
+        .bind(i64::try_from(Webhooks::MAX_DELIVERIES).unwrap_or(i64::MAX))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "repo::fetch_webhook_deliveries", skip_all)]
+    async fn fetch_webhook_deliveries(
+        &self,
+        user_id: &str,
+    ) -> RepositoryResult<Vec<WebhookDelivery>> {
+        let rows = sqlx::query(
+            "SELECT id, webhook_id, feed_url, entry_id, entry_title, attempts, status_code, error,
+                    delivered_at
+             FROM webhook_deliveries
+             WHERE user_id = ?
+             ORDER BY rowid DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(WebhookDelivery {
+                    id: row.try_get("id")?,
+                    webhook_id: row.try_get("webhook_id")?,
+                    feed_url: Self::feed_url(row.try_get("feed_url")?)?,
+                    entry_id: row.try_get("entry_id")?,
+                    entry_title: row.try_get("entry_title")?,
+                    attempts: row.try_get("attempts")?,
+                    status_code: row.try_get("status_code")?,
+                    error: row.try_get("error")?,
+                    delivered_at: row.try_get("delivered_at")?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn migration_is_idempotent() {
+        let repo = SqliteRepository::in_memory().await.unwrap();
+        repo.migrate().await.unwrap();
+
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&repo.pool)
+            .await
+            .unwrap();
+        assert_eq!(version, i64::try_from(MIGRATIONS.len()).unwrap());
+    }
+}
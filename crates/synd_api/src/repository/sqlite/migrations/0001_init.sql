@@ -0,0 +1,44 @@
+CREATE TABLE subscriptions (
+    id       INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id  TEXT NOT NULL,
+    feed_url TEXT NOT NULL,
+    UNIQUE (user_id, feed_url)
+);
+
+CREATE TABLE subscription_annotations (
+    subscription_id INTEGER PRIMARY KEY REFERENCES subscriptions (id) ON DELETE CASCADE,
+    requirement     TEXT,
+    category        TEXT
+);
+
+CREATE TABLE feed_read_states (
+    user_id    TEXT NOT NULL,
+    feed_url   TEXT NOT NULL,
+    read_until TEXT,
+    PRIMARY KEY (user_id, feed_url)
+);
+
+CREATE TABLE entry_read_states (
+    user_id  TEXT    NOT NULL,
+    feed_url TEXT    NOT NULL,
+    entry_id TEXT    NOT NULL,
+    read     INTEGER NOT NULL,
+    PRIMARY KEY (user_id, feed_url, entry_id)
+);
+
+CREATE TABLE starred_entries (
+    id          INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id     TEXT NOT NULL,
+    feed_url    TEXT NOT NULL,
+    entry_id    TEXT NOT NULL,
+    title       TEXT,
+    website_url TEXT,
+    summary     TEXT,
+    published   TEXT,
+    updated     TEXT,
+    feed_title  TEXT,
+    requirement TEXT,
+    category    TEXT,
+    starred_at  TEXT NOT NULL,
+    UNIQUE (user_id, feed_url, entry_id)
+);
@@ -0,0 +1,4 @@
+ALTER TABLE subscription_annotations ADD COLUMN title TEXT;
+ALTER TABLE subscription_annotations ADD COLUMN note TEXT;
+-- JSON array of tags
+ALTER TABLE subscription_annotations ADD COLUMN tags TEXT;
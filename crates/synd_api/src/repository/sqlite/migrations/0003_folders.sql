@@ -0,0 +1,12 @@
+CREATE TABLE folders (
+    id        INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id   TEXT    NOT NULL,
+    name      TEXT    NOT NULL,
+    parent_id INTEGER REFERENCES folders (id) ON DELETE CASCADE
+);
+
+-- Subscriptions are ordered by position ascending and newly subscribed one comes first
+ALTER TABLE subscriptions ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+UPDATE subscriptions SET position = -id;
+
+ALTER TABLE subscriptions ADD COLUMN folder_id INTEGER REFERENCES folders (id) ON DELETE SET NULL;
@@ -0,0 +1,11 @@
+CREATE TABLE api_tokens (
+    id          TEXT    PRIMARY KEY,
+    user_id     TEXT    NOT NULL,
+    name        TEXT    NOT NULL,
+    secret_hash TEXT    NOT NULL,
+    scope       TEXT    NOT NULL,
+    created_at  TEXT    NOT NULL,
+    expires_at  TEXT
+);
+
+CREATE INDEX api_tokens_user_id ON api_tokens (user_id);
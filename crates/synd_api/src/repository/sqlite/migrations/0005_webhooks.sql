@@ -0,0 +1,29 @@
+CREATE TABLE webhooks (
+    id          TEXT    PRIMARY KEY,
+    user_id     TEXT    NOT NULL,
+    url         TEXT    NOT NULL,
+    secret      TEXT    NOT NULL,
+    requirement TEXT    NOT NULL,
+    -- JSON array of categories
+    categories  TEXT    NOT NULL,
+    -- JSON payload template
+    template    TEXT,
+    created_at  TEXT    NOT NULL
+);
+
+CREATE INDEX webhooks_user_id ON webhooks (user_id);
+
+CREATE TABLE webhook_deliveries (
+    id           TEXT    PRIMARY KEY,
+    user_id      TEXT    NOT NULL,
+    webhook_id   TEXT    NOT NULL,
+    feed_url     TEXT    NOT NULL,
+    entry_id     TEXT    NOT NULL,
+    entry_title  TEXT,
+    attempts     INTEGER NOT NULL,
+    status_code  INTEGER,
+    error        TEXT,
+    delivered_at TEXT    NOT NULL
+);
+
+CREATE INDEX webhook_deliveries_user_id ON webhook_deliveries (user_id);
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::repository::{
+    subscription::RepositoryResult,
+    types::{EntryReference, StarredEntry},
+};
+
+#[async_trait]
+pub trait StarredEntryRepository: Send + Sync {
+    /// Star given entry. if the entry is already starred, its snapshot is replaced
+    async fn star_entry(&self, user_id: &str, entry: StarredEntry) -> RepositoryResult<()>;
+
+    /// Unstar given entry. if the entry is not starred, do nothing
+    async fn unstar_entry(&self, user_id: &str, entry: EntryReference) -> RepositoryResult<()>;
+
+    /// Fetch starred entries ordered by the time they were starred, most recent first
+    async fn fetch_starred_entries(&self, user_id: &str) -> RepositoryResult<Vec<StarredEntry>>;
+
+    /// Delete all starred entries of the user
+    async fn delete_starred_entries(&self, user_id: &str) -> RepositoryResult<()>;
+}
+
+#[async_trait]
+impl<T> StarredEntryRepository for Arc<T>
+where
+    T: StarredEntryRepository,
+{
+    async fn star_entry(&self, user_id: &str, entry: StarredEntry) -> RepositoryResult<()> {
+        (**self).star_entry(user_id, entry).await
+    }
+
+    async fn unstar_entry(&self, user_id: &str, entry: EntryReference) -> RepositoryResult<()> {
+        (**self).unstar_entry(user_id, entry).await
+    }
+
+    async fn fetch_starred_entries(&self, user_id: &str) -> RepositoryResult<Vec<StarredEntry>> {
+        (**self).fetch_starred_entries(user_id).await
+    }
+
+    async fn delete_starred_entries(&self, user_id: &str) -> RepositoryResult<()> {
+        (**self).delete_starred_entries(user_id).await
+    }
+}
@@ -1,8 +1,12 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use synd_feed::types::FeedUrl;
 
-use crate::repository::{self, types::SubscribedFeeds};
+use crate::repository::{
+    self,
+    types::{Folder, FolderId, SubscribedFeeds},
+};
 
 use super::RepositoryError;
 
@@ -15,12 +19,50 @@ pub trait SubscriptionRepository: Send + Sync {
         feed: repository::types::FeedSubscription,
     ) -> RepositoryResult<()>;
 
+    /// Subscribe the feed only if the number of the user's subscriptions does not exceed `max_subscriptions`.
+    /// Updating annotations of the subscribed feed is always allowed.
+    /// The limit is checked atomically with the write. Return false if the feed is not subscribed
+    async fn put_feed_subscription_within(
+        &self,
+        feed: repository::types::FeedSubscription,
+        max_subscriptions: usize,
+    ) -> RepositoryResult<bool>;
+
     async fn delete_feed_subscription(
         &self,
         feed: repository::types::FeedSubscription,
     ) -> RepositoryResult<()>;
 
     async fn fetch_subscribed_feeds(&self, _user_id: &str) -> RepositoryResult<SubscribedFeeds>;
+
+    /// Ids of all users who have data in the repository, used to iterate over all users
+    async fn fetch_user_ids(&self) -> RepositoryResult<Vec<String>>;
+
+    /// Delete all subscriptions and folders of the user
+    async fn delete_subscriptions(&self, user_id: &str) -> RepositoryResult<()>;
+
+    async fn create_folder(
+        &self,
+        user_id: &str,
+        name: String,
+        parent: Option<FolderId>,
+    ) -> RepositoryResult<Folder>;
+
+    async fn rename_folder(
+        &self,
+        user_id: &str,
+        folder_id: FolderId,
+        name: String,
+    ) -> RepositoryResult<()>;
+
+    /// See [`SubscribedFeeds::move_feed`]
+    async fn move_feed(
+        &self,
+        user_id: &str,
+        url: FeedUrl,
+        folder_id: Option<FolderId>,
+        position: Option<usize>,
+    ) -> RepositoryResult<()>;
 }
 
 #[async_trait]
@@ -32,17 +74,63 @@ where
         &self,
         feed: repository::types::FeedSubscription,
     ) -> RepositoryResult<()> {
-        self.put_feed_subscription(feed).await
+        (**self).put_feed_subscription(feed).await
+    }
+
+    async fn put_feed_subscription_within(
+        &self,
+        feed: repository::types::FeedSubscription,
+        max_subscriptions: usize,
+    ) -> RepositoryResult<bool> {
+        (**self)
+            .put_feed_subscription_within(feed, max_subscriptions)
+            .await
     }
 
     async fn delete_feed_subscription(
         &self,
         feed: repository::types::FeedSubscription,
     ) -> RepositoryResult<()> {
-        self.delete_feed_subscription(feed).await
+        (**self).delete_feed_subscription(feed).await
     }
 
     async fn fetch_subscribed_feeds(&self, user_id: &str) -> RepositoryResult<SubscribedFeeds> {
-        self.fetch_subscribed_feeds(user_id).await
+        (**self).fetch_subscribed_feeds(user_id).await
+    }
+
+    async fn fetch_user_ids(&self) -> RepositoryResult<Vec<String>> {
+        (**self).fetch_user_ids().await
+    }
+
+    async fn delete_subscriptions(&self, user_id: &str) -> RepositoryResult<()> {
+        (**self).delete_subscriptions(user_id).await
+    }
+
+    async fn create_folder(
+        &self,
+        user_id: &str,
+        name: String,
+        parent: Option<FolderId>,
+    ) -> RepositoryResult<Folder> {
+        (**self).create_folder(user_id, name, parent).await
+    }
+
+    async fn rename_folder(
+        &self,
+        user_id: &str,
+        folder_id: FolderId,
+        name: String,
+    ) -> RepositoryResult<()> {
+        (**self).rename_folder(user_id, folder_id, name).await
+    }
+
+    async fn move_feed(
+        &self,
+        user_id: &str,
+        url: FeedUrl,
+        folder_id: Option<FolderId>,
+        position: Option<usize>,
+    ) -> RepositoryResult<()> {
+        (**self).move_feed(user_id, url, folder_id, position).await
     }
 }
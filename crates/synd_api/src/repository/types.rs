@@ -1,8 +1,13 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
 
 use kvsd::Value;
 use serde::{Deserialize, Serialize};
-use synd_feed::types::{self, Annotated, Category, FeedUrl, Requirement};
+use sha2::{Digest, Sha256};
+use synd_feed::types::{self, Annotated, Category, FeedUrl, Requirement, Time};
 
 use crate::repository::RepositoryError;
 
@@ -18,18 +23,72 @@ pub struct FeedSubscription {
     pub url: FeedUrl,
     pub requirement: Option<Requirement>,
     pub category: Option<Category<'static>>,
+    pub title: Option<String>,
+    pub note: Option<String>,
+    pub tags: BTreeSet<Category<'static>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+impl FeedSubscription {
+    pub fn annotations(&self) -> FeedAnnotations {
+        FeedAnnotations {
+            requirement: self.requirement,
+            category: self.category.clone(),
+            title: self.title.clone(),
+            note: self.note.clone(),
+            tags: self.tags.clone(),
+        }
+    }
+}
+
+/// Subscribed feeds of the user
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct SubscribedFeeds {
+    /// Feeds in the order defined by the user
     pub urls: Vec<FeedUrl>,
     pub annotations: Option<HashMap<FeedUrl, FeedAnnotations>>,
+    /// Folders in the order of creation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub folders: Vec<Folder>,
+    /// Folder which the feed belongs to. Feeds not in this are at the top level
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub feed_folders: HashMap<FeedUrl, FolderId>,
+}
+
+pub type FolderId = i64;
+
+/// Named group of subscribed feeds, which is independent of category and can be nested
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Folder {
+    pub id: FolderId,
+    pub name: String,
+    /// Folder which contains this folder. None if the folder is at the top level
+    pub parent: Option<FolderId>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct FeedAnnotations {
     pub requirement: Option<Requirement>,
     pub category: Option<Category<'static>>,
+    /// Title given by the user which overrides the title of the feed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<Category<'static>>,
+}
+
+impl FeedAnnotations {
+    pub fn annotate<T>(self, feed: T) -> Annotated<T> {
+        Annotated {
+            feed,
+            requirement: self.requirement,
+            category: self.category,
+            title: self.title,
+            note: self.note,
+            tags: self.tags,
+        }
+    }
 }
 
 impl TryFrom<Value> for SubscribedFeeds {
@@ -50,6 +109,79 @@ impl TryFrom<SubscribedFeeds> for Value {
 }
 
 impl SubscribedFeeds {
+    /// Subscribe the feed or update its annotations.
+    /// Newly subscribed feed comes first
+    pub fn put(&mut self, url: FeedUrl, annotations: FeedAnnotations) {
+        if !self.urls.contains(&url) {
+            self.urls.insert(0, url.clone());
+        }
+        self.annotations
+            .get_or_insert_with(HashMap::new)
+            .insert(url, annotations);
+    }
+
+    /// Whether the feed can be subscribed without exceeding `max_subscriptions`.
+    /// Subscribing the subscribed feed does not increase subscriptions
+    pub fn can_subscribe(&self, url: &FeedUrl, max_subscriptions: usize) -> bool {
+        self.urls.len() < max_subscriptions || self.urls.contains(url)
+    }
+
+    pub fn remove(&mut self, url: &FeedUrl) {
+        self.urls.retain(|u| u != url);
+        self.annotations.as_mut().map(|m| m.remove(url));
+        self.feed_folders.remove(url);
+    }
+
+    pub fn folder(&self, id: FolderId) -> Option<&Folder> {
+        self.folders.iter().find(|folder| folder.id == id)
+    }
+
+    /// Create the folder with the next id
+    pub fn create_folder(&mut self, name: String, parent: Option<FolderId>) -> Folder {
+        let id = self
+            .folders
+            .iter()
+            .map(|folder| folder.id)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let folder = Folder { id, name, parent };
+        self.folders.push(folder.clone());
+        folder
+    }
+
+    pub fn rename_folder(&mut self, id: FolderId, name: String) {
+        if let Some(folder) = self.folders.iter_mut().find(|folder| folder.id == id) {
+            folder.name = name;
+        }
+    }
+
+    /// Move the feed into the folder(top level if None) at the position among the feeds in the folder.
+    /// If the position is not given or out of range, the feed is placed at the end of the folder
+    pub fn move_feed(&mut self, url: &FeedUrl, folder: Option<FolderId>, position: Option<usize>) {
+        let Some(current) = self.urls.iter().position(|u| u == url) else {
+            return;
+        };
+        let url = self.urls.remove(current);
+        match folder {
+            Some(folder) => self.feed_folders.insert(url.clone(), folder),
+            None => self.feed_folders.remove(&url),
+        };
+
+        let siblings = self
+            .urls
+            .iter()
+            .enumerate()
+            .filter(|(_, u)| self.feed_folders.get(*u).copied() == folder)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let index = match position.and_then(|position| siblings.get(position)) {
+            Some(&index) => index,
+            None => siblings.last().map_or(self.urls.len(), |&last| last + 1),
+        };
+        self.urls.insert(index, url);
+    }
+
     pub fn annotate<Iter>(self, feeds: Iter) -> impl Iterator<Item = Annotated<Arc<types::Feed>>>
     where
         Iter: IntoIterator<Item = Arc<types::Feed>>,
@@ -57,21 +189,567 @@ impl SubscribedFeeds {
         let mut annotations = self.annotations;
 
         feeds.into_iter().map(move |feed| {
-            match annotations
+            annotations
                 .as_mut()
                 .and_then(|annotations| annotations.remove(feed.meta().url()))
+                .unwrap_or_default()
+                .annotate(feed)
+        })
+    }
+}
+
+/// Reference to the entry of the feed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntryReference {
+    pub feed_url: FeedUrl,
+    pub entry_id: String,
+}
+
+/// Read state of entries per feed
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ReadStates {
+    pub feeds: HashMap<FeedUrl, FeedReadState>,
+}
+
+impl ReadStates {
+    /// Mark given entries as read or unread
+    pub fn mark_entries(&mut self, entries: Vec<EntryReference>, read: bool) {
+        for EntryReference { feed_url, entry_id } in entries {
+            let read_state = self.feeds.entry(feed_url).or_default();
+            if read {
+                read_state.unread.remove(&entry_id);
+                read_state.read.insert(entry_id);
+            } else {
+                read_state.read.remove(&entry_id);
+                read_state.unread.insert(entry_id);
+            }
+        }
+    }
+
+    /// Mark all entries of the feed as read
+    pub fn mark_feed_read(
+        &mut self,
+        feed_url: FeedUrl,
+        read_until: Option<Time>,
+        entry_ids: Vec<String>,
+    ) {
+        let read_state = self.feeds.entry(feed_url).or_default();
+        read_state.read_until = read_state.read_until.max(read_until);
+        // Replacing explicit states also prune the entries which are no longer in the feed
+        read_state.read = entry_ids.into_iter().collect();
+        read_state.unread.clear();
+    }
+
+    /// Retain explicit states of the feed only for given entry ids
+    pub fn prune(&mut self, feed_url: &FeedUrl, retained: &HashSet<String>) {
+        if let Some(read_state) = self.feeds.get_mut(feed_url) {
+            read_state
+                .read
+                .retain(|entry_id| retained.contains(entry_id));
+            read_state
+                .unread
+                .retain(|entry_id| retained.contains(entry_id));
+        }
+    }
+
+    pub fn feed(&self, feed_url: &FeedUrl) -> Option<&FeedReadState> {
+        self.feeds.get(feed_url)
+    }
+
+    pub fn is_read(&self, feed_url: &FeedUrl, entry: &types::Entry) -> bool {
+        self.feed(feed_url)
+            .is_some_and(|read_state| read_state.is_read(entry))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct FeedReadState {
+    /// Entries published at or before this time are regarded as read
+    pub read_until: Option<Time>,
+    /// Entry ids which are explicitly marked as read
+    pub read: HashSet<String>,
+    /// Entry ids which are explicitly marked as unread
+    pub unread: HashSet<String>,
+}
+
+impl FeedReadState {
+    pub fn is_read(&self, entry: &types::Entry) -> bool {
+        let id = entry.id_ref().to_string();
+        if self.unread.contains(&id) {
+            return false;
+        }
+        if self.read.contains(&id) {
+            return true;
+        }
+        match (self.read_until, entry.published().or(entry.updated())) {
+            (Some(read_until), Some(time)) => time <= read_until,
+            _ => false,
+        }
+    }
+
+    /// Count entries which are not read
+    pub fn count_unread<'a>(
+        read_state: Option<&Self>,
+        entries: impl Iterator<Item = &'a types::Entry>,
+    ) -> usize {
+        entries
+            .filter(|entry| !read_state.is_some_and(|read_state| read_state.is_read(entry)))
+            .count()
+    }
+}
+
+impl TryFrom<Value> for ReadStates {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<ReadStates> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: ReadStates) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Snapshot of the entry taken when it was starred so that it outlives the feed cache
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StarredEntry {
+    pub feed_url: FeedUrl,
+    pub entry_id: String,
+    pub title: Option<String>,
+    pub website_url: Option<String>,
+    pub summary: Option<String>,
+    pub published: Option<Time>,
+    pub updated: Option<Time>,
+    pub feed_title: Option<String>,
+    pub requirement: Option<Requirement>,
+    pub category: Option<Category<'static>>,
+    pub starred_at: Time,
+}
+
+impl StarredEntry {
+    pub fn new(meta: &Annotated<types::FeedMeta>, entry: &types::Entry, starred_at: Time) -> Self {
+        Self {
+            feed_url: meta.feed.url().clone(),
+            entry_id: entry.id_ref().to_string(),
+            title: entry.title().map(ToOwned::to_owned),
+            website_url: entry.website_url(meta.feed.r#type()).map(ToOwned::to_owned),
+            summary: entry.summary().or(entry.content()).map(ToOwned::to_owned),
+            published: entry.published(),
+            updated: entry.updated(),
+            feed_title: meta
+                .title
+                .clone()
+                .or_else(|| meta.feed.title().map(ToOwned::to_owned)),
+            requirement: meta.requirement,
+            category: meta.category.clone(),
+            starred_at,
+        }
+    }
+
+    pub fn is(&self, reference: &EntryReference) -> bool {
+        self.feed_url == reference.feed_url && self.entry_id == reference.entry_id
+    }
+}
+
+/// Starred entries ordered by the time they were starred, most recent first
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct StarredEntries {
+    pub entries: Vec<StarredEntry>,
+}
+
+impl StarredEntries {
+    /// Star the entry. Starring again moves the entry to the front
+    pub fn star(&mut self, entry: StarredEntry) {
+        self.entries.retain(|starred| {
+            starred.feed_url != entry.feed_url || starred.entry_id != entry.entry_id
+        });
+        self.entries.insert(0, entry);
+    }
+
+    pub fn unstar(&mut self, entry: &EntryReference) {
+        self.entries.retain(|starred| !starred.is(entry));
+    }
+}
+
+impl TryFrom<Value> for StarredEntries {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<StarredEntries> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: StarredEntries) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Index of the users since kvsd does not support listing keys
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct UserIds {
+    pub ids: BTreeSet<String>,
+}
+
+impl TryFrom<Value> for UserIds {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<UserIds> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: UserIds) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Personal access token issued by the user for scripting.
+/// Only the hash of the secret is stored
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiToken {
+    pub id: String,
+    pub user_id: String,
+    /// Name given by the user to identify the token
+    pub name: String,
+    /// Hex encoded sha256 of the secret
+    pub secret_hash: String,
+    pub scope: ApiTokenScope,
+    pub created_at: Time,
+    /// The token never expires if None
+    pub expires_at: Option<Time>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiTokenScope {
+    ReadWrite,
+    ReadOnly,
+    /// Read write token issued in exchange for the password of a local user.
+    /// Authenticated as the login rather than a personal access token
+    Session,
+}
+
+impl ApiTokenScope {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ApiTokenScope::ReadWrite => "read_write",
+            ApiTokenScope::ReadOnly => "read_only",
+            ApiTokenScope::Session => "session",
+        }
+    }
+}
+
+impl FromStr for ApiTokenScope {
+    type Err = RepositoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read_write" => Ok(ApiTokenScope::ReadWrite),
+            "read_only" => Ok(ApiTokenScope::ReadOnly),
+            "session" => Ok(ApiTokenScope::Session),
+            _ => Err(RepositoryError::internal(anyhow::anyhow!(
+                "unknown api token scope: {s}"
+            ))),
+        }
+    }
+}
+
+impl ApiToken {
+    /// Separator of the plaintext token `{user_id}.{id}.{secret}`
+    const SEPARATOR: char = '.';
+
+    /// Issue a new token. Return the token to be stored and its plaintext which is shown to the user only once
+    pub fn issue(
+        user_id: impl Into<String>,
+        name: impl Into<String>,
+        scope: ApiTokenScope,
+        created_at: Time,
+        expires_at: Option<Time>,
+    ) -> (Self, String) {
+        let id = hex::encode(rand::random::<[u8; 8]>());
+        let secret = hex::encode(rand::random::<[u8; 32]>());
+        let token = ApiToken {
+            id,
+            user_id: user_id.into(),
+            name: name.into(),
+            secret_hash: Self::hash_secret(&secret),
+            scope,
+            created_at,
+            expires_at,
+        };
+        let plaintext = format!(
+            "{user_id}{sep}{id}{sep}{secret}",
+            user_id = token.user_id,
+            id = token.id,
+            sep = Self::SEPARATOR,
+        );
+        (token, plaintext)
+    }
+
+    /// Split the plaintext token into user id, token id and secret
+    pub fn parse_plaintext(plaintext: &str) -> Option<(&str, &str, &str)> {
+        let mut split = plaintext.splitn(3, Self::SEPARATOR);
+        match (split.next(), split.next(), split.next()) {
+            (Some(user_id), Some(id), Some(secret))
+                if !user_id.is_empty() && !id.is_empty() && !secret.is_empty() =>
             {
-                Some(annotations) => Annotated {
-                    feed,
-                    requirement: annotations.requirement,
-                    category: annotations.category,
-                },
-                None => Annotated {
-                    feed,
-                    requirement: None,
-                    category: None,
-                },
+                Some((user_id, id, secret))
             }
-        })
+            _ => None,
+        }
+    }
+
+    fn hash_secret(secret: &str) -> String {
+        hex::encode(Sha256::digest(secret.as_bytes()))
+    }
+
+    /// Return true if the secret matches and the token is not expired
+    pub fn verify(&self, secret: &str, now: Time) -> bool {
+        let hash = Self::hash_secret(secret);
+        // Compare without short circuit to not leak the position of the mismatch
+        let matched = hash.len() == self.secret_hash.len()
+            && hash
+                .bytes()
+                .zip(self.secret_hash.bytes())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0;
+
+        matched && !self.is_expired(now)
+    }
+
+    pub fn is_expired(&self, now: Time) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// Api tokens of the user in the order of creation
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ApiTokens {
+    pub tokens: Vec<ApiToken>,
+}
+
+impl TryFrom<Value> for ApiTokens {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<ApiTokens> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: ApiTokens) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+/// Endpoint to which new entries of the subscribed feeds are posted
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Webhook {
+    pub id: String,
+    pub user_id: String,
+    pub url: String,
+    /// Key to sign the payload with HMAC-SHA256
+    pub secret: String,
+    /// Only entries of the feeds which satisfy the requirement are delivered
+    pub requirement: Requirement,
+    /// Only entries of the feeds which belong to any of the categories are delivered.
+    /// Empty means all categories
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<Category<'static>>,
+    /// JSON payload with placeholders. The default payload is used if None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<serde_json::Value>,
+    pub created_at: Time,
+}
+
+impl Webhook {
+    /// Register a new webhook. If the secret is not given, a random one is generated
+    pub fn register(
+        user_id: impl Into<String>,
+        url: impl Into<String>,
+        secret: Option<String>,
+        requirement: Requirement,
+        categories: Vec<Category<'static>>,
+        template: Option<serde_json::Value>,
+        created_at: Time,
+    ) -> Self {
+        Self {
+            id: hex::encode(rand::random::<[u8; 8]>()),
+            user_id: user_id.into(),
+            url: url.into(),
+            secret: secret.unwrap_or_else(|| hex::encode(rand::random::<[u8; 32]>())),
+            requirement,
+            categories,
+            template,
+            created_at,
+        }
+    }
+}
+
+/// Result of posting an entry to the webhook
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: String,
+    pub webhook_id: String,
+    pub feed_url: FeedUrl,
+    pub entry_id: String,
+    pub entry_title: Option<String>,
+    /// Number of requests including retries
+    pub attempts: u32,
+    /// Http status of the last response
+    pub status_code: Option<u16>,
+    /// Error of the last attempt. None if the delivery succeeded
+    pub error: Option<String>,
+    pub delivered_at: Time,
+}
+
+impl WebhookDelivery {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Webhooks of the user in the order of creation and their recent deliveries
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Webhooks {
+    pub webhooks: Vec<Webhook>,
+    /// Most recent delivery first
+    #[serde(default)]
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+impl Webhooks {
+    /// Deliveries older than this are discarded
+    pub const MAX_DELIVERIES: usize = 100;
+
+    pub fn push_delivery(&mut self, delivery: WebhookDelivery) {
+        self.deliveries.insert(0, delivery);
+        self.deliveries.truncate(Self::MAX_DELIVERIES);
+    }
+}
+
+impl TryFrom<Value> for Webhooks {
+    type Error = RepositoryError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        serde_json::from_slice(&value).map_err(RepositoryError::internal)
+    }
+}
+
+impl TryFrom<Webhooks> for Value {
+    type Error = RepositoryError;
+
+    fn try_from(value: Webhooks) -> Result<Self, Self::Error> {
+        let value = serde_json::to_vec(&value).map_err(RepositoryError::internal)?;
+        Ok(Value::new(value).unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn url() -> FeedUrl {
+        FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap()
+    }
+
+    /// Entries "a" published on June 1, "b" on June 3 and "c" without time
+    fn entries() -> Vec<types::Entry> {
+        let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom"><id>test</id><title>test</title>
+            <entry><id>a</id><published>2024-06-01T00:00:00Z</published></entry>
+            <entry><id>b</id><updated>2024-06-03T00:00:00Z</updated></entry>
+            <entry><id>c</id></entry>
+        </feed>"#;
+        types::Feed::from((url(), feed_rs::parser::parse(atom.as_bytes()).unwrap()))
+            .entries()
+            .cloned()
+            .collect()
+    }
+
+    fn reference(entry_id: &str) -> EntryReference {
+        EntryReference {
+            feed_url: url(),
+            entry_id: entry_id.into(),
+        }
+    }
+
+    fn read_entries(read_states: &ReadStates, entries: &[types::Entry]) -> Vec<String> {
+        entries
+            .iter()
+            .filter(|entry| read_states.is_read(&url(), entry))
+            .map(|entry| entry.id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn explicit_state_take_precedence_over_read_until() {
+        let entries = entries();
+        let mut read_states = ReadStates::default();
+        assert!(read_entries(&read_states, &entries).is_empty());
+
+        read_states.mark_feed_read(
+            url(),
+            Some(Utc.with_ymd_and_hms(2024, 6, 2, 0, 0, 0).unwrap()),
+            Vec::new(),
+        );
+        // Entries without time are not regarded as read by read_until
+        assert_eq!(read_entries(&read_states, &entries), vec!["a"]);
+
+        read_states.mark_entries(vec![reference("a")], false);
+        read_states.mark_entries(vec![reference("b"), reference("c")], true);
+        assert_eq!(read_entries(&read_states, &entries), vec!["b", "c"]);
+
+        read_states.mark_entries(vec![reference("b")], false);
+        assert_eq!(read_entries(&read_states, &entries), vec!["c"]);
+    }
+
+    #[test]
+    fn count_unread() {
+        let entries = entries();
+        assert_eq!(FeedReadState::count_unread(None, entries.iter()), 3);
+
+        let mut read_states = ReadStates::default();
+        read_states.mark_entries(vec![reference("a"), reference("unknown")], true);
+        assert_eq!(
+            FeedReadState::count_unread(read_states.feed(&url()), entries.iter()),
+            2
+        );
+
+        read_states.mark_feed_read(url(), None, vec!["a".into(), "b".into(), "c".into()]);
+        assert_eq!(
+            FeedReadState::count_unread(read_states.feed(&url()), entries.iter()),
+            0
+        );
+    }
+
+    #[test]
+    fn prune_explicit_states() {
+        let mut read_states = ReadStates::default();
+        read_states.mark_entries(vec![reference("a"), reference("old")], true);
+        read_states.mark_entries(vec![reference("b"), reference("older")], false);
+
+        read_states.prune(&url(), &HashSet::from(["a".into(), "b".into()]));
+
+        let state = read_states.feed(&url()).unwrap();
+        assert_eq!(state.read, HashSet::from(["a".into()]));
+        assert_eq!(state.unread, HashSet::from(["b".into()]));
     }
 }
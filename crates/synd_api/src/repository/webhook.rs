@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::repository::{
+    subscription::RepositoryResult,
+    types::{Webhook, WebhookDelivery},
+};
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    /// Store the registered webhook
+    async fn put_webhook(&self, webhook: Webhook) -> RepositoryResult<()>;
+
+    /// Fetch webhooks of the user in the order of creation
+    async fn fetch_webhooks(&self, user_id: &str) -> RepositoryResult<Vec<Webhook>>;
+
+    /// Delete the webhook. Return false if the webhook does not exist.
+    /// Deliveries of the webhook are kept in the log
+    async fn delete_webhook(&self, user_id: &str, webhook_id: &str) -> RepositoryResult<bool>;
+
+    /// Delete all webhooks and deliveries of the user
+    async fn delete_webhooks(&self, user_id: &str) -> RepositoryResult<()>;
+
+    /// Append the delivery to the log.
+    /// Only the latest [`Webhooks::MAX_DELIVERIES`](crate::repository::types::Webhooks::MAX_DELIVERIES) deliveries are kept
+    async fn put_webhook_delivery(
+        &self,
+        user_id: &str,
+        delivery: WebhookDelivery,
+    ) -> RepositoryResult<()>;
+
+    /// Fetch deliveries of the user, most recent first
+    async fn fetch_webhook_deliveries(
+        &self,
+        user_id: &str,
+    ) -> RepositoryResult<Vec<WebhookDelivery>>;
+}
+
+#[async_trait]
+impl<T> WebhookRepository for Arc<T>
+where
+    T: WebhookRepository,
+{
+    async fn put_webhook(&self, webhook: Webhook) -> RepositoryResult<()> {
+        (**self).put_webhook(webhook).await
+    }
+
+    async fn fetch_webhooks(&self, user_id: &str) -> RepositoryResult<Vec<Webhook>> {
+        (**self).fetch_webhooks(user_id).await
+    }
+
+    async fn delete_webhook(&self, user_id: &str, webhook_id: &str) -> RepositoryResult<bool> {
+        (**self).delete_webhook(user_id, webhook_id).await
+    }
+
+    async fn delete_webhooks(&self, user_id: &str) -> RepositoryResult<()> {
+        (**self).delete_webhooks(user_id).await
+    }
+
+    async fn put_webhook_delivery(
+        &self,
+        user_id: &str,
+        delivery: WebhookDelivery,
+    ) -> RepositoryResult<()> {
+        (**self).put_webhook_delivery(user_id, delivery).await
+    }
+
+    async fn fetch_webhook_deliveries(
+        &self,
+        user_id: &str,
+    ) -> RepositoryResult<Vec<WebhookDelivery>> {
+        (**self).fetch_webhook_deliveries(user_id).await
+    }
+}
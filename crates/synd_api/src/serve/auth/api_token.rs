@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context as _};
+use async_trait::async_trait;
+
+use crate::{
+    principal::{Principal, User},
+    repository::{
+        types::{ApiToken, ApiTokenScope},
+        ApiTokenRepository,
+    },
+    serve::auth::AuthBackend,
+};
+
+/// Authenticate personal access tokens with `synd` scheme
+pub struct ApiTokenBackend {
+    repository: Arc<dyn ApiTokenRepository>,
+}
+
+impl ApiTokenBackend {
+    pub const SCHEME: &'static str = "synd";
+
+    pub fn new(repository: Arc<dyn ApiTokenRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for ApiTokenBackend {
+    fn scheme(&self) -> &'static str {
+        Self::SCHEME
+    }
+
+    /// Api tokens are not cached so that revocation takes effect immediately
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    async fn authenticate(&self, plaintext: &str) -> anyhow::Result<Principal> {
+        let (user_id, token_id, secret) =
+            ApiToken::parse_plaintext(plaintext).ok_or_else(|| anyhow!("malformed api token"))?;
+
+        let token = self
+            .repository
+            .fetch_api_tokens(user_id)
+            .await
+            .context("fetch api tokens")?
+            .into_iter()
+            .find(|token| token.id == token_id && token.verify(secret, chrono::Utc::now()))
+            .ok_or_else(|| anyhow!("invalid or expired api token"))?;
+
+        let user = User::from_id(token.user_id)
+            .with_read_only(token.scope == ApiTokenScope::ReadOnly)
+            .with_session(token.scope == ApiTokenScope::Session);
+        Ok(Principal::User(user))
+    }
+}
+
+/// Authenticate personal access tokens sent by Google Reader api clients as `GoogleLogin auth={token}`
+pub struct GoogleLoginBackend {
+    api_token: ApiTokenBackend,
+}
+
+impl GoogleLoginBackend {
+    pub const SCHEME: &'static str = "GoogleLogin";
+
+    pub fn new(repository: Arc<dyn ApiTokenRepository>) -> Self {
+        Self {
+            api_token: ApiTokenBackend::new(repository),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for GoogleLoginBackend {
+    fn scheme(&self) -> &'static str {
+        Self::SCHEME
+    }
+
+    fn cacheable(&self) -> bool {
+        self.api_token.cacheable()
+    }
+
+    async fn authenticate(&self, credential: &str) -> anyhow::Result<Principal> {
+        let plaintext = credential
+            .strip_prefix("auth=")
+            .ok_or_else(|| anyhow!("malformed GoogleLogin credential"))?;
+        self.api_token.authenticate(plaintext).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+
+    use crate::{repository::memory::MemoryRepository, serve::auth::Authenticator};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn authenticate_api_token() {
+        let repository = Arc::new(MemoryRepository::new());
+        let authenticator = Authenticator::new()
+            .unwrap()
+            .with_api_tokens(repository.clone());
+
+        let (read_only, read_only_token) =
+            ApiToken::issue("alice", "ci", ApiTokenScope::ReadOnly, Utc::now(), None);
+        let (_, expired_token) = {
+            let (token, plaintext) = ApiToken::issue(
+                "alice",
+                "expired",
+                ApiTokenScope::ReadWrite,
+                Utc::now() - Duration::try_days(2).unwrap(),
+                Some(Utc::now() - Duration::try_days(1).unwrap()),
+            );
+            repository.put_api_token(token.clone()).await.unwrap();
+            (token, plaintext)
+        };
+        repository.put_api_token(read_only.clone()).await.unwrap();
+
+        let principal = authenticator
+            .authenticate(format!("synd {read_only_token}"))
+            .await
+            .unwrap();
+        assert_eq!(principal.user_id(), Some("alice"));
+        assert!(principal.is_read_only());
+        assert!(principal.is_api_token());
+
+        assert!(authenticator
+            .authenticate(format!("synd {expired_token}"))
+            .await
+            .is_err());
+        assert!(authenticator
+            .authenticate(format!("synd alice.{}.invalid", read_only.id))
+            .await
+            .is_err());
+        assert!(authenticator.authenticate("synd malformed").await.is_err());
+
+        // Session tokens issued on login are not regarded as personal access tokens
+        let (session, session_token) = ApiToken::issue(
+            "alice",
+            "synd-term",
+            ApiTokenScope::Session,
+            Utc::now(),
+            Some(Utc::now() + Duration::try_days(1).unwrap()),
+        );
+        repository.put_api_token(session).await.unwrap();
+        let principal = authenticator
+            .authenticate(format!("synd {session_token}"))
+            .await
+            .unwrap();
+        assert!(!principal.is_read_only());
+        assert!(!principal.is_api_token());
+
+        repository
+            .delete_api_token("alice", &read_only.id)
+            .await
+            .unwrap();
+        assert!(authenticator
+            .authenticate(format!("synd {read_only_token}"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn authenticate_google_login() {
+        let repository = Arc::new(MemoryRepository::new());
+        let authenticator = Authenticator::new()
+            .unwrap()
+            .with_backend(GoogleLoginBackend::new(repository.clone()));
+        let (token, plaintext) = ApiToken::issue(
+            "alice",
+            "reader",
+            ApiTokenScope::ReadWrite,
+            Utc::now(),
+            None,
+        );
+        repository.put_api_token(token).await.unwrap();
+
+        let principal = authenticator
+            .authenticate(format!("GoogleLogin auth={plaintext}"))
+            .await
+            .unwrap();
+        assert_eq!(principal.user_id(), Some("alice"));
+        assert!(principal.is_api_token());
+        assert!(authenticator
+            .authenticate(format!("GoogleLogin {plaintext}"))
+            .await
+            .is_err());
+        // Only the GoogleLogin scheme is registered
+        assert!(authenticator
+            .authenticate(format!("synd {plaintext}"))
+            .await
+            .is_err());
+    }
+}
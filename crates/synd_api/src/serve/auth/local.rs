@@ -0,0 +1,302 @@
+use std::{collections::HashMap, path::Path};
+
+use anyhow::{anyhow, Context as _};
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use moka::future::Cache;
+use serde::Deserialize;
+use tokio::sync::Semaphore;
+
+use crate::{
+    config,
+    principal::{Principal, User},
+    serve::auth::AuthBackend,
+};
+
+/// User defined in the local users file
+#[derive(Deserialize, Debug, Clone)]
+pub struct LocalUser {
+    pub username: String,
+    /// Argon2 hash in PHC string format generated by `synd-api admin hash-password`
+    pub password_hash: String,
+    /// Share the data with the user who logged in with github or google by the email
+    pub email: Option<String>,
+}
+
+/// Local users file
+///
+/// ```toml
+/// [[users]]
+/// username = "alice"
+/// password_hash = "$argon2id$v=19$m=19456,t=2,p=1$..."
+/// ```
+#[derive(Deserialize, Debug, Default)]
+pub struct LocalUsers {
+    #[serde(default)]
+    pub users: Vec<LocalUser>,
+}
+
+impl LocalUsers {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let users =
+            std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+        toml::from_str(&users).with_context(|| format!("parse {}", path.display()))
+    }
+}
+
+/// Authenticate the users defined by the self hoster with `local` scheme.
+/// The credential is base64 encoded `username:password` as in basic authentication
+pub struct LocalBackend {
+    users: HashMap<String, LocalUser>,
+    /// Verified when the user is not found so that the response time does not reveal usernames
+    dummy_hash: String,
+    /// Failed logins of each username to throttle password guessing
+    failures: Cache<String, u32>,
+    /// Bound the cpu used by argon2 regardless of usernames
+    verifications: Semaphore,
+}
+
+impl LocalBackend {
+    pub const SCHEME: &'static str = "local";
+
+    pub fn new(users: LocalUsers) -> anyhow::Result<Self> {
+        let mut map = HashMap::with_capacity(users.users.len());
+        for user in users.users {
+            anyhow::ensure!(
+                !user.username.is_empty() && !user.username.contains(':'),
+                "invalid username `{}`: must not be empty or contain ':'",
+                user.username,
+            );
+            PasswordHash::new(&user.password_hash)
+                .map_err(|err| anyhow!("invalid password hash of `{}`: {err}", user.username))?;
+            let username = user.username.clone();
+            anyhow::ensure!(
+                map.insert(username.clone(), user).is_none(),
+                "duplicate username `{username}`"
+            );
+        }
+
+        Ok(Self {
+            users: map,
+            dummy_hash: hash_password("")?,
+            failures: Cache::builder()
+                .max_capacity(config::auth::FAILED_LOGIN_CAPACITY)
+                .time_to_live(config::auth::FAILED_LOGIN_WINDOW)
+                .build(),
+            verifications: Semaphore::new(config::auth::MAX_CONCURRENT_PASSWORD_VERIFICATIONS),
+        })
+    }
+
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        Self::new(LocalUsers::load(path)?)
+    }
+}
+
+#[async_trait]
+impl AuthBackend for LocalBackend {
+    fn scheme(&self) -> &'static str {
+        Self::SCHEME
+    }
+
+    /// The credential contains the password, so it is not kept in memory as the cache key.
+    /// Local credentials are sent only on login
+    fn cacheable(&self) -> bool {
+        false
+    }
+
+    async fn authenticate(&self, credential: &str) -> anyhow::Result<Principal> {
+        let credential = STANDARD
+            .decode(credential)
+            .ok()
+            .and_then(|credential| String::from_utf8(credential).ok())
+            .ok_or_else(|| anyhow!("malformed local credential"))?;
+        let (username, password) = credential
+            .split_once(':')
+            .ok_or_else(|| anyhow!("malformed local credential"))?;
+
+        if self
+            .failures
+            .get(username)
+            .await
+            .is_some_and(|failures| failures >= config::auth::MAX_FAILED_LOGINS)
+        {
+            return Err(anyhow!("too many failed logins of `{username}`"));
+        }
+
+        let user = self.users.get(username);
+        let password_hash = user.map_or(&self.dummy_hash, |user| &user.password_hash);
+        let verified = {
+            let _permit = self.verifications.acquire().await?;
+            verify_password(password.to_owned(), password_hash.clone()).await
+        };
+
+        match user {
+            Some(user) if verified => {
+                self.failures.invalidate(username).await;
+                let user = match user.email.as_deref() {
+                    Some(email) => User::from_email(email),
+                    None => User::from_local_username(username),
+                };
+                Ok(Principal::User(user))
+            }
+            _ => {
+                self.failures
+                    .entry_by_ref(username)
+                    .and_upsert_with(|failures| async move {
+                        failures.map_or(1, |failures| failures.into_value() + 1)
+                    })
+                    .await;
+                Err(anyhow!("invalid username or password"))
+            }
+        }
+    }
+}
+
+/// Hash the password with argon2 and return it in PHC string format
+pub fn hash_password(password: &str) -> anyhow::Result<String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|err| anyhow!("hash password: {err}"))
+}
+
+/// Argon2 is cpu intensive by design, so verify on the blocking threads
+async fn verify_password(password: String, password_hash: String) -> bool {
+    tokio::task::spawn_blocking(move || {
+        PasswordHash::new(&password_hash).is_ok_and(|hash| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &hash)
+                .is_ok()
+        })
+    })
+    .await
+    .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credential(username: &str, password: &str) -> String {
+        STANDARD.encode(format!("{username}:{password}"))
+    }
+
+    #[tokio::test]
+    async fn authenticate_local_user() {
+        let users: LocalUsers = toml::from_str(&format!(
+            r#"
+            [[users]]
+            username = "alice"
+            password_hash = "{alice}"
+
+            [[users]]
+            username = "bob"
+            password_hash = "{bob}"
+            email = "bob@syndicationd.ymgyt.io"
+            "#,
+            alice = hash_password("alice secret").unwrap(),
+            bob = hash_password("bob secret").unwrap(),
+        ))
+        .unwrap();
+        let backend = LocalBackend::new(users).unwrap();
+
+        let alice = backend
+            .authenticate(&credential("alice", "alice secret"))
+            .await
+            .unwrap();
+        assert_eq!(
+            alice.user_id(),
+            User::from_local_username("alice").id().into()
+        );
+
+        let bob = backend
+            .authenticate(&credential("bob", "bob secret"))
+            .await
+            .unwrap();
+        assert_eq!(
+            bob.user_id(),
+            User::from_email("bob@syndicationd.ymgyt.io").id().into()
+        );
+
+        for credential in [
+            credential("alice", "bob secret"),
+            credential("carol", "alice secret"),
+            "alice:alice secret".into(),
+            STANDARD.encode("alice"),
+        ] {
+            assert!(backend.authenticate(&credential).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn throttle_failed_logins() {
+        let users: LocalUsers = toml::from_str(&format!(
+            r#"
+            [[users]]
+            username = "alice"
+            password_hash = "{alice}"
+
+            [[users]]
+            username = "bob"
+            password_hash = "{bob}"
+            "#,
+            alice = hash_password("alice secret").unwrap(),
+            bob = hash_password("bob secret").unwrap(),
+        ))
+        .unwrap();
+        let backend = LocalBackend::new(users).unwrap();
+        assert!(!backend.cacheable());
+
+        // Failures are reset on success
+        assert!(backend
+            .authenticate(&credential("alice", "guess"))
+            .await
+            .is_err());
+        assert!(backend
+            .authenticate(&credential("alice", "alice secret"))
+            .await
+            .is_ok());
+
+        for _ in 0..config::auth::MAX_FAILED_LOGINS {
+            assert!(backend
+                .authenticate(&credential("alice", "guess"))
+                .await
+                .is_err());
+        }
+        let err = backend
+            .authenticate(&credential("alice", "alice secret"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("too many failed logins"), "{err}");
+
+        // Other users are not affected
+        assert!(backend
+            .authenticate(&credential("bob", "bob secret"))
+            .await
+            .is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_users() {
+        let user = |username: &str, password_hash: &str| LocalUser {
+            username: username.into(),
+            password_hash: password_hash.into(),
+            email: None,
+        };
+        let hash = hash_password("secret").unwrap();
+
+        for users in [
+            vec![user("", &hash)],
+            vec![user("a:b", &hash)],
+            vec![user("alice", "plaintext")],
+            vec![user("alice", &hash), user("alice", &hash)],
+        ] {
+            assert!(LocalBackend::new(LocalUsers { users }).is_err());
+        }
+    }
+}
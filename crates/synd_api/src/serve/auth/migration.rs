@@ -0,0 +1,164 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex as StdMutex},
+};
+
+use tokio::sync::Mutex;
+
+use crate::{
+    admin::{self, MovedUser},
+    dependency::Repositories,
+    principal::Principal,
+};
+
+/// Move the data stored under the legacy user id to the current one when the user is authenticated
+#[derive(Clone)]
+pub struct UserIdMigration {
+    repositories: Arc<Repositories>,
+    /// Lock of each legacy id, which holds whether the id has been checked by this process
+    checked: Arc<StdMutex<HashMap<String, Arc<Mutex<bool>>>>>,
+}
+
+impl UserIdMigration {
+    pub fn new(repositories: Repositories) -> Self {
+        Self {
+            repositories: Arc::new(repositories),
+            checked: Arc::default(),
+        }
+    }
+
+    /// Migrate the data of the principal if any.
+    /// Failure is logged and does not prevent the authentication
+    pub async fn migrate(&self, principal: &Principal) {
+        let Principal::User(user) = principal;
+        let Some(legacy_id) = user.legacy_id() else {
+            return;
+        };
+
+        // Hold the lock of the legacy id while moving so that concurrent requests of the user do not import the data twice.
+        // Requests of other users are not blocked
+        let lock = Arc::clone(
+            self.checked
+                .lock()
+                .unwrap()
+                .entry(legacy_id.to_owned())
+                .or_default(),
+        );
+        let mut checked = lock.lock().await;
+        if *checked {
+            return;
+        }
+        match admin::move_user(&self.repositories, legacy_id, user.id()).await {
+            Ok(moved) => {
+                if let Some(MovedUser { revoked_api_tokens }) = moved {
+                    tracing::info!(legacy_id, user_id = user.id(), "Migrate user id");
+                    if !revoked_api_tokens.is_empty() {
+                        tracing::warn!(
+                            legacy_id,
+                            user_id = user.id(),
+                            ?revoked_api_tokens,
+                            "Api tokens and syndication feed urls of the user are revoked by the migration and must be created again"
+                        );
+                    }
+                }
+                *checked = true;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    legacy_id,
+                    user_id = user.id(),
+                    "Failed to migrate user id: {err}"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixture::{subscription, url},
+        principal::User,
+        repository::memory::MemoryRepository,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn migrate_legacy_user_id() {
+        let repositories = Repositories::new(MemoryRepository::new());
+        let user = User::from_email("foo@ymgyt.io");
+        repositories
+            .subscription
+            .put_feed_subscription(subscription(
+                "585779d8c9b2e06d",
+                &url("https://a.ymgyt.io/feed"),
+            ))
+            .await
+            .unwrap();
+        let migration = UserIdMigration::new(repositories.clone());
+
+        for _ in 0..2 {
+            migration.migrate(&Principal::User(user.clone())).await;
+            assert_eq!(
+                repositories.subscription.fetch_user_ids().await.unwrap(),
+                vec!["cc80eb522a51b6b3".to_owned()],
+            );
+        }
+        let feeds = repositories
+            .subscription
+            .fetch_subscribed_feeds("cc80eb522a51b6b3")
+            .await
+            .unwrap();
+        assert_eq!(feeds.urls.len(), 1);
+    }
+
+    /// Moving again after the legacy data failed to be deleted does not duplicate folders
+    #[tokio::test]
+    async fn retry_partially_migrated_user_id() {
+        let repositories = Repositories::new(MemoryRepository::new());
+        let user = User::from_email("foo@ymgyt.io");
+        for user_id in ["585779d8c9b2e06d", "cc80eb522a51b6b3"] {
+            let folder = repositories
+                .subscription
+                .create_folder(user_id, "blogs".into(), None)
+                .await
+                .unwrap();
+            repositories
+                .subscription
+                .put_feed_subscription(subscription(user_id, &url("https://a.ymgyt.io/feed")))
+                .await
+                .unwrap();
+            repositories
+                .subscription
+                .move_feed(
+                    user_id,
+                    url("https://a.ymgyt.io/feed"),
+                    Some(folder.id),
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        UserIdMigration::new(repositories.clone())
+            .migrate(&Principal::User(user))
+            .await;
+
+        let feeds = repositories
+            .subscription
+            .fetch_subscribed_feeds("cc80eb522a51b6b3")
+            .await
+            .unwrap();
+        assert_eq!(feeds.folders.len(), 1);
+        assert_eq!(feeds.urls.len(), 1);
+        assert_eq!(
+            feeds.feed_folders.get(&url("https://a.ymgyt.io/feed")),
+            Some(&feeds.folders[0].id)
+        );
+        assert_eq!(
+            repositories.subscription.fetch_user_ids().await.unwrap(),
+            vec!["cc80eb522a51b6b3".to_owned()],
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use moka::future::Cache;
+use synd_auth::jwt::google::JwtService as GoogleJwtService;
+use tracing::warn;
+
+use crate::{
+    client::github::GithubClient,
+    principal::{Principal, Role, User},
+    repository::ApiTokenRepository,
+    serve::layer::authenticate::Authenticate,
+};
+
+mod api_token;
+pub use api_token::{ApiTokenBackend, GoogleLoginBackend};
+mod local;
+mod migration;
+pub use local::{hash_password, LocalBackend, LocalUser, LocalUsers};
+pub use migration::UserIdMigration;
+mod oauth;
+pub use oauth::{GithubBackend, GoogleBackend, OidcBackend};
+
+/// Backend which authenticates the credential of an `Authorization` scheme
+#[async_trait]
+pub trait AuthBackend: Send + Sync {
+    /// Scheme of the `Authorization` header handled by this backend(e.g. `github`)
+    fn scheme(&self) -> &'static str;
+
+    /// Whether the authenticated principal can be cached by the credential
+    fn cacheable(&self) -> bool {
+        true
+    }
+
+    /// Authenticate the credential which follows the scheme
+    async fn authenticate(&self, credential: &str) -> anyhow::Result<Principal>;
+}
+
+/// Users who are granted the admin role, matched by the user id.
+/// Emails are resolved to the user ids derived from them, so that the principals
+/// authenticated without email(e.g. api tokens) are matched as well
+#[derive(Clone, Default)]
+pub struct Admins {
+    user_ids: Arc<HashSet<String>>,
+}
+
+impl Admins {
+    pub fn new<I, S>(users: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let user_ids = users
+            .into_iter()
+            .map(Into::into)
+            .map(|user| {
+                if user.contains('@') {
+                    User::from_email(user).id().to_owned()
+                } else {
+                    user
+                }
+            })
+            .collect();
+        Self {
+            user_ids: Arc::new(user_ids),
+        }
+    }
+
+    /// Assign the role to the authenticated principal
+    fn assign_role(&self, principal: Principal) -> Principal {
+        let is_admin = match &principal {
+            Principal::User(user) => self.user_ids.contains(user.id()),
+        };
+        if is_admin {
+            principal.with_role(Role::Admin)
+        } else {
+            principal
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Authenticator {
+    backends: HashMap<&'static str, Arc<dyn AuthBackend>>,
+    cache: Cache<String, Principal>,
+    admins: Admins,
+    migration: Option<UserIdMigration>,
+}
+
+impl Authenticator {
+    /// Construct `Authenticator` with github and google backends
+    pub fn new() -> anyhow::Result<Self> {
+        let cache = Cache::builder()
+            .max_capacity(1024 * 1024)
+            .time_to_live(Duration::from_secs(60 * 60))
+            .build();
+
+        Ok(Self {
+            backends: HashMap::new(),
+            cache,
+            admins: Admins::default(),
+            migration: None,
+        }
+        .with_backend(GithubBackend::new(GithubClient::new()?))
+        .with_backend(GoogleBackend::new(GoogleJwtService::default())))
+    }
+
+    /// Register the backend. The backend which has the same scheme is replaced
+    #[must_use]
+    pub fn with_backend(mut self, backend: impl AuthBackend + 'static) -> Self {
+        self.backends.insert(backend.scheme(), Arc::new(backend));
+        self
+    }
+
+    #[must_use]
+    pub fn with_client(self, github: GithubClient) -> Self {
+        self.with_backend(GithubBackend::new(github))
+    }
+
+    /// Accept api tokens issued by the users with `synd` scheme
+    #[must_use]
+    pub fn with_api_tokens(self, api_tokens: Arc<dyn ApiTokenRepository>) -> Self {
+        self.with_backend(ApiTokenBackend::new(api_tokens))
+    }
+
+    /// Grant the admin role to the given users
+    #[must_use]
+    pub fn with_admins(self, admins: Admins) -> Self {
+        Self { admins, ..self }
+    }
+
+    /// Move the data stored under the legacy user ids on authentication
+    #[must_use]
+    pub fn with_user_id_migration(self, migration: UserIdMigration) -> Self {
+        Self {
+            migration: Some(migration),
+            ..self
+        }
+    }
+
+    /// Authenticate from given token
+    #[tracing::instrument(skip_all)]
+    pub async fn authenticate<S>(&self, token: S) -> Result<Principal, ()>
+    where
+        S: AsRef<str>,
+    {
+        let token = token.as_ref();
+        let mut split = token.splitn(2, ' ');
+        let (Some(scheme), Some(credential)) = (split.next(), split.next()) else {
+            return Err(());
+        };
+        let Some(backend) = self.backends.get(scheme) else {
+            return Err(());
+        };
+
+        let cacheable = backend.cacheable();
+        if cacheable {
+            if let Some(principal) = self.cache.get(token).await {
+                tracing::debug!("Principal cache hit");
+                return Ok(principal);
+            }
+        }
+
+        match backend.authenticate(credential).await {
+            Ok(principal) => {
+                if let Some(migration) = &self.migration {
+                    migration.migrate(&principal).await;
+                }
+                let principal = self
+                    .admins
+                    .assign_role(principal.with_auth_scheme(backend.scheme()));
+                if cacheable {
+                    self.cache.insert(token.to_owned(), principal.clone()).await;
+                }
+                Ok(principal)
+            }
+            Err(err) => {
+                warn!("Failed to authenticate {scheme}: {err}");
+                Err(())
+            }
+        }
+    }
+}
+
+impl Authenticate for Authenticator {
+    type Output = BoxFuture<'static, Result<Principal, ()>>;
+
+    fn authenticate(&self, token: Option<String>) -> Self::Output {
+        let this = self.clone();
+        Box::pin(async move {
+            match token {
+                Some(token) => Authenticator::authenticate(&this, token).await,
+                None => Err(()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    struct Counting {
+        cacheable: bool,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl AuthBackend for Counting {
+        fn scheme(&self) -> &'static str {
+            "counting"
+        }
+
+        fn cacheable(&self) -> bool {
+            self.cacheable
+        }
+
+        async fn authenticate(&self, credential: &str) -> anyhow::Result<Principal> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            anyhow::ensure!(credential == "valid", "invalid credential");
+            Ok(Principal::User(User::from_id("alice")))
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatch_by_scheme() {
+        for (cacheable, expected_calls) in [(true, 2), (false, 3)] {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let authenticator = Authenticator::new().unwrap().with_backend(Counting {
+                cacheable,
+                calls: calls.clone(),
+            });
+
+            for _ in 0..2 {
+                let principal = authenticator.authenticate("counting valid").await.unwrap();
+                assert_eq!(principal.user_id(), Some("alice"));
+            }
+            assert!(authenticator
+                .authenticate("counting invalid")
+                .await
+                .is_err());
+            assert!(authenticator.authenticate("unknown valid").await.is_err());
+            assert!(authenticator.authenticate("counting").await.is_err());
+
+            assert_eq!(calls.load(Ordering::Relaxed), expected_calls);
+        }
+    }
+
+    #[tokio::test]
+    async fn assign_admin_role() {
+        let authenticate = |admins: Admins| async move {
+            Authenticator::new()
+                .unwrap()
+                .with_backend(Counting {
+                    cacheable: true,
+                    calls: Arc::default(),
+                })
+                .with_admins(admins)
+                .authenticate("counting valid")
+                .await
+                .unwrap()
+        };
+
+        assert!(!authenticate(Admins::default()).await.is_admin());
+        assert!(!authenticate(Admins::new(["bob"])).await.is_admin());
+        assert!(authenticate(Admins::new(["bob", "alice"])).await.is_admin());
+    }
+
+    #[test]
+    fn resolve_admin_emails() {
+        let admins = Admins::new(["alice@syndicationd.ymgyt.io"]);
+
+        assert!(admins
+            .assign_role(Principal::User(User::from_email(
+                "alice@syndicationd.ymgyt.io"
+            )))
+            .is_admin());
+        // Principals authenticated by api tokens have only the user id
+        let id = User::from_email("alice@syndicationd.ymgyt.io")
+            .id()
+            .to_owned();
+        assert!(admins
+            .assign_role(Principal::User(User::from_id(id)))
+            .is_admin());
+        assert!(!admins
+            .assign_role(Principal::User(User::from_email(
+                "bob@syndicationd.ymgyt.io"
+            )))
+            .is_admin());
+    }
+}
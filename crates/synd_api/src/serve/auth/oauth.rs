@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use synd_auth::jwt::{google::JwtService as GoogleJwtService, oidc::JwtService as OidcJwtService};
+
+use crate::{
+    client::github::GithubClient,
+    principal::{Principal, User},
+    serve::auth::AuthBackend,
+};
+
+/// Authenticate github access tokens with `github` scheme
+pub struct GithubBackend {
+    client: GithubClient,
+}
+
+impl GithubBackend {
+    pub fn new(client: GithubClient) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for GithubBackend {
+    fn scheme(&self) -> &'static str {
+        "github"
+    }
+
+    async fn authenticate(&self, access_token: &str) -> anyhow::Result<Principal> {
+        let email = self.client.authenticate(access_token).await?;
+        Ok(Principal::User(User::from_email(email)))
+    }
+}
+
+/// Authenticate google id tokens with `google` scheme
+pub struct GoogleBackend {
+    jwt_service: GoogleJwtService,
+}
+
+impl GoogleBackend {
+    pub fn new(jwt_service: GoogleJwtService) -> Self {
+        Self { jwt_service }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for GoogleBackend {
+    fn scheme(&self) -> &'static str {
+        "google"
+    }
+
+    async fn authenticate(&self, id_token: &str) -> anyhow::Result<Principal> {
+        // Id a lot of intentional invalid id tokens are sent
+        // google's api limit will be exceeded.
+        // To prevent this, it is necessary to cache the currently valid kids
+        // and discard jwt headers with other kids.
+        let claims = self.jwt_service.decode_id_token(id_token).await?;
+        anyhow::ensure!(
+            claims.email_verified,
+            "google jwt claims email is not verified"
+        );
+
+        Ok(Principal::User(User::from_email(claims.email)))
+    }
+}
+
+/// Authenticate id tokens issued by the configured `OpenID` Connect provider with `oidc` scheme
+pub struct OidcBackend {
+    jwt_service: OidcJwtService,
+}
+
+impl OidcBackend {
+    pub fn new(jwt_service: OidcJwtService) -> Self {
+        Self { jwt_service }
+    }
+}
+
+#[async_trait]
+impl AuthBackend for OidcBackend {
+    fn scheme(&self) -> &'static str {
+        "oidc"
+    }
+
+    async fn authenticate(&self, id_token: &str) -> anyhow::Result<Principal> {
+        let claims = self.jwt_service.decode_id_token(id_token).await?;
+        anyhow::ensure!(
+            claims.email_verified,
+            "oidc jwt claims email is not verified"
+        );
+        let Some(email) = claims.email else {
+            anyhow::bail!("oidc jwt claims email not found");
+        };
+
+        Ok(Principal::User(User::from_email(email)))
+    }
+}
@@ -1,3 +1,4 @@
 pub mod authenticate;
+pub mod rate_limit;
 pub mod request_metrics;
 pub mod trace;
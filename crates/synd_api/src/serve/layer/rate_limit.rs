@@ -0,0 +1,204 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use synd_o11y::metric;
+use tower::{Layer, Service};
+
+use crate::{gql, principal::Principal};
+
+/// Token bucket which is refilled continuously
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn full(capacity: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            refilled_at: now,
+        }
+    }
+
+    fn refill(&mut self, capacity: f64, per_second: f64, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * per_second).min(capacity);
+        self.refilled_at = now;
+    }
+}
+
+/// Per principal rate limiter
+/// Each user has a token bucket which holds `burst` tokens at most and refilled `per_second` tokens per second
+#[derive(Debug)]
+pub struct RateLimiter {
+    burst: f64,
+    per_second: f64,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Full buckets are evicted when the number of buckets exceeds this
+    const MAX_BUCKETS: usize = 10 * 1024;
+
+    pub fn new(burst: u32, per_second: u32) -> Self {
+        Self {
+            burst: f64::from(burst.max(1)),
+            per_second: f64::from(per_second),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Consume a token of the user. If no tokens remain, return the duration until a token is available
+    pub fn acquire(&self, user_id: &str, now: Instant) -> Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if buckets.len() >= Self::MAX_BUCKETS && !buckets.contains_key(user_id) {
+            // Full buckets are equivalent to absent ones
+            buckets.retain(|_, bucket| {
+                bucket.refill(self.burst, self.per_second, now);
+                bucket.tokens < self.burst
+            });
+        }
+
+        let bucket = buckets
+            .entry(user_id.to_owned())
+            .or_insert_with(|| TokenBucket::full(self.burst, now));
+        bucket.refill(self.burst, self.per_second, now);
+
+        if bucket.tokens >= 1. {
+            bucket.tokens -= 1.;
+            Ok(())
+        } else if self.per_second > 0. {
+            Err(Duration::from_secs_f64(
+                (1. - bucket.tokens) / self.per_second,
+            ))
+        } else {
+            Err(Duration::MAX)
+        }
+    }
+}
+
+/// Rate limit requests per principal
+/// This layer should be applied after `AuthenticateLayer` to get the principal
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: RateLimiter) -> Self {
+        Self {
+            limiter: Arc::new(limiter),
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response, Error = Infallible>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        let user_id = req
+            .extensions()
+            .get::<Principal>()
+            .and_then(Principal::user_id);
+
+        if let Some(user_id) = user_id {
+            if let Err(retry_after) = self.limiter.acquire(user_id, Instant::now()) {
+                metric!(monotonic_counter.rate_limit.exceeded = 1);
+                tracing::debug!(user_id, "Rate limit exceeded");
+
+                return Box::pin(std::future::ready(Ok(rate_limited(retry_after))));
+            }
+        }
+
+        Box::pin(self.inner.call(req))
+    }
+}
+
+fn rate_limited(retry_after: Duration) -> Response {
+    let body = serde_json::to_vec(&gql::rate_limited_response()).unwrap_or_default();
+    let retry_after = retry_after.as_secs_f64().ceil().min(f64::from(u32::MAX));
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, body).into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    headers.insert(header::RETRY_AFTER, HeaderValue::from(retry_after as u32));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket_per_user() {
+        let limiter = RateLimiter::new(2, 1);
+        let now = Instant::now();
+
+        assert!(limiter.acquire("alice", now).is_ok());
+        assert!(limiter.acquire("alice", now).is_ok());
+        assert_eq!(limiter.acquire("alice", now), Err(Duration::from_secs(1)));
+        // other users are not affected
+        assert!(limiter.acquire("bob", now).is_ok());
+
+        let now = now + Duration::from_millis(500);
+        assert_eq!(
+            limiter.acquire("alice", now),
+            Err(Duration::from_millis(500))
+        );
+
+        let now = now + Duration::from_millis(500);
+        assert!(limiter.acquire("alice", now).is_ok());
+        assert!(limiter.acquire("alice", now).is_err());
+
+        // bucket never exceeds the burst
+        let now = now + Duration::from_secs(60);
+        assert!(limiter.acquire("alice", now).is_ok());
+        assert!(limiter.acquire("alice", now).is_ok());
+        assert!(limiter.acquire("alice", now).is_err());
+    }
+}
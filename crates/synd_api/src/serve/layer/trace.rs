@@ -1,6 +1,8 @@
 use tower_http::trace::HttpMakeClassifier;
 use tracing::Level;
 
+use crate::config;
+
 #[derive(Clone)]
 pub struct MakeSpan;
 
@@ -14,11 +16,22 @@ impl<B> tower_http::trace::MakeSpan<B> for MakeSpan {
             .get(synd_o11y::REQUEST_ID_KEY)
             .map_or("?".into(), |v| v.as_str());
 
+        // Syndication token in the path should not be recorded
+        let uri = if request
+            .uri()
+            .path()
+            .starts_with(config::syndication::PATH_PREFIX)
+        {
+            config::syndication::PATH_PREFIX.into()
+        } else {
+            request.uri().to_string()
+        };
+
         let span = tracing::span!(
             Level::INFO,
             "http",
             method = %request.method(),
-            uri = %request.uri(),
+            %uri,
             %request_id,
         );
 
@@ -1,4 +1,4 @@
-use std::{net::IpAddr, time::Duration};
+use std::{net::IpAddr, sync::Arc, time::Duration};
 
 use axum::{
     error_handling::HandleErrorLayer,
@@ -19,12 +19,19 @@ use crate::{
     config,
     dependency::Dependency,
     gql::{self, SyndSchema},
-    serve::layer::{authenticate, request_metrics::RequestMetricsLayer, trace},
+    serve::layer::{
+        authenticate,
+        rate_limit::{RateLimitLayer, RateLimiter},
+        request_metrics::RequestMetricsLayer,
+        trace,
+    },
     shutdown::Shutdown,
 };
 
 pub mod auth;
 mod probe;
+mod reader;
+pub mod syndication;
 
 pub mod layer;
 
@@ -37,12 +44,21 @@ pub struct ServeOptions {
     pub timeout: Duration,
     pub body_limit_bytes: usize,
     pub concurrency_limit: usize,
+    /// Rate limit per principal. disabled if None
+    pub rate_limit: Option<RateLimitOptions>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitOptions {
+    pub burst: u32,
+    pub per_second: u32,
 }
 
 #[derive(Clone)]
 pub(crate) struct Context {
     pub gql_monitor: TaskMonitor,
     pub schema: SyndSchema,
+    pub authenticator: auth::Authenticator,
 }
 
 /// Bind tcp listener and serve.
@@ -66,28 +82,76 @@ pub async fn serve(
     let Dependency {
         authenticator,
         runtime,
+        syndication,
+        reader_api,
         tls_config,
         serve_options:
             ServeOptions {
                 timeout: request_timeout,
                 body_limit_bytes: request_body_limit_bytes,
                 concurrency_limit,
+                rate_limit,
             },
         monitors,
     } = dep;
 
+    let runtime = Arc::new(runtime);
     let cx = Context {
         gql_monitor: monitors.gql.clone(),
-        schema: gql::schema_builder().data(runtime).finish(),
+        schema: gql::schema_builder().data(Arc::clone(&runtime)).finish(),
+        authenticator: authenticator.clone(),
     };
 
     tokio::spawn(monitors.monitor(config::metrics::MONITOR_INTERVAL));
 
-    let service = Router::new()
+    let reader_cx = reader::Context {
+        runtime: Arc::clone(&runtime),
+        authenticator: authenticator.clone(),
+        items: reader::ItemIndex::default(),
+    };
+    let syndication_cx = syndication::Context {
+        runtime,
+        authenticator: authenticator.clone(),
+    };
+
+    let mut authenticated = Router::new()
         .route("/graphql", post(gql::handler::graphql))
-        .layer(Extension(cx))
+        .layer(Extension(cx.clone()));
+    if reader_api {
+        authenticated = authenticated.merge(reader::router(reader_cx.clone()));
+    }
+    // Rate limit is applied after authentication since it is keyed by the principal
+    if let Some(RateLimitOptions { burst, per_second }) = rate_limit {
+        authenticated =
+            authenticated.layer(RateLimitLayer::new(RateLimiter::new(burst, per_second)));
+    }
+
+    let mut service = authenticated
         .layer(authenticate::AuthenticateLayer::new(authenticator))
         .route("/graphql", get(gql::handler::graphiql))
+        // Websocket is authenticated by the connection init payload
+        .route(
+            config::serve::GRAPHQL_WS_PATH,
+            get(gql::handler::graphql_ws).layer(Extension(cx)),
+        );
+
+    // Syndication endpoint is authenticated by the token in the path
+    if syndication {
+        service = service.route(
+            config::syndication::PATH,
+            get(syndication::syndicate).layer(Extension(syndication_cx)),
+        );
+    }
+
+    // Reader api clients obtain the token by logging in with the personal access token
+    if reader_api {
+        service = service.route(
+            config::reader::CLIENT_LOGIN_PATH,
+            post(reader::client_login).layer(Extension(reader_cx)),
+        );
+    }
+
+    let service = service
         .layer(
             ServiceBuilder::new()
                 .layer(SetSensitiveHeadersLayer::new(std::iter::once(
@@ -0,0 +1,1065 @@
+//! Google Reader compatible api so that existing feed readers(e.g. on mobile) can sync with synd-api.
+//! Clients log in by `ClientLogin` with a personal access token as the password,
+//! then send the token as `Authorization: GoogleLogin auth={token}`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Display,
+    ops::Bound,
+    sync::Arc,
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, RawQuery},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::DateTime;
+use serde_json::json;
+use synd_feed::types::{Annotated, Category, Feed, FeedUrl, Time};
+use url::form_urlencoded;
+
+use crate::{
+    config,
+    principal::Principal,
+    repository::types::StarredEntry,
+    serve::auth::{Authenticator, GoogleLoginBackend},
+    usecase::{
+        self, authorize::Unauthorized, EntryCursor, FetchEntries, FetchEntriesInput,
+        FetchEntriesOutput, FetchStarredEntries, FetchStarredEntriesInput,
+        FetchStarredEntriesOutput, FetchSubscribedFeeds, FetchSubscribedFeedsInput,
+        FetchSubscribedFeedsOutput, MarkEntriesInput, MarkEntriesRead, MarkEntriesUnread,
+        MarkFeedRead, MarkFeedReadInput, Output, Patch, Runtime, StarEntry, StarEntryInput,
+        SubscribeFeed, SubscribeFeedInput, SubscribeFeedOutput, SubscriptionFilter, UnstarEntry,
+        UnstarEntryInput, UnsubscribeFeed, UnsubscribeFeedInput, UpdateFeedAnnotations,
+        UpdateFeedAnnotationsInput,
+    },
+};
+
+mod render;
+mod stream;
+
+pub(crate) use self::stream::ItemIndex;
+use self::{
+    render::{ItemContent, ItemRef, ItemRefs, Label, StreamContents, Subscription, UnreadCount},
+    stream::{Item, ItemId, Items, StreamId, StreamQuery},
+};
+
+#[derive(Clone)]
+pub(crate) struct Context {
+    pub runtime: Arc<Runtime>,
+    pub authenticator: Authenticator,
+    pub items: ItemIndex,
+}
+
+/// Routes which require the `GoogleLogin` authorization
+pub(crate) fn router(cx: Context) -> Router {
+    Router::new()
+        .route("/reader/api/0/token", get(token))
+        .route("/reader/api/0/user-info", get(user_info))
+        .route("/reader/api/0/subscription/list", get(subscription_list))
+        .route("/reader/api/0/subscription/edit", post(subscription_edit))
+        .route("/reader/api/0/subscription/quickadd", post(quickadd))
+        .route("/reader/api/0/tag/list", get(tag_list))
+        .route("/reader/api/0/unread-count", get(unread_count))
+        .route("/reader/api/0/stream/items/ids", get(stream_item_ids))
+        .route(
+            "/reader/api/0/stream/items/contents",
+            get(stream_item_contents).post(stream_item_contents),
+        )
+        .route("/reader/api/0/stream/contents", get(stream_contents))
+        .route(
+            "/reader/api/0/stream/contents/*stream",
+            get(stream_contents),
+        )
+        .route("/reader/api/0/edit-tag", post(edit_tag))
+        .route("/reader/api/0/mark-all-as-read", post(mark_all_as_read))
+        .layer(Extension(cx))
+}
+
+/// Parameters in the query and the form body. Some keys such as `i` are repeated
+struct Params(Vec<(String, String)>);
+
+impl Params {
+    fn parse(query: Option<&str>, body: &[u8]) -> Self {
+        let query = form_urlencoded::parse(query.unwrap_or_default().as_bytes());
+        let body = form_urlencoded::parse(body);
+        Self(query.into_owned().chain(body.into_owned()).collect())
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> {
+        self.0
+            .iter()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn parse_stream(&self, key: &str) -> Result<Option<StreamId>, ReaderError> {
+        self.get(key)
+            .map(|s| {
+                StreamId::parse(s)
+                    .ok_or_else(|| ReaderError::BadRequest(format!("invalid {key}: {s}")))
+            })
+            .transpose()
+    }
+
+    fn parse_time(&self, key: &str) -> Option<Time> {
+        self.get(key)
+            .and_then(|t| t.parse().ok())
+            .and_then(|t| DateTime::from_timestamp(t, 0))
+    }
+
+    fn query(&self, stream: Option<StreamId>) -> Result<StreamQuery, ReaderError> {
+        let stream = match stream {
+            Some(stream) => stream,
+            None => self.parse_stream("s")?.unwrap_or(StreamId::ReadingList),
+        };
+        Ok(StreamQuery {
+            stream,
+            exclude: self.parse_stream("xt")?,
+            include: self.parse_stream("it")?,
+            oldest: self.parse_time("ot"),
+            newest: self.parse_time("nt"),
+            oldest_first: self.get("r") == Some("o"),
+            count: self
+                .get("n")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(config::reader::DEFAULT_ITEMS)
+                .clamp(1, config::reader::MAX_ITEMS),
+            continuation: self
+                .get("c")
+                .map(|c| {
+                    EntryCursor::decode(c)
+                        .map_err(|_| ReaderError::BadRequest(format!("invalid continuation: {c}")))
+                })
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+enum ReaderError {
+    BadRequest(String),
+    Unauthorized(Unauthorized),
+    Internal,
+}
+
+impl<E: Display> From<usecase::Error<E>> for ReaderError {
+    fn from(err: usecase::Error<E>) -> Self {
+        match err {
+            usecase::Error::Usecase(err) => ReaderError::BadRequest(err.to_string()),
+            usecase::Error::Unauthorized(err) => ReaderError::Unauthorized(err),
+            usecase::Error::Repository(err) => {
+                tracing::error!("Reader api repository error: {err}");
+                ReaderError::Internal
+            }
+        }
+    }
+}
+
+impl IntoResponse for ReaderError {
+    fn into_response(self) -> Response {
+        match self {
+            ReaderError::BadRequest(message) => (StatusCode::BAD_REQUEST, message).into_response(),
+            ReaderError::Unauthorized(err) => {
+                (StatusCode::FORBIDDEN, err.to_string()).into_response()
+            }
+            ReaderError::Internal => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
+
+type Result<T, E = ReaderError> = std::result::Result<T, E>;
+
+/// Authenticate the personal access token given as the password and return it as the auth token
+pub(crate) async fn client_login(
+    Extension(cx): Extension<Context>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Response {
+    let params = Params::parse(query.as_deref(), &body);
+    let Some(token) = params.get("Passwd").filter(|token| !token.is_empty()) else {
+        return bad_authentication();
+    };
+    let authorization = format!("{} auth={token}", GoogleLoginBackend::SCHEME);
+    if cx.authenticator.authenticate(authorization).await.is_err() {
+        return bad_authentication();
+    }
+
+    if params.get("output") == Some("json") {
+        Json(json!({ "SID": token, "LSID": token, "Auth": token })).into_response()
+    } else {
+        format!("SID={token}\nLSID={token}\nAuth={token}\n").into_response()
+    }
+}
+
+fn bad_authentication() -> Response {
+    (StatusCode::UNAUTHORIZED, "Error=BadAuthentication\n").into_response()
+}
+
+/// Edit tokens are not verified since every request is authenticated by the header
+async fn token(Extension(principal): Extension<Principal>) -> String {
+    principal.user_id().unwrap_or_default().to_owned()
+}
+
+async fn user_info(Extension(principal): Extension<Principal>) -> Json<serde_json::Value> {
+    let user_id = principal.user_id().unwrap_or_default();
+    Json(json!({
+        "userId": user_id,
+        "userName": user_id,
+        "userProfileId": user_id,
+        "userEmail": "",
+    }))
+}
+
+async fn subscription_list(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+) -> Result<Json<serde_json::Value>> {
+    let subscriptions = fetch_subscribed_feeds(&cx, principal)
+        .await?
+        .feeds
+        .into_iter()
+        .map(|feed| match feed {
+            Ok(feed) => {
+                let meta = feed.feed.meta();
+                Subscription {
+                    id: StreamId::Feed(meta.url().clone()).to_string(),
+                    title: feed_title(&feed),
+                    categories: feed.category.iter().map(label).collect(),
+                    url: meta.url().to_string(),
+                    html_url: meta.website_url().unwrap_or_default().to_owned(),
+                    icon_url: String::new(),
+                }
+            }
+            Err((url, _)) => Subscription {
+                id: StreamId::Feed(url.clone()).to_string(),
+                title: url.to_string(),
+                categories: Vec::new(),
+                url: url.to_string(),
+                html_url: String::new(),
+                icon_url: String::new(),
+            },
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({ "subscriptions": subscriptions })))
+}
+
+async fn subscription_edit(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<&'static str> {
+    let params = Params::parse(query.as_deref(), &body);
+    let urls = params
+        .all("s")
+        .map(|s| match StreamId::parse(s) {
+            Some(StreamId::Feed(url)) => Ok(url),
+            _ => Err(ReaderError::BadRequest(format!("invalid feed: {s}"))),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let category = match params.parse_stream("a")? {
+        Some(StreamId::Label(category)) => Some(category),
+        Some(_) => return Err(ReaderError::BadRequest("only labels can be added".into())),
+        None => None,
+    };
+    let title = params
+        .get("t")
+        .filter(|title| !title.is_empty())
+        .map(ToOwned::to_owned);
+
+    for url in urls {
+        match params.get("ac") {
+            Some("subscribe") => {
+                cx.runtime
+                    .run::<SubscribeFeed, _, _>(
+                        principal.clone(),
+                        SubscribeFeedInput {
+                            url,
+                            requirement: None,
+                            category: category.clone(),
+                            title: title.clone(),
+                            note: None,
+                            tags: BTreeSet::new(),
+                        },
+                    )
+                    .await?;
+            }
+            Some("unsubscribe") => {
+                cx.runtime
+                    .run::<UnsubscribeFeed, _, _>(principal.clone(), UnsubscribeFeedInput { url })
+                    .await?;
+            }
+            Some("edit") => {
+                let category = match (&category, params.get("r")) {
+                    (Some(category), _) => Patch::Set(category.clone()),
+                    (None, Some(_)) => Patch::Clear,
+                    (None, None) => Patch::Keep,
+                };
+                cx.runtime
+                    .run::<UpdateFeedAnnotations, _, _>(
+                        principal.clone(),
+                        UpdateFeedAnnotationsInput {
+                            url,
+                            requirement: Patch::Keep,
+                            category,
+                            title: title.clone().map_or(Patch::Keep, Patch::Set),
+                            note: Patch::Keep,
+                            tags: None,
+                        },
+                    )
+                    .await?;
+            }
+            action => {
+                return Err(ReaderError::BadRequest(format!(
+                    "unknown action: {}",
+                    action.unwrap_or_default()
+                )))
+            }
+        }
+    }
+
+    Ok("OK")
+}
+
+async fn quickadd(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<Json<serde_json::Value>> {
+    let params = Params::parse(query.as_deref(), &body);
+    let query = params.get("quickadd").unwrap_or_default();
+    let url = FeedUrl::try_from(query.strip_prefix("feed/").unwrap_or(query))
+        .map_err(|err| ReaderError::BadRequest(format!("invalid feed url: {err}")))?;
+
+    let Output {
+        output: SubscribeFeedOutput { feed },
+    } = cx
+        .runtime
+        .run::<SubscribeFeed, _, _>(
+            principal,
+            SubscribeFeedInput {
+                url: url.clone(),
+                requirement: None,
+                category: None,
+                title: None,
+                note: None,
+                tags: BTreeSet::new(),
+            },
+        )
+        .await?;
+
+    Ok(Json(json!({
+        "numResults": 1,
+        "query": query,
+        "streamId": StreamId::Feed(url).to_string(),
+        "streamName": feed_title(&feed),
+    })))
+}
+
+async fn tag_list(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+) -> Result<Json<serde_json::Value>> {
+    let categories = fetch_subscribed_feeds(&cx, principal)
+        .await?
+        .feeds
+        .into_iter()
+        .filter_map(|feed| feed.ok().and_then(|feed| feed.category))
+        .collect::<BTreeSet<_>>();
+    let tags = std::iter::once(json!({ "id": StreamId::Starred.to_string() }))
+        .chain(categories.into_iter().map(
+            |category| json!({ "id": StreamId::Label(category).to_string(), "type": "folder" }),
+        ))
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({ "tags": tags })))
+}
+
+async fn unread_count(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+) -> Result<Json<serde_json::Value>> {
+    let items = fetch_items(&cx, principal, true).await?;
+
+    let mut counts = BTreeMap::<String, (usize, Option<Time>)>::new();
+    for item in items.iter().filter(|item| item.subscribed && !item.read) {
+        let streams = [
+            Some(StreamId::ReadingList),
+            Some(StreamId::Feed(item.entry.feed_url.clone())),
+            item.category.clone().map(StreamId::Label),
+        ];
+        for stream in streams.into_iter().flatten() {
+            let (count, newest) = counts.entry(stream.to_string()).or_default();
+            *count += 1;
+            *newest = (*newest).max(item.published);
+        }
+    }
+    let unread_counts = counts
+        .into_iter()
+        .map(|(id, (count, newest))| UnreadCount {
+            id,
+            count,
+            newest_item_timestamp_usec: newest.map_or_else(|| "0".into(), render::usec),
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(json!({
+        "max": config::reader::MAX_ITEMS,
+        "unreadcounts": unread_counts,
+    })))
+}
+
+async fn stream_item_ids(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    RawQuery(query): RawQuery,
+) -> Result<Json<ItemRefs>> {
+    let query = Params::parse(query.as_deref(), &[]).query(None)?;
+    let (page, continuation) = query_items(&cx, principal, &query).await?;
+    let now = chrono::Utc::now();
+
+    Ok(Json(ItemRefs {
+        item_refs: page.iter().map(|item| ItemRef::new(item, now)).collect(),
+        continuation,
+    }))
+}
+
+async fn stream_contents(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    stream: Option<Path<String>>,
+    RawQuery(query): RawQuery,
+) -> Result<Json<StreamContents>> {
+    let stream = stream
+        .map(|Path(stream)| {
+            StreamId::parse(&stream)
+                .ok_or_else(|| ReaderError::BadRequest(format!("invalid stream: {stream}")))
+        })
+        .transpose()?;
+    let query = Params::parse(query.as_deref(), &[]).query(stream)?;
+    let (page, continuation) = query_items(&cx, principal, &query).await?;
+    let now = chrono::Utc::now();
+
+    Ok(Json(StreamContents {
+        direction: "ltr",
+        id: query.stream.to_string(),
+        updated: now.timestamp(),
+        items: page
+            .iter()
+            .map(|item| ItemContent::new(item, now))
+            .collect(),
+        continuation,
+    }))
+}
+
+async fn stream_item_contents(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<Json<StreamContents>> {
+    let params = Params::parse(query.as_deref(), &body);
+    let items = lookup_items(&cx, principal, &params).await?;
+    let now = chrono::Utc::now();
+
+    Ok(Json(StreamContents {
+        direction: "ltr",
+        id: StreamId::ReadingList.to_string(),
+        updated: now.timestamp(),
+        items: find_items(&items, &params)
+            .map(|item| ItemContent::new(item, now))
+            .collect(),
+        continuation: None,
+    }))
+}
+
+/// Add(`a`) or remove(`r`) the read or starred state of the items(`i`)
+async fn edit_tag(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<&'static str> {
+    let params = Params::parse(query.as_deref(), &body);
+    let items = lookup_items(&cx, principal.clone(), &params).await?;
+    let targets = find_items(&items, &params).collect::<Vec<_>>();
+    let entries = || targets.iter().map(|item| item.entry.clone()).collect();
+
+    let tags = params
+        .all("a")
+        .map(|tag| (true, tag))
+        .chain(params.all("r").map(|tag| (false, tag)));
+    for (add, tag) in tags {
+        match (StreamId::parse(tag), add) {
+            (Some(StreamId::Read), true) | (Some(StreamId::KeptUnread), false) => {
+                cx.runtime
+                    .run::<MarkEntriesRead, _, _>(
+                        principal.clone(),
+                        MarkEntriesInput { entries: entries() },
+                    )
+                    .await?;
+            }
+            (Some(StreamId::Read), false) | (Some(StreamId::KeptUnread), true) => {
+                cx.runtime
+                    .run::<MarkEntriesUnread, _, _>(
+                        principal.clone(),
+                        MarkEntriesInput { entries: entries() },
+                    )
+                    .await?;
+            }
+            (Some(StreamId::Starred), true) => {
+                for item in targets.iter().filter(|item| !item.starred) {
+                    cx.runtime
+                        .run::<StarEntry, _, _>(
+                            principal.clone(),
+                            StarEntryInput {
+                                entry: item.entry.clone(),
+                            },
+                        )
+                        .await?;
+                }
+            }
+            (Some(StreamId::Starred), false) => {
+                for item in targets.iter().filter(|item| item.starred) {
+                    cx.runtime
+                        .run::<UnstarEntry, _, _>(
+                            principal.clone(),
+                            UnstarEntryInput {
+                                entry: item.entry.clone(),
+                            },
+                        )
+                        .await?;
+                }
+            }
+            // Labels are given to feeds, not to items
+            _ => tracing::debug!(tag, "Ignore unsupported tag"),
+        }
+    }
+
+    Ok("OK")
+}
+
+/// Mark the items in the stream(`s`) published at or before `ts` as read
+async fn mark_all_as_read(
+    Extension(cx): Extension<Context>,
+    Extension(principal): Extension<Principal>,
+    RawQuery(query): RawQuery,
+    body: Bytes,
+) -> Result<&'static str> {
+    let params = Params::parse(query.as_deref(), &body);
+    let stream = params
+        .parse_stream("s")?
+        .ok_or_else(|| ReaderError::BadRequest("stream is required".into()))?;
+    let until = params
+        .get("ts")
+        .and_then(|ts| ts.parse().ok())
+        .and_then(DateTime::from_timestamp_micros);
+
+    if let (StreamId::Feed(url), None) = (&stream, until) {
+        cx.runtime
+            .run::<MarkFeedRead, _, _>(principal, MarkFeedReadInput { url: url.clone() })
+            .await?;
+        return Ok("OK");
+    }
+
+    let query = StreamQuery {
+        stream,
+        exclude: Some(StreamId::Read),
+        include: None,
+        oldest: None,
+        newest: None,
+        oldest_first: false,
+        count: usize::MAX,
+        continuation: None,
+    };
+    let (page, _) = query_items(&cx, principal.clone(), &query).await?;
+    // Items without the time are regarded as within the bound, otherwise they could never be marked as read
+    let entries = page
+        .into_iter()
+        .filter(|item| {
+            until.map_or(true, |until| {
+                item.published.map_or(true, |published| published <= until)
+            })
+        })
+        .map(|item| item.entry.clone())
+        .collect::<Vec<_>>();
+    if !entries.is_empty() {
+        cx.runtime
+            .run::<MarkEntriesRead, _, _>(principal, MarkEntriesInput { entries })
+            .await?;
+    }
+
+    Ok("OK")
+}
+
+async fn fetch_subscribed_feeds(
+    cx: &Context,
+    principal: Principal,
+) -> Result<FetchSubscribedFeedsOutput> {
+    let Output { output } = cx
+        .runtime
+        .run::<FetchSubscribedFeeds, _, _>(
+            principal,
+            FetchSubscribedFeedsInput {
+                after: None,
+                first: usize::MAX,
+                filter: SubscriptionFilter::default(),
+            },
+        )
+        .await?;
+    Ok(output)
+}
+
+/// All items of the user
+async fn fetch_items(cx: &Context, principal: Principal, unread_only: bool) -> Result<Items> {
+    let starred = fetch_starred_entries(cx, principal.clone()).await?;
+    let Output { output } = cx
+        .runtime
+        .run::<FetchEntries, _, _>(
+            principal,
+            FetchEntriesInput {
+                after: None,
+                before: None,
+                first: None,
+                last: None,
+                filter: SubscriptionFilter::default(),
+                unread_only,
+            },
+        )
+        .await?;
+
+    Ok(Items::new(output, &starred, ..))
+}
+
+/// Return the items of the page and the continuation of the next page.
+/// Entries are fetched by the batch after the continuation until the page is filled
+async fn query_items(
+    cx: &Context,
+    principal: Principal,
+    query: &StreamQuery,
+) -> Result<(Vec<Item>, Option<String>)> {
+    let starred = fetch_starred_entries(cx, principal.clone()).await?;
+    let batch = Some(query.count.clamp(1, config::reader::BATCH_ITEMS));
+    let mut cursor = query.continuation.clone();
+    let mut page = Vec::new();
+
+    loop {
+        let input = if query.oldest_first {
+            FetchEntriesInput {
+                after: None,
+                before: cursor.clone(),
+                first: None,
+                last: batch,
+                filter: SubscriptionFilter::default(),
+                unread_only: false,
+            }
+        } else {
+            FetchEntriesInput {
+                after: cursor.clone(),
+                before: None,
+                first: batch,
+                last: None,
+                filter: SubscriptionFilter::default(),
+                unread_only: false,
+            }
+        };
+        let Output { output } = cx
+            .runtime
+            .run::<FetchEntries, _, _>(principal.clone(), input)
+            .await?;
+
+        // End of the batch in the order of the query, which is the cursor of the next batch
+        let (end, has_more) = batch_end(&output, query.oldest_first);
+        let range = match (query.oldest_first, cursor, end.clone()) {
+            (false, after, end) => (
+                after.map_or(Bound::Unbounded, Bound::Excluded),
+                end.map_or(Bound::Unbounded, Bound::Included),
+            ),
+            (true, before, end) => (
+                end.map_or(Bound::Unbounded, Bound::Included),
+                before.map_or(Bound::Unbounded, Bound::Excluded),
+            ),
+        };
+        let (matched, passed) = Items::new(output, &starred, range).query(query);
+        cx.items.insert(&matched).await;
+
+        let remaining = query.count - page.len();
+        let overflowed = matched.len() > remaining;
+        page.extend(matched.into_iter().take(remaining));
+        if page.len() == query.count {
+            let continuation = (overflowed || (has_more && !passed))
+                .then(|| page.last().map(|item| item.cursor().encode()))
+                .flatten();
+            return Ok((page, continuation));
+        }
+        if passed || !has_more {
+            return Ok((page, None));
+        }
+        cursor = end;
+    }
+}
+
+/// Cursor of the last entry in the order of the query if more entries follow
+fn batch_end(output: &FetchEntriesOutput, oldest_first: bool) -> (Option<EntryCursor>, bool) {
+    let (end, has_more) = if oldest_first {
+        (output.entries.first(), output.has_previous_page)
+    } else {
+        (output.entries.last(), output.has_next_page)
+    };
+    let end = end
+        .filter(|_| has_more)
+        .map(|(entry, feed_url)| EntryCursor::new(entry, feed_url));
+    (end, has_more)
+}
+
+async fn fetch_starred_entries(cx: &Context, principal: Principal) -> Result<Vec<StarredEntry>> {
+    let Output {
+        output: FetchStarredEntriesOutput { entries },
+    } = cx
+        .runtime
+        .run::<FetchStarredEntries, _, _>(
+            principal,
+            FetchStarredEntriesInput {
+                after: None,
+                first: usize::MAX,
+            },
+        )
+        .await?;
+    Ok(entries)
+}
+
+/// Items which contain the ones specified by `i`.
+/// Only the feeds of the items are fetched. The feeds of the items not served recently are found by scanning the subscribed feeds
+async fn lookup_items(cx: &Context, principal: Principal, params: &Params) -> Result<Items> {
+    let mut ids = params
+        .all("i")
+        .filter_map(ItemId::parse)
+        .collect::<HashSet<_>>();
+    let mut feed_urls = HashSet::new();
+    for id in ids.clone() {
+        if let Some(entry) = cx.items.get(id).await {
+            feed_urls.insert(entry.feed_url);
+            ids.remove(&id);
+        }
+    }
+    if !ids.is_empty() {
+        for feed in fetch_subscribed_feeds(cx, principal.clone())
+            .await?
+            .feeds
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            let url = feed.feed.meta().url();
+            if feed
+                .feed
+                .entries()
+                .any(|entry| ids.contains(&ItemId::new(url, &entry.id().to_string())))
+            {
+                feed_urls.insert(url.clone());
+            }
+        }
+    }
+
+    let starred = fetch_starred_entries(cx, principal.clone()).await?;
+    let Output { output } = cx
+        .runtime
+        .run::<FetchEntries, _, _>(
+            principal,
+            FetchEntriesInput {
+                after: None,
+                before: None,
+                first: None,
+                last: None,
+                filter: SubscriptionFilter {
+                    feed_urls: Some(feed_urls.into_iter().collect()),
+                    ..Default::default()
+                },
+                unread_only: false,
+            },
+        )
+        .await?;
+
+    Ok(Items::new(output, &starred, ..))
+}
+
+/// Items specified by `i`. Unknown ids are ignored
+fn find_items<'a>(items: &'a Items, params: &'a Params) -> impl Iterator<Item = &'a Item> {
+    params
+        .all("i")
+        .filter_map(ItemId::parse)
+        .filter_map(|id| items.find(id))
+}
+
+fn feed_title(feed: &Annotated<Arc<Feed>>) -> String {
+    let meta = feed.feed.meta();
+    feed.title
+        .clone()
+        .or_else(|| meta.title().map(ToOwned::to_owned))
+        .unwrap_or_else(|| meta.url().to_string())
+}
+
+fn label(category: &Category<'static>) -> Label {
+    Label {
+        id: StreamId::Label(category.clone()).to_string(),
+        label: category.as_str().to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use synd_feed::feed::cache::CacheLayer;
+
+    use crate::{
+        dependency::Repositories,
+        fixture::{self, feed, subscription, StaticFeeds},
+        repository::memory::MemoryRepository,
+        usecase::{
+            authorize::{Authorizer, Quota},
+            MakeUsecase,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn parse_params() {
+        let cursor = EntryCursor {
+            published: None,
+            feed_url: FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap(),
+            entry_id: "1".into(),
+        };
+        let query = format!(
+            "s=feed%2Fhttps%3A%2F%2Fblog.ymgyt.io%2Fatom.xml&n=5000&r=o&c={}&ot=1717200000",
+            cursor.encode()
+        );
+        let params = Params::parse(
+            Some(&query),
+            b"i=1&i=tag%3Agoogle.com%2C2005%3Areader%2Fitem%2F0000000000000002&a=user%2F-%2Fstate%2Fcom.google%2Fread",
+        );
+        assert_eq!(params.all("i").count(), 2);
+
+        let query = params.query(None).unwrap();
+        assert_eq!(
+            query.stream,
+            StreamId::Feed(FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap())
+        );
+        assert_eq!(query.count, config::reader::MAX_ITEMS);
+        assert_eq!(query.continuation, Some(cursor));
+        assert!(query.oldest_first);
+        assert_eq!(query.oldest.unwrap().timestamp(), 1_717_200_000);
+        assert_eq!(params.parse_stream("a").unwrap(), Some(StreamId::Read));
+
+        let params = Params::parse(None, &[]);
+        let query = params.query(None).unwrap();
+        assert_eq!(query.stream, StreamId::ReadingList);
+        assert_eq!(query.count, config::reader::DEFAULT_ITEMS);
+
+        assert!(Params::parse(Some("s=unknown"), &[]).query(None).is_err());
+        assert!(Params::parse(Some("c=40"), &[]).query(None).is_err());
+    }
+
+    async fn context(feeds: &[Feed], starred: &[StarredEntry]) -> Context {
+        let repositories = Repositories::new(MemoryRepository::new());
+        let publisher = Arc::new(StaticFeeds::default());
+        for feed in feeds {
+            publisher.insert(feed.clone());
+            repositories
+                .subscription
+                .put_feed_subscription(subscription("alice", feed.meta().url()))
+                .await
+                .unwrap();
+        }
+        for entry in starred {
+            repositories
+                .starred_entry
+                .star_entry("alice", entry.clone())
+                .await
+                .unwrap();
+        }
+        let cache = CacheLayer::new(publisher);
+        let make = MakeUsecase {
+            subscription_repo: repositories.subscription,
+            read_state_repo: repositories.read_state,
+            starred_entry_repo: repositories.starred_entry,
+            api_token_repo: repositories.api_token,
+            webhook_repo: repositories.webhook,
+            fetch_feed: Arc::new(cache.clone()),
+            feed_updates: Arc::new(cache),
+            quota: Quota {
+                max_subscriptions: 10,
+            },
+        };
+        Context {
+            runtime: Arc::new(Runtime::new(make, Authorizer::new())),
+            authenticator: Authenticator::new().unwrap(),
+            items: ItemIndex::default(),
+        }
+    }
+
+    fn starred(feed_url: &FeedUrl, entry_id: &str, published: Option<Time>) -> StarredEntry {
+        StarredEntry {
+            feed_url: feed_url.clone(),
+            entry_id: entry_id.into(),
+            title: None,
+            website_url: None,
+            summary: None,
+            published,
+            updated: None,
+            feed_title: None,
+            requirement: None,
+            category: None,
+            starred_at: Utc::now(),
+        }
+    }
+
+    /// Follow the continuations and return the entry ids of each page
+    async fn pages(cx: &Context, mut query: StreamQuery) -> Vec<Vec<String>> {
+        let mut pages = Vec::new();
+        loop {
+            let (page, continuation) = query_items(cx, fixture::principal(), &query).await.unwrap();
+            pages.push(page.into_iter().map(|item| item.entry.entry_id).collect());
+            match continuation {
+                Some(continuation) => {
+                    query.continuation = Some(EntryCursor::decode(&continuation).unwrap());
+                }
+                None => return pages,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn paginate_by_continuation() {
+        let url = fixture::url("https://blog.ymgyt.io/atom.xml");
+        let cx = context(
+            &[feed(
+                &url,
+                &[("5", 5), ("4", 4), ("3", 3), ("2", 2), ("1", 1)],
+            )],
+            &[
+                starred(
+                    &url,
+                    "4",
+                    Utc.with_ymd_and_hms(2024, 6, 4, 0, 0, 0).single(),
+                ),
+                // No longer in the feed
+                starred(
+                    &url,
+                    "removed",
+                    Utc.with_ymd_and_hms(2024, 6, 3, 12, 0, 0).single(),
+                ),
+            ],
+        )
+        .await;
+        let query = |stream, count, oldest_first| StreamQuery {
+            stream,
+            exclude: None,
+            include: None,
+            oldest: None,
+            newest: None,
+            oldest_first,
+            count,
+            continuation: None,
+        };
+
+        assert_eq!(
+            pages(&cx, query(StreamId::ReadingList, 2, false)).await,
+            vec![vec!["5", "4"], vec!["3", "2"], vec!["1"]]
+        );
+        assert_eq!(
+            pages(&cx, query(StreamId::ReadingList, 3, true)).await,
+            vec![vec!["1", "2", "3"], vec!["4", "5"]]
+        );
+        // Starred entries which are no longer in the feed are ordered along with the entries
+        assert_eq!(
+            pages(&cx, query(StreamId::Starred, 2, false)).await,
+            vec![vec!["4", "removed"], vec![]]
+        );
+        assert_eq!(
+            pages(&cx, query(StreamId::Starred, 1, true)).await,
+            vec![vec!["removed"], vec!["4"], vec![]]
+        );
+    }
+
+    /// Unread entry ids of the reading list
+    async fn unread(cx: &Context) -> Vec<String> {
+        let query = StreamQuery {
+            stream: StreamId::ReadingList,
+            exclude: Some(StreamId::Read),
+            include: None,
+            oldest: None,
+            newest: None,
+            oldest_first: false,
+            count: usize::MAX,
+            continuation: None,
+        };
+        let (page, _) = query_items(cx, fixture::principal(), &query).await.unwrap();
+        page.into_iter().map(|item| item.entry.entry_id).collect()
+    }
+
+    #[tokio::test]
+    async fn mark_undated_items_as_read() {
+        let url = fixture::url("https://blog.ymgyt.io/atom.xml");
+        let cx = context(&[feed(&url, &[("2", 2), ("1", 1), ("undated", 0)])], &[]).await;
+        let ts = Utc
+            .with_ymd_and_hms(2024, 6, 1, 0, 0, 0)
+            .unwrap()
+            .timestamp_micros();
+
+        mark_all_as_read(
+            Extension(cx.clone()),
+            Extension(fixture::principal()),
+            RawQuery(Some(format!(
+                "s=user%2F-%2Fstate%2Fcom.google%2Freading-list&ts={ts}"
+            ))),
+            Bytes::new(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(unread(&cx).await, vec!["2"]);
+    }
+
+    #[tokio::test]
+    async fn edit_tag_of_items_not_served() {
+        let (a, b) = (
+            fixture::url("https://a.ymgyt.io/atom.xml"),
+            fixture::url("https://b.ymgyt.io/atom.xml"),
+        );
+        let cx = context(&[feed(&a, &[("1", 1)]), feed(&b, &[("2", 2)])], &[]).await;
+        let edit_tag = |id: ItemId| {
+            edit_tag(
+                Extension(cx.clone()),
+                Extension(fixture::principal()),
+                RawQuery(None),
+                Bytes::from(format!(
+                    "i={}&a=user%2F-%2Fstate%2Fcom.google%2Fread",
+                    id.long_form()
+                )),
+            )
+        };
+
+        // Not served yet, so the feed of the item is found by scanning the subscribed feeds
+        edit_tag(ItemId::new(&a, "1")).await.unwrap();
+        assert_eq!(unread(&cx).await, vec!["2"]);
+
+        // Served by the query above
+        assert!(cx.items.get(ItemId::new(&b, "2")).await.is_some());
+        edit_tag(ItemId::new(&b, "2")).await.unwrap();
+        assert!(unread(&cx).await.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+use serde::Serialize;
+use synd_feed::types::Time;
+
+use super::stream::{Item, StreamId};
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ItemRef {
+    id: String,
+    direct_stream_ids: Vec<String>,
+    timestamp_usec: String,
+}
+
+impl ItemRef {
+    pub(super) fn new(item: &Item, now: Time) -> Self {
+        Self {
+            id: item.id.to_string(),
+            direct_stream_ids: Vec::new(),
+            timestamp_usec: usec(item.published.unwrap_or(now)),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ItemRefs {
+    pub item_refs: Vec<ItemRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct StreamContents {
+    pub direction: &'static str,
+    pub id: String,
+    pub updated: i64,
+    pub items: Vec<ItemContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ItemContent {
+    id: String,
+    crawl_time_msec: String,
+    timestamp_usec: String,
+    published: i64,
+    updated: i64,
+    title: String,
+    canonical: Vec<Link>,
+    alternate: Vec<Link>,
+    summary: Content,
+    categories: Vec<String>,
+    origin: Origin,
+}
+
+#[derive(Serialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Serialize)]
+struct Content {
+    direction: &'static str,
+    content: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Origin {
+    stream_id: String,
+    title: String,
+    html_url: String,
+}
+
+impl ItemContent {
+    pub(super) fn new(item: &Item, now: Time) -> Self {
+        let time = item.published.unwrap_or(now);
+        let links = item
+            .url
+            .iter()
+            .map(|href| Link { href: href.clone() })
+            .collect::<Vec<_>>();
+        Self {
+            id: item.id.long_form(),
+            crawl_time_msec: time.timestamp_millis().to_string(),
+            timestamp_usec: usec(time),
+            published: time.timestamp(),
+            updated: time.timestamp(),
+            title: item.title.clone().unwrap_or_default(),
+            canonical: links
+                .iter()
+                .map(|link| Link {
+                    href: link.href.clone(),
+                })
+                .collect(),
+            alternate: links,
+            summary: Content {
+                direction: "ltr",
+                content: item.content.clone().unwrap_or_default(),
+            },
+            categories: item.categories(),
+            origin: Origin {
+                stream_id: StreamId::Feed(item.entry.feed_url.clone()).to_string(),
+                title: item
+                    .feed_title
+                    .clone()
+                    .unwrap_or_else(|| item.entry.feed_url.to_string()),
+                html_url: item.website_url.clone().unwrap_or_default(),
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Subscription {
+    pub id: String,
+    pub title: String,
+    pub categories: Vec<Label>,
+    pub url: String,
+    pub html_url: String,
+    pub icon_url: String,
+}
+
+#[derive(Serialize)]
+pub(super) struct Label {
+    pub id: String,
+    pub label: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct UnreadCount {
+    pub id: String,
+    pub count: usize,
+    pub newest_item_timestamp_usec: String,
+}
+
+pub(super) fn usec(time: Time) -> String {
+    time.timestamp_micros().to_string()
+}
@@ -0,0 +1,498 @@
+use std::{collections::HashSet, fmt, ops::RangeBounds};
+
+use moka::future::Cache;
+use sha2::{Digest, Sha256};
+use synd_feed::types::{Category, FeedUrl, Time};
+
+use crate::{
+    config,
+    repository::types::{EntryReference, FeedReadState, StarredEntry},
+    usecase::{EntryCursor, FetchEntriesOutput},
+};
+
+const ITEM_ID_PREFIX: &str = "tag:google.com,2005:reader/item/";
+const STATE_PREFIX: &str = "user/-/state/com.google/";
+const LABEL_PREFIX: &str = "user/-/label/";
+const FEED_PREFIX: &str = "feed/";
+
+/// Stream of items such as a feed, a label or a state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum StreamId {
+    ReadingList,
+    Starred,
+    Read,
+    /// Used to mark items as unread
+    KeptUnread,
+    Feed(FeedUrl),
+    /// Category of the feed
+    Label(Category<'static>),
+}
+
+impl StreamId {
+    /// Parse the stream id. `user/{user_id}/` is regarded as `user/-/`
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        if let Some(url) = s.strip_prefix(FEED_PREFIX) {
+            return FeedUrl::try_from(url).ok().map(StreamId::Feed);
+        }
+        let (_user, path) = s.strip_prefix("user/")?.split_once('/')?;
+        if let Some(state) = path.strip_prefix("state/com.google/") {
+            return match state {
+                "reading-list" => Some(StreamId::ReadingList),
+                "starred" => Some(StreamId::Starred),
+                "read" => Some(StreamId::Read),
+                "kept-unread" => Some(StreamId::KeptUnread),
+                _ => None,
+            };
+        }
+        path.strip_prefix("label/")
+            .and_then(|label| Category::new(label.to_owned()).ok())
+            .map(StreamId::Label)
+    }
+}
+
+impl fmt::Display for StreamId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamId::ReadingList => write!(f, "{STATE_PREFIX}reading-list"),
+            StreamId::Starred => write!(f, "{STATE_PREFIX}starred"),
+            StreamId::Read => write!(f, "{STATE_PREFIX}read"),
+            StreamId::KeptUnread => write!(f, "{STATE_PREFIX}kept-unread"),
+            StreamId::Feed(url) => write!(f, "{FEED_PREFIX}{url}"),
+            StreamId::Label(category) => write!(f, "{LABEL_PREFIX}{}", category.as_str()),
+        }
+    }
+}
+
+/// Item id derived from the feed url and the entry id.
+/// Clients send it in the long form(`tag:google.com,2005:reader/item/{hex}`) or the short form(decimal)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) struct ItemId(i64);
+
+impl ItemId {
+    pub(super) fn new(feed_url: &FeedUrl, entry_id: &str) -> Self {
+        let digest = Sha256::new()
+            .chain_update(feed_url.as_str())
+            .chain_update(b"\n")
+            .chain_update(entry_id)
+            .finalize();
+        let bytes: [u8; 8] = digest[..8].try_into().unwrap();
+        // Keep it positive since some clients parse it as a signed integer
+        Self(i64::from_be_bytes(bytes) & i64::MAX)
+    }
+
+    pub(super) fn parse(s: &str) -> Option<Self> {
+        match s.strip_prefix(ITEM_ID_PREFIX) {
+            Some(hex) => u64::from_str_radix(hex, 16)
+                .ok()
+                .map(|id| Self(i64::from_be_bytes(id.to_be_bytes()))),
+            None => s.parse().ok().map(Self),
+        }
+    }
+
+    pub(super) fn long_form(self) -> String {
+        format!("{ITEM_ID_PREFIX}{:016x}", self.0)
+    }
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Entries of the items served recently.
+/// Item ids are hashes, so the entries specified by the ids are resolved by this without fetching all entries
+#[derive(Clone)]
+pub(crate) struct ItemIndex(Cache<ItemId, EntryReference>);
+
+impl Default for ItemIndex {
+    fn default() -> Self {
+        Self(
+            Cache::builder()
+                .max_capacity(config::reader::ITEM_INDEX_CAPACITY)
+                .time_to_idle(config::reader::ITEM_INDEX_TIME_TO_IDLE)
+                .build(),
+        )
+    }
+}
+
+impl ItemIndex {
+    pub(super) async fn insert(&self, items: &[Item]) {
+        for item in items {
+            self.0.insert(item.id, item.entry.clone()).await;
+        }
+    }
+
+    pub(super) async fn get(&self, id: ItemId) -> Option<EntryReference> {
+        self.0.get(&id).await
+    }
+}
+
+/// Entry with its feed and state
+pub(super) struct Item {
+    pub id: ItemId,
+    pub entry: EntryReference,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    /// Html content or summary
+    pub content: Option<String>,
+    pub published: Option<Time>,
+    pub feed_title: Option<String>,
+    pub website_url: Option<String>,
+    pub category: Option<Category<'static>>,
+    pub read: bool,
+    pub starred: bool,
+    /// False if the entry is no longer in the subscribed feeds and kept only as a starred entry
+    pub subscribed: bool,
+}
+
+impl Item {
+    /// Position of the item in the entries, returned as the continuation
+    pub(super) fn cursor(&self) -> EntryCursor {
+        EntryCursor {
+            published: self.published,
+            feed_url: self.entry.feed_url.clone(),
+            entry_id: self.entry.entry_id.clone(),
+        }
+    }
+
+    fn matches(&self, stream: &StreamId) -> bool {
+        match stream {
+            StreamId::ReadingList => self.subscribed,
+            StreamId::Starred => self.starred,
+            StreamId::Read => self.read,
+            StreamId::KeptUnread => !self.read,
+            StreamId::Feed(url) => &self.entry.feed_url == url,
+            StreamId::Label(label) => self.category.as_ref() == Some(label),
+        }
+    }
+
+    /// Stream ids the item belongs to
+    pub(super) fn categories(&self) -> Vec<String> {
+        let mut categories = vec![StreamId::ReadingList.to_string()];
+        if let Some(category) = &self.category {
+            categories.push(StreamId::Label(category.clone()).to_string());
+        }
+        if self.read {
+            categories.push(StreamId::Read.to_string());
+        }
+        if self.starred {
+            categories.push(StreamId::Starred.to_string());
+        }
+        categories
+    }
+}
+
+/// Query of the items in a stream
+#[derive(Debug)]
+pub(super) struct StreamQuery {
+    pub stream: StreamId,
+    /// Items in this stream are excluded(`xt`)
+    pub exclude: Option<StreamId>,
+    /// Only items in this stream are included(`it`)
+    pub include: Option<StreamId>,
+    /// Items published before this time are excluded(`ot`)
+    pub oldest: Option<Time>,
+    /// Items published after this time are excluded(`nt`)
+    pub newest: Option<Time>,
+    /// Return older items first(`r=o`)
+    pub oldest_first: bool,
+    /// Number of items(`n`)
+    pub count: usize,
+    /// Last item of the previous page returned as the continuation(`c`)
+    pub continuation: Option<EntryCursor>,
+}
+
+impl StreamQuery {
+    fn matches(&self, item: &Item) -> bool {
+        item.matches(&self.stream)
+            && !self.exclude.as_ref().is_some_and(|s| item.matches(s))
+            && self.include.as_ref().map_or(true, |s| item.matches(s))
+            && self
+                .oldest
+                .map_or(true, |oldest| item.published.is_some_and(|t| t >= oldest))
+            && self
+                .newest
+                .map_or(true, |newest| item.published.is_some_and(|t| t <= newest))
+    }
+
+    /// Whether the item and the following items in the order of the query are out of the time range
+    fn passed(&self, item: &Item) -> bool {
+        if self.oldest_first {
+            self.newest
+                .is_some_and(|newest| item.published.is_some_and(|t| t > newest))
+        } else {
+            // Items without the time come last
+            self.oldest
+                .is_some_and(|oldest| item.published.map_or(true, |t| t < oldest))
+        }
+    }
+}
+
+/// Items of the entries in the order of the entries, newer first
+pub(super) struct Items {
+    items: Vec<Item>,
+}
+
+impl Items {
+    /// Starred entries which are no longer in the feeds are not paginated along with the entries,
+    /// so only the ones in the range of the entries are included
+    pub(super) fn new(
+        FetchEntriesOutput {
+            entries,
+            feeds,
+            read_states,
+            ..
+        }: FetchEntriesOutput,
+        starred: &[StarredEntry],
+        range: impl RangeBounds<EntryCursor>,
+    ) -> Self {
+        let mut starred_ids = starred
+            .iter()
+            .map(|entry| ItemId::new(&entry.feed_url, &entry.entry_id))
+            .collect::<HashSet<_>>();
+        let mut items = Vec::with_capacity(entries.len() + starred.len());
+
+        for (entry, feed_url) in entries {
+            let meta = feeds.get(&feed_url);
+            let entry_id = entry.id().to_string();
+            let id = ItemId::new(&feed_url, &entry_id);
+            items.push(Item {
+                id,
+                title: entry.title().map(ToOwned::to_owned),
+                url: meta
+                    .and_then(|meta| entry.website_url(meta.feed.r#type()))
+                    .map(ToOwned::to_owned),
+                content: entry.content().or(entry.summary()).map(ToOwned::to_owned),
+                published: entry.published().or(entry.updated()),
+                feed_title: meta.and_then(|meta| {
+                    meta.title
+                        .clone()
+                        .or_else(|| meta.feed.title().map(ToOwned::to_owned))
+                }),
+                website_url: meta
+                    .and_then(|meta| meta.feed.website_url())
+                    .map(ToOwned::to_owned),
+                category: meta.and_then(|meta| meta.category.clone()),
+                read: read_states.is_read(&feed_url, &entry),
+                starred: starred_ids.remove(&id),
+                subscribed: true,
+                entry: EntryReference { feed_url, entry_id },
+            });
+        }
+
+        // Starred entries which are no longer in the feeds
+        for entry in starred {
+            let id = ItemId::new(&entry.feed_url, &entry.entry_id);
+            if !starred_ids.contains(&id) {
+                continue;
+            }
+            let published = entry.published.or(entry.updated);
+            let item = Item {
+                id,
+                title: entry.title.clone(),
+                url: entry.website_url.clone(),
+                content: entry.summary.clone(),
+                published,
+                feed_title: entry.feed_title.clone(),
+                website_url: None,
+                category: entry.category.clone(),
+                read: read_states
+                    .feed(&entry.feed_url)
+                    .is_some_and(|state| Self::is_read(state, &entry.entry_id, published)),
+                starred: true,
+                subscribed: false,
+                entry: EntryReference {
+                    feed_url: entry.feed_url.clone(),
+                    entry_id: entry.entry_id.clone(),
+                },
+            };
+            if range.contains(&item.cursor()) {
+                items.push(item);
+            }
+        }
+        items.sort_by_cached_key(Item::cursor);
+
+        Self { items }
+    }
+
+    fn is_read(state: &FeedReadState, entry_id: &str, published: Option<Time>) -> bool {
+        if state.unread.contains(entry_id) {
+            return false;
+        }
+        state.read.contains(entry_id)
+            || matches!((state.read_until, published), (Some(until), Some(time)) if time <= until)
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter()
+    }
+
+    pub(super) fn find(&self, id: ItemId) -> Option<&Item> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    /// Items in the stream in the order of the query.
+    /// Also return whether the rest of the stream is out of the time range of the query
+    pub(super) fn query(self, query: &StreamQuery) -> (Vec<Item>, bool) {
+        let mut items = self.items;
+        if query.oldest_first {
+            items.reverse();
+        }
+        let end = items.iter().position(|item| query.passed(item));
+        if let Some(end) = end {
+            items.truncate(end);
+        }
+        items.retain(|item| query.matches(item));
+
+        (items, end.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    #[test]
+    fn parse_stream_id() {
+        let cases = [
+            (
+                "user/-/state/com.google/reading-list",
+                Some(StreamId::ReadingList),
+            ),
+            (
+                "user/1005921515/state/com.google/starred",
+                Some(StreamId::Starred),
+            ),
+            ("user/-/state/com.google/read", Some(StreamId::Read)),
+            (
+                "user/-/label/Rust",
+                Some(StreamId::Label(Category::new("rust").unwrap())),
+            ),
+            (
+                "feed/https://blog.ymgyt.io/atom.xml",
+                Some(StreamId::Feed(
+                    FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap(),
+                )),
+            ),
+            ("user/-/state/com.google/broadcast", None),
+            ("feed/not a url", None),
+            ("pop/-/topic", None),
+        ];
+
+        for (input, expected) in cases {
+            let parsed = StreamId::parse(input);
+            assert_eq!(parsed, expected, "{input}");
+            if let Some(parsed) = parsed.filter(|_| !input.contains("1005921515")) {
+                assert_eq!(parsed.to_string(), input.to_ascii_lowercase());
+            }
+        }
+    }
+
+    fn item(feed: &str, entry_id: &str, published: i64, read: bool, starred: bool) -> Item {
+        let feed_url = FeedUrl::try_from(feed).unwrap();
+        Item {
+            id: ItemId::new(&feed_url, entry_id),
+            entry: EntryReference {
+                feed_url,
+                entry_id: entry_id.into(),
+            },
+            title: None,
+            url: None,
+            content: None,
+            published: DateTime::from_timestamp(published, 0),
+            feed_title: None,
+            website_url: None,
+            category: Some(Category::new("rust").unwrap()),
+            read,
+            starred,
+            subscribed: true,
+        }
+    }
+
+    #[test]
+    fn query_stream() {
+        let items = || Items {
+            items: vec![
+                item("https://a.ymgyt.io/feed", "3", 300, false, true),
+                item("https://b.ymgyt.io/feed", "2", 200, true, false),
+                item("https://a.ymgyt.io/feed", "1", 100, false, false),
+            ],
+        };
+        let query = |stream| StreamQuery {
+            stream,
+            exclude: None,
+            include: None,
+            oldest: None,
+            newest: None,
+            oldest_first: false,
+            count: 10,
+            continuation: None,
+        };
+        let ids = |query: &StreamQuery| {
+            let (matched, passed) = items().query(query);
+            (
+                matched
+                    .iter()
+                    .map(|item| item.entry.entry_id.clone())
+                    .collect::<Vec<_>>(),
+                passed,
+            )
+        };
+
+        assert_eq!(ids(&query(StreamId::ReadingList)).0, vec!["3", "2", "1"]);
+        assert_eq!(ids(&query(StreamId::Starred)).0, vec!["3"]);
+        assert_eq!(
+            ids(&query(StreamId::Feed(
+                FeedUrl::try_from("https://a.ymgyt.io/feed").unwrap()
+            )))
+            .0,
+            vec!["3", "1"]
+        );
+        assert_eq!(
+            ids(&StreamQuery {
+                exclude: Some(StreamId::Read),
+                oldest_first: true,
+                ..query(StreamId::Label(Category::new("rust").unwrap()))
+            })
+            .0,
+            vec!["1", "3"]
+        );
+        // The rest of the stream is out of the time range
+        assert_eq!(
+            ids(&StreamQuery {
+                oldest: DateTime::from_timestamp(200, 0),
+                ..query(StreamId::ReadingList)
+            }),
+            (vec!["3".to_owned(), "2".to_owned()], true)
+        );
+        assert_eq!(
+            ids(&StreamQuery {
+                newest: DateTime::from_timestamp(200, 0),
+                oldest_first: true,
+                ..query(StreamId::ReadingList)
+            }),
+            (vec!["1".to_owned(), "2".to_owned()], true)
+        );
+        assert!(!ids(&query(StreamId::ReadingList)).1);
+    }
+
+    #[test]
+    fn item_id_forms() {
+        let id = ItemId::new(
+            &FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap(),
+            "entry-1",
+        );
+        assert!(id.0 >= 0);
+        assert_eq!(ItemId::parse(&id.long_form()), Some(id));
+        assert_eq!(ItemId::parse(&id.to_string()), Some(id));
+        assert_eq!(
+            ItemId::parse("tag:google.com,2005:reader/item/000000000000001f"),
+            Some(ItemId(31))
+        );
+        assert_eq!(ItemId::parse("-1"), Some(ItemId(-1)));
+        assert_eq!(ItemId::parse("tag:google.com,2005:reader/item/zz"), None);
+    }
+}
@@ -0,0 +1,378 @@
+//! Re-syndicate subscribed entries as a feed so that other feed readers
+//! can consume the aggregated entries.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query},
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    Extension,
+};
+use serde::Deserialize;
+use synd_feed::types::{Category, Requirement};
+
+use crate::{
+    config,
+    principal::Principal,
+    serve::auth::{ApiTokenBackend, Authenticator},
+    usecase::{
+        self, EntryCursor, FetchEntries, FetchEntriesInput, FetchEntriesOutput, Output, Runtime,
+        SubscriptionFilter,
+    },
+};
+
+mod render;
+
+use self::render::Syndication;
+
+#[derive(Clone)]
+pub(crate) struct Context {
+    pub runtime: Arc<Runtime>,
+    pub authenticator: Authenticator,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Format {
+    #[default]
+    Atom,
+    Json,
+}
+
+impl Format {
+    fn as_str(self) -> &'static str {
+        match self {
+            Format::Atom => "atom",
+            Format::Json => "json",
+        }
+    }
+
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Atom => "application/atom+xml; charset=utf-8",
+            Format::Json => "application/feed+json; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub(crate) struct SyndicationParams {
+    #[serde(default)]
+    format: Format,
+    requirement: Option<String>,
+    category: Option<String>,
+    first: Option<usize>,
+    /// Cursor of the last entry of the previous page, taken from the next link
+    after: Option<String>,
+    #[serde(default)]
+    unread_only: bool,
+}
+
+impl SyndicationParams {
+    fn input(&self) -> Result<FetchEntriesInput, String> {
+        let requirement = self
+            .requirement
+            .as_deref()
+            .map(str::parse::<Requirement>)
+            .transpose()
+            .map_err(ToOwned::to_owned)?;
+        let category = self
+            .category
+            .clone()
+            .map(Category::new)
+            .transpose()
+            .map_err(|err| format!("invalid category: {err}"))?;
+        let after = self
+            .after
+            .as_deref()
+            .map(EntryCursor::decode)
+            .transpose()
+            .map_err(|err| err.to_string())?;
+        let first = self
+            .first
+            .unwrap_or(config::syndication::DEFAULT_ENTRIES)
+            .min(config::syndication::MAX_ENTRIES);
+
+        Ok(FetchEntriesInput {
+            after,
+            before: None,
+            first: Some(first),
+            last: None,
+            filter: SubscriptionFilter {
+                requirement,
+                categories: category.map(|category| vec![category]),
+                ..Default::default()
+            },
+            unread_only: self.unread_only,
+        })
+    }
+
+    /// Url of the page which follows the entry, relative to the host
+    fn next(&self, token: &str, after: &EntryCursor) -> String {
+        let path = format!("{}{token}?", config::syndication::PATH_PREFIX);
+        let mut query = url::form_urlencoded::Serializer::for_suffix(path.clone(), path.len());
+        query.append_pair("format", self.format.as_str());
+        if let Some(requirement) = self.requirement.as_deref() {
+            query.append_pair("requirement", requirement);
+        }
+        if let Some(category) = self.category.as_deref() {
+            query.append_pair("category", category);
+        }
+        if let Some(first) = self.first {
+            query.append_pair("first", &first.to_string());
+        }
+        if self.unread_only {
+            query.append_pair("unread_only", "true");
+        }
+        query.append_pair("after", &after.encode());
+        query.finish()
+    }
+}
+
+impl usecase::Context for Principal {
+    fn principal(&self) -> Principal {
+        self.clone()
+    }
+}
+
+/// Serve subscribed entries of the user who own given personal access token.
+/// Only read-only tokens are accepted since the token is exposed in the feed url
+pub(crate) async fn syndicate(
+    Extension(Context {
+        runtime,
+        authenticator,
+    }): Extension<Context>,
+    Path(token): Path<String>,
+    Query(params): Query<SyndicationParams>,
+) -> Response {
+    let principal = match authenticator
+        .authenticate(format!("{} {token}", ApiTokenBackend::SCHEME))
+        .await
+    {
+        Ok(principal) if principal.is_read_only() => principal,
+        _ => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+    let input = match params.input() {
+        Ok(input) => input,
+        Err(err) => return (StatusCode::BAD_REQUEST, err).into_response(),
+    };
+
+    let Output {
+        output:
+            FetchEntriesOutput {
+                entries,
+                feeds,
+                has_next_page,
+                ..
+            },
+    } = match runtime
+        .run::<FetchEntries, _, _>(principal.clone(), input)
+        .await
+    {
+        Ok(output) => output,
+        Err(usecase::Error::Unauthorized(_)) => {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let next = entries
+        .last()
+        .filter(|_| has_next_page)
+        .map(|(entry, feed_url)| params.next(&token, &EntryCursor::new(entry, feed_url)));
+
+    let syndication = Syndication {
+        user_id: principal.user_id().unwrap_or_default(),
+        entries: &entries,
+        feeds: &feeds,
+        next: next.as_deref(),
+        now: chrono::Utc::now(),
+    };
+    let format = params.format;
+    let body = match format {
+        Format::Atom => syndication.atom(),
+        Format::Json => match syndication.json_feed() {
+            Ok(body) => body,
+            Err(err) => {
+                tracing::error!("Failed to serialize json feed: {err}");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        },
+    };
+
+    ([(CONTENT_TYPE, format.content_type())], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+    use chrono::Utc;
+    use synd_feed::feed::cache::CacheLayer;
+
+    use crate::{
+        dependency::Repositories,
+        fixture::{self, feed, subscription, StaticFeeds},
+        repository::{
+            memory::MemoryRepository,
+            types::{ApiToken, ApiTokenScope},
+        },
+        usecase::{
+            authorize::{Authorizer, Quota},
+            MakeUsecase,
+        },
+    };
+
+    use super::*;
+
+    /// Context in which alice subscribes a feed of 3 entries.
+    /// Return the plaintexts of alice's read-only and read-write tokens as well
+    async fn context() -> (Context, String, String) {
+        let repositories = Repositories::new(MemoryRepository::new());
+        let url = fixture::url("https://blog.ymgyt.io/atom.xml");
+        let feeds = Arc::new(StaticFeeds::default());
+        feeds.insert(feed(&url, &[("3", 3), ("2", 2), ("1", 1)]));
+        repositories
+            .subscription
+            .put_feed_subscription(subscription("alice", &url))
+            .await
+            .unwrap();
+        let mut plaintexts = Vec::new();
+        for scope in [ApiTokenScope::ReadOnly, ApiTokenScope::ReadWrite] {
+            let (token, plaintext) =
+                ApiToken::issue("alice", "feed reader", scope, Utc::now(), None);
+            repositories.api_token.put_api_token(token).await.unwrap();
+            plaintexts.push(plaintext);
+        }
+        let cache = CacheLayer::new(feeds);
+        let make = MakeUsecase {
+            subscription_repo: repositories.subscription,
+            read_state_repo: repositories.read_state,
+            starred_entry_repo: repositories.starred_entry,
+            api_token_repo: repositories.api_token.clone(),
+            webhook_repo: repositories.webhook,
+            fetch_feed: Arc::new(cache.clone()),
+            feed_updates: Arc::new(cache),
+            quota: Quota {
+                max_subscriptions: 10,
+            },
+        };
+        let cx = Context {
+            runtime: Arc::new(Runtime::new(make, Authorizer::new())),
+            authenticator: Authenticator::new()
+                .unwrap()
+                .with_api_tokens(repositories.api_token),
+        };
+        let read_write = plaintexts.pop().unwrap();
+        let read_only = plaintexts.pop().unwrap();
+        (cx, read_only, read_write)
+    }
+
+    fn params(format: Format) -> SyndicationParams {
+        SyndicationParams {
+            format,
+            requirement: None,
+            category: None,
+            first: None,
+            after: None,
+            unread_only: false,
+        }
+    }
+
+    async fn syndicate_body(cx: &Context, token: &str, params: SyndicationParams) -> String {
+        let response = syndicate(Extension(cx.clone()), Path(token.into()), Query(params)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn reject_invalid_token() {
+        let (cx, read_only, read_write) = context().await;
+        let (user_id, rest) = read_only.split_once('.').unwrap();
+
+        for token in [
+            format!("bob.{rest}"),
+            format!("{user_id}.{}", rest.replace('.', ".0")),
+            "alice".into(),
+            // Read-write tokens must not be exposed in feed urls
+            read_write,
+        ] {
+            let response = syndicate(
+                Extension(cx.clone()),
+                Path(token),
+                Query(params(Format::Atom)),
+            )
+            .await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn serve_syndication_format() {
+        let (cx, token, _) = context().await;
+
+        for format in [Format::Atom, Format::Json] {
+            let response = syndicate(
+                Extension(cx.clone()),
+                Path(token.clone()),
+                Query(params(format)),
+            )
+            .await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(response.headers()[CONTENT_TYPE], format.content_type(),);
+        }
+
+        for invalid in [
+            SyndicationParams {
+                requirement: Some("unknown".into()),
+                ..params(Format::Atom)
+            },
+            SyndicationParams {
+                after: Some("unknown".into()),
+                ..params(Format::Atom)
+            },
+        ] {
+            let response =
+                syndicate(Extension(cx.clone()), Path(token.clone()), Query(invalid)).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[tokio::test]
+    async fn paginate_by_next_link() {
+        let (cx, token, _) = context().await;
+        let first_page = SyndicationParams {
+            first: Some(2),
+            ..params(Format::Json)
+        };
+
+        let body = syndicate_body(&cx, &token, first_page.clone()).await;
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        let ids = |json: &serde_json::Value| {
+            json["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["id"].as_str().unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&json), vec!["3", "2"]);
+
+        let next = json["next_url"].as_str().unwrap();
+        let (path, query) = next.split_once('?').unwrap();
+        assert_eq!(path, format!("/syndication/{token}"));
+        let next_page: SyndicationParams = parse_params(query);
+        assert_eq!(next_page.first, Some(2));
+
+        let body = syndicate_body(&cx, &token, next_page).await;
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(ids(&json), vec!["1"]);
+        assert!(json.get("next_url").is_none());
+    }
+
+    /// Parse the query of the next link as the handler does
+    fn parse_params(query: &str) -> SyndicationParams {
+        let uri: axum::http::Uri = format!("http://localhost/?{query}").parse().unwrap();
+        Query::try_from_uri(&uri).unwrap().0
+    }
+}
@@ -0,0 +1,273 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Write as _};
+
+use chrono::SecondsFormat;
+use serde::Serialize;
+use synd_feed::types::{Annotated, Entry, FeedMeta, FeedUrl, Time};
+
+use crate::config;
+
+const TITLE: &str = "syndicationd";
+
+/// Entries to be syndicated with their feed metadata
+pub(super) struct Syndication<'a> {
+    pub user_id: &'a str,
+    pub entries: &'a [(Entry, FeedUrl)],
+    pub feeds: &'a HashMap<FeedUrl, Annotated<FeedMeta>>,
+    /// Url of the next page if any
+    pub next: Option<&'a str>,
+    pub now: Time,
+}
+
+impl<'a> Syndication<'a> {
+    fn feed(&self, url: &FeedUrl) -> Option<&'a Annotated<FeedMeta>> {
+        self.feeds.get(url)
+    }
+
+    fn entry_time(entry: &Entry) -> Option<Time> {
+        entry.updated().or(entry.published())
+    }
+
+    fn updated(&self) -> Time {
+        self.entries
+            .iter()
+            .filter_map(|(entry, _)| Self::entry_time(entry))
+            .max()
+            .unwrap_or(self.now)
+    }
+
+    /// Render entries as Atom(RFC 4287)
+    pub(super) fn atom(&self) -> String {
+        let mut xml = String::with_capacity(1024 * self.entries.len().max(1));
+
+        xml.push_str(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+        xml.push('\n');
+        xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+        let _ = write!(
+            xml,
+            "<id>urn:syndicationd:{user_id}</id><title>{TITLE}</title><updated>{updated}</updated><generator version=\"{version}\">{name}</generator>",
+            user_id = escape(self.user_id),
+            updated = rfc3339(self.updated()),
+            version = config::VERSION,
+            name = config::NAME,
+        );
+        if let Some(next) = self.next {
+            let _ = write!(xml, r#"<link rel="next" href="{}"/>"#, escape(next));
+        }
+
+        for (entry, feed_url) in self.entries {
+            let feed = self.feed(feed_url);
+            xml.push_str("<entry>");
+            let _ = write!(xml, "<id>{}</id>", escape(&entry.id().to_string()));
+            let _ = write!(
+                xml,
+                "<title>{}</title>",
+                escape(entry.title().unwrap_or_default())
+            );
+            let _ = write!(
+                xml,
+                "<updated>{}</updated>",
+                rfc3339(Self::entry_time(entry).unwrap_or(self.now))
+            );
+            if let Some(published) = entry.published() {
+                let _ = write!(xml, "<published>{}</published>", rfc3339(published));
+            }
+            if let Some(url) = feed.and_then(|feed| entry.website_url(feed.feed.r#type())) {
+                let _ = write!(xml, r#"<link rel="alternate" href="{}"/>"#, escape(url));
+            }
+            if let Some(category) = feed.and_then(|feed| feed.category.as_ref()) {
+                let _ = write!(xml, r#"<category term="{}"/>"#, escape(category.as_str()));
+            }
+            if let Some(summary) = entry.summary() {
+                let _ = write!(xml, r#"<summary type="html">{}</summary>"#, escape(summary));
+            }
+            if let Some(content) = entry.content() {
+                let _ = write!(xml, r#"<content type="html">{}</content>"#, escape(content));
+            }
+            xml.push_str("<source>");
+            let _ = write!(xml, "<id>{}</id>", escape(feed_url.as_str()));
+            if let Some(title) = feed.and_then(|feed| feed.feed.title()) {
+                let _ = write!(xml, "<title>{}</title>", escape(title));
+            }
+            let _ = write!(
+                xml,
+                r#"<link rel="self" href="{}"/>"#,
+                escape(feed_url.as_str())
+            );
+            xml.push_str("</source>");
+            xml.push_str("</entry>");
+        }
+
+        xml.push_str("</feed>\n");
+        xml
+    }
+
+    /// Render entries as JSON Feed version 1.1
+    pub(super) fn json_feed(&self) -> serde_json::Result<String> {
+        let items = self
+            .entries
+            .iter()
+            .map(|(entry, feed_url)| {
+                let feed = self.feed(feed_url);
+                JsonFeedItem {
+                    id: entry.id().to_string(),
+                    url: feed.and_then(|feed| entry.website_url(feed.feed.r#type())),
+                    title: entry.title(),
+                    content_html: entry.content(),
+                    summary: entry.summary(),
+                    date_published: entry.published().map(rfc3339),
+                    date_modified: entry.updated().map(rfc3339),
+                    tags: feed
+                        .and_then(|feed| feed.category.as_ref())
+                        .map(|category| vec![category.as_str()])
+                        .unwrap_or_default(),
+                    synd: JsonFeedItemExtension {
+                        feed_url: feed_url.as_str(),
+                        feed_title: feed.and_then(|feed| feed.feed.title()),
+                        requirement: feed
+                            .and_then(|feed| feed.requirement)
+                            .map(|requirement| requirement.to_string()),
+                    },
+                }
+            })
+            .collect();
+
+        serde_json::to_string(&JsonFeed {
+            version: "https://jsonfeed.org/version/1.1",
+            title: TITLE,
+            next_url: self.next,
+            items,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_url: Option<&'a str>,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_published: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    date_modified: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<&'a str>,
+    // Custom extension, key should start with underscore
+    #[serde(rename = "_synd")]
+    synd: JsonFeedItemExtension<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItemExtension<'a> {
+    feed_url: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    feed_title: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    requirement: Option<String>,
+}
+
+fn rfc3339(t: Time) -> String {
+    t.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn escape(s: &str) -> Cow<'_, str> {
+    if !s.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(s);
+    }
+    let mut escaped = String::with_capacity(s.len() + 16);
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use synd_feed::types::{Category, Feed, Requirement};
+
+    use super::{super::Format, *};
+
+    fn syndicate(format: Format) -> String {
+        let url = FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap();
+        let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <id>blog</id><title>ymgyt blog</title>
+            <entry>
+              <id>entry-1</id><title>Tom &amp; Jerry</title>
+              <link rel="alternate" href="https://blog.ymgyt.io/entry/1"/>
+              <published>2024-06-01T00:00:00Z</published>
+              <updated>2024-06-02T00:00:00Z</updated>
+              <summary>summary &lt;b&gt;1&lt;/b&gt;</summary>
+            </entry>
+            <entry><id>entry-2</id></entry>
+        </feed>"#;
+        let feed = Feed::from((
+            url.clone(),
+            feed_rs::parser::parse(atom.as_bytes()).unwrap(),
+        ));
+        let entries = feed
+            .entries()
+            .map(|entry| (entry.clone(), url.clone()))
+            .collect::<Vec<_>>();
+        let mut annotated = Annotated::new(feed.meta().clone());
+        annotated.requirement = Some(Requirement::Must);
+        annotated.category = Some(Category::new("rust").unwrap());
+        let feeds = HashMap::from([(url, annotated)]);
+
+        let syndication = Syndication {
+            user_id: "585779d8c9b2e06d",
+            entries: &entries,
+            feeds: &feeds,
+            next: Some("/syndication/token?format=atom&after=xxx"),
+            now: "2024-07-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+        };
+        match format {
+            Format::Atom => syndication.atom(),
+            Format::Json => syndication.json_feed().unwrap(),
+        }
+    }
+
+    #[test]
+    fn render_atom() {
+        // Redact version so that the snapshot does not change on every release
+        let atom = syndicate(Format::Atom).replace(config::VERSION, "[version]");
+        insta::assert_snapshot!(atom);
+    }
+
+    #[test]
+    fn render_json_feed() {
+        let json = syndicate(Format::Json);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        insta::assert_snapshot!(serde_json::to_string_pretty(&json).unwrap());
+    }
+
+    #[test]
+    fn escape_xml() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(
+            escape(r#"<a href="x">Tom & 'Jerry'</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;"
+        );
+    }
+}
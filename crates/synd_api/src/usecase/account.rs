@@ -0,0 +1,243 @@
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::{
+    admin::UserRecord,
+    dependency::Repositories,
+    principal::Principal,
+    repository::{
+        ApiTokenRepository, ReadStateRepository, StarredEntryRepository, SubscriptionRepository,
+        WebhookRepository,
+    },
+    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+};
+
+#[derive(Error, Debug)]
+pub enum AccountError {
+    #[error("serialize user data: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+/// Export all data of the principal in the same format as `synd-api admin export`
+pub struct ExportMyData {
+    pub repositories: Repositories,
+}
+
+pub struct ExportMyDataInput {}
+
+pub struct ExportMyDataOutput {
+    /// JSON of the user data
+    pub data: String,
+}
+
+impl Usecase for ExportMyData {
+    type Input = ExportMyDataInput;
+
+    type Output = ExportMyDataOutput;
+
+    type Error = AccountError;
+
+    const READ_ONLY: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repositories: Repositories {
+                subscription: make.subscription_repo.clone(),
+                read_state: make.read_state_repo.clone(),
+                starred_entry: make.starred_entry_repo.clone(),
+                api_token: make.api_token_repo.clone(),
+                webhook: make.webhook_repo.clone(),
+            },
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "export_my_data", skip_all)]
+    async fn usecase(
+        &self,
+        Input { principal, .. }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal.user_id().unwrap().to_owned();
+
+        let mut record = UserRecord::export(&self.repositories, user_id).await?;
+        // Token hashes and webhook secrets are credentials rather than user data
+        record.api_tokens.clear();
+        record.webhooks.clear();
+
+        let data = serde_json::to_string(&record)
+            .map_err(|err| Error::Usecase(AccountError::Serialize(err)))?;
+
+        Ok(Output {
+            output: ExportMyDataOutput { data },
+        })
+    }
+}
+
+/// Delete all data of the principal including api tokens and webhooks.
+/// Users can subscribe again with the same account afterwards
+pub struct DeleteMyAccount {
+    pub subscription_repo: Arc<dyn SubscriptionRepository>,
+    pub read_state_repo: Arc<dyn ReadStateRepository>,
+    pub starred_entry_repo: Arc<dyn StarredEntryRepository>,
+    pub api_token_repo: Arc<dyn ApiTokenRepository>,
+    pub webhook_repo: Arc<dyn WebhookRepository>,
+}
+
+pub struct DeleteMyAccountInput {}
+
+pub struct DeleteMyAccountOutput {}
+
+impl Usecase for DeleteMyAccount {
+    type Input = DeleteMyAccountInput;
+
+    type Output = DeleteMyAccountOutput;
+
+    type Error = AccountError;
+
+    const API_TOKEN: bool = false;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            subscription_repo: make.subscription_repo.clone(),
+            read_state_repo: make.read_state_repo.clone(),
+            starred_entry_repo: make.starred_entry_repo.clone(),
+            api_token_repo: make.api_token_repo.clone(),
+            webhook_repo: make.webhook_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "delete_my_account", skip_all)]
+    async fn usecase(
+        &self,
+        Input { principal, .. }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal.user_id().unwrap();
+
+        // Revoke tokens first so that a partial failure does not leave usable credentials
+        self.api_token_repo.delete_api_tokens(user_id).await?;
+        self.webhook_repo.delete_webhooks(user_id).await?;
+        self.starred_entry_repo
+            .delete_starred_entries(user_id)
+            .await?;
+        self.read_state_repo.delete_read_states(user_id).await?;
+        self.subscription_repo.delete_subscriptions(user_id).await?;
+
+        tracing::info!(user_id, "Account deleted");
+
+        Ok(Output {
+            output: DeleteMyAccountOutput {},
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixture::{input, principal, subscription, url},
+        repository::{memory::MemoryRepository, types::FeedSubscription},
+        serve::auth::{ApiTokenBackend, GoogleLoginBackend},
+        usecase::authorize::Authorizer,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn export_and_delete() {
+        let repository = Arc::new(MemoryRepository::new());
+        for user_id in ["alice", "bob"] {
+            repository
+                .put_feed_subscription(FeedSubscription {
+                    title: Some("ymgyt".into()),
+                    ..subscription(user_id, &url("https://a.ymgyt.io/feed"))
+                })
+                .await
+                .unwrap();
+        }
+        let export = ExportMyData {
+            repositories: Repositories {
+                subscription: repository.clone(),
+                read_state: repository.clone(),
+                starred_entry: repository.clone(),
+                api_token: repository.clone(),
+                webhook: repository.clone(),
+            },
+        };
+
+        let data = export
+            .usecase(input(ExportMyDataInput {}))
+            .await
+            .unwrap()
+            .output
+            .data;
+        let record: UserRecord = serde_json::from_str(&data).unwrap();
+        assert_eq!(record.user_id, "alice");
+        assert_eq!(record.subscriptions.len(), 1);
+        assert_eq!(record.subscriptions[0].title.as_deref(), Some("ymgyt"));
+
+        DeleteMyAccount {
+            subscription_repo: repository.clone(),
+            read_state_repo: repository.clone(),
+            starred_entry_repo: repository.clone(),
+            api_token_repo: repository.clone(),
+            webhook_repo: repository.clone(),
+        }
+        .usecase(input(DeleteMyAccountInput {}))
+        .await
+        .unwrap();
+
+        let data = export
+            .usecase(input(ExportMyDataInput {}))
+            .await
+            .unwrap()
+            .output
+            .data;
+        let record: UserRecord = serde_json::from_str(&data).unwrap();
+        assert!(record.subscriptions.is_empty());
+        assert_eq!(
+            repository.fetch_user_ids().await.unwrap(),
+            vec!["bob".to_owned()]
+        );
+    }
+
+    #[tokio::test]
+    async fn api_token_cannot_delete_account() {
+        let repository = Arc::new(MemoryRepository::new());
+        let delete = DeleteMyAccount {
+            subscription_repo: repository.clone(),
+            read_state_repo: repository.clone(),
+            starred_entry_repo: repository.clone(),
+            api_token_repo: repository.clone(),
+            webhook_repo: repository,
+        };
+        for (scheme, allowed) in [
+            ("github", true),
+            (ApiTokenBackend::SCHEME, false),
+            (GoogleLoginBackend::SCHEME, false),
+        ] {
+            let authorized = Authorizer::new()
+                .authorize(
+                    principal().with_auth_scheme(scheme),
+                    &delete,
+                    &DeleteMyAccountInput {},
+                )
+                .await;
+            assert_eq!(authorized.is_ok(), allowed, "{scheme}");
+        }
+    }
+}
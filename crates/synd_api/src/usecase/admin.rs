@@ -0,0 +1,274 @@
+use std::sync::Arc;
+
+use synd_feed::{
+    feed::{
+        cache::{CacheStats, FetchCachedFeed},
+        service::FetchFeedError,
+    },
+    types::{Feed, FeedUrl},
+};
+use thiserror::Error;
+
+use crate::{
+    principal::Principal,
+    repository::{types::SubscribedFeeds, SubscriptionRepository},
+    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+};
+
+#[derive(Error, Debug)]
+pub enum AdminError {
+    #[error("fetch feed error: {0}")]
+    FetchFeed(FetchFeedError),
+}
+
+/// List the users who have subscriptions
+pub struct FetchUsers {
+    pub repository: Arc<dyn SubscriptionRepository>,
+}
+
+pub struct FetchUsersInput {}
+
+pub struct FetchUsersOutput {
+    /// Sorted user ids
+    pub user_ids: Vec<String>,
+}
+
+impl Usecase for FetchUsers {
+    type Input = FetchUsersInput;
+
+    type Output = FetchUsersOutput;
+
+    type Error = AdminError;
+
+    const READ_ONLY: bool = true;
+
+    const ADMIN: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        _: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let mut user_ids = self.repository.fetch_user_ids().await?;
+        user_ids.sort_unstable();
+
+        Ok(Output {
+            output: FetchUsersOutput { user_ids },
+        })
+    }
+}
+
+/// Inspect the subscriptions of the user
+pub struct FetchUserSubscriptions {
+    pub repository: Arc<dyn SubscriptionRepository>,
+}
+
+pub struct FetchUserSubscriptionsInput {
+    pub user_id: String,
+}
+
+pub struct FetchUserSubscriptionsOutput {
+    pub feeds: SubscribedFeeds,
+}
+
+impl Usecase for FetchUserSubscriptions {
+    type Input = FetchUserSubscriptionsInput;
+
+    type Output = FetchUserSubscriptionsOutput;
+
+    type Error = AdminError;
+
+    const READ_ONLY: bool = true;
+
+    const ADMIN: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            input: FetchUserSubscriptionsInput { user_id },
+            ..
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let feeds = self.repository.fetch_subscribed_feeds(&user_id).await?;
+
+        Ok(Output {
+            output: FetchUserSubscriptionsOutput { feeds },
+        })
+    }
+}
+
+/// Return the statistics of the feed cache
+pub struct FetchCacheStats {
+    pub fetch_feed: Arc<dyn FetchCachedFeed>,
+}
+
+pub struct FetchCacheStatsInput {}
+
+pub struct FetchCacheStatsOutput {
+    pub stats: CacheStats,
+}
+
+impl Usecase for FetchCacheStats {
+    type Input = FetchCacheStatsInput;
+
+    type Output = FetchCacheStatsOutput;
+
+    type Error = AdminError;
+
+    const READ_ONLY: bool = true;
+
+    const ADMIN: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            fetch_feed: make.fetch_feed.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        _: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let stats = self.fetch_feed.stats().await;
+
+        Ok(Output {
+            output: FetchCacheStatsOutput { stats },
+        })
+    }
+}
+
+/// Fetch the feed bypassing the cache so that users see the latest entries without waiting for the periodic refresh
+pub struct RefreshFeed {
+    pub fetch_feed: Arc<dyn FetchCachedFeed>,
+}
+
+pub struct RefreshFeedInput {
+    pub url: FeedUrl,
+}
+
+pub struct RefreshFeedOutput {
+    pub feed: Arc<Feed>,
+}
+
+impl Usecase for RefreshFeed {
+    type Input = RefreshFeedInput;
+
+    type Output = RefreshFeedOutput;
+
+    type Error = AdminError;
+
+    const ADMIN: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            fetch_feed: make.fetch_feed.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            input: RefreshFeedInput { url },
+            ..
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        tracing::info!(url = url.as_str(), "Refresh feed");
+
+        let feed = self
+            .fetch_feed
+            .refresh_feed(url)
+            .await
+            .map_err(|err| Error::Usecase(AdminError::FetchFeed(err)))?;
+
+        Ok(Output {
+            output: RefreshFeedOutput { feed },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        principal::{Role, User},
+        repository::memory::MemoryRepository,
+        usecase::authorize::Authorizer,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn admin_only() {
+        let usecase = FetchUsers {
+            repository: Arc::new(MemoryRepository::new()),
+        };
+        let authorizer = Authorizer::new();
+        let user = User::from_id("alice");
+
+        assert!(matches!(
+            authorizer
+                .authorize(Principal::User(user.clone()), &usecase, &FetchUsersInput {})
+                .await,
+            Err(Unauthorized::Forbidden)
+        ));
+        assert!(authorizer
+            .authorize(
+                Principal::User(user.clone().with_role(Role::Admin)),
+                &usecase,
+                &FetchUsersInput {}
+            )
+            .await
+            .is_ok());
+        // Read only tokens of the admin can run read only admin usecases
+        assert!(authorizer
+            .authorize(
+                Principal::User(user.with_role(Role::Admin).with_read_only(true)),
+                &usecase,
+                &FetchUsersInput {}
+            )
+            .await
+            .is_ok());
+    }
+}
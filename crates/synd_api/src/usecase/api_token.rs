@@ -0,0 +1,427 @@
+use std::sync::Arc;
+
+use synd_feed::types::Time;
+use thiserror::Error;
+
+use crate::{
+    config,
+    principal::Principal,
+    repository::{
+        types::{ApiToken, ApiTokenScope},
+        ApiTokenRepository, RepositoryError,
+    },
+    serve::auth::LocalBackend,
+    usecase::{
+        authorize::Unauthorized, update_feed_annotations::non_blank, Error, Input, MakeUsecase,
+        Output, Usecase,
+    },
+};
+
+#[derive(Error, Debug)]
+pub enum ApiTokenError {
+    #[error("api token name must not be empty")]
+    EmptyName,
+    #[error("api token expiration must be in the future")]
+    AlreadyExpired,
+    #[error("api token not found")]
+    NotFound,
+}
+
+pub struct CreateApiToken {
+    pub repository: Arc<dyn ApiTokenRepository>,
+}
+
+pub struct CreateApiTokenInput {
+    pub name: String,
+    pub scope: ApiTokenScope,
+    /// The token never expires if None
+    pub expires_at: Option<Time>,
+}
+
+pub struct CreateApiTokenOutput {
+    pub token: ApiToken,
+    /// Plaintext of the token. This is the only chance to know it
+    pub plaintext: String,
+}
+
+impl Usecase for CreateApiToken {
+    type Input = CreateApiTokenInput;
+
+    type Output = CreateApiTokenOutput;
+
+    type Error = ApiTokenError;
+
+    const API_TOKEN: bool = false;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.api_token_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "create_api_token", skip_all)]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input:
+                CreateApiTokenInput {
+                    name,
+                    scope,
+                    expires_at,
+                },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+        let name = non_blank(Some(name)).ok_or(Error::Usecase(ApiTokenError::EmptyName))?;
+        let now = chrono::Utc::now();
+        if expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(Error::Usecase(ApiTokenError::AlreadyExpired));
+        }
+
+        // Tokens requested with the password of a local user are the sessions of the login
+        let login = principal.auth_scheme() == Some(LocalBackend::SCHEME);
+        let scope = if login { ApiTokenScope::Session } else { scope };
+
+        let (token, plaintext) = ApiToken::issue(user_id, name, scope, now, expires_at);
+        self.repository.put_api_token(token.clone()).await?;
+        if login {
+            self.revoke_stale_sessions(user_id, now).await?;
+        }
+
+        Ok(Output {
+            output: CreateApiTokenOutput { token, plaintext },
+        })
+    }
+}
+
+impl CreateApiToken {
+    /// Revoke the expired sessions and the oldest ones over the limit so that sessions do not pile up on every login
+    async fn revoke_stale_sessions(&self, user_id: &str, now: Time) -> Result<(), RepositoryError> {
+        let tokens = self.repository.fetch_api_tokens(user_id).await?;
+        // Latest session first
+        let sessions = tokens
+            .iter()
+            .rev()
+            .filter(|token| token.scope == ApiTokenScope::Session);
+
+        for (i, session) in sessions.enumerate() {
+            let expired = session
+                .expires_at
+                .is_some_and(|expires_at| expires_at <= now);
+            if expired || i >= config::auth::MAX_SESSIONS_PER_USER {
+                self.repository
+                    .delete_api_token(user_id, &session.id)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct FetchApiTokens {
+    pub repository: Arc<dyn ApiTokenRepository>,
+}
+
+pub struct FetchApiTokensInput {}
+
+pub struct FetchApiTokensOutput {
+    pub tokens: Vec<ApiToken>,
+}
+
+#[derive(Error, Debug)]
+pub enum FetchApiTokensError {}
+
+impl Usecase for FetchApiTokens {
+    type Input = FetchApiTokensInput;
+
+    type Output = FetchApiTokensOutput;
+
+    type Error = FetchApiTokensError;
+
+    const READ_ONLY: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.api_token_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "fetch_api_tokens", skip_all)]
+    async fn usecase(
+        &self,
+        Input { principal, .. }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        let tokens = self.repository.fetch_api_tokens(user_id).await?;
+
+        Ok(Output {
+            output: FetchApiTokensOutput { tokens },
+        })
+    }
+}
+
+pub struct RevokeApiToken {
+    pub repository: Arc<dyn ApiTokenRepository>,
+}
+
+pub struct RevokeApiTokenInput {
+    pub id: String,
+}
+
+pub struct RevokeApiTokenOutput {
+    pub id: String,
+}
+
+impl Usecase for RevokeApiToken {
+    type Input = RevokeApiTokenInput;
+
+    type Output = RevokeApiTokenOutput;
+
+    type Error = ApiTokenError;
+
+    const API_TOKEN: bool = false;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.api_token_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "revoke_api_token", skip_all)]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: RevokeApiTokenInput { id },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        if !self.repository.delete_api_token(user_id, &id).await? {
+            return Err(Error::Usecase(ApiTokenError::NotFound));
+        }
+
+        Ok(Output {
+            output: RevokeApiTokenOutput { id },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use crate::{
+        fixture::{input, principal},
+        repository::memory::MemoryRepository,
+        serve::auth::{ApiTokenBackend, GoogleLoginBackend},
+        usecase::authorize::{Authorized, Authorizer},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn create_then_revoke() {
+        let repository = Arc::new(MemoryRepository::new());
+        let create = CreateApiToken {
+            repository: repository.clone(),
+        };
+
+        let CreateApiTokenOutput { token, plaintext } = create
+            .usecase(input(CreateApiTokenInput {
+                name: " ci ".into(),
+                scope: ApiTokenScope::ReadOnly,
+                expires_at: Some(chrono::Utc::now() + Duration::try_days(30).unwrap()),
+            }))
+            .await
+            .unwrap()
+            .output;
+        assert_eq!(token.name, "ci");
+        assert_eq!(token.user_id, "alice");
+        assert!(plaintext.starts_with(&format!("alice.{}.", token.id)));
+
+        for (name, expires_at) in [
+            ("  ", None),
+            (
+                "expired",
+                Some(chrono::Utc::now() - Duration::try_days(1).unwrap()),
+            ),
+        ] {
+            assert!(matches!(
+                create
+                    .usecase(input(CreateApiTokenInput {
+                        name: name.into(),
+                        scope: ApiTokenScope::ReadWrite,
+                        expires_at,
+                    }))
+                    .await,
+                Err(Error::Usecase(
+                    ApiTokenError::EmptyName | ApiTokenError::AlreadyExpired
+                ))
+            ));
+        }
+
+        let fetch = FetchApiTokens {
+            repository: repository.clone(),
+        };
+        let tokens = fetch
+            .usecase(input(FetchApiTokensInput {}))
+            .await
+            .unwrap()
+            .output
+            .tokens;
+        assert_eq!(tokens, vec![token.clone()]);
+
+        let revoke = RevokeApiToken { repository };
+        revoke
+            .usecase(input(RevokeApiTokenInput {
+                id: token.id.clone(),
+            }))
+            .await
+            .unwrap();
+        assert!(matches!(
+            revoke
+                .usecase(input(RevokeApiTokenInput { id: token.id }))
+                .await,
+            Err(Error::Usecase(ApiTokenError::NotFound))
+        ));
+        assert!(fetch
+            .usecase(input(FetchApiTokensInput {}))
+            .await
+            .unwrap()
+            .output
+            .tokens
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn local_login_issues_session() {
+        let repository = Arc::new(MemoryRepository::new());
+        let create = CreateApiToken {
+            repository: repository.clone(),
+        };
+        let login = |name: &str| Input {
+            principal: Authorized::unchecked(principal().with_auth_scheme(LocalBackend::SCHEME)),
+            input: CreateApiTokenInput {
+                name: name.into(),
+                scope: ApiTokenScope::ReadWrite,
+                expires_at: Some(chrono::Utc::now() + Duration::try_days(30).unwrap()),
+            },
+        };
+
+        let (expired, _) = ApiToken::issue(
+            "alice",
+            "expired",
+            ApiTokenScope::Session,
+            chrono::Utc::now() - Duration::try_days(31).unwrap(),
+            Some(chrono::Utc::now() - Duration::try_days(1).unwrap()),
+        );
+        repository.put_api_token(expired).await.unwrap();
+        let (ci, _) = ApiToken::issue(
+            "alice",
+            "ci",
+            ApiTokenScope::ReadWrite,
+            chrono::Utc::now(),
+            None,
+        );
+        repository.put_api_token(ci.clone()).await.unwrap();
+
+        let mut sessions = Vec::new();
+        for i in 0..=config::auth::MAX_SESSIONS_PER_USER {
+            let token = create
+                .usecase(login(&format!("login {i}")))
+                .await
+                .unwrap()
+                .output
+                .token;
+            assert_eq!(token.scope, ApiTokenScope::Session);
+            sessions.push(token);
+        }
+
+        // The expired and the oldest sessions are revoked, personal access tokens are kept
+        let mut expected = vec![ci];
+        expected.extend(sessions.into_iter().skip(1));
+        assert_eq!(
+            repository.fetch_api_tokens("alice").await.unwrap(),
+            expected
+        );
+    }
+
+    // A leaked token must not be able to issue tokens which outlive it
+    #[tokio::test]
+    async fn api_token_cannot_manage_api_tokens() {
+        let repository = Arc::new(MemoryRepository::new());
+        let authorizer = Authorizer::new();
+        let create = CreateApiToken {
+            repository: repository.clone(),
+        };
+        let revoke = RevokeApiToken {
+            repository: repository.clone(),
+        };
+        let fetch = FetchApiTokens { repository };
+
+        for (scheme, allowed) in [
+            ("github", true),
+            (ApiTokenBackend::SCHEME, false),
+            (GoogleLoginBackend::SCHEME, false),
+        ] {
+            let principal = principal().with_auth_scheme(scheme);
+            let created = authorizer
+                .authorize(
+                    principal.clone(),
+                    &create,
+                    &CreateApiTokenInput {
+                        name: "ci".into(),
+                        scope: ApiTokenScope::ReadWrite,
+                        expires_at: None,
+                    },
+                )
+                .await;
+            assert_eq!(created.is_ok(), allowed, "{scheme}");
+            let revoked = authorizer
+                .authorize(
+                    principal.clone(),
+                    &revoke,
+                    &RevokeApiTokenInput { id: "1".into() },
+                )
+                .await;
+            assert_eq!(revoked.is_ok(), allowed, "{scheme}");
+            assert!(authorizer
+                .authorize(principal, &fetch, &FetchApiTokensInput {})
+                .await
+                .is_ok());
+        }
+    }
+}
@@ -12,6 +12,12 @@ impl Authorized<Principal> {
     fn new(principal: Principal) -> Self {
         Self { principal }
     }
+
+    /// Skip authorization to test usecases directly
+    #[cfg(test)]
+    pub(crate) fn unchecked(principal: Principal) -> Self {
+        Self::new(principal)
+    }
 }
 
 impl<T> Deref for Authorized<T> {
@@ -22,8 +28,28 @@ impl<T> Deref for Authorized<T> {
     }
 }
 
-#[derive(Debug)]
-pub struct Unauthorized;
+#[derive(Debug, thiserror::Error)]
+pub enum Unauthorized {
+    /// Principal does not have enough permissions
+    #[error("unauthorized error")]
+    Forbidden,
+    /// Principal reached the quota and cannot create more resources
+    #[error(transparent)]
+    QuotaExceeded(QuotaExceeded),
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("quota exceeded: {resource} is limited to {limit} per user")]
+pub struct QuotaExceeded {
+    pub resource: &'static str,
+    pub limit: usize,
+}
+
+/// Limits of the resources which each user can create
+#[derive(Debug, Clone, Copy)]
+pub struct Quota {
+    pub max_subscriptions: usize,
+}
 
 pub struct Authorizer {}
 
@@ -38,6 +64,16 @@ impl Authorizer {
         usecase: &U,
         input: &U::Input,
     ) -> Result<Authorized<Principal>, Unauthorized> {
+        if principal.is_read_only() && !U::READ_ONLY {
+            return Err(Unauthorized::Forbidden);
+        }
+        if U::ADMIN && !principal.is_admin() {
+            return Err(Unauthorized::Forbidden);
+        }
+        if !U::API_TOKEN && principal.is_api_token() {
+            return Err(Unauthorized::Forbidden);
+        }
+
         usecase
             .authorize(principal, input)
             .await
@@ -1,40 +1,129 @@
-use std::{cmp::Ordering, collections::HashMap, sync::Arc};
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
 
 use futures_util::{stream::FuturesUnordered, StreamExt};
+use serde::{Deserialize, Serialize};
 use synd_feed::{
     feed::{cache::FetchCachedFeed, service::FetchFeedError},
-    types::{self, Annotated, Entry, EntryId, FeedMeta, FeedUrl},
+    types::{self, Annotated, Entry, EntryId, FeedMeta, FeedUrl, Time},
 };
 use thiserror::Error;
 
 use crate::{
     principal::Principal,
     repository::{
-        types::{FeedAnnotations, SubscribedFeeds},
-        SubscriptionRepository,
+        types::{FeedAnnotations, ReadStates, SubscribedFeeds},
+        ReadStateRepository, SubscriptionRepository,
+    },
+    usecase::{
+        authorize::Unauthorized, Error, Input, MakeUsecase, Output, SubscriptionFilter, Usecase,
     },
-    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
 };
 
 pub struct FetchEntries {
     pub repository: Arc<dyn SubscriptionRepository>,
+    pub read_state_repository: Arc<dyn ReadStateRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
 }
 
 pub struct FetchEntriesInput {
-    pub after: Option<EntryId<'static>>,
-    pub first: usize,
+    /// Return entries after the cursor
+    pub after: Option<EntryCursor>,
+    /// Return entries before the cursor
+    pub before: Option<EntryCursor>,
+    /// Return the first n entries
+    pub first: Option<usize>,
+    /// Return the last n entries. applied after `first`
+    pub last: Option<usize>,
+    pub filter: SubscriptionFilter,
+    /// Entries which are already read are excluded
+    pub unread_only: bool,
 }
 
 #[derive(Default)]
 pub struct FetchEntriesOutput {
     pub entries: Vec<(types::Entry, types::FeedUrl)>,
     pub feeds: HashMap<types::FeedUrl, Annotated<types::FeedMeta>>,
+    pub read_states: ReadStates,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
 }
 
 #[derive(Error, Debug)]
 pub enum FetchEntriesError {}
 
+/// Position of the entry in the entries ordered by published time.
+/// Entries are ordered by the key which does not depend on other entries,
+/// so the position is stable even if entries are evicted or new entries arrive
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryCursor {
+    pub published: Option<Time>,
+    pub feed_url: FeedUrl,
+    pub entry_id: String,
+}
+
+#[derive(Error, Debug)]
+#[error("invalid cursor")]
+pub struct InvalidCursor;
+
+/// Newer entries first, entries without time last. ties are broken by feed url and entry id
+type SortKey<'a> = (bool, Reverse<Option<Time>>, &'a str, EntryId<'a>);
+
+impl EntryCursor {
+    pub fn new(entry: &Entry, feed_url: &FeedUrl) -> Self {
+        Self {
+            published: Self::published(entry),
+            feed_url: feed_url.clone(),
+            entry_id: entry.id().to_string(),
+        }
+    }
+
+    /// Encode to opaque string
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).expect("serialize cursor"))
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, InvalidCursor> {
+        let bytes = hex::decode(cursor).map_err(|_| InvalidCursor)?;
+        serde_json::from_slice(&bytes).map_err(|_| InvalidCursor)
+    }
+
+    fn key(&self) -> SortKey<'_> {
+        (
+            self.published.is_none(),
+            Reverse(self.published),
+            self.feed_url.as_str(),
+            EntryId::from(self.entry_id.as_str()),
+        )
+    }
+
+    fn entry_key<'a>(entry: &'a Entry, feed_url: &'a FeedUrl) -> SortKey<'a> {
+        let published = Self::published(entry);
+        (
+            published.is_none(),
+            Reverse(published),
+            feed_url.as_str(),
+            entry.id_ref(),
+        )
+    }
+
+    fn published(entry: &Entry) -> Option<Time> {
+        entry.published().or(entry.updated())
+    }
+}
+
+/// Ordered in the same order as the entries
+impl Ord for EntryCursor {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for EntryCursor {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Usecase for FetchEntries {
     type Input = FetchEntriesInput;
 
@@ -42,9 +131,12 @@ impl Usecase for FetchEntries {
 
     type Error = FetchEntriesError;
 
+    const READ_ONLY: bool = true;
+
     fn new(make: &MakeUsecase) -> Self {
         Self {
             repository: make.subscription_repo.clone(),
+            read_state_repository: make.read_state_repo.clone(),
             fetch_feed: make.fetch_feed.clone(),
         }
     }
@@ -62,22 +154,37 @@ impl Usecase for FetchEntries {
         &self,
         Input {
             principal,
-            input: FetchEntriesInput { after, first },
+            input:
+                FetchEntriesInput {
+                    after,
+                    before,
+                    first,
+                    last,
+                    filter,
+                    unread_only,
+                },
         }: Input<Self::Input>,
     ) -> Result<Output<Self::Output>, Error<Self::Error>> {
         let user_id = principal
             .user_id()
             .expect("user id not found. this is a bug");
 
-        let SubscribedFeeds { urls, annotations } =
-            self.repository.fetch_subscribed_feeds(user_id).await?;
+        let SubscribedFeeds {
+            urls, annotations, ..
+        } = self.repository.fetch_subscribed_feeds(user_id).await?;
+        let read_states = self
+            .read_state_repository
+            .fetch_read_states(user_id)
+            .await?;
 
         let output = self
-            .operation(urls, annotations)
+            .operation(urls, annotations, read_states)
+            .select(&filter)
             .fetch()
             .await
             .sort()
-            .paginate(first, after);
+            .filter(&filter, unread_only)
+            .paginate(after.as_ref(), before.as_ref(), first, last);
 
         Ok(output)
     }
@@ -88,6 +195,7 @@ impl FetchEntries {
         &self,
         urls: Vec<FeedUrl>,
         annotations: Option<HashMap<FeedUrl, FeedAnnotations>>,
+        read_states: ReadStates,
     ) -> FetchOperation {
         let len = urls.len();
         FetchOperation {
@@ -95,6 +203,7 @@ impl FetchEntries {
             metas: HashMap::with_capacity(len),
             entries: Vec::with_capacity(len * 5),
             annotations,
+            read_states,
             fetch_feed: self.fetch_feed.clone(),
         }
     }
@@ -105,6 +214,8 @@ struct FetchOperation {
     urls: Option<Vec<FeedUrl>>,
     // feed annotations got from repository
     annotations: Option<HashMap<FeedUrl, FeedAnnotations>>,
+    // read states got from repository
+    read_states: ReadStates,
     // fetch service
     fetch_feed: Arc<dyn FetchCachedFeed>,
 
@@ -114,6 +225,20 @@ struct FetchOperation {
 }
 
 impl FetchOperation {
+    // exclude feeds which do not satisfy the filter before fetching
+    fn select(mut self, filter: &SubscriptionFilter) -> Self {
+        let annotations = self.annotations.as_ref();
+        self.urls.as_mut().unwrap().retain(|url| {
+            let annotations = annotations.and_then(|annotations| annotations.get(url));
+            filter.subscription(
+                url,
+                annotations.and_then(|a| a.requirement),
+                annotations.and_then(|a| a.category.as_ref()),
+            )
+        });
+        self
+    }
+
     // fetch given urls respecting concurrency limit
     async fn fetch(mut self) -> Self {
         let mut tasks = FuturesUnordered::new();
@@ -148,18 +273,12 @@ impl FetchOperation {
 
         let meta = feed.meta().clone();
         let feed_url = meta.url().to_owned();
-        let meta = match self
+        let meta = self
             .annotations
             .as_mut()
             .and_then(|annotations| annotations.remove(&feed_url))
-        {
-            Some(feed_annotations) => Annotated {
-                feed: meta,
-                requirement: feed_annotations.requirement,
-                category: feed_annotations.category,
-            },
-            None => Annotated::new(meta),
-        };
+            .unwrap_or_default()
+            .annotate(meta);
         self.metas.insert(feed_url.clone(), meta);
         self.entries.extend(
             feed.entries()
@@ -170,13 +289,25 @@ impl FetchOperation {
 
     // sort entries
     fn sort(mut self) -> Self {
-        self.entries.sort_unstable_by(|(a, _), (b, _)| {
-            match (a.published().or(a.updated()), b.published().or(b.updated())) {
-                (Some(a), Some(b)) => b.cmp(&a),
-                (None, Some(_)) => Ordering::Greater,
-                (Some(_), None) => Ordering::Less,
-                (None, None) => std::cmp::Ordering::Equal,
+        self.entries.sort_unstable_by(|(a, a_url), (b, b_url)| {
+            EntryCursor::entry_key(a, a_url).cmp(&EntryCursor::entry_key(b, b_url))
+        });
+        self
+    }
+
+    // retain entries which satisfy given conditions
+    fn filter(mut self, filter: &SubscriptionFilter, unread_only: bool) -> Self {
+        if !filter.requires_content() && !unread_only {
+            return self;
+        }
+        let metas = &self.metas;
+        let read_states = &self.read_states;
+        self.entries.retain(|(entry, feed_url)| {
+            if unread_only && read_states.is_read(feed_url, entry) {
+                return false;
             }
+            let feed_title = metas.get(feed_url).and_then(|meta| meta.feed.title());
+            filter.entry(entry, feed_title)
         });
         self
     }
@@ -184,31 +315,185 @@ impl FetchOperation {
     // paginate entries and return output
     fn paginate(
         mut self,
-        first: usize,
-        after: Option<EntryId<'static>>,
+        after: Option<&EntryCursor>,
+        before: Option<&EntryCursor>,
+        first: Option<usize>,
+        last: Option<usize>,
     ) -> Output<FetchEntriesOutput> {
-        let start = after
-            .and_then(|after| {
-                self.entries
-                    .iter()
-                    .position(|(entry, _)| entry.id_ref() == after)
-                    .map(|position| position + 1)
-            })
-            .unwrap_or(0);
+        let len = self.entries.len();
+        let start = after.map_or(0, |after| {
+            let after = after.key();
+            self.entries
+                .partition_point(|(entry, url)| EntryCursor::entry_key(entry, url) <= after)
+        });
+        let end = before.map_or(len, |before| {
+            let before = before.key();
+            self.entries
+                .partition_point(|(entry, url)| EntryCursor::entry_key(entry, url) < before)
+        });
+
+        let mut has_previous_page = start > 0;
+        let mut has_next_page = end < len;
+        self.entries.truncate(end);
+        let mut entries = self.entries.split_off(start.min(end));
 
-        if start >= self.entries.len() {
-            return Output {
-                output: FetchEntriesOutput::default(),
-            };
+        if let Some(first) = first {
+            if entries.len() > first {
+                entries.truncate(first);
+                has_next_page = true;
+            }
+        }
+        if let Some(last) = last {
+            if entries.len() > last {
+                entries.drain(..entries.len() - last);
+                has_previous_page = true;
+            }
         }
-        let mut entries = self.entries.split_off(start);
-        entries.truncate(first);
 
         Output {
             output: FetchEntriesOutput {
                 entries,
                 feeds: self.metas,
+                read_states: self.read_states,
+                has_previous_page,
+                has_next_page,
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use futures_util::FutureExt;
+
+    use crate::{
+        fixture::{feed, StaticFeeds},
+        repository::types::EntryReference,
+    };
+
+    use super::*;
+
+    fn url() -> FeedUrl {
+        FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap()
+    }
+
+    fn static_feed(entries: &[(&str, u32)]) -> Arc<StaticFeeds> {
+        let fetch_feed = Arc::new(StaticFeeds::default());
+        fetch_feed.insert(feed(&url(), entries));
+        fetch_feed
+    }
+
+    async fn paginate(
+        fetch_feed: &Arc<StaticFeeds>,
+        after: Option<&EntryCursor>,
+        before: Option<&EntryCursor>,
+        first: Option<usize>,
+        last: Option<usize>,
+    ) -> FetchEntriesOutput {
+        FetchOperation {
+            urls: Some(vec![url()]),
+            annotations: None,
+            read_states: ReadStates::default(),
+            fetch_feed: fetch_feed.clone(),
+            metas: HashMap::new(),
+            entries: Vec::new(),
+        }
+        .fetch()
+        .await
+        .sort()
+        .paginate(after, before, first, last)
+        .output
+    }
+
+    fn ids(output: &FetchEntriesOutput) -> Vec<String> {
+        output
+            .entries
+            .iter()
+            .map(|(entry, _)| entry.id().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn paginate_forward_and_backward() {
+        let fetch_feed = static_feed(&[("a", 5), ("b", 4), ("c", 3), ("d", 2), ("e", 1)]);
+
+        let page = paginate(&fetch_feed, None, None, Some(2), None).await;
+        assert_eq!(ids(&page), vec!["a", "b"]);
+        assert!(!page.has_previous_page);
+        assert!(page.has_next_page);
+
+        let (entry, feed_url) = page.entries.last().unwrap();
+        let cursor = EntryCursor::decode(&EntryCursor::new(entry, feed_url).encode()).unwrap();
+        let page = paginate(&fetch_feed, Some(&cursor), None, Some(2), None).await;
+        assert_eq!(ids(&page), vec!["c", "d"]);
+        assert!(page.has_previous_page);
+        assert!(page.has_next_page);
+
+        let (entry, feed_url) = page.entries.first().unwrap();
+        let cursor = EntryCursor::new(entry, feed_url);
+        let page = paginate(&fetch_feed, None, Some(&cursor), None, Some(1)).await;
+        assert_eq!(ids(&page), vec!["b"]);
+        assert!(page.has_previous_page);
+        assert!(page.has_next_page);
+
+        let page = paginate(&fetch_feed, None, None, None, Some(2)).await;
+        assert_eq!(ids(&page), vec!["d", "e"]);
+        assert!(page.has_previous_page);
+        assert!(!page.has_next_page);
+    }
+
+    #[tokio::test]
+    async fn cursor_is_stable_across_refresh() {
+        let fetch_feed = static_feed(&[("a", 5), ("b", 4), ("c", 3), ("d", 2)]);
+
+        let page = paginate(&fetch_feed, None, None, Some(2), None).await;
+        let (entry, feed_url) = page.entries.last().unwrap();
+        let cursor = EntryCursor::new(entry, feed_url);
+
+        // New entry arrived and the cursor entry was evicted
+        fetch_feed.insert(feed(&url(), &[("new", 6), ("a", 5), ("c", 3), ("d", 2)]));
+
+        let page = paginate(&fetch_feed, Some(&cursor), None, Some(2), None).await;
+        assert_eq!(ids(&page), vec!["c", "d"]);
+    }
+
+    #[tokio::test]
+    async fn exclude_read_entries_if_unread_only() {
+        let fetch_feed = static_feed(&[("a", 3), ("b", 2), ("c", 1)]);
+        let mut read_states = ReadStates::default();
+        read_states.mark_entries(
+            vec![EntryReference {
+                feed_url: url(),
+                entry_id: "b".into(),
+            }],
+            true,
+        );
+        let fetch = |unread_only| {
+            FetchOperation {
+                urls: Some(vec![url()]),
+                annotations: None,
+                read_states: read_states.clone(),
+                fetch_feed: fetch_feed.clone(),
+                metas: HashMap::new(),
+                entries: Vec::new(),
+            }
+            .fetch()
+            .map(move |operation| {
+                operation
+                    .sort()
+                    .filter(&SubscriptionFilter::default(), unread_only)
+                    .paginate(None, None, None, None)
+                    .output
+            })
+        };
+
+        assert_eq!(ids(&fetch(false).await), vec!["a", "b", "c"]);
+        assert_eq!(ids(&fetch(true).await), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn invalid_cursor() {
+        assert!(EntryCursor::decode("xxx").is_err());
+        assert!(EntryCursor::decode(&hex::encode("{}")).is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use std::{cmp::Reverse, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use synd_feed::types::{FeedUrl, Time};
+use thiserror::Error;
+
+use crate::{
+    principal::Principal,
+    repository::{types::StarredEntry, StarredEntryRepository},
+    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+};
+
+pub struct FetchStarredEntries {
+    pub repository: Arc<dyn StarredEntryRepository>,
+}
+
+pub struct FetchStarredEntriesInput {
+    /// Return entries after the cursor
+    pub after: Option<StarredEntryCursor>,
+    pub first: usize,
+}
+
+#[derive(Default)]
+pub struct FetchStarredEntriesOutput {
+    pub entries: Vec<StarredEntry>,
+}
+
+#[derive(Error, Debug)]
+pub enum FetchStarredEntriesError {}
+
+/// Position of the starred entry in the entries ordered by the time they were starred.
+/// The position is stable even if the cursor entry is unstarred
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StarredEntryCursor {
+    pub starred_at: Time,
+    pub feed_url: FeedUrl,
+    pub entry_id: String,
+}
+
+#[derive(Error, Debug)]
+#[error("invalid cursor")]
+pub struct InvalidStarredEntryCursor;
+
+/// Recently starred entries first. ties are broken by feed url and entry id
+type SortKey<'a> = (Reverse<Time>, &'a str, &'a str);
+
+impl StarredEntryCursor {
+    pub fn new(entry: &StarredEntry) -> Self {
+        Self {
+            starred_at: entry.starred_at,
+            feed_url: entry.feed_url.clone(),
+            entry_id: entry.entry_id.clone(),
+        }
+    }
+
+    /// Encode to opaque string
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).expect("serialize cursor"))
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, InvalidStarredEntryCursor> {
+        let bytes = hex::decode(cursor).map_err(|_| InvalidStarredEntryCursor)?;
+        serde_json::from_slice(&bytes).map_err(|_| InvalidStarredEntryCursor)
+    }
+
+    fn key(&self) -> SortKey<'_> {
+        (
+            Reverse(self.starred_at),
+            self.feed_url.as_str(),
+            &self.entry_id,
+        )
+    }
+
+    fn entry_key(entry: &StarredEntry) -> SortKey<'_> {
+        (
+            Reverse(entry.starred_at),
+            entry.feed_url.as_str(),
+            &entry.entry_id,
+        )
+    }
+}
+
+impl Usecase for FetchStarredEntries {
+    type Input = FetchStarredEntriesInput;
+
+    type Output = FetchStarredEntriesOutput;
+
+    type Error = FetchStarredEntriesError;
+
+    const READ_ONLY: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.starred_entry_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "fetch_starred_entries", skip(self, principal))]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: FetchStarredEntriesInput { after, first },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        let mut entries = self.repository.fetch_starred_entries(user_id).await?;
+        entries.sort_by(|a, b| {
+            StarredEntryCursor::entry_key(a).cmp(&StarredEntryCursor::entry_key(b))
+        });
+
+        let start = after.map_or(0, |after| {
+            let after = after.key();
+            entries.partition_point(|entry| StarredEntryCursor::entry_key(entry) <= after)
+        });
+        let mut entries = entries.split_off(start.min(entries.len()));
+        entries.truncate(first);
+
+        Ok(Output {
+            output: FetchStarredEntriesOutput { entries },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        fixture::input,
+        repository::{memory::MemoryRepository, types::EntryReference},
+    };
+
+    use super::*;
+
+    fn starred(feed_url: &str, entry_id: &str, day: u32) -> StarredEntry {
+        StarredEntry {
+            feed_url: FeedUrl::try_from(feed_url).unwrap(),
+            entry_id: entry_id.into(),
+            title: None,
+            website_url: None,
+            summary: None,
+            published: None,
+            updated: None,
+            feed_title: None,
+            requirement: None,
+            category: None,
+            starred_at: Utc.with_ymd_and_hms(2024, 6, day, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn paginate_with_cursor() {
+        let repository = Arc::new(MemoryRepository::new());
+        // Same entry id in different feeds
+        for entry in [
+            starred("https://a.ymgyt.io/feed", "1", 1),
+            starred("https://b.ymgyt.io/feed", "1", 2),
+            starred("https://a.ymgyt.io/feed", "2", 3),
+            starred("https://b.ymgyt.io/feed", "2", 4),
+        ] {
+            repository.star_entry("alice", entry).await.unwrap();
+        }
+        let usecase = FetchStarredEntries {
+            repository: repository.clone(),
+        };
+        let fetch = |after: Option<StarredEntryCursor>| {
+            let usecase = &usecase;
+            async move {
+                usecase
+                    .usecase(input(FetchStarredEntriesInput { after, first: 2 }))
+                    .await
+                    .unwrap()
+                    .output
+                    .entries
+            }
+        };
+        let keys = |entries: &[StarredEntry]| {
+            entries
+                .iter()
+                .map(|entry| format!("{}#{}", entry.feed_url.as_str(), entry.entry_id))
+                .collect::<Vec<_>>()
+        };
+
+        let page = fetch(None).await;
+        assert_eq!(
+            keys(&page),
+            vec!["https://b.ymgyt.io/feed#2", "https://a.ymgyt.io/feed#2"]
+        );
+        let cursor =
+            StarredEntryCursor::decode(&StarredEntryCursor::new(page.last().unwrap()).encode())
+                .unwrap();
+
+        // The cursor entry is unstarred
+        repository
+            .unstar_entry(
+                "alice",
+                EntryReference {
+                    feed_url: cursor.feed_url.clone(),
+                    entry_id: cursor.entry_id.clone(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let page = fetch(Some(cursor)).await;
+        assert_eq!(
+            keys(&page),
+            vec!["https://b.ymgyt.io/feed#1", "https://a.ymgyt.io/feed#1"]
+        );
+        let cursor = StarredEntryCursor::new(page.last().unwrap());
+        assert!(fetch(Some(cursor)).await.is_empty());
+    }
+
+    #[test]
+    fn invalid_cursor() {
+        assert!(StarredEntryCursor::decode("xxx").is_err());
+        assert!(StarredEntryCursor::decode(&hex::encode("{}")).is_err());
+    }
+}
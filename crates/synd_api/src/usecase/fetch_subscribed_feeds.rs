@@ -1,31 +1,42 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use synd_feed::{
-    feed::{cache::FetchCachedFeed, service::FetchFeedError},
+    feed::{cache::FetchCachedFeed, health::FeedHealth, service::FetchFeedError},
     types::{self, Annotated, FeedUrl},
 };
 use thiserror::Error;
 
 use crate::{
     principal::Principal,
-    repository::{types::SubscribedFeeds, SubscriptionRepository},
-    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+    repository::{
+        types::{Folder, FolderId, ReadStates, SubscribedFeeds},
+        ReadStateRepository, SubscriptionRepository,
+    },
+    usecase::{
+        authorize::Unauthorized, Error, Input, MakeUsecase, Output, SubscriptionFilter, Usecase,
+    },
 };
 
 pub struct FetchSubscribedFeeds {
     pub repository: Arc<dyn SubscriptionRepository>,
+    pub read_state_repository: Arc<dyn ReadStateRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
 }
 
 pub struct FetchSubscribedFeedsInput {
     pub after: Option<String>,
     pub first: usize,
+    pub filter: SubscriptionFilter,
 }
 
 #[derive(Default)]
 pub struct FetchSubscribedFeedsOutput {
     #[allow(clippy::type_complexity)]
     pub feeds: Vec<Result<Annotated<Arc<types::Feed>>, (FeedUrl, FetchFeedError)>>,
+    pub read_states: ReadStates,
+    pub folders: Vec<Folder>,
+    pub feed_folders: HashMap<FeedUrl, FolderId>,
+    pub healths: HashMap<FeedUrl, FeedHealth>,
 }
 
 #[derive(Error, Debug)]
@@ -38,9 +49,12 @@ impl Usecase for FetchSubscribedFeeds {
 
     type Error = FetchSubscribedFeedsError;
 
+    const READ_ONLY: bool = true;
+
     fn new(make: &MakeUsecase) -> Self {
         Self {
             repository: make.subscription_repo.clone(),
+            read_state_repository: make.read_state_repo.clone(),
             fetch_feed: make.fetch_feed.clone(),
         }
     }
@@ -57,7 +71,12 @@ impl Usecase for FetchSubscribedFeeds {
         &self,
         Input {
             principal,
-            input: FetchSubscribedFeedsInput { after, first },
+            input:
+                FetchSubscribedFeedsInput {
+                    after,
+                    first,
+                    filter,
+                },
         }: Input<Self::Input>,
     ) -> Result<Output<Self::Output>, Error<Self::Error>> {
         let user_id = principal.user_id().unwrap();
@@ -65,6 +84,8 @@ impl Usecase for FetchSubscribedFeeds {
         let SubscribedFeeds {
             mut urls,
             mut annotations,
+            folders,
+            feed_folders,
         } = self.repository.fetch_subscribed_feeds(user_id).await?;
 
         // paginate
@@ -78,46 +99,133 @@ impl Usecase for FetchSubscribedFeeds {
                 .unwrap_or(0);
             if start >= urls.len() {
                 return Ok(Output {
-                    output: FetchSubscribedFeedsOutput::default(),
+                    output: FetchSubscribedFeedsOutput {
+                        folders,
+                        feed_folders,
+                        ..Default::default()
+                    },
                 });
             }
             let mut urls = urls.split_off(start);
-            urls.truncate(first);
+            urls.retain(|url| {
+                let annotations = annotations
+                    .as_ref()
+                    .and_then(|annotations| annotations.get(url));
+                filter.subscription(
+                    url,
+                    annotations.and_then(|a| a.requirement),
+                    annotations.and_then(|a| a.category.as_ref()),
+                )
+            });
             urls
         };
 
-        // fetch feeds
-        let fetched_feeds = self.fetch_feed.fetch_feeds_parallel(&urls).await;
-
-        // annotate fetched feeds
-        let feeds = fetched_feeds
-            .into_iter()
-            .zip(urls)
-            .map(|(result, url)| {
-                result
-                    .map(|feed| {
-                        match annotations
-                            .as_mut()
-                            .and_then(|annotations| annotations.remove(feed.meta().url()))
-                        {
-                            Some(annotations) => Annotated {
-                                feed,
-                                requirement: annotations.requirement,
-                                category: annotations.category,
-                            },
-                            None => Annotated {
-                                feed,
-                                requirement: None,
-                                category: None,
-                            },
-                        }
-                    })
-                    .map_err(|err| (url.clone(), err))
-            })
-            .collect::<Vec<_>>();
+        let read_states = self
+            .read_state_repository
+            .fetch_read_states(user_id)
+            .await?;
+
+        // Whether feeds match the filter is unknown until fetched,
+        // so feeds are fetched by page size until the page is filled
+        let mut feeds = Vec::with_capacity(first.min(urls.len()));
+        let mut healths = HashMap::new();
+        for urls in urls.chunks(first.max(1)) {
+            let fetched_feeds = self.fetch_feed.fetch_feeds_parallel(urls).await;
+            healths.extend(
+                urls.iter()
+                    .filter_map(|url| Some((url.clone(), self.fetch_feed.health(url)?))),
+            );
+
+            // annotate fetched feeds
+            feeds.extend(
+                fetched_feeds
+                    .into_iter()
+                    .zip(urls)
+                    // feeds failed to fetch are kept to report errors
+                    .filter(|(result, _)| result.as_ref().map_or(true, |feed| filter.feed(feed)))
+                    .map(|(result, url)| {
+                        result
+                            .map(|feed| {
+                                annotations
+                                    .as_mut()
+                                    .and_then(|annotations| annotations.remove(feed.meta().url()))
+                                    .unwrap_or_default()
+                                    .annotate(feed)
+                            })
+                            .map_err(|err| (url.clone(), err))
+                    }),
+            );
+            if feeds.len() >= first {
+                break;
+            }
+        }
+        feeds.truncate(first);
 
         Ok(Output {
-            output: FetchSubscribedFeedsOutput { feeds },
+            output: FetchSubscribedFeedsOutput {
+                feeds,
+                read_states,
+                folders,
+                feed_folders,
+                healths,
+            },
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{
+        fixture::{feed, input, subscription, url, StaticFeeds},
+        repository::memory::MemoryRepository,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fetch_first_feeds_matching_filter() {
+        let repository = Arc::new(MemoryRepository::new());
+        let fetch_feed = Arc::new(StaticFeeds::default());
+        // Only even feeds have entries published in the period
+        for (i, day) in [(1, 1), (2, 20), (3, 1), (4, 20), (5, 1), (6, 20)] {
+            let url = url(&format!("https://{i}.ymgyt.io/feed"));
+            repository
+                .put_feed_subscription(subscription("alice", &url))
+                .await
+                .unwrap();
+            fetch_feed.insert(feed(&url, &[("1", day)]));
+        }
+        let usecase = FetchSubscribedFeeds {
+            repository: repository.clone(),
+            read_state_repository: repository,
+            fetch_feed: fetch_feed.clone(),
+        };
+
+        let Output { output } = usecase
+            .usecase(input(FetchSubscribedFeedsInput {
+                after: None,
+                first: 2,
+                filter: SubscriptionFilter {
+                    since: Some(Utc.with_ymd_and_hms(2024, 6, 10, 0, 0, 0).unwrap()),
+                    ..Default::default()
+                },
+            }))
+            .await
+            .unwrap();
+
+        let urls = output
+            .feeds
+            .iter()
+            .map(|feed| feed.as_ref().unwrap().feed.meta().url().as_str())
+            .collect::<Vec<_>>();
+        // Subscriptions are listed from the latest
+        assert_eq!(
+            urls,
+            vec!["https://6.ymgyt.io/feed", "https://4.ymgyt.io/feed"]
+        );
+        // Feeds after the page are not fetched
+        assert_eq!(fetch_feed.fetched(), 4);
+    }
+}
@@ -0,0 +1,191 @@
+use synd_feed::types::{Category, Entry, Feed, FeedUrl, Requirement, Time};
+
+/// Conditions to narrow down subscribed feeds and their entries.
+/// Each condition is ignored if not specified
+#[derive(Debug, Default, Clone)]
+pub struct SubscriptionFilter {
+    /// Feeds which do not satisfy the requirement are excluded
+    pub requirement: Option<Requirement>,
+    /// Feeds which do not belong to any of the categories are excluded
+    pub categories: Option<Vec<Category<'static>>>,
+    /// Feeds which are not one of the urls are excluded
+    pub feed_urls: Option<Vec<FeedUrl>>,
+    /// Entries published before this time are excluded
+    pub since: Option<Time>,
+    /// Entries published after this time are excluded
+    pub until: Option<Time>,
+    /// Whitespace separated keywords. Titles which do not contain all of them are excluded.
+    /// Keywords are matched case insensitively
+    pub query: Option<String>,
+}
+
+impl SubscriptionFilter {
+    /// Requirement assumed for feeds which are not annotated
+    pub const DEFAULT_REQUIREMENT: Requirement = Requirement::Should;
+
+    /// Return true if the filter needs fetched feeds in addition to the subscription
+    pub fn requires_content(&self) -> bool {
+        self.since.is_some() || self.until.is_some() || self.keywords().next().is_some()
+    }
+
+    /// Judge the subscribed feed by the url and annotations
+    pub fn subscription(
+        &self,
+        url: &FeedUrl,
+        requirement: Option<Requirement>,
+        category: Option<&Category>,
+    ) -> bool {
+        if let Some(feed_urls) = &self.feed_urls {
+            if !feed_urls.contains(url) {
+                return false;
+            }
+        }
+        if let Some(required) = self.requirement {
+            if !requirement
+                .unwrap_or(Self::DEFAULT_REQUIREMENT)
+                .is_satisfied(required)
+            {
+                return false;
+            }
+        }
+        if let Some(categories) = &self.categories {
+            if !category.is_some_and(|category| categories.contains(category)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Judge the entry by the published time and title.
+    /// Entry matches the query if either the title of entry or the feed contains keywords
+    pub fn entry(&self, entry: &Entry, feed_title: Option<&str>) -> bool {
+        if !self.in_period(entry) {
+            return false;
+        }
+        self.matches(&[entry.title(), feed_title])
+    }
+
+    /// Judge the fetched feed.
+    /// If the period is specified, feeds which have no entries in the period are excluded
+    pub fn feed(&self, feed: &Feed) -> bool {
+        if (self.since.is_some() || self.until.is_some())
+            && !feed.entries().any(|entry| self.in_period(entry))
+        {
+            return false;
+        }
+        let meta = feed.meta();
+        self.matches(&[meta.title(), meta.website_url()])
+    }
+
+    fn in_period(&self, entry: &Entry) -> bool {
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        let Some(published) = entry.published().or(entry.updated()) else {
+            return false;
+        };
+        self.since.map_or(true, |since| since <= published)
+            && self.until.map_or(true, |until| published <= until)
+    }
+
+    fn matches(&self, haystacks: &[Option<&str>]) -> bool {
+        let haystacks = haystacks
+            .iter()
+            .flatten()
+            .map(|haystack| haystack.to_lowercase())
+            .collect::<Vec<_>>();
+        self.keywords().all(|keyword| {
+            let keyword = keyword.to_lowercase();
+            haystacks.iter().any(|haystack| haystack.contains(&keyword))
+        })
+    }
+
+    fn keywords(&self) -> impl Iterator<Item = &str> {
+        self.query.iter().flat_map(|query| query.split_whitespace())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn url(s: &str) -> FeedUrl {
+        FeedUrl::try_from(s).unwrap()
+    }
+
+    #[test]
+    fn subscription() {
+        let rust = Category::new("rust").unwrap();
+        let filter = SubscriptionFilter {
+            requirement: Some(Requirement::Should),
+            categories: Some(vec![rust.clone()]),
+            feed_urls: Some(vec![url("https://a.ymgyt.io/feed")]),
+            ..Default::default()
+        };
+
+        assert!(filter.subscription(
+            &url("https://a.ymgyt.io/feed"),
+            Some(Requirement::Must),
+            Some(&rust)
+        ));
+        assert!(!filter.subscription(
+            &url("https://b.ymgyt.io/feed"),
+            Some(Requirement::Must),
+            Some(&rust)
+        ));
+        assert!(!filter.subscription(
+            &url("https://a.ymgyt.io/feed"),
+            Some(Requirement::May),
+            Some(&rust)
+        ));
+        assert!(!filter.subscription(&url("https://a.ymgyt.io/feed"), None, None));
+        // Unannotated feed is assumed to be Should
+        assert!(SubscriptionFilter {
+            requirement: Some(Requirement::Should),
+            ..Default::default()
+        }
+        .subscription(&url("https://a.ymgyt.io/feed"), None, None));
+    }
+
+    #[test]
+    fn entry() {
+        let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Rust Blog</title>
+  <id>https://blog.rust-lang.org/</id>
+  <updated>2024-05-02T00:00:00Z</updated>
+  <entry>
+    <title>Announcing Rust 1.78.0</title>
+    <id>https://blog.rust-lang.org/2024/05/02/Rust-1.78.0.html</id>
+    <published>2024-05-02T00:00:00Z</published>
+    <updated>2024-05-02T00:00:00Z</updated>
+  </entry>
+</feed>"#;
+        let feed = Feed::from((
+            url("https://blog.rust-lang.org/feed.xml"),
+            feed_rs::parser::parse(atom.as_bytes()).unwrap(),
+        ));
+        let entry = feed.entries().next().unwrap();
+        let filter = |query: &str, since: Option<Time>, until: Option<Time>| SubscriptionFilter {
+            query: Some(query.into()),
+            since,
+            until,
+            ..Default::default()
+        };
+        let may = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        let june = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+
+        assert!(filter("", None, None).entry(entry, None));
+        assert!(filter("rust ANNOUNCING", None, None).entry(entry, None));
+        assert!(filter("rust blog", None, None).entry(entry, Some("Rust Blog")));
+        assert!(!filter("go", None, None).entry(entry, Some("Rust Blog")));
+        assert!(filter("", Some(may), Some(june)).entry(entry, None));
+        assert!(!filter("", Some(june), None).entry(entry, None));
+        assert!(!filter("", None, Some(may)).entry(entry, None));
+
+        assert!(filter("rust blog", Some(may), None).feed(&feed));
+        assert!(!filter("", Some(june), None).feed(&feed));
+    }
+}
@@ -0,0 +1,340 @@
+use std::sync::Arc;
+
+use synd_feed::types::FeedUrl;
+use thiserror::Error;
+
+use crate::{
+    principal::Principal,
+    repository::{
+        types::{Folder, FolderId, SubscribedFeeds},
+        SubscriptionRepository,
+    },
+    usecase::{
+        authorize::Unauthorized, update_feed_annotations::non_blank, Error, Input, MakeUsecase,
+        Output, Usecase,
+    },
+};
+
+#[derive(Error, Debug)]
+pub enum FolderError {
+    #[error("feed is not subscribed")]
+    NotSubscribed,
+    #[error("folder not found")]
+    FolderNotFound,
+    #[error("folder name must not be empty")]
+    EmptyFolderName,
+}
+
+/// Return the folder name if it is valid
+fn folder_name(name: String) -> Result<String, Error<FolderError>> {
+    non_blank(Some(name)).ok_or(Error::Usecase(FolderError::EmptyFolderName))
+}
+
+/// Check that the folder, if given, belongs to the user
+fn ensure_folder(
+    feeds: &SubscribedFeeds,
+    folder_id: Option<FolderId>,
+) -> Result<(), Error<FolderError>> {
+    match folder_id {
+        Some(id) if feeds.folder(id).is_none() => Err(Error::Usecase(FolderError::FolderNotFound)),
+        _ => Ok(()),
+    }
+}
+
+pub struct CreateFolder {
+    pub repository: Arc<dyn SubscriptionRepository>,
+}
+
+pub struct CreateFolderInput {
+    pub name: String,
+    /// Create the folder in this folder. None means the top level
+    pub parent_id: Option<FolderId>,
+}
+
+pub struct CreateFolderOutput {
+    pub folder: Folder,
+}
+
+impl Usecase for CreateFolder {
+    type Input = CreateFolderInput;
+
+    type Output = CreateFolderOutput;
+
+    type Error = FolderError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "create_folder", skip(self, principal))]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: CreateFolderInput { name, parent_id },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+        let name = folder_name(name)?;
+
+        let feeds = self.repository.fetch_subscribed_feeds(user_id).await?;
+        ensure_folder(&feeds, parent_id)?;
+
+        let folder = self
+            .repository
+            .create_folder(user_id, name, parent_id)
+            .await?;
+
+        Ok(Output {
+            output: CreateFolderOutput { folder },
+        })
+    }
+}
+
+pub struct RenameFolder {
+    pub repository: Arc<dyn SubscriptionRepository>,
+}
+
+pub struct RenameFolderInput {
+    pub folder_id: FolderId,
+    pub name: String,
+}
+
+pub struct RenameFolderOutput {
+    pub folder: Folder,
+}
+
+impl Usecase for RenameFolder {
+    type Input = RenameFolderInput;
+
+    type Output = RenameFolderOutput;
+
+    type Error = FolderError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "rename_folder", skip(self, principal))]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: RenameFolderInput { folder_id, name },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+        let name = folder_name(name)?;
+
+        let feeds = self.repository.fetch_subscribed_feeds(user_id).await?;
+        let Some(folder) = feeds.folder(folder_id) else {
+            return Err(Error::Usecase(FolderError::FolderNotFound));
+        };
+        let folder = Folder {
+            name: name.clone(),
+            ..folder.clone()
+        };
+
+        self.repository
+            .rename_folder(user_id, folder_id, name)
+            .await?;
+
+        Ok(Output {
+            output: RenameFolderOutput { folder },
+        })
+    }
+}
+
+pub struct MoveFeed {
+    pub repository: Arc<dyn SubscriptionRepository>,
+}
+
+pub struct MoveFeedInput {
+    pub url: FeedUrl,
+    /// Destination folder. None means the top level
+    pub folder_id: Option<FolderId>,
+    /// Position among the feeds in the destination folder. None means the last
+    pub position: Option<usize>,
+}
+
+pub struct MoveFeedOutput {
+    pub url: FeedUrl,
+    pub folder_id: Option<FolderId>,
+}
+
+impl Usecase for MoveFeed {
+    type Input = MoveFeedInput;
+
+    type Output = MoveFeedOutput;
+
+    type Error = FolderError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "move_feed", skip(self, principal))]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input:
+                MoveFeedInput {
+                    url,
+                    folder_id,
+                    position,
+                },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        let feeds = self.repository.fetch_subscribed_feeds(user_id).await?;
+        if !feeds.urls.contains(&url) {
+            return Err(Error::Usecase(FolderError::NotSubscribed));
+        }
+        ensure_folder(&feeds, folder_id)?;
+
+        self.repository
+            .move_feed(user_id, url.clone(), folder_id, position)
+            .await?;
+
+        Ok(Output {
+            output: MoveFeedOutput { url, folder_id },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixture::{input, subscription},
+        repository::memory::MemoryRepository,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn organize_feeds_into_folders() {
+        let repository = Arc::new(MemoryRepository::new());
+        let url = FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap();
+        repository
+            .put_feed_subscription(subscription("alice", &url))
+            .await
+            .unwrap();
+
+        let create = CreateFolder {
+            repository: repository.clone(),
+        };
+        let blogs = create
+            .usecase(input(CreateFolderInput {
+                name: " blogs ".into(),
+                parent_id: None,
+            }))
+            .await
+            .unwrap()
+            .output
+            .folder;
+        assert_eq!(blogs.name, "blogs");
+        let rust = create
+            .usecase(input(CreateFolderInput {
+                name: "rust".into(),
+                parent_id: Some(blogs.id),
+            }))
+            .await
+            .unwrap()
+            .output
+            .folder;
+        assert_eq!(rust.parent, Some(blogs.id));
+        assert!(matches!(
+            create
+                .usecase(input(CreateFolderInput {
+                    name: "rust".into(),
+                    parent_id: Some(100),
+                }))
+                .await,
+            Err(Error::Usecase(FolderError::FolderNotFound))
+        ));
+
+        let rename = RenameFolder {
+            repository: repository.clone(),
+        };
+        assert!(matches!(
+            rename
+                .usecase(input(RenameFolderInput {
+                    folder_id: rust.id,
+                    name: "  ".into(),
+                }))
+                .await,
+            Err(Error::Usecase(FolderError::EmptyFolderName))
+        ));
+        rename
+            .usecase(input(RenameFolderInput {
+                folder_id: rust.id,
+                name: "rustacean".into(),
+            }))
+            .await
+            .unwrap();
+
+        let move_feed = MoveFeed {
+            repository: repository.clone(),
+        };
+        move_feed
+            .usecase(input(MoveFeedInput {
+                url: url.clone(),
+                folder_id: Some(rust.id),
+                position: None,
+            }))
+            .await
+            .unwrap();
+        assert!(matches!(
+            move_feed
+                .usecase(input(MoveFeedInput {
+                    url: FeedUrl::try_from("https://example.ymgyt.io/atom.xml").unwrap(),
+                    folder_id: None,
+                    position: None,
+                }))
+                .await,
+            Err(Error::Usecase(FolderError::NotSubscribed))
+        ));
+
+        let feeds = repository.fetch_subscribed_feeds("alice").await.unwrap();
+        assert_eq!(feeds.feed_folders.get(&url), Some(&rust.id));
+        assert_eq!(feeds.folder(rust.id).unwrap().name, "rustacean");
+    }
+}
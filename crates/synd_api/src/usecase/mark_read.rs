@@ -0,0 +1,288 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+use synd_feed::{
+    feed::{cache::FetchCachedFeed, service::FetchFeedError},
+    types::FeedUrl,
+};
+use thiserror::Error;
+
+use crate::{
+    principal::Principal,
+    repository::{types::EntryReference, ReadStateRepository, RepositoryError},
+    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+};
+
+pub struct MarkEntriesInput {
+    pub entries: Vec<EntryReference>,
+}
+
+pub struct MarkEntriesOutput {}
+
+/// Prune explicit read states of the entries which are no longer in the feeds.
+/// Marked entries are always retained even if the cached feed has not caught up with them
+async fn prune_read_states(
+    repository: &dyn ReadStateRepository,
+    fetch_feed: &dyn FetchCachedFeed,
+    user_id: &str,
+    entries: Vec<EntryReference>,
+) -> Result<(), RepositoryError> {
+    let mut feeds = HashMap::<FeedUrl, HashSet<String>>::new();
+    for EntryReference { feed_url, entry_id } in entries {
+        feeds.entry(feed_url).or_default().insert(entry_id);
+    }
+
+    for (feed_url, mut retained) in feeds {
+        let feed = match fetch_feed.fetch_feed(feed_url.clone()).await {
+            Ok(feed) => feed,
+            Err(err) => {
+                // Retain the states as we could not know which entries are in the feed
+                tracing::debug!(%feed_url, "Skip pruning read states: {err}");
+                continue;
+            }
+        };
+        retained.extend(feed.entries().map(|entry| entry.id_ref().to_string()));
+        repository
+            .prune_read_states(user_id, feed_url, retained)
+            .await?;
+    }
+    Ok(())
+}
+
+pub struct MarkEntriesRead {
+    pub repository: Arc<dyn ReadStateRepository>,
+    pub fetch_feed: Arc<dyn FetchCachedFeed>,
+}
+
+impl Usecase for MarkEntriesRead {
+    type Input = MarkEntriesInput;
+
+    type Output = MarkEntriesOutput;
+
+    type Error = anyhow::Error;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.read_state_repo.clone(),
+            fetch_feed: make.fetch_feed.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: MarkEntriesInput { entries },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal.user_id().unwrap();
+
+        self.repository
+            .mark_entries(user_id, entries.clone(), true)
+            .await?;
+        prune_read_states(&*self.repository, &*self.fetch_feed, user_id, entries).await?;
+
+        Ok(Output {
+            output: MarkEntriesOutput {},
+        })
+    }
+}
+
+pub struct MarkEntriesUnread {
+    pub repository: Arc<dyn ReadStateRepository>,
+    pub fetch_feed: Arc<dyn FetchCachedFeed>,
+}
+
+impl Usecase for MarkEntriesUnread {
+    type Input = MarkEntriesInput;
+
+    type Output = MarkEntriesOutput;
+
+    type Error = anyhow::Error;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.read_state_repo.clone(),
+            fetch_feed: make.fetch_feed.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: MarkEntriesInput { entries },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal.user_id().unwrap();
+
+        self.repository
+            .mark_entries(user_id, entries.clone(), false)
+            .await?;
+        prune_read_states(&*self.repository, &*self.fetch_feed, user_id, entries).await?;
+
+        Ok(Output {
+            output: MarkEntriesOutput {},
+        })
+    }
+}
+
+pub struct MarkFeedRead {
+    pub repository: Arc<dyn ReadStateRepository>,
+    pub fetch_feed: Arc<dyn FetchCachedFeed>,
+}
+
+pub struct MarkFeedReadInput {
+    pub url: FeedUrl,
+}
+
+pub struct MarkFeedReadOutput {}
+
+#[derive(Error, Debug)]
+pub enum MarkFeedReadError {
+    #[error("fetch feed error: {0}")]
+    FetchFeed(FetchFeedError),
+}
+
+impl Usecase for MarkFeedRead {
+    type Input = MarkFeedReadInput;
+
+    type Output = MarkFeedReadOutput;
+
+    type Error = MarkFeedReadError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.read_state_repo.clone(),
+            fetch_feed: make.fetch_feed.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: MarkFeedReadInput { url },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal.user_id().unwrap();
+
+        // Mark the entries which the user currently see
+        let feed = self
+            .fetch_feed
+            .fetch_feed(url.clone())
+            .await
+            .map_err(|err| Error::Usecase(MarkFeedReadError::FetchFeed(err)))?;
+        let read_until = feed
+            .entries()
+            .filter_map(|entry| entry.published().or(entry.updated()))
+            .max();
+        let entry_ids = feed
+            .entries()
+            .map(|entry| entry.id_ref().to_string())
+            .collect();
+
+        self.repository
+            .mark_feed_read(user_id, url, read_until, entry_ids)
+            .await?;
+
+        Ok(Output {
+            output: MarkFeedReadOutput {},
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixture::{feed, input, url, StaticFeeds},
+        repository::memory::MemoryRepository,
+    };
+
+    use super::*;
+
+    fn entries(feed_url: &FeedUrl, entry_ids: &[&str]) -> MarkEntriesInput {
+        MarkEntriesInput {
+            entries: entry_ids
+                .iter()
+                .map(|entry_id| EntryReference {
+                    feed_url: feed_url.clone(),
+                    entry_id: (*entry_id).to_owned(),
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn mark_entries_read_and_unread() {
+        let repository = Arc::new(MemoryRepository::new());
+        let fetch_feed = Arc::new(StaticFeeds::default());
+        let blog = url("https://blog.ymgyt.io/atom.xml");
+        fetch_feed.insert(feed(&blog, &[("a", 3), ("b", 2), ("c", 1)]));
+        let read = MarkEntriesRead {
+            repository: repository.clone(),
+            fetch_feed: fetch_feed.clone(),
+        };
+        let unread = MarkEntriesUnread {
+            repository: repository.clone(),
+            fetch_feed: fetch_feed.clone(),
+        };
+        let state = || async {
+            let read_states = repository.fetch_read_states("alice").await.unwrap();
+            let state = read_states.feed(&blog).cloned().unwrap_or_default();
+            let mut read = state.read.into_iter().collect::<Vec<_>>();
+            let mut unread = state.unread.into_iter().collect::<Vec<_>>();
+            read.sort();
+            unread.sort();
+            (read, unread)
+        };
+
+        read.usecase(input(entries(&blog, &["a", "b"])))
+            .await
+            .unwrap();
+        assert_eq!(state().await, (vec!["a".into(), "b".into()], vec![]));
+
+        unread.usecase(input(entries(&blog, &["b"]))).await.unwrap();
+        assert_eq!(state().await, (vec!["a".into()], vec!["b".into()]));
+
+        // Entries evicted from the feed are pruned, marked entries are retained
+        // even if the feed has not caught up with them
+        fetch_feed.insert(feed(&blog, &[("d", 4), ("b", 2)]));
+        read.usecase(input(entries(&blog, &["e"]))).await.unwrap();
+        assert_eq!(state().await, (vec!["e".into()], vec!["b".into()]));
+
+        // States are retained if the feed could not be fetched
+        let unknown = url("https://unknown.ymgyt.io/feed");
+        read.usecase(input(entries(&unknown, &["x"])))
+            .await
+            .unwrap();
+        let read_states = repository.fetch_read_states("alice").await.unwrap();
+        assert!(read_states.feed(&unknown).unwrap().read.contains("x"));
+    }
+}
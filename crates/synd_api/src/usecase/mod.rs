@@ -12,27 +12,105 @@ pub use fetch_subscribed_feeds::{
     FetchSubscribedFeedsOutput,
 };
 
+mod filter;
+pub use filter::SubscriptionFilter;
+
 mod fetch_entries;
-pub use fetch_entries::{FetchEntries, FetchEntriesError, FetchEntriesInput, FetchEntriesOutput};
+pub use fetch_entries::{
+    EntryCursor, FetchEntries, FetchEntriesError, FetchEntriesInput, FetchEntriesOutput,
+    InvalidCursor,
+};
+
+mod update_feed_annotations;
+pub use update_feed_annotations::{
+    Patch, UpdateFeedAnnotations, UpdateFeedAnnotationsError, UpdateFeedAnnotationsInput,
+    UpdateFeedAnnotationsOutput,
+};
+
+mod folder;
+pub use folder::{
+    CreateFolder, CreateFolderInput, CreateFolderOutput, FolderError, MoveFeed, MoveFeedInput,
+    MoveFeedOutput, RenameFolder, RenameFolderInput, RenameFolderOutput,
+};
+
+mod mark_read;
+pub use mark_read::{
+    MarkEntriesInput, MarkEntriesOutput, MarkEntriesRead, MarkEntriesUnread, MarkFeedRead,
+    MarkFeedReadError, MarkFeedReadInput, MarkFeedReadOutput,
+};
+
+mod star_entry;
+pub use star_entry::{
+    StarEntry, StarEntryError, StarEntryInput, StarEntryOutput, UnstarEntry, UnstarEntryInput,
+    UnstarEntryOutput,
+};
+
+mod fetch_starred_entries;
+pub use fetch_starred_entries::{
+    FetchStarredEntries, FetchStarredEntriesError, FetchStarredEntriesInput,
+    FetchStarredEntriesOutput, StarredEntryCursor,
+};
+
+mod api_token;
+pub use api_token::{
+    ApiTokenError, CreateApiToken, CreateApiTokenInput, CreateApiTokenOutput, FetchApiTokens,
+    FetchApiTokensError, FetchApiTokensInput, FetchApiTokensOutput, RevokeApiToken,
+    RevokeApiTokenInput, RevokeApiTokenOutput,
+};
+
+mod webhook;
+pub use webhook::{
+    CreateWebhook, CreateWebhookInput, CreateWebhookOutput, DeleteWebhook, DeleteWebhookInput,
+    DeleteWebhookOutput, FetchWebhookDeliveries, FetchWebhookDeliveriesError,
+    FetchWebhookDeliveriesInput, FetchWebhookDeliveriesOutput, FetchWebhooks, FetchWebhooksError,
+    FetchWebhooksInput, FetchWebhooksOutput, WebhookError,
+};
+
+mod watch_new_entries;
+pub use watch_new_entries::{
+    NewEntries, WatchNewEntries, WatchNewEntriesError, WatchNewEntriesInput, WatchNewEntriesOutput,
+};
+
+mod account;
+pub use account::{
+    AccountError, DeleteMyAccount, DeleteMyAccountInput, DeleteMyAccountOutput, ExportMyData,
+    ExportMyDataInput, ExportMyDataOutput,
+};
+
+mod admin;
+pub use admin::{
+    AdminError, FetchCacheStats, FetchCacheStatsInput, FetchCacheStatsOutput,
+    FetchUserSubscriptions, FetchUserSubscriptionsInput, FetchUserSubscriptionsOutput, FetchUsers,
+    FetchUsersInput, FetchUsersOutput, RefreshFeed, RefreshFeedInput, RefreshFeedOutput,
+};
 
 use tracing::error;
 
 pub mod authorize;
 use std::{future::Future, sync::Arc};
 
-use synd_feed::feed::cache::FetchCachedFeed;
+use synd_feed::feed::cache::{FetchCachedFeed, SubscribeFeedUpdates};
 use synd_o11y::{audit, metric, tracing_subscriber::audit::Audit};
 
 use crate::{
     principal::Principal,
-    repository::{RepositoryError, SubscriptionRepository},
+    repository::{
+        ApiTokenRepository, ReadStateRepository, RepositoryError, StarredEntryRepository,
+        SubscriptionRepository, WebhookRepository,
+    },
 };
 
-use self::authorize::{Authorized, Authorizer, Unauthorized};
+use self::authorize::{Authorized, Authorizer, Quota, Unauthorized};
 
 pub struct MakeUsecase {
     pub subscription_repo: Arc<dyn SubscriptionRepository>,
+    pub read_state_repo: Arc<dyn ReadStateRepository>,
+    pub starred_entry_repo: Arc<dyn StarredEntryRepository>,
+    pub api_token_repo: Arc<dyn ApiTokenRepository>,
+    pub webhook_repo: Arc<dyn WebhookRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
+    pub feed_updates: Arc<dyn SubscribeFeedUpdates>,
+    pub quota: Quota,
 }
 
 impl MakeUsecase {
@@ -54,7 +132,7 @@ pub struct Output<T> {
 pub enum Error<T> {
     #[error(transparent)]
     Usecase(T),
-    #[error("unauthorized error")]
+    #[error(transparent)]
     Unauthorized(Unauthorized),
     #[error("repository error")]
     Repository(#[from] RepositoryError),
@@ -65,6 +143,18 @@ pub trait Usecase {
     type Output;
     type Error: std::fmt::Debug;
 
+    /// Whether the usecase does not modify the data.
+    /// Principals with read only scope can run only read only usecases
+    const READ_ONLY: bool = false;
+
+    /// Whether only the principals with the admin role can run the usecase
+    const ADMIN: bool = false;
+
+    /// Whether the principals authenticated by personal access tokens can run the usecase.
+    /// Usecases which manage the credentials or the account require the user's login
+    /// so that a leaked token cannot outlive its revocation or expiration
+    const API_TOKEN: bool = true;
+
     fn new(make: &MakeUsecase) -> Self;
 
     fn audit_operation(&self) -> &'static str {
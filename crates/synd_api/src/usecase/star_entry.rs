@@ -0,0 +1,207 @@
+use std::sync::Arc;
+
+use synd_feed::feed::{cache::FetchCachedFeed, service::FetchFeedError};
+use thiserror::Error;
+
+use crate::{
+    principal::Principal,
+    repository::{
+        types::{EntryReference, StarredEntry, SubscribedFeeds},
+        StarredEntryRepository, SubscriptionRepository,
+    },
+    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+};
+
+pub struct StarEntry {
+    pub repository: Arc<dyn StarredEntryRepository>,
+    pub subscription_repository: Arc<dyn SubscriptionRepository>,
+    pub fetch_feed: Arc<dyn FetchCachedFeed>,
+}
+
+pub struct StarEntryInput {
+    pub entry: EntryReference,
+}
+
+pub struct StarEntryOutput {
+    pub entry: StarredEntry,
+}
+
+#[derive(Error, Debug)]
+pub enum StarEntryError {
+    #[error("fetch feed error: {0}")]
+    FetchFeed(FetchFeedError),
+    #[error("entry not found")]
+    EntryNotFound,
+    #[error("feed not subscribed")]
+    FeedNotSubscribed,
+}
+
+impl Usecase for StarEntry {
+    type Input = StarEntryInput;
+
+    type Output = StarEntryOutput;
+
+    type Error = StarEntryError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.starred_entry_repo.clone(),
+            subscription_repository: make.subscription_repo.clone(),
+            fetch_feed: make.fetch_feed.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: StarEntryInput { entry },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal.user_id().unwrap();
+
+        // Only the entries of the subscribed feeds can be starred
+        let SubscribedFeeds {
+            urls, annotations, ..
+        } = self
+            .subscription_repository
+            .fetch_subscribed_feeds(user_id)
+            .await?;
+        if !urls.contains(&entry.feed_url) {
+            return Err(Error::Usecase(StarEntryError::FeedNotSubscribed));
+        }
+
+        let feed = self
+            .fetch_feed
+            .fetch_feed(entry.feed_url.clone())
+            .await
+            .map_err(|err| Error::Usecase(StarEntryError::FetchFeed(err)))?;
+        let Some(target) = feed
+            .entries()
+            .find(|e| e.id_ref().to_string() == entry.entry_id)
+        else {
+            return Err(Error::Usecase(StarEntryError::EntryNotFound));
+        };
+
+        // Snapshot the annotations of the feed as well
+        let annotations =
+            annotations.and_then(|mut annotations| annotations.remove(&entry.feed_url));
+        let meta = annotations
+            .unwrap_or_default()
+            .annotate(feed.meta().clone());
+
+        let starred = StarredEntry::new(&meta, target, chrono::Utc::now());
+        self.repository.star_entry(user_id, starred.clone()).await?;
+
+        Ok(Output {
+            output: StarEntryOutput { entry: starred },
+        })
+    }
+}
+
+pub struct UnstarEntry {
+    pub repository: Arc<dyn StarredEntryRepository>,
+}
+
+pub struct UnstarEntryInput {
+    pub entry: EntryReference,
+}
+
+pub struct UnstarEntryOutput {}
+
+impl Usecase for UnstarEntry {
+    type Input = UnstarEntryInput;
+
+    type Output = UnstarEntryOutput;
+
+    type Error = anyhow::Error;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.starred_entry_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: UnstarEntryInput { entry },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal.user_id().unwrap();
+
+        self.repository.unstar_entry(user_id, entry).await?;
+
+        Ok(Output {
+            output: UnstarEntryOutput {},
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixture::{feed, input, subscription, url, StaticFeeds},
+        repository::memory::MemoryRepository,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn star_only_subscribed_feed_entries() {
+        let repository = Arc::new(MemoryRepository::new());
+        let fetch_feed = Arc::new(StaticFeeds::default());
+        let blog = url("https://blog.ymgyt.io/atom.xml");
+        fetch_feed.insert(feed(&blog, &[("a", 1)]));
+        let usecase = StarEntry {
+            repository: repository.clone(),
+            subscription_repository: repository.clone(),
+            fetch_feed,
+        };
+        let star = || {
+            usecase.usecase(input(StarEntryInput {
+                entry: EntryReference {
+                    feed_url: blog.clone(),
+                    entry_id: "a".into(),
+                },
+            }))
+        };
+
+        assert!(matches!(
+            star().await,
+            Err(Error::Usecase(StarEntryError::FeedNotSubscribed))
+        ));
+
+        repository
+            .put_feed_subscription(subscription("alice", &blog))
+            .await
+            .unwrap();
+        let starred = star().await.unwrap().output.entry;
+        assert_eq!(starred.entry_id, "a");
+        assert_eq!(
+            repository
+                .fetch_starred_entries("alice")
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
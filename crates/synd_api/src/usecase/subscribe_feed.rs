@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 use synd_feed::{
     feed::{cache::FetchCachedFeed, service::FetchFeedError},
@@ -13,17 +13,26 @@ use crate::{
     usecase::{Input, Output},
 };
 
-use super::{authorize::Unauthorized, Usecase};
+use super::{
+    authorize::{QuotaExceeded, Unauthorized},
+    update_feed_annotations::non_blank,
+    Usecase,
+};
 
 pub struct SubscribeFeed {
     pub repository: Arc<dyn SubscriptionRepository>,
     pub fetch_feed: Arc<dyn FetchCachedFeed>,
+    pub max_subscriptions: usize,
 }
 
+/// Annotations which are not given are cleared
 pub struct SubscribeFeedInput {
     pub url: FeedUrl,
     pub requirement: Option<Requirement>,
     pub category: Option<Category<'static>>,
+    pub title: Option<String>,
+    pub note: Option<String>,
+    pub tags: BTreeSet<Category<'static>>,
 }
 
 pub struct SubscribeFeedOutput {
@@ -47,6 +56,7 @@ impl Usecase for SubscribeFeed {
         Self {
             repository: make.subscription_repo.clone(),
             fetch_feed: make.fetch_feed.clone(),
+            max_subscriptions: make.quota.max_subscriptions,
         }
     }
 
@@ -55,6 +65,9 @@ impl Usecase for SubscribeFeed {
         principal: Principal,
         _: &SubscribeFeedInput,
     ) -> Result<Principal, Unauthorized> {
+        if principal.user_id().is_none() {
+            return Err(Unauthorized::Forbidden);
+        }
         Ok(principal)
     }
 
@@ -67,12 +80,16 @@ impl Usecase for SubscribeFeed {
                     url,
                     requirement,
                     category,
+                    title,
+                    note,
+                    tags,
                 },
             ..
         }: Input<Self::Input>,
     ) -> Result<Output<Self::Output>, super::Error<Self::Error>> {
         tracing::debug!("Subscribe feed: {url}");
 
+        let user_id = principal.user_id().unwrap();
         let feed = self
             .fetch_feed
             .fetch_feed(url.clone())
@@ -81,25 +98,204 @@ impl Usecase for SubscribeFeed {
 
         tracing::debug!("{:?}", feed.meta());
 
-        self.repository
-            .put_feed_subscription(repository::types::FeedSubscription {
-                user_id: principal.user_id().unwrap().to_owned(),
-                url: feed.meta().url().to_owned(),
-                requirement,
-                category: category.clone(),
-            })
-            .await?;
-
-        metric!(monotonic_counter.feed.subscription = 1);
-
-        let feed = Annotated {
-            feed,
+        // Subscription is keyed by the url of the fetched feed which may differ from the given url after redirects,
+        // so the quota is checked against it when the subscription is written
+        let subscription = repository::types::FeedSubscription {
+            user_id: user_id.to_owned(),
+            url: feed.meta().url().to_owned(),
             requirement,
             category,
+            title: non_blank(title),
+            note: non_blank(note),
+            tags,
         };
+        let annotations = subscription.annotations();
+        if !self
+            .repository
+            .put_feed_subscription_within(subscription, self.max_subscriptions)
+            .await?
+        {
+            return Err(super::Error::Unauthorized(Unauthorized::QuotaExceeded(
+                QuotaExceeded {
+                    resource: "subscriptions",
+                    limit: self.max_subscriptions,
+                },
+            )));
+        }
+
+        metric!(monotonic_counter.feed.subscription = 1);
+
+        let feed = annotations.annotate(feed);
 
         Ok(Output {
             output: SubscribeFeedOutput { feed },
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use synd_feed::feed::service::FetchFeedResult;
+
+    use crate::{
+        fixture::{self, subscription, url, StaticFeeds},
+        principal::User,
+        repository::memory::MemoryRepository,
+        usecase::{authorize::Authorized, Error},
+    };
+
+    use super::*;
+
+    fn input(url: &str) -> SubscribeFeedInput {
+        SubscribeFeedInput {
+            url: fixture::url(url),
+            requirement: None,
+            category: None,
+            title: None,
+            note: None,
+            tags: BTreeSet::new(),
+        }
+    }
+
+    fn feeds(urls: &[&str]) -> Arc<StaticFeeds> {
+        let feeds = StaticFeeds::default();
+        for u in urls {
+            feeds.insert(fixture::feed(&url(u), &[]));
+        }
+        Arc::new(feeds)
+    }
+
+    async fn subscribe(
+        usecase: &SubscribeFeed,
+        user_id: &str,
+        url: &str,
+    ) -> Result<Output<SubscribeFeedOutput>, Error<SubscribeFeedError>> {
+        usecase
+            .usecase(Input {
+                principal: Authorized::unchecked(Principal::User(User::from_id(user_id))),
+                input: input(url),
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn enforce_max_subscriptions() {
+        let usecase = SubscribeFeed {
+            repository: Arc::new(MemoryRepository::new()),
+            fetch_feed: feeds(&[
+                "https://a.ymgyt.io/feed",
+                "https://b.ymgyt.io/feed",
+                "https://c.ymgyt.io/feed",
+            ]),
+            max_subscriptions: 2,
+        };
+        for url in ["https://a.ymgyt.io/feed", "https://b.ymgyt.io/feed"] {
+            assert!(subscribe(&usecase, "alice", url).await.is_ok());
+        }
+
+        assert!(matches!(
+            subscribe(&usecase, "alice", "https://c.ymgyt.io/feed").await,
+            Err(Error::Unauthorized(Unauthorized::QuotaExceeded(
+                QuotaExceeded { limit: 2, .. }
+            )))
+        ));
+        // Annotations of the subscribed feed can be updated
+        assert!(subscribe(&usecase, "alice", "https://a.ymgyt.io/feed")
+            .await
+            .is_ok());
+        // Other users are not affected
+        assert!(subscribe(&usecase, "bob", "https://c.ymgyt.io/feed")
+            .await
+            .is_ok());
+    }
+
+    /// Subscribe another feed while fetching the feed as if the user subscribed concurrently
+    struct Concurrent {
+        repository: Arc<MemoryRepository>,
+        feeds: Arc<StaticFeeds>,
+    }
+
+    #[async_trait]
+    impl FetchCachedFeed for Concurrent {
+        async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<Feed>> {
+            self.repository
+                .put_feed_subscription(subscription(
+                    "alice",
+                    &fixture::url("https://b.ymgyt.io/feed"),
+                ))
+                .await
+                .unwrap();
+            self.feeds.fetch_feed(url).await
+        }
+
+        async fn fetch_feeds_parallel(&self, _urls: &[FeedUrl]) -> Vec<FetchFeedResult<Arc<Feed>>> {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn enforce_quota_on_concurrent_subscription() {
+        let repository = Arc::new(MemoryRepository::new());
+        let usecase = SubscribeFeed {
+            repository: repository.clone(),
+            fetch_feed: Arc::new(Concurrent {
+                repository: repository.clone(),
+                feeds: feeds(&["https://a.ymgyt.io/feed"]),
+            }),
+            max_subscriptions: 1,
+        };
+
+        assert!(matches!(
+            subscribe(&usecase, "alice", "https://a.ymgyt.io/feed").await,
+            Err(Error::Unauthorized(Unauthorized::QuotaExceeded(_)))
+        ));
+        assert_eq!(
+            repository
+                .fetch_subscribed_feeds("alice")
+                .await
+                .unwrap()
+                .urls,
+            vec![url("https://b.ymgyt.io/feed")]
+        );
+    }
+
+    /// Redirect every url to the feed
+    struct Redirect(Arc<StaticFeeds>, FeedUrl);
+
+    #[async_trait]
+    impl FetchCachedFeed for Redirect {
+        async fn fetch_feed(&self, _url: FeedUrl) -> FetchFeedResult<Arc<Feed>> {
+            self.0.fetch_feed(self.1.clone()).await
+        }
+
+        async fn fetch_feeds_parallel(&self, _urls: &[FeedUrl]) -> Vec<FetchFeedResult<Arc<Feed>>> {
+            unreachable!()
+        }
+    }
+
+    #[tokio::test]
+    async fn check_quota_by_redirected_url() {
+        let repository = Arc::new(MemoryRepository::new());
+        let usecase = SubscribeFeed {
+            repository: repository.clone(),
+            fetch_feed: Arc::new(Redirect(
+                feeds(&["https://a.ymgyt.io/feed"]),
+                url("https://a.ymgyt.io/feed"),
+            )),
+            max_subscriptions: 1,
+        };
+
+        for url in ["https://a.ymgyt.io/feed", "https://a.ymgyt.io/old/feed"] {
+            assert!(subscribe(&usecase, "alice", url).await.is_ok(), "{url}");
+        }
+        assert_eq!(
+            repository
+                .fetch_subscribed_feeds("alice")
+                .await
+                .unwrap()
+                .urls,
+            vec![url("https://a.ymgyt.io/feed")]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeSet, sync::Arc};
 
 use synd_feed::types::FeedUrl;
 use synd_o11y::metric;
@@ -58,6 +58,9 @@ impl Usecase for UnsubscribeFeed {
                 url,
                 requirement: None,
                 category: None,
+                title: None,
+                note: None,
+                tags: BTreeSet::new(),
             })
             .await?;
 
@@ -0,0 +1,220 @@
+use std::{collections::BTreeSet, sync::Arc};
+
+use synd_feed::types::{Category, FeedUrl, Requirement};
+use thiserror::Error;
+
+use crate::{
+    principal::Principal,
+    repository::{
+        types::{FeedAnnotations, FeedSubscription},
+        SubscriptionRepository,
+    },
+    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+};
+
+pub struct UpdateFeedAnnotations {
+    pub repository: Arc<dyn SubscriptionRepository>,
+}
+
+/// Update of an annotation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Patch<T> {
+    /// Keep the current value
+    #[default]
+    Keep,
+    Clear,
+    Set(T),
+}
+
+impl<T> Patch<T> {
+    fn apply(self, current: Option<T>) -> Option<T> {
+        match self {
+            Patch::Keep => current,
+            Patch::Clear => None,
+            Patch::Set(value) => Some(value),
+        }
+    }
+}
+
+pub struct UpdateFeedAnnotationsInput {
+    pub url: FeedUrl,
+    pub requirement: Patch<Requirement>,
+    pub category: Patch<Category<'static>>,
+    pub title: Patch<String>,
+    pub note: Patch<String>,
+    /// Replace all tags if given
+    pub tags: Option<BTreeSet<Category<'static>>>,
+}
+
+pub struct UpdateFeedAnnotationsOutput {
+    pub url: FeedUrl,
+    pub annotations: FeedAnnotations,
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateFeedAnnotationsError {
+    #[error("feed is not subscribed")]
+    NotSubscribed,
+}
+
+impl Usecase for UpdateFeedAnnotations {
+    type Input = UpdateFeedAnnotationsInput;
+
+    type Output = UpdateFeedAnnotationsOutput;
+
+    type Error = UpdateFeedAnnotationsError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "update_feed_annotations", skip(self, principal))]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input:
+                UpdateFeedAnnotationsInput {
+                    url,
+                    requirement,
+                    category,
+                    title,
+                    note,
+                    tags,
+                },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        let mut feeds = self.repository.fetch_subscribed_feeds(user_id).await?;
+        if !feeds.urls.contains(&url) {
+            return Err(Error::Usecase(UpdateFeedAnnotationsError::NotSubscribed));
+        }
+        let current = feeds
+            .annotations
+            .as_mut()
+            .and_then(|annotations| annotations.remove(&url))
+            .unwrap_or_default();
+
+        let subscription = FeedSubscription {
+            user_id: user_id.to_owned(),
+            url: url.clone(),
+            requirement: requirement.apply(current.requirement),
+            category: category.apply(current.category),
+            title: non_blank(title.apply(current.title)),
+            note: non_blank(note.apply(current.note)),
+            tags: tags.unwrap_or(current.tags),
+        };
+        let annotations = subscription.annotations();
+        self.repository.put_feed_subscription(subscription).await?;
+
+        Ok(Output {
+            output: UpdateFeedAnnotationsOutput { url, annotations },
+        })
+    }
+}
+
+/// Blank text is treated as not given
+pub(super) fn non_blank(s: Option<String>) -> Option<String> {
+    s.map(|s| s.trim().to_owned()).filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixture::{input, subscription},
+        repository::memory::MemoryRepository,
+    };
+
+    use super::*;
+
+    fn url() -> FeedUrl {
+        FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap()
+    }
+
+    #[test]
+    fn patch() {
+        assert_eq!(Patch::Keep.apply(Some(1)), Some(1));
+        assert_eq!(Patch::Clear.apply(Some(1)), None);
+        assert_eq!(Patch::Set(2).apply(Some(1)), Some(2));
+        assert_eq!(Patch::Set(2).apply(None), Some(2));
+    }
+
+    #[tokio::test]
+    async fn update_only_given_annotations() {
+        let repository = Arc::new(MemoryRepository::new());
+        let rust = Category::new("rust").unwrap();
+        repository
+            .put_feed_subscription(FeedSubscription {
+                requirement: Some(Requirement::Must),
+                category: Some(rust.clone()),
+                title: Some("ymgyt".into()),
+                ..subscription("alice", &url())
+            })
+            .await
+            .unwrap();
+        let uc = UpdateFeedAnnotations {
+            repository: repository.clone(),
+        };
+        let update = |update| async { uc.usecase(input(update)).await };
+
+        let Output {
+            output: UpdateFeedAnnotationsOutput { annotations, .. },
+        } = update(UpdateFeedAnnotationsInput {
+            url: url(),
+            requirement: Patch::Keep,
+            category: Patch::Keep,
+            title: Patch::Clear,
+            note: Patch::Set(" weekly digest ".into()),
+            tags: Some(BTreeSet::from([Category::new("blog").unwrap()])),
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            annotations,
+            FeedAnnotations {
+                requirement: Some(Requirement::Must),
+                category: Some(rust),
+                title: None,
+                note: Some("weekly digest".into()),
+                tags: BTreeSet::from([Category::new("blog").unwrap()]),
+            }
+        );
+        let stored = repository
+            .fetch_subscribed_feeds("alice")
+            .await
+            .unwrap()
+            .annotations
+            .unwrap()
+            .remove(&url())
+            .unwrap();
+        assert_eq!(stored, annotations);
+
+        let not_subscribed = update(UpdateFeedAnnotationsInput {
+            url: FeedUrl::try_from("https://example.ymgyt.io/atom.xml").unwrap(),
+            requirement: Patch::Keep,
+            category: Patch::Keep,
+            title: Patch::Keep,
+            note: Patch::Keep,
+            tags: None,
+        })
+        .await;
+        assert!(matches!(
+            not_subscribed,
+            Err(Error::Usecase(UpdateFeedAnnotationsError::NotSubscribed))
+        ));
+    }
+}
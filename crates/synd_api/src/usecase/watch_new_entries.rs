@@ -0,0 +1,214 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use futures_util::{stream::BoxStream, StreamExt};
+use synd_feed::{
+    feed::cache::{FeedUpdate, FeedUpdates, SubscribeFeedUpdates},
+    types::{Annotated, Entry, FeedMeta},
+};
+use thiserror::Error;
+
+use crate::{
+    principal::Principal,
+    repository::{types::SubscribedFeeds, SubscriptionRepository},
+    usecase::{authorize::Unauthorized, Error, Input, MakeUsecase, Output, Usecase},
+};
+
+pub struct WatchNewEntries {
+    pub repository: Arc<dyn SubscriptionRepository>,
+    pub feed_updates: Arc<dyn SubscribeFeedUpdates>,
+}
+
+pub struct WatchNewEntriesInput;
+
+/// New entries of a subscribed feed
+pub struct NewEntries {
+    pub feed: Annotated<FeedMeta>,
+    pub entries: Vec<Entry>,
+}
+
+pub struct WatchNewEntriesOutput {
+    /// Emit whenever a subscribed feed is updated with new entries
+    pub new_entries: BoxStream<'static, NewEntries>,
+}
+
+#[derive(Error, Debug)]
+pub enum WatchNewEntriesError {}
+
+impl Usecase for WatchNewEntries {
+    type Input = WatchNewEntriesInput;
+
+    type Output = WatchNewEntriesOutput;
+
+    type Error = WatchNewEntriesError;
+
+    const READ_ONLY: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.subscription_repo.clone(),
+            feed_updates: make.feed_updates.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "watch_new_entries", skip_all)]
+    async fn usecase(
+        &self,
+        Input { principal, .. }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug")
+            .to_owned();
+
+        let new_entries = futures_util::stream::unfold(
+            (self.feed_updates.subscribe(), self.repository.clone(), None),
+            move |(mut updates, repository, mut subscriptions)| {
+                let user_id = user_id.clone();
+                async move {
+                    let new_entries =
+                        Self::next(&mut updates, &*repository, &user_id, &mut subscriptions)
+                            .await?;
+                    Some((new_entries, (updates, repository, subscriptions)))
+                }
+            },
+        )
+        .boxed();
+
+        Ok(Output {
+            output: WatchNewEntriesOutput { new_entries },
+        })
+    }
+}
+
+/// Subscribed feeds of the user loaded by the stream
+struct Subscriptions {
+    feeds: SubscribedFeeds,
+    loaded_at: Instant,
+}
+
+impl WatchNewEntries {
+    /// Subscriptions are reloaded after this duration to follow subscribe and unsubscribe
+    const SUBSCRIPTIONS_TTL: Duration = Duration::from_secs(60);
+
+    /// Wait for the next update of the feeds which the user subscribes.
+    /// Subscriptions are loaded on the first update and reused until expired
+    async fn next(
+        updates: &mut FeedUpdates,
+        repository: &dyn SubscriptionRepository,
+        user_id: &str,
+        subscriptions: &mut Option<Subscriptions>,
+    ) -> Option<NewEntries> {
+        loop {
+            let FeedUpdate {
+                url,
+                feed,
+                new_entries,
+            } = updates.recv().await?;
+
+            if subscriptions
+                .as_ref()
+                .map_or(true, |s| s.loaded_at.elapsed() > Self::SUBSCRIPTIONS_TTL)
+            {
+                match repository.fetch_subscribed_feeds(user_id).await {
+                    Ok(feeds) => {
+                        *subscriptions = Some(Subscriptions {
+                            feeds,
+                            loaded_at: Instant::now(),
+                        });
+                    }
+                    // Expired subscriptions are still used if any
+                    Err(err) => tracing::warn!("Failed to fetch subscribed feeds: {err}"),
+                }
+            }
+            let Some(Subscriptions {
+                feeds: SubscribedFeeds {
+                    urls, annotations, ..
+                },
+                ..
+            }) = subscriptions.as_ref()
+            else {
+                continue;
+            };
+            if !urls.contains(&url) {
+                continue;
+            }
+
+            let feed = annotations
+                .as_ref()
+                .and_then(|annotations| annotations.get(&url).cloned())
+                .unwrap_or_default()
+                .annotate(feed.meta().clone());
+            return Some(NewEntries {
+                feed,
+                entries: new_entries,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use synd_feed::{
+        feed::cache::{CacheLayer, FetchCachedFeed},
+        types::Requirement,
+    };
+
+    use crate::{
+        fixture::{feed, subscription, url, StaticFeeds},
+        repository::{memory::MemoryRepository, types::FeedSubscription},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn emit_only_subscribed_feeds() {
+        let subscribed = url("https://a.ymgyt.io/feed");
+        let other = url("https://b.ymgyt.io/feed");
+        let repository = MemoryRepository::new();
+        repository
+            .put_feed_subscription(FeedSubscription {
+                requirement: Some(Requirement::Must),
+                ..subscription("alice", &subscribed)
+            })
+            .await
+            .unwrap();
+        let publisher = Arc::new(StaticFeeds::default());
+        let cache = CacheLayer::new(publisher.clone());
+        let mut updates = cache.subscribe();
+
+        for (url, entries) in [
+            (&subscribed, &[("1", 1)][..]),
+            (&other, &[("1", 1)]),
+            (&other, &[("2", 2), ("1", 1)]),
+            (&subscribed, &[("2", 2), ("1", 1)]),
+        ] {
+            publisher.insert(feed(url, entries));
+            cache.refresh_feed(url.clone()).await.unwrap();
+        }
+
+        let NewEntries { feed, entries } =
+            WatchNewEntries::next(&mut updates, &repository, "alice", &mut None)
+                .await
+                .unwrap();
+        assert_eq!(feed.feed.url(), &subscribed);
+        assert_eq!(feed.requirement, Some(Requirement::Must));
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.id().to_string())
+                .collect::<Vec<_>>(),
+            vec!["2".to_owned()],
+        );
+    }
+}
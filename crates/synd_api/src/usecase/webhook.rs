@@ -0,0 +1,423 @@
+use std::sync::Arc;
+
+use synd_feed::types::{Category, Requirement};
+use thiserror::Error;
+use url::Url;
+
+use crate::{
+    config,
+    principal::Principal,
+    repository::{
+        types::{Webhook, WebhookDelivery},
+        WebhookRepository,
+    },
+    usecase::{
+        authorize::{QuotaExceeded, Unauthorized},
+        update_feed_annotations::non_blank,
+        Error, Input, MakeUsecase, Output, Usecase,
+    },
+    webhook,
+};
+
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("webhook url must be http or https: {0}")]
+    InvalidUrl(String),
+    #[error("webhook url must not be a local address: {0}")]
+    LocalAddress(String),
+    #[error("webhook template must be JSON: {0}")]
+    InvalidTemplate(String),
+    #[error("webhook not found")]
+    NotFound,
+}
+
+pub struct CreateWebhook {
+    pub repository: Arc<dyn WebhookRepository>,
+}
+
+pub struct CreateWebhookInput {
+    pub url: String,
+    /// Random secret is generated if None
+    pub secret: Option<String>,
+    pub requirement: Requirement,
+    pub categories: Vec<Category<'static>>,
+    /// JSON payload with placeholders. The default payload is used if None
+    pub template: Option<String>,
+}
+
+pub struct CreateWebhookOutput {
+    pub webhook: Webhook,
+}
+
+impl Usecase for CreateWebhook {
+    type Input = CreateWebhookInput;
+
+    type Output = CreateWebhookOutput;
+
+    type Error = WebhookError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.webhook_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        let Some(user_id) = principal.user_id() else {
+            return Err(Unauthorized::Forbidden);
+        };
+        let webhooks = match self.repository.fetch_webhooks(user_id).await {
+            Ok(webhooks) => webhooks,
+            Err(err) => {
+                tracing::warn!("Failed to fetch webhooks to check quota: {err}");
+                return Ok(principal);
+            }
+        };
+        if webhooks.len() >= config::webhook::MAX_WEBHOOKS_PER_USER {
+            return Err(Unauthorized::QuotaExceeded(QuotaExceeded {
+                resource: "webhooks",
+                limit: config::webhook::MAX_WEBHOOKS_PER_USER,
+            }));
+        }
+
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "create_webhook", skip_all)]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input:
+                CreateWebhookInput {
+                    url,
+                    secret,
+                    requirement,
+                    categories,
+                    template,
+                },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        let url = match Url::parse(url.trim()) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => url,
+            _ => return Err(Error::Usecase(WebhookError::InvalidUrl(url))),
+        };
+        if webhook::check_url(&url).is_err() {
+            return Err(Error::Usecase(WebhookError::LocalAddress(url.into())));
+        }
+        let template = non_blank(template)
+            .map(|template| serde_json::from_str(&template))
+            .transpose()
+            .map_err(|err| Error::Usecase(WebhookError::InvalidTemplate(err.to_string())))?;
+
+        let webhook = Webhook::register(
+            user_id,
+            url,
+            non_blank(secret),
+            requirement,
+            categories,
+            template,
+            chrono::Utc::now(),
+        );
+        self.repository.put_webhook(webhook.clone()).await?;
+
+        Ok(Output {
+            output: CreateWebhookOutput { webhook },
+        })
+    }
+}
+
+pub struct FetchWebhooks {
+    pub repository: Arc<dyn WebhookRepository>,
+}
+
+pub struct FetchWebhooksInput {}
+
+pub struct FetchWebhooksOutput {
+    pub webhooks: Vec<Webhook>,
+}
+
+#[derive(Error, Debug)]
+pub enum FetchWebhooksError {}
+
+impl Usecase for FetchWebhooks {
+    type Input = FetchWebhooksInput;
+
+    type Output = FetchWebhooksOutput;
+
+    type Error = FetchWebhooksError;
+
+    const READ_ONLY: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.webhook_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "fetch_webhooks", skip_all)]
+    async fn usecase(
+        &self,
+        Input { principal, .. }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        let webhooks = self.repository.fetch_webhooks(user_id).await?;
+
+        Ok(Output {
+            output: FetchWebhooksOutput { webhooks },
+        })
+    }
+}
+
+pub struct FetchWebhookDeliveries {
+    pub repository: Arc<dyn WebhookRepository>,
+}
+
+pub struct FetchWebhookDeliveriesInput {
+    /// Deliveries of all webhooks if None
+    pub webhook_id: Option<String>,
+}
+
+pub struct FetchWebhookDeliveriesOutput {
+    /// Most recent delivery first
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+#[derive(Error, Debug)]
+pub enum FetchWebhookDeliveriesError {}
+
+impl Usecase for FetchWebhookDeliveries {
+    type Input = FetchWebhookDeliveriesInput;
+
+    type Output = FetchWebhookDeliveriesOutput;
+
+    type Error = FetchWebhookDeliveriesError;
+
+    const READ_ONLY: bool = true;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.webhook_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "fetch_webhook_deliveries", skip_all)]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: FetchWebhookDeliveriesInput { webhook_id },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        let mut deliveries = self.repository.fetch_webhook_deliveries(user_id).await?;
+        if let Some(webhook_id) = webhook_id {
+            deliveries.retain(|delivery| delivery.webhook_id == webhook_id);
+        }
+
+        Ok(Output {
+            output: FetchWebhookDeliveriesOutput { deliveries },
+        })
+    }
+}
+
+pub struct DeleteWebhook {
+    pub repository: Arc<dyn WebhookRepository>,
+}
+
+pub struct DeleteWebhookInput {
+    pub id: String,
+}
+
+pub struct DeleteWebhookOutput {
+    pub id: String,
+}
+
+impl Usecase for DeleteWebhook {
+    type Input = DeleteWebhookInput;
+
+    type Output = DeleteWebhookOutput;
+
+    type Error = WebhookError;
+
+    fn new(make: &MakeUsecase) -> Self {
+        Self {
+            repository: make.webhook_repo.clone(),
+        }
+    }
+
+    async fn authorize(
+        &self,
+        principal: Principal,
+        _: &Self::Input,
+    ) -> Result<Principal, Unauthorized> {
+        Ok(principal)
+    }
+
+    #[tracing::instrument(name = "delete_webhook", skip_all)]
+    async fn usecase(
+        &self,
+        Input {
+            principal,
+            input: DeleteWebhookInput { id },
+        }: Input<Self::Input>,
+    ) -> Result<Output<Self::Output>, Error<Self::Error>> {
+        let user_id = principal
+            .user_id()
+            .expect("user id not found. this is a bug");
+
+        if !self.repository.delete_webhook(user_id, &id).await? {
+            return Err(Error::Usecase(WebhookError::NotFound));
+        }
+
+        Ok(Output {
+            output: DeleteWebhookOutput { id },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fixture::{input, principal},
+        repository::memory::MemoryRepository,
+    };
+
+    use super::*;
+
+    fn create_input(url: &str, template: Option<&str>) -> CreateWebhookInput {
+        CreateWebhookInput {
+            url: url.into(),
+            secret: Some(" ".into()),
+            requirement: Requirement::Must,
+            categories: Vec::new(),
+            template: template.map(Into::into),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_then_delete() {
+        let repository = Arc::new(MemoryRepository::new());
+        let create = CreateWebhook {
+            repository: repository.clone(),
+        };
+
+        let webhook = create
+            .usecase(input(create_input(
+                " https://chat.ymgyt.io/hook ",
+                Some(r#"{"text": "{{title}}"}"#),
+            )))
+            .await
+            .unwrap()
+            .output
+            .webhook;
+        assert_eq!(webhook.url, "https://chat.ymgyt.io/hook");
+        assert_eq!(webhook.user_id, "alice");
+        // Blank secret is replaced with generated one
+        assert_eq!(webhook.secret.len(), 64);
+        assert_eq!(
+            webhook.template,
+            Some(serde_json::json!({ "text": "{{title}}" }))
+        );
+
+        for (url, template) in [
+            ("ftp://chat.ymgyt.io", None),
+            ("chat.ymgyt.io/hook", None),
+            ("https://chat.ymgyt.io/hook", Some("{text}")),
+            ("http://169.254.169.254/latest/meta-data", None),
+            ("http://localhost:8080/hook", None),
+        ] {
+            assert!(matches!(
+                create.usecase(input(create_input(url, template))).await,
+                Err(Error::Usecase(
+                    WebhookError::InvalidUrl(_)
+                        | WebhookError::InvalidTemplate(_)
+                        | WebhookError::LocalAddress(_)
+                ))
+            ));
+        }
+
+        let fetch = FetchWebhooks {
+            repository: repository.clone(),
+        };
+        let webhooks = fetch
+            .usecase(input(FetchWebhooksInput {}))
+            .await
+            .unwrap()
+            .output
+            .webhooks;
+        assert_eq!(webhooks, vec![webhook.clone()]);
+
+        let delete = DeleteWebhook { repository };
+        delete
+            .usecase(input(DeleteWebhookInput {
+                id: webhook.id.clone(),
+            }))
+            .await
+            .unwrap();
+        assert!(matches!(
+            delete
+                .usecase(input(DeleteWebhookInput { id: webhook.id }))
+                .await,
+            Err(Error::Usecase(WebhookError::NotFound))
+        ));
+    }
+
+    #[tokio::test]
+    async fn webhooks_quota() {
+        let repository = Arc::new(MemoryRepository::new());
+        let create = CreateWebhook {
+            repository: repository.clone(),
+        };
+        for _ in 0..config::webhook::MAX_WEBHOOKS_PER_USER {
+            assert!(create
+                .authorize(principal(), &create_input("https://chat.ymgyt.io", None))
+                .await
+                .is_ok());
+            create
+                .usecase(input(create_input("https://chat.ymgyt.io", None)))
+                .await
+                .unwrap();
+        }
+
+        assert!(matches!(
+            create
+                .authorize(principal(), &create_input("https://chat.ymgyt.io", None))
+                .await,
+            Err(Unauthorized::QuotaExceeded(QuotaExceeded {
+                resource: "webhooks",
+                ..
+            }))
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+//! Webhooks must not reach the network of synd-api such as the cloud metadata endpoint and internal services.
+//! Urls are checked on registration, and resolved addresses are checked again on delivery
+//! since the host name can resolve to another address after registration
+
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+};
+
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use thiserror::Error;
+use url::{Host, Url};
+
+#[derive(Error, Debug, Clone)]
+#[error("webhook must not be delivered to local address: {0}")]
+pub struct LocalAddress(pub String);
+
+/// Reject the url whose host is a local address or a local host name.
+/// Other host names are checked after they are resolved on delivery
+pub fn check_url(url: &Url) -> Result<(), LocalAddress> {
+    let local = match url.host() {
+        Some(Host::Ipv4(ip)) => !is_global(IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => !is_global(IpAddr::V6(ip)),
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.');
+            domain.eq_ignore_ascii_case("localhost") || domain.ends_with(".localhost")
+        }
+        None => false,
+    };
+    if local {
+        Err(LocalAddress(url.host_str().unwrap_or_default().to_owned()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Whether the address is reachable on the internet.
+/// Loopback, private, link-local and the other special purpose addresses are not
+fn is_global(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_global_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_global_v4(ip),
+            None => is_global_v6(ip),
+        },
+    }
+}
+
+fn is_global_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(a == 0
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_multicast()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        // Shared address space(100.64.0.0/10)
+        || (a == 100 && (b & 0b1100_0000) == 64)
+        // IETF protocol assignments(192.0.0.0/24)
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking(198.18.0.0/15)
+        || (a == 198 && (b & 0xfe) == 18)
+        // Reserved(240.0.0.0/4)
+        || a >= 240)
+}
+
+fn is_global_v6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        // Unique local(fc00::/7)
+        || (segments[0] & 0xfe00) == 0xfc00
+        // Link-local(fe80::/10) and deprecated site-local(fec0::/10)
+        || (segments[0] & 0xffc0) == 0xfe80
+        || (segments[0] & 0xffc0) == 0xfec0
+        // Documentation(2001:db8::/32)
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+        // NAT64(64:ff9b::/96) embeds the ipv4 address
+        || (segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0]
+            && !is_global_v4(Ipv4Addr::from(
+                (u32::from(segments[6]) << 16) | u32::from(segments[7]),
+            ))))
+}
+
+/// Resolve host names by the system resolver and drop the addresses which are not global,
+/// so that the connection is made only to the checked addresses
+pub(super) struct GlobalResolver;
+
+impl GlobalResolver {
+    pub(super) fn new() -> Arc<Self> {
+        Arc::new(Self)
+    }
+}
+
+impl Resolve for GlobalResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|addr| is_global(addr.ip()))
+                .collect::<Vec<SocketAddr>>();
+            if addrs.is_empty() {
+                return Err(LocalAddress(host.to_owned()).into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reject_local_addresses() {
+        for url in [
+            "http://127.0.0.1:8080/hook",
+            "http://10.0.0.1/hook",
+            "http://172.16.0.1/hook",
+            "http://192.168.1.1/hook",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/hook",
+            "http://0.0.0.0/hook",
+            "http://[::1]/hook",
+            "http://[fd00::1]/hook",
+            "http://[fe80::1]/hook",
+            "http://[::ffff:127.0.0.1]/hook",
+            "http://[64:ff9b::a9fe:a9fe]/hook",
+            "http://localhost:8080/hook",
+            "http://api.localhost./hook",
+        ] {
+            assert!(check_url(&Url::parse(url).unwrap()).is_err(), "{url}");
+        }
+        for url in [
+            "https://chat.ymgyt.io/hook",
+            "http://8.8.8.8/hook",
+            "http://[2606:4700:4700::1111]/hook",
+            "http://[64:ff9b::808:808]/hook",
+        ] {
+            assert!(check_url(&Url::parse(url).unwrap()).is_ok(), "{url}");
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::{collections::HashMap, time::Instant};
+
+use synd_feed::types::FeedUrl;
+
+use crate::repository::{RepositoryError, SubscriptionRepository, WebhookRepository};
+
+/// Users who have webhooks by the feeds they subscribe.
+/// Scanning all users on each update is avoided by rebuilding the index only when it is expired
+pub(super) struct SubscriberIndex {
+    users: HashMap<FeedUrl, Vec<String>>,
+    built_at: Instant,
+}
+
+impl SubscriberIndex {
+    pub(super) async fn build(
+        subscription_repo: &dyn SubscriptionRepository,
+        webhook_repo: &dyn WebhookRepository,
+    ) -> Result<Self, RepositoryError> {
+        let built_at = Instant::now();
+        let mut users = HashMap::<FeedUrl, Vec<String>>::new();
+        for user_id in subscription_repo.fetch_user_ids().await? {
+            if webhook_repo.fetch_webhooks(&user_id).await?.is_empty() {
+                continue;
+            }
+            for url in subscription_repo
+                .fetch_subscribed_feeds(&user_id)
+                .await?
+                .urls
+            {
+                users.entry(url).or_default().push(user_id.clone());
+            }
+        }
+        Ok(Self { users, built_at })
+    }
+
+    pub(super) fn built_at(&self) -> Instant {
+        self.built_at
+    }
+
+    pub(super) fn users(&self, url: &FeedUrl) -> &[String] {
+        self.users.get(url).map_or(&[], Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use synd_feed::types::Requirement;
+
+    use crate::{
+        fixture::{subscription, url},
+        repository::{memory::MemoryRepository, types::Webhook},
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn index_users_with_webhooks() {
+        let repository = MemoryRepository::new();
+        let (a, b) = (
+            url("https://a.ymgyt.io/feed"),
+            url("https://b.ymgyt.io/feed"),
+        );
+        for (user_id, url) in [("alice", &a), ("alice", &b), ("bob", &a)] {
+            repository
+                .put_feed_subscription(subscription(user_id, url))
+                .await
+                .unwrap();
+        }
+        repository
+            .put_webhook(Webhook::register(
+                "alice",
+                "https://chat.ymgyt.io/hook",
+                None,
+                Requirement::Must,
+                Vec::new(),
+                None,
+                chrono::Utc::now(),
+            ))
+            .await
+            .unwrap();
+
+        let index = SubscriberIndex::build(&repository, &repository)
+            .await
+            .unwrap();
+        assert_eq!(index.users(&a), ["alice"]);
+        assert_eq!(index.users(&b), ["alice"]);
+        assert!(index.users(&url("https://c.ymgyt.io/feed")).is_empty());
+    }
+}
@@ -0,0 +1,456 @@
+//! Deliver new entries of the subscribed feeds to the webhooks registered by users
+
+use std::{sync::Arc, time::Duration};
+
+use futures_util::{stream::FuturesUnordered, StreamExt};
+use reqwest::{header, redirect, StatusCode};
+use synd_feed::{
+    feed::cache::{FeedUpdate, FeedUpdates},
+    types::{Annotated, Entry, FeedMeta, FeedUrl},
+};
+use synd_o11y::metric;
+use thiserror::Error;
+use tokio::sync::{Mutex, Semaphore};
+use url::Url;
+
+use crate::{
+    config,
+    dependency::Repositories,
+    repository::{
+        types::{SubscribedFeeds, Webhook, WebhookDelivery},
+        RepositoryError, SubscriptionRepository, WebhookRepository,
+    },
+    usecase::SubscriptionFilter,
+};
+
+mod address;
+pub use address::{check_url, LocalAddress};
+mod index;
+use index::SubscriberIndex;
+mod payload;
+pub use payload::{default_template, render, sign};
+
+#[derive(Error, Debug)]
+enum SendError {
+    #[error("invalid webhook url: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error(transparent)]
+    LocalAddress(LocalAddress),
+    #[error(transparent)]
+    Request(reqwest::Error),
+}
+
+impl From<reqwest::Error> for SendError {
+    /// Addresses rejected by the resolver are reported in the source of the request error
+    fn from(err: reqwest::Error) -> Self {
+        let mut source = std::error::Error::source(&err);
+        while let Some(cause) = source {
+            if let Some(local) = cause.downcast_ref::<LocalAddress>() {
+                return SendError::LocalAddress(local.clone());
+            }
+            source = cause.source();
+        }
+        SendError::Request(err)
+    }
+}
+
+/// Post new entries to the webhooks whenever feeds are refreshed.
+/// Results are recorded in the delivery log of the user
+pub struct WebhookWorker {
+    subscription_repo: Arc<dyn SubscriptionRepository>,
+    webhook_repo: Arc<dyn WebhookRepository>,
+    client: reqwest::Client,
+    max_attempts: u32,
+    retry_backoff: Duration,
+    allow_private_addresses: bool,
+    /// Built on the first update
+    index: Mutex<Option<SubscriberIndex>>,
+    deliveries: Semaphore,
+}
+
+impl WebhookWorker {
+    pub fn new(repositories: &Repositories) -> anyhow::Result<Self> {
+        Ok(Self {
+            subscription_repo: repositories.subscription.clone(),
+            webhook_repo: repositories.webhook.clone(),
+            client: Self::client(false)?,
+            max_attempts: config::webhook::MAX_ATTEMPTS,
+            retry_backoff: config::webhook::RETRY_BACKOFF,
+            allow_private_addresses: false,
+            index: Mutex::new(None),
+            deliveries: Semaphore::new(config::webhook::MAX_CONCURRENT_DELIVERIES),
+        })
+    }
+
+    /// Redirects are not followed since the location is not checked
+    fn client(allow_private_addresses: bool) -> reqwest::Result<reqwest::Client> {
+        let builder = reqwest::ClientBuilder::new()
+            .user_agent(config::USER_AGENT)
+            .timeout(config::webhook::REQUEST_TIMEOUT)
+            .connect_timeout(config::webhook::REQUEST_TIMEOUT)
+            .redirect(redirect::Policy::none());
+        if allow_private_addresses {
+            builder.build()
+        } else {
+            builder.dns_resolver(address::GlobalResolver::new()).build()
+        }
+    }
+
+    /// Allow delivering to loopback, private and link-local addresses such as local receivers in tests
+    pub fn with_private_addresses(self) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: Self::client(true)?,
+            allow_private_addresses: true,
+            ..self
+        })
+    }
+
+    #[must_use]
+    pub fn with_retry(self, max_attempts: u32, retry_backoff: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            retry_backoff,
+            ..self
+        }
+    }
+
+    /// Deliver updates until the feed cache is dropped.
+    /// Each update is delivered concurrently so that slow receivers do not delay the others
+    pub async fn run(self, mut updates: FeedUpdates) {
+        let worker = Arc::new(self);
+        while let Some(update) = updates.recv().await {
+            if update.new_entries.is_empty() {
+                continue;
+            }
+            let worker = Arc::clone(&worker);
+            tokio::spawn(async move {
+                if let Err(err) = worker.deliver(&update).await {
+                    tracing::warn!(url = %update.url, "Failed to deliver webhooks: {err}");
+                }
+            });
+        }
+    }
+
+    /// Deliver new entries of the update to the webhooks of the users who subscribe the feed.
+    /// Return the number of deliveries
+    #[tracing::instrument(name = "webhook::deliver", skip_all, fields(url = %update.url))]
+    pub async fn deliver(&self, update: &FeedUpdate) -> Result<usize, RepositoryError> {
+        let mut deliveries = FuturesUnordered::new();
+        for user_id in self.subscribers(&update.url).await? {
+            // Webhooks and subscriptions may have changed since the index was built
+            let webhooks = self.webhook_repo.fetch_webhooks(&user_id).await?;
+            let SubscribedFeeds {
+                urls, annotations, ..
+            } = self
+                .subscription_repo
+                .fetch_subscribed_feeds(&user_id)
+                .await?;
+            if !urls.contains(&update.url) {
+                continue;
+            }
+            let feed = Arc::new(
+                annotations
+                    .and_then(|mut annotations| annotations.remove(&update.url))
+                    .unwrap_or_default()
+                    .annotate(update.feed.meta().clone()),
+            );
+
+            for webhook in webhooks
+                .into_iter()
+                .filter(|webhook| Self::matches(webhook, &feed))
+            {
+                deliveries.push(self.deliver_webhook(
+                    user_id.clone(),
+                    webhook,
+                    Arc::clone(&feed),
+                    &update.new_entries,
+                ));
+            }
+        }
+
+        // Other webhooks are delivered even if recording a delivery fails
+        let (mut delivered, mut error) = (0, None);
+        while let Some(result) = deliveries.next().await {
+            match result {
+                Ok(n) => delivered += n,
+                Err(err) => error = Some(err),
+            }
+        }
+        error.map_or(Ok(delivered), Err)
+    }
+
+    /// Users who have webhooks and subscribe the feed. The index is rebuilt if expired
+    async fn subscribers(&self, url: &FeedUrl) -> Result<Vec<String>, RepositoryError> {
+        // Hold the lock while building so that concurrent updates wait for the same index
+        let mut index = self.index.lock().await;
+        let expired = index.as_ref().map_or(true, |index| {
+            index.built_at().elapsed() > config::webhook::SUBSCRIBER_INDEX_TTL
+        });
+        if expired {
+            *index =
+                Some(SubscriberIndex::build(&*self.subscription_repo, &*self.webhook_repo).await?);
+        }
+        Ok(index
+            .as_ref()
+            .map(|index| index.users(url).to_vec())
+            .unwrap_or_default())
+    }
+
+    /// Post the entries to the webhook in order.
+    /// Webhooks are delivered concurrently up to [`config::webhook::MAX_CONCURRENT_DELIVERIES`]
+    async fn deliver_webhook(
+        &self,
+        user_id: String,
+        webhook: Webhook,
+        feed: Arc<Annotated<FeedMeta>>,
+        entries: &[Entry],
+    ) -> Result<usize, RepositoryError> {
+        let _permit = self
+            .deliveries
+            .acquire()
+            .await
+            .expect("semaphore is never closed");
+
+        for entry in entries {
+            let delivery = self.post(&webhook, &feed, entry).await;
+            metric!(
+                monotonic_counter.webhook.delivery = 1,
+                succeeded = delivery.succeeded()
+            );
+            self.webhook_repo
+                .put_webhook_delivery(&user_id, delivery)
+                .await?;
+        }
+        Ok(entries.len())
+    }
+
+    fn matches(webhook: &Webhook, feed: &Annotated<FeedMeta>) -> bool {
+        let filter = SubscriptionFilter {
+            requirement: Some(webhook.requirement),
+            categories: (!webhook.categories.is_empty()).then(|| webhook.categories.clone()),
+            ..Default::default()
+        };
+        filter.subscription(feed.feed.url(), feed.requirement, feed.category.as_ref())
+    }
+
+    /// Post the entry and retry with exponential backoff on server errors
+    async fn post(
+        &self,
+        webhook: &Webhook,
+        feed: &Annotated<FeedMeta>,
+        entry: &Entry,
+    ) -> WebhookDelivery {
+        let payload = render(webhook.template.as_ref(), feed, entry);
+        let body = serde_json::to_vec(&payload).expect("json value is always serializable");
+        let mut delivery = WebhookDelivery {
+            id: hex::encode(rand::random::<[u8; 8]>()),
+            webhook_id: webhook.id.clone(),
+            feed_url: feed.feed.url().clone(),
+            entry_id: entry.id().to_string(),
+            entry_title: entry.title().map(ToOwned::to_owned),
+            attempts: 0,
+            status_code: None,
+            error: None,
+            delivered_at: chrono::Utc::now(),
+        };
+
+        let mut backoff = self.retry_backoff;
+        loop {
+            delivery.attempts += 1;
+            let retryable = match self.send(webhook, &delivery.id, body.clone()).await {
+                Ok(status) => {
+                    delivery.status_code = Some(status.as_u16());
+                    delivery.error = (!status.is_success())
+                        .then(|| format!("unexpected response status {status}"));
+                    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(err) => {
+                    delivery.status_code = None;
+                    delivery.error = Some(err.to_string());
+                    matches!(err, SendError::Request(_))
+                }
+            };
+            if delivery.succeeded() || !retryable || delivery.attempts >= self.max_attempts {
+                break;
+            }
+            tracing::debug!(
+                webhook_id = webhook.id,
+                attempts = delivery.attempts,
+                "Retry webhook delivery"
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        }
+
+        delivery.delivered_at = chrono::Utc::now();
+        delivery
+    }
+
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery_id: &str,
+        body: Vec<u8>,
+    ) -> Result<StatusCode, SendError> {
+        let url = Url::parse(&webhook.url)?;
+        if !self.allow_private_addresses {
+            check_url(&url).map_err(SendError::LocalAddress)?;
+        }
+        let signature = sign(&webhook.secret, &body);
+        let response = self
+            .client
+            .post(url)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(config::webhook::DELIVERY_HEADER, delivery_id)
+            .header(config::webhook::SIGNATURE_HEADER, signature)
+            .body(body)
+            .send()
+            .await?;
+        Ok(response.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use synd_feed::types::{Category, Requirement};
+    use synd_test::mock::WEBHOOK_SECRET;
+    use tokio::net::TcpListener;
+
+    use crate::{
+        fixture::{feed, subscription, url},
+        repository::{memory::MemoryRepository, types::FeedSubscription},
+    };
+
+    use super::*;
+
+    fn update(url: &FeedUrl) -> FeedUpdate {
+        let feed = feed(url, &[("1", 2), ("2", 1)]);
+        let new_entries = feed.entries().cloned().collect();
+        FeedUpdate {
+            url: url.clone(),
+            feed: Arc::new(feed),
+            new_entries,
+        }
+    }
+
+    async fn subscribe(repositories: &Repositories, url: &FeedUrl, requirement: Requirement) {
+        repositories
+            .subscription
+            .put_feed_subscription(FeedSubscription {
+                requirement: Some(requirement),
+                category: Some(Category::new("rust").unwrap()),
+                ..subscription("alice", url)
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn register(repositories: &Repositories, url: String, secret: &str) -> Webhook {
+        let webhook = Webhook::register(
+            "alice",
+            url,
+            Some(secret.to_owned()),
+            Requirement::Must,
+            Vec::new(),
+            Some(serde_json::json!({ "text": "{{feed_url}} {{title}}" })),
+            chrono::Utc::now(),
+        );
+        repositories
+            .webhook
+            .put_webhook(webhook.clone())
+            .await
+            .unwrap();
+        webhook
+    }
+
+    #[tokio::test]
+    async fn deliver_must_entries() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let receiver = format!("http://{}/webhook", listener.local_addr().unwrap());
+        tokio::spawn(synd_test::mock::serve(listener));
+
+        let repositories = Repositories::new(MemoryRepository::new());
+        let must = url("https://must.ymgyt.io/feed");
+        let should = url("https://should.ymgyt.io/feed");
+        subscribe(&repositories, &must, Requirement::Must).await;
+        subscribe(&repositories, &should, Requirement::Should).await;
+        let chat = register(&repositories, format!("{receiver}/chat"), WEBHOOK_SECRET).await;
+        let flaky = register(&repositories, format!("{receiver}/flaky"), WEBHOOK_SECRET).await;
+        let forged = register(&repositories, format!("{receiver}/forged"), "forged").await;
+
+        let worker = WebhookWorker::new(&repositories)
+            .unwrap()
+            .with_private_addresses()
+            .unwrap()
+            .with_retry(3, Duration::from_millis(10));
+        assert_eq!(worker.deliver(&update(&should)).await.unwrap(), 0);
+        assert_eq!(worker.deliver(&update(&must)).await.unwrap(), 6);
+
+        for name in ["chat", "flaky"] {
+            let payloads: Vec<serde_json::Value> = reqwest::get(format!("{receiver}/{name}"))
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            assert_eq!(
+                payloads,
+                vec![
+                    serde_json::json!({ "text": "https://must.ymgyt.io/feed 1" }),
+                    serde_json::json!({ "text": "https://must.ymgyt.io/feed 2" }),
+                ]
+            );
+        }
+
+        let deliveries = repositories
+            .webhook
+            .fetch_webhook_deliveries("alice")
+            .await
+            .unwrap();
+        let attempts = |webhook: &Webhook| {
+            deliveries
+                .iter()
+                .filter(|delivery| delivery.webhook_id == webhook.id)
+                .map(|delivery| {
+                    (
+                        delivery.attempts,
+                        delivery.status_code,
+                        delivery.succeeded(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(attempts(&chat), vec![(1, Some(200), true); 2]);
+        // Server errors are retried
+        assert_eq!(attempts(&flaky), vec![(2, Some(200), true); 2]);
+        // Client errors are not retried
+        assert_eq!(attempts(&forged), vec![(1, Some(401), false); 2]);
+    }
+
+    #[tokio::test]
+    async fn reject_local_address() {
+        let repositories = Repositories::new(MemoryRepository::new());
+        let must = url("https://must.ymgyt.io/feed");
+        subscribe(&repositories, &must, Requirement::Must).await;
+        register(
+            &repositories,
+            "http://169.254.169.254/latest/meta-data".into(),
+            WEBHOOK_SECRET,
+        )
+        .await;
+
+        let worker = WebhookWorker::new(&repositories).unwrap();
+        assert_eq!(worker.deliver(&update(&must)).await.unwrap(), 2);
+
+        let deliveries = repositories
+            .webhook
+            .fetch_webhook_deliveries("alice")
+            .await
+            .unwrap();
+        for delivery in deliveries {
+            // Not retried
+            assert_eq!((delivery.attempts, delivery.status_code), (1, None));
+            assert!(delivery.error.unwrap().contains("local address"));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use synd_feed::types::{Annotated, Entry, FeedMeta};
+
+use crate::usecase::SubscriptionFilter;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Payload used when the webhook does not have its own template.
+/// `text` is understood by Slack compatible incoming webhooks
+pub fn default_template() -> Value {
+    serde_json::json!({
+        "text": "[{{feed_title}}] {{title}}\n{{url}}",
+    })
+}
+
+/// Render the payload by replacing `{{placeholder}}` in string values of the template.
+/// Unknown placeholders are left as they are
+pub fn render(template: Option<&Value>, feed: &Annotated<FeedMeta>, entry: &Entry) -> Value {
+    let variables = Variables::new(feed, entry);
+    let mut payload = template.cloned().unwrap_or_else(default_template);
+    variables.replace(&mut payload);
+    payload
+}
+
+/// Signature sent in the signature header so that receivers can verify the payload.
+/// Formatted as `sha256={hex(hmac_sha256(secret, body))}`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("hmac accepts any key size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+struct Variables {
+    values: [(&'static str, String); 9],
+}
+
+impl Variables {
+    fn new(feed: &Annotated<FeedMeta>, entry: &Entry) -> Self {
+        let feed_url = feed.feed.url().to_string();
+        let feed_title = feed
+            .title
+            .as_deref()
+            .or(feed.feed.title())
+            .map_or_else(|| feed_url.clone(), ToOwned::to_owned);
+        let published = entry
+            .published()
+            .or(entry.updated())
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default();
+
+        Self {
+            values: [
+                ("entry_id", entry.id().to_string()),
+                ("title", entry.title().unwrap_or_default().to_owned()),
+                (
+                    "url",
+                    entry
+                        .website_url(feed.feed.r#type())
+                        .unwrap_or_default()
+                        .to_owned(),
+                ),
+                ("summary", entry.summary().unwrap_or_default().to_owned()),
+                ("published", published),
+                ("feed_title", feed_title),
+                ("feed_url", feed_url),
+                (
+                    "requirement",
+                    feed.requirement
+                        .unwrap_or(SubscriptionFilter::DEFAULT_REQUIREMENT)
+                        .to_string(),
+                ),
+                (
+                    "category",
+                    feed.category
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                ),
+            ],
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn replace(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.replace_str(s),
+            Value::Array(values) => values.iter_mut().for_each(|value| self.replace(value)),
+            Value::Object(map) => map.values_mut().for_each(|value| self.replace(value)),
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+    }
+
+    /// Substituted values are not scanned again so that entry titles can not inject placeholders
+    fn replace_str(&self, mut s: &str) -> String {
+        let mut rendered = String::with_capacity(s.len());
+        while let Some(start) = s.find("{{") {
+            let Some(end) = s[start..].find("}}").map(|end| start + end) else {
+                break;
+            };
+            rendered.push_str(&s[..start]);
+            match self.get(s[start + 2..end].trim()) {
+                Some(value) => rendered.push_str(value),
+                None => rendered.push_str(&s[start..end + 2]),
+            }
+            s = &s[end + 2..];
+        }
+        rendered.push_str(s);
+        rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use synd_feed::types::{Category, Feed, FeedUrl, Requirement};
+
+    use super::*;
+
+    fn feed() -> Feed {
+        let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+            <id>test</id><title>ymgyt blog</title>
+            <entry>
+                <id>1</id><title>Rust {{url}}</title>
+                <link href="https://blog.ymgyt.io/entry/1"/>
+                <published>2024-06-01T00:00:00Z</published>
+            </entry>
+        </feed>"#;
+        Feed::from((
+            FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap(),
+            feed_rs::parser::parse(atom.as_bytes()).unwrap(),
+        ))
+    }
+
+    #[test]
+    fn render_template() {
+        let feed = feed();
+        let entry = feed.entries().next().unwrap();
+        let mut meta = Annotated::new(feed.meta().clone());
+        meta.requirement = Some(Requirement::Must);
+        meta.category = Some(Category::new("rust").unwrap());
+
+        assert_eq!(
+            render(None, &meta, entry),
+            serde_json::json!({
+                "text": "[ymgyt blog] Rust {{url}}\nhttps://blog.ymgyt.io/entry/1",
+            })
+        );
+
+        let template = serde_json::json!({
+            "blocks": [{ "text": "{{ requirement }}/{{category}} {{unknown}} {{title" }],
+            "published": "{{published}}",
+            "count": 1,
+        });
+        assert_eq!(
+            render(Some(&template), &meta, entry),
+            serde_json::json!({
+                "blocks": [{ "text": "MUST/rust {{unknown}} {{title" }],
+                "published": "2024-06-01T00:00:00+00:00",
+                "count": 1,
+            })
+        );
+    }
+
+    #[test]
+    fn sign_payload() {
+        // echo -n '{"text":"hello"}' | openssl dgst -sha256 -hmac secret
+        assert_eq!(
+            sign("secret", br#"{"text":"hello"}"#),
+            "sha256=3b3b2696b97f30066225d75f057c5960f6518d7a42d500f01f4704290c7fdf8a"
+        );
+    }
+}
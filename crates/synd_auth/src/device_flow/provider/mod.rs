@@ -1,4 +1,5 @@
 pub mod github;
 pub mod google;
+pub mod oidc;
 
-pub use {github::Github, google::Google};
+pub use {github::Github, google::Google, oidc::Oidc};
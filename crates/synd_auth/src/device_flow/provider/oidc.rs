@@ -0,0 +1,78 @@
+use std::borrow::Cow;
+
+use reqwest::Url;
+
+use crate::{
+    device_flow::{DeviceAccessTokenRequest, DeviceAuthorizationRequest, Provider},
+    oidc::ProviderMetadata,
+};
+
+/// Generic `OpenID` Connect provider which supports the device authorization grant
+#[derive(Clone)]
+pub struct Oidc {
+    client_id: Cow<'static, str>,
+    device_authorization_endpoint: Url,
+    token_endpoint: Url,
+    scope: Cow<'static, str>,
+}
+
+impl Oidc {
+    /// `offline_access` is requested to get the refresh token
+    const SCOPE: &'static str = "openid email offline_access";
+
+    /// Construct `Oidc` from discovered metadata.
+    /// Return error if the provider does not support the device authorization grant
+    pub fn new(
+        metadata: &ProviderMetadata,
+        client_id: impl Into<Cow<'static, str>>,
+    ) -> anyhow::Result<Self> {
+        let Some(device_authorization_endpoint) = metadata.device_authorization_endpoint.as_deref()
+        else {
+            anyhow::bail!(
+                "oidc provider {} does not support device authorization grant",
+                metadata.issuer
+            );
+        };
+
+        Ok(Self {
+            client_id: client_id.into(),
+            device_authorization_endpoint: Url::parse(device_authorization_endpoint)?,
+            token_endpoint: Url::parse(&metadata.token_endpoint)?,
+            scope: Self::SCOPE.into(),
+        })
+    }
+
+    #[must_use]
+    pub fn with_scope(self, scope: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            scope: scope.into(),
+            ..self
+        }
+    }
+}
+
+impl Provider for Oidc {
+    type DeviceAccessTokenRequest<'d> = DeviceAccessTokenRequest<'d>;
+
+    fn device_authorization_endpoint(&self) -> Url {
+        self.device_authorization_endpoint.clone()
+    }
+
+    fn token_endpoint(&self) -> Url {
+        self.token_endpoint.clone()
+    }
+
+    fn device_authorization_request(&self) -> DeviceAuthorizationRequest {
+        DeviceAuthorizationRequest {
+            client_id: self.client_id.clone(),
+            scope: self.scope.clone(),
+        }
+    }
+
+    fn device_access_token_request<'d, 'p: 'd>(
+        &'p self,
+        device_code: &'d str,
+    ) -> DeviceAccessTokenRequest<'d> {
+        DeviceAccessTokenRequest::new(device_code, self.client_id.as_ref())
+    }
+}
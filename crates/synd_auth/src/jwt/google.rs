@@ -9,25 +9,12 @@ use chrono::{DateTime, TimeZone, Utc};
 use jsonwebtoken::{DecodingKey, Validation};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use thiserror::Error;
 
 use crate::{config, USER_AGENT};
 
-type Kid = String;
+pub use crate::jwt::JwtError;
 
-#[derive(Debug, Error)]
-pub enum JwtError {
-    #[error("fetch pem: {0}")]
-    FetchPem(#[from] reqwest::Error),
-    #[error("decoding key pem not found")]
-    DecodingKeyPemNotFound,
-    #[error("decode id token: {0}")]
-    Decode(#[from] jsonwebtoken::errors::Error),
-    #[error("invalid jwt header: {0}")]
-    InvalidHeader(String),
-    #[error("refresh id token: {0}")]
-    RefreshToken(reqwest::Error),
-}
+type Kid = String;
 
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
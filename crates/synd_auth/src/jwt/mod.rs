@@ -1 +1,26 @@
+use thiserror::Error;
+
+use crate::oidc::DiscoveryError;
+
 pub mod google;
+pub mod oidc;
+
+#[derive(Debug, Error)]
+pub enum JwtError {
+    #[error("fetch pem: {0}")]
+    FetchPem(#[from] reqwest::Error),
+    #[error("decoding key pem not found")]
+    DecodingKeyPemNotFound,
+    #[error("decode id token: {0}")]
+    Decode(#[from] jsonwebtoken::errors::Error),
+    #[error("invalid jwt header: {0}")]
+    InvalidHeader(String),
+    #[error("refresh id token: {0}")]
+    RefreshToken(reqwest::Error),
+    #[error("discover oidc provider: {0}")]
+    Discovery(#[from] DiscoveryError),
+    #[error("fetch jwks: {0}")]
+    FetchJwks(reqwest::Error),
+    #[error("decoding key not found: {kid}")]
+    DecodingKeyNotFound { kid: String },
+}
@@ -0,0 +1,264 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, TimeZone, Utc};
+use jsonwebtoken::{
+    jwk::{AlgorithmParameters, JwkSet, PublicKeyUse},
+    DecodingKey, Validation,
+};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::{jwt::JwtError, oidc::ProviderMetadata, USER_AGENT};
+
+type Kid = String;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct Claims {
+    iss: String,
+    sub: String,
+    pub email: Option<String>,
+    #[serde(default)]
+    pub email_verified: bool,
+    exp: i64,
+}
+
+impl Claims {
+    /// Return this `Claims` expire at given `DateTime`
+    pub fn is_expired_at(&self, t: DateTime<Utc>) -> bool {
+        self.exp < t.timestamp()
+    }
+
+    /// Return `DateTime` at when `Claims` expire
+    pub fn expired_at(&self) -> DateTime<Utc> {
+        Utc.timestamp_opt(self.exp, 0)
+            .single()
+            .unwrap_or_else(Utc::now)
+    }
+}
+
+/// Tokens returned by refreshing
+pub struct RefreshedTokens {
+    pub id_token: String,
+    /// Some providers rotate the refresh token
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Default)]
+struct KeyCache {
+    keys: HashMap<Kid, Arc<DecodingKey>>,
+    refreshed_at: Option<Instant>,
+}
+
+/// Validate id tokens issued by a generic `OpenID` Connect provider.
+/// The provider metadata is discovered from the issuer on first use
+#[derive(Clone)]
+pub struct JwtService {
+    client: Client,
+    issuer: Cow<'static, str>,
+    client_id: Cow<'static, str>,
+    metadata: Arc<RwLock<Option<Arc<ProviderMetadata>>>>,
+    key_cache: Arc<RwLock<KeyCache>>,
+}
+
+impl JwtService {
+    /// Unknown kids do not trigger fetching jwks more frequently than this interval
+    /// so that a lot of invalid id tokens do not flood the provider
+    const MIN_JWKS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub fn new(
+        issuer: impl Into<Cow<'static, str>>,
+        client_id: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let client = reqwest::ClientBuilder::new()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap();
+
+        Self {
+            client,
+            issuer: issuer.into(),
+            client_id: client_id.into(),
+            metadata: Arc::new(RwLock::default()),
+            key_cache: Arc::new(RwLock::default()),
+        }
+    }
+
+    pub fn issuer(&self) -> &str {
+        self.issuer.as_ref()
+    }
+
+    pub fn client_id(&self) -> &str {
+        self.client_id.as_ref()
+    }
+
+    /// Return the provider metadata. discovery document is fetched only once
+    pub async fn metadata(&self) -> Result<Arc<ProviderMetadata>, JwtError> {
+        if let Some(metadata) = self.metadata.read().unwrap().as_ref() {
+            return Ok(metadata.clone());
+        }
+
+        let metadata = Arc::new(ProviderMetadata::discover(&self.client, &self.issuer).await?);
+        *self.metadata.write().unwrap() = Some(metadata.clone());
+
+        Ok(metadata)
+    }
+
+    /// Decode and validate JWT id token
+    pub async fn decode_id_token(&self, id_token: &str) -> Result<Claims, JwtError> {
+        let header = jsonwebtoken::decode_header(id_token).map_err(JwtError::Decode)?;
+        let kid = header
+            .kid
+            .ok_or_else(|| JwtError::InvalidHeader("kid not found".into()))?;
+        let decoding_key = self.lookup_decoding_key(&kid).await?;
+        let validation = {
+            let mut v = Validation::new(header.alg);
+            v.set_audience(&[self.client_id.as_ref()]);
+            v.set_issuer(&[self.issuer.as_ref()]);
+            v.set_required_spec_claims(&["exp", "iss", "aud"]);
+            v.validate_exp = true;
+            v
+        };
+
+        jsonwebtoken::decode(id_token, &decoding_key, &validation)
+            .map_err(JwtError::Decode)
+            .map(|data| data.claims)
+    }
+
+    /// Decode JWT id token without signature validation
+    pub fn decode_id_token_insecure(
+        &self,
+        id_token: &str,
+        validate_exp: bool,
+    ) -> Result<Claims, JwtError> {
+        let decoding_key = DecodingKey::from_secret(&[]);
+        let validation = {
+            let mut v = Validation::default();
+            v.insecure_disable_signature_validation();
+            v.set_audience(&[self.client_id.as_ref()]);
+            v.set_issuer(&[self.issuer.as_ref()]);
+            v.set_required_spec_claims(&["exp"]);
+            v.validate_exp = validate_exp;
+            v
+        };
+
+        jsonwebtoken::decode(id_token, &decoding_key, &validation)
+            .map_err(JwtError::Decode)
+            .map(|data| data.claims)
+    }
+
+    async fn lookup_decoding_key(&self, kid: &str) -> Result<Arc<DecodingKey>, JwtError> {
+        let should_refresh = {
+            let cache = self.key_cache.read().unwrap();
+            if let Some(key) = cache.keys.get(kid) {
+                return Ok(key.clone());
+            }
+            cache
+                .refreshed_at
+                .map_or(true, |at| at.elapsed() >= Self::MIN_JWKS_REFRESH_INTERVAL)
+        };
+
+        if should_refresh {
+            self.refresh_key_cache().await?;
+        }
+
+        self.key_cache
+            .read()
+            .unwrap()
+            .keys
+            .get(kid)
+            .cloned()
+            .ok_or_else(|| JwtError::DecodingKeyNotFound {
+                kid: kid.to_owned(),
+            })
+    }
+
+    async fn refresh_key_cache(&self) -> Result<(), JwtError> {
+        let metadata = self.metadata().await?;
+        let jwks = self
+            .client
+            .get(&metadata.jwks_uri)
+            .header(http::header::ACCEPT, "application/json")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(JwtError::FetchJwks)?
+            .json::<JwkSet>()
+            .await
+            .map_err(JwtError::FetchJwks)?;
+
+        let keys = jwks
+            .keys
+            .into_iter()
+            // Only asymmetric signing keys are used to verify id tokens
+            .filter(|jwk| {
+                !matches!(jwk.algorithm, AlgorithmParameters::OctetKey(_))
+                    && !matches!(jwk.common.public_key_use, Some(PublicKeyUse::Encryption))
+            })
+            .filter_map(|jwk| {
+                let kid = jwk.common.key_id.clone()?;
+                match DecodingKey::from_jwk(&jwk) {
+                    Ok(key) => Some((kid, Arc::new(key))),
+                    Err(err) => {
+                        tracing::warn!("failed to create jwt decoding key from jwk: {err}");
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        let mut cache = self.key_cache.write().unwrap();
+        cache.keys = keys;
+        cache.refreshed_at = Some(Instant::now());
+
+        Ok(())
+    }
+
+    /// Refresh id token with the refresh token
+    /// <https://openid.net/specs/openid-connect-core-1_0.html#RefreshTokens>
+    pub async fn refresh_id_token(&self, refresh_token: &str) -> Result<RefreshedTokens, JwtError> {
+        #[derive(Serialize)]
+        struct Request<'s> {
+            client_id: &'s str,
+            refresh_token: &'s str,
+            grant_type: &'static str,
+        }
+
+        #[derive(Deserialize)]
+        struct Response {
+            id_token: String,
+            refresh_token: Option<String>,
+        }
+
+        let metadata = self.metadata().await?;
+        let request = &Request {
+            client_id: self.client_id.as_ref(),
+            refresh_token,
+            grant_type: "refresh_token",
+        };
+        let response = self
+            .client
+            .post(&metadata.token_endpoint)
+            .header(http::header::ACCEPT, "application/json")
+            .form(request)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(JwtError::RefreshToken)?
+            .json::<Response>()
+            .await
+            .map_err(JwtError::RefreshToken)?;
+
+        Ok(RefreshedTokens {
+            id_token: response.id_token,
+            refresh_token: response.refresh_token,
+        })
+    }
+}
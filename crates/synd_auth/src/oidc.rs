@@ -0,0 +1,74 @@
+//! `OpenID` Connect provider discovery
+//! <https://openid.net/specs/openid-connect-discovery-1_0.html>
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DiscoveryError {
+    #[error("fetch discovery document: {0}")]
+    Fetch(#[from] reqwest::Error),
+    #[error("issuer mismatch. expected: {expected} actual: {actual}")]
+    IssuerMismatch { expected: String, actual: String },
+}
+
+/// Subset of the provider metadata which syndicationd requires
+/// <https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata>
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderMetadata {
+    pub issuer: String,
+    pub jwks_uri: String,
+    pub token_endpoint: String,
+    /// <https://datatracker.ietf.org/doc/html/rfc8628#section-4>
+    #[serde(default)]
+    pub device_authorization_endpoint: Option<String>,
+}
+
+impl ProviderMetadata {
+    const WELL_KNOWN_PATH: &'static str = "/.well-known/openid-configuration";
+
+    /// Return the url of the discovery document of given issuer
+    pub fn discovery_url(issuer: &str) -> String {
+        format!("{}{}", issuer.trim_end_matches('/'), Self::WELL_KNOWN_PATH)
+    }
+
+    /// Fetch the discovery document and validate that its issuer is identical to given issuer
+    pub async fn discover(client: &Client, issuer: &str) -> Result<Self, DiscoveryError> {
+        let metadata = client
+            .get(Self::discovery_url(issuer))
+            .header(http::header::ACCEPT, "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ProviderMetadata>()
+            .await?;
+
+        // https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderConfigurationValidation
+        if metadata.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+            return Err(DiscoveryError::IssuerMismatch {
+                expected: issuer.to_owned(),
+                actual: metadata.issuer,
+            });
+        }
+
+        Ok(metadata)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery_url() {
+        assert_eq!(
+            ProviderMetadata::discovery_url("https://auth.example.com/realms/synd/"),
+            "https://auth.example.com/realms/synd/.well-known/openid-configuration",
+        );
+        assert_eq!(
+            ProviderMetadata::discovery_url("https://dex.example.com"),
+            "https://dex.example.com/.well-known/openid-configuration",
+        );
+    }
+}
@@ -1,15 +1,22 @@
 use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
+use tokio::sync::broadcast;
 
 use crate::{
-    feed::service::{FetchFeed, FetchFeedResult},
+    feed::{
+        health::FeedHealth,
+        service::{FetchFeed, FetchFeedResult},
+    },
     types::{self, FeedUrl},
 };
 
 mod periodic_refresher;
 pub use periodic_refresher::PeriodicRefresher;
 
+mod update;
+pub use update::{FeedUpdate, FeedUpdates};
+
 type Cache = moka::future::Cache<FeedUrl, Arc<types::Feed>>;
 
 #[derive(Clone, Copy)]
@@ -54,6 +61,34 @@ pub trait FetchCachedFeed: Send + Sync {
         &self,
         urls: &[FeedUrl],
     ) -> Vec<FetchFeedResult<Arc<types::Feed>>>;
+    /// Return the health of the feed. None if the health is not recorded
+    fn health(&self, _url: &FeedUrl) -> Option<FeedHealth> {
+        None
+    }
+    /// Fetch the feed bypassing the cache and replace the cached one
+    async fn refresh_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<types::Feed>> {
+        self.fetch_feed(url).await
+    }
+    /// Return the statistics of the cache
+    async fn stats(&self) -> CacheStats {
+        CacheStats::default()
+    }
+}
+
+/// Statistics of the feed cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of the cached feeds
+    pub entry_count: u64,
+    /// Approximate size of the cached feeds in bytes
+    pub weighted_size: u64,
+    /// Max size of the cache in bytes
+    pub max_capacity: u64,
+}
+
+pub trait SubscribeFeedUpdates: Send + Sync {
+    /// Subscribe to feeds which are updated with new entries
+    fn subscribe(&self) -> FeedUpdates;
 }
 
 #[derive(Clone)]
@@ -62,6 +97,8 @@ pub struct CacheLayer<S> {
     // Use Arc to avoid expensive clone
     // https://github.com/moka-rs/moka?tab=readme-ov-file#avoiding-to-clone-the-value-at-get
     cache: Cache,
+    updates: broadcast::Sender<FeedUpdate>,
+    max_cache_size: u64,
 }
 impl<S> CacheLayer<S> {
     /// Construct `CacheLayer` with default config
@@ -83,8 +120,14 @@ impl<S> CacheLayer<S> {
             .max_capacity(max_cache_size)
             .time_to_live(time_to_live)
             .build();
+        let (updates, _) = broadcast::channel(update::CHANNEL_CAPACITY);
 
-        Self { service, cache }
+        Self {
+            service,
+            cache,
+            updates,
+            max_cache_size,
+        }
     }
 }
 
@@ -94,6 +137,16 @@ where
 {
     pub fn periodic_refresher(&self) -> PeriodicRefresher<S> {
         PeriodicRefresher::new(self.service.clone(), self.cache.clone())
+            .with_updates(self.updates.clone())
+    }
+}
+
+impl<S> SubscribeFeedUpdates for CacheLayer<S>
+where
+    S: Send + Sync,
+{
+    fn subscribe(&self) -> FeedUpdates {
+        FeedUpdates::new(self.updates.subscribe())
     }
 }
 
@@ -139,4 +192,29 @@ where
 
         results
     }
+
+    fn health(&self, url: &FeedUrl) -> Option<FeedHealth> {
+        self.service.health(url)
+    }
+
+    #[tracing::instrument(skip_all, fields(%url))]
+    async fn refresh_feed(&self, url: FeedUrl) -> FetchFeedResult<Arc<types::Feed>> {
+        let feed = self.service.fetch_feed(url.clone()).await.map(Arc::new)?;
+
+        // Subscribers are notified in the same way as the periodic refresh
+        update::insert(&self.cache, &self.updates, url, Arc::clone(&feed)).await;
+
+        Ok(feed)
+    }
+
+    async fn stats(&self) -> CacheStats {
+        // Counts are updated lazily, apply pending insertions and evictions first
+        self.cache.run_pending_tasks().await;
+
+        CacheStats {
+            entry_count: self.cache.entry_count(),
+            weighted_size: self.cache.weighted_size(),
+            max_capacity: self.max_cache_size,
+        }
+    }
 }
@@ -1,27 +1,38 @@
 use std::{sync::Arc, time::Duration};
 
 use synd_o11y::metric;
+use tokio::sync::broadcast;
 use tracing::{error, info, warn};
 
 use crate::feed::service::FetchFeed;
 
-use super::Cache;
+use super::{update, Cache, FeedUpdate};
 
 pub struct PeriodicRefresher<S> {
     service: S,
     cache: Cache,
+    updates: broadcast::Sender<FeedUpdate>,
     emit_metrics: bool,
 }
 
 impl<S> PeriodicRefresher<S> {
     pub fn new(service: S, cache: Cache) -> Self {
+        // Updates are not published unless the refresher is obtained from `CacheLayer`
+        let (updates, _) = broadcast::channel(update::CHANNEL_CAPACITY);
         Self {
             service,
             cache,
+            updates,
             emit_metrics: false,
         }
     }
 
+    /// Publish the updates of the feeds to the subscribers of the channel
+    #[must_use]
+    pub(super) fn with_updates(self, updates: broadcast::Sender<FeedUpdate>) -> Self {
+        Self { updates, ..self }
+    }
+
     #[must_use]
     pub fn with_emit_metrics(self, emit_metrics: bool) -> Self {
         Self {
@@ -55,7 +66,7 @@ where
             let feed_url = Arc::unwrap_or_clone(feed_url);
             match self.service.fetch_feed(feed_url.clone()).await {
                 Ok(new_feed) => {
-                    self.cache.insert(feed_url, Arc::new(new_feed)).await;
+                    update::insert(&self.cache, &self.updates, feed_url, Arc::new(new_feed)).await;
                 }
                 Err(err) => {
                     warn!(
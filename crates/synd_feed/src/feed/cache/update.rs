@@ -0,0 +1,148 @@
+use std::{collections::HashSet, sync::Arc};
+
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::types::{Entry, Feed, FeedUrl};
+
+use super::Cache;
+
+/// Updates exceeding this are dropped for lagging subscribers
+pub(super) const CHANNEL_CAPACITY: usize = 256;
+
+/// Feed inserted into the cache which has entries not seen in the previously cached feed
+#[derive(Debug, Clone)]
+pub struct FeedUpdate {
+    pub url: FeedUrl,
+    pub feed: Arc<Feed>,
+    /// Entries which were not in the previously cached feed
+    pub new_entries: Vec<Entry>,
+}
+
+/// Receiver of [`FeedUpdate`]
+pub struct FeedUpdates {
+    rx: broadcast::Receiver<FeedUpdate>,
+}
+
+impl FeedUpdates {
+    pub(super) fn new(rx: broadcast::Receiver<FeedUpdate>) -> Self {
+        Self { rx }
+    }
+
+    /// Wait for the next update.
+    /// Return None if the cache is dropped.
+    /// Lagging subscribers miss the oldest updates instead of blocking the cache
+    pub async fn recv(&mut self) -> Option<FeedUpdate> {
+        loop {
+            match self.rx.recv().await {
+                Ok(update) => return Some(update),
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "Feed update subscriber lagged");
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// Insert the feed into the cache and notify subscribers of its new entries.
+/// If the feed is not cached, there is no way to know which entries are new, so nothing is notified
+pub(super) async fn insert(
+    cache: &Cache,
+    updates: &broadcast::Sender<FeedUpdate>,
+    url: FeedUrl,
+    feed: Arc<Feed>,
+) {
+    let prev = cache.get(&url).await;
+    cache.insert(url.clone(), Arc::clone(&feed)).await;
+
+    if updates.receiver_count() == 0 {
+        return;
+    }
+    let Some(prev) = prev else {
+        return;
+    };
+    let new_entries = new_entries(&prev, &feed);
+    if new_entries.is_empty() {
+        return;
+    }
+
+    tracing::debug!(
+        url = url.as_str(),
+        new_entries = new_entries.len(),
+        "Notify feed update"
+    );
+    // Error means that there are no receivers
+    updates
+        .send(FeedUpdate {
+            url,
+            feed,
+            new_entries,
+        })
+        .ok();
+}
+
+fn new_entries(prev: &Feed, current: &Feed) -> Vec<Entry> {
+    let seen = prev.entries().map(Entry::id_ref).collect::<HashSet<_>>();
+    current
+        .entries()
+        .filter(|entry| !seen.contains(&entry.id_ref()))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt::Write as _, time::Duration};
+
+    use super::*;
+
+    fn feed(entry_ids: &[&str]) -> Feed {
+        let entries = entry_ids.iter().fold(String::new(), |mut xml, id| {
+            let _ = write!(xml, "<entry><title>{id}</title><id>{id}</id></entry>");
+            xml
+        });
+        let atom = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom"><title>test</title><id>test</id>{entries}</feed>"#
+        );
+        Feed::from((
+            url(),
+            feed_rs::parser::parse(atom.as_bytes()).expect("parse atom"),
+        ))
+    }
+
+    fn url() -> FeedUrl {
+        FeedUrl::try_from("https://ymgyt.io/feed").unwrap()
+    }
+
+    #[tokio::test]
+    async fn notify_only_new_entries() {
+        let cache = Cache::new(10);
+        let (tx, rx) = broadcast::channel(CHANNEL_CAPACITY);
+        let mut updates = FeedUpdates::new(rx);
+
+        // First insertion has no baseline
+        insert(&cache, &tx, url(), Arc::new(feed(&["1", "2"]))).await;
+        // No new entries
+        insert(&cache, &tx, url(), Arc::new(feed(&["2", "1"]))).await;
+        insert(&cache, &tx, url(), Arc::new(feed(&["3", "2", "1"]))).await;
+
+        let update = tokio::time::timeout(Duration::from_secs(1), updates.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(update.url, url());
+        assert_eq!(
+            update
+                .new_entries
+                .iter()
+                .map(|entry| entry.id().to_string())
+                .collect::<Vec<_>>(),
+            vec!["3".to_owned()],
+        );
+        assert!(updates.rx.try_recv().is_err());
+
+        drop(tx);
+        assert!(updates.recv().await.is_none());
+    }
+}
@@ -0,0 +1,174 @@
+//! Health of feeds observed on the fetch path.
+//! Each fetch outcome of `FeedService` is recorded so that
+//! feeds which have been failing for a long time can be noticed.
+//! Healths are kept in memory only, so they are lost when the process restarts.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    feed::service::{FetchFeedError, FetchFeedResult},
+    types::FeedUrl,
+};
+
+/// Classification of the error occurred while fetching the feed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum FetchErrorKind {
+    /// Failed to connect or the request timed out
+    Network,
+    /// Server responded with the error status
+    HttpStatus,
+    /// Response body exceeded the limit
+    ResponseLimitExceeded,
+    /// Response body is not a valid feed
+    InvalidFeed,
+    /// Fetching is disallowed by robots.txt
+    DisallowedByRobotsTxt,
+    /// Server requested to retry later
+    RetryAfter,
+    Other,
+}
+
+/// Accumulated fetch outcomes of the feed
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeedHealth {
+    /// Time of the last successful fetch
+    pub last_success: Option<DateTime<Utc>>,
+    /// Time of the last failed fetch
+    pub last_failure: Option<DateTime<Utc>>,
+    /// Kind of the last error. None if the last fetch succeeded
+    pub last_error: Option<FetchErrorKind>,
+    /// Number of failures since the last success
+    pub consecutive_failures: u32,
+    /// HTTP status of the last response
+    pub last_http_status: Option<u16>,
+    /// Average latency of the requests sent
+    pub average_latency: Option<Duration>,
+    latency_samples: u32,
+}
+
+impl FeedHealth {
+    fn record<T>(
+        &mut self,
+        result: &FetchFeedResult<T>,
+        http_status: Option<u16>,
+        latency: Duration,
+        now: DateTime<Utc>,
+    ) {
+        match result {
+            Ok(_) => {
+                self.last_success = Some(now);
+                self.last_error = None;
+                self.consecutive_failures = 0;
+            }
+            Err(err) => {
+                self.last_failure = Some(now);
+                self.last_error = Some(err.kind());
+                self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+            }
+        }
+        if http_status.is_some() {
+            self.last_http_status = http_status;
+            // cumulative moving average
+            let samples = self.latency_samples.saturating_add(1);
+            let average = self.average_latency.unwrap_or_default();
+            self.average_latency = Some(if latency >= average {
+                average + (latency - average) / samples
+            } else {
+                average - (average - latency) / samples
+            });
+            self.latency_samples = samples;
+        }
+    }
+}
+
+/// Record the health of each feed
+#[derive(Clone)]
+pub(crate) struct HealthRecorder {
+    healths: moka::sync::Cache<FeedUrl, FeedHealth>,
+}
+
+impl Default for HealthRecorder {
+    fn default() -> Self {
+        Self {
+            healths: moka::sync::Cache::builder()
+                .max_capacity(Self::CAPACITY)
+                .time_to_idle(Self::TIME_TO_IDLE)
+                .build(),
+        }
+    }
+}
+
+impl HealthRecorder {
+    /// Upper bound of the feeds whose health is recorded
+    const CAPACITY: u64 = 10_000;
+    /// Health of the feeds which are neither fetched nor looked up for this duration is discarded
+    const TIME_TO_IDLE: Duration = Duration::from_secs(60 * 60 * 24);
+
+    /// Record the outcome of the fetch.
+    /// `http_status` is None if no response was received
+    pub(crate) fn record<T>(
+        &self,
+        url: &FeedUrl,
+        result: &FetchFeedResult<T>,
+        http_status: Option<u16>,
+        latency: Duration,
+    ) {
+        // Deferred fetches did not reach the server, so they tell nothing about the feed
+        if http_status.is_none() && matches!(result, Err(FetchFeedError::RetryAfter(_))) {
+            return;
+        }
+        self.healths.entry_by_ref(url).and_upsert_with(|health| {
+            let mut health = health.map(moka::Entry::into_value).unwrap_or_default();
+            health.record(result, http_status, latency, Utc::now());
+            health
+        });
+    }
+
+    pub(crate) fn health(&self, url: &FeedUrl) -> Option<FeedHealth> {
+        self.healths.get(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_fetch_outcomes() {
+        let recorder = HealthRecorder::default();
+        let url = FeedUrl::try_from("https://blog.ymgyt.io/atom.xml").unwrap();
+        let ok: FetchFeedResult<()> = Ok(());
+        let limit: FetchFeedResult<()> = Err(FetchFeedError::ResponseLimitExceed);
+
+        recorder.record(&url, &ok, Some(200), Duration::from_millis(100));
+        recorder.record(&url, &limit, Some(200), Duration::from_millis(300));
+        recorder.record(&url, &limit, Some(200), Duration::from_millis(200));
+        // deferred without sending request
+        recorder.record(
+            &url,
+            &Err::<(), _>(FetchFeedError::RetryAfter(Duration::from_secs(1))),
+            None,
+            Duration::ZERO,
+        );
+
+        let health = recorder.health(&url).unwrap();
+        assert!(health.last_success.is_some());
+        assert!(health.last_failure.is_some());
+        assert_eq!(
+            health.last_error,
+            Some(FetchErrorKind::ResponseLimitExceeded)
+        );
+        assert_eq!(health.consecutive_failures, 2);
+        assert_eq!(health.last_http_status, Some(200));
+        assert_eq!(health.average_latency, Some(Duration::from_millis(200)));
+
+        recorder.record(&url, &ok, Some(304), Duration::from_millis(200));
+        let health = recorder.health(&url).unwrap();
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.last_error, None);
+        assert_eq!(health.last_http_status, Some(304));
+    }
+}
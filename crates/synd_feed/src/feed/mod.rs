@@ -1,2 +1,4 @@
 pub mod cache;
+pub mod health;
+mod politeness;
 pub mod service;
@@ -0,0 +1,453 @@
+//! Politeness policy which `FeedService` follows as a well-behaved crawler.
+//! * Respect robots.txt ([RFC 9309](https://www.rfc-editor.org/rfc/rfc9309.html)) if enabled
+//! * Honour `Retry-After` header on 429(Too Many Requests) and 503(Service Unavailable)
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use reqwest::{
+    header::{HeaderMap, RETRY_AFTER},
+    StatusCode,
+};
+use url::Url;
+
+use crate::feed::service::{FetchFeedError, FetchFeedResult};
+
+type RobotsCache = moka::future::Cache<String, Arc<RobotsTxt>>;
+
+#[derive(Clone)]
+pub(crate) struct Politeness {
+    // None if robots.txt check is disabled
+    robots: Option<RobotsCache>,
+    // product token of user agent to match robots.txt group
+    product_token: String,
+    // origin => instant until which fetch should be deferred
+    retry_after: moka::sync::Cache<String, Instant>,
+}
+
+impl Politeness {
+    /// Upper bound of robots.txt bytes to be parsed
+    const ROBOTS_TXT_LIMIT: usize = 500 * 1024;
+    const ROBOTS_TXT_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+    const ROBOTS_TXT_CACHE_CAPACITY: u64 = 10_000;
+    /// Upper bound of `Retry-After` to be honoured
+    const MAX_RETRY_AFTER: Duration = Duration::from_secs(60 * 60 * 6);
+    /// Upper bound of the origins whose `Retry-After` is recorded
+    const RETRY_AFTER_CAPACITY: u64 = 10_000;
+
+    pub(crate) fn new(user_agent: &str) -> Self {
+        let product_token = user_agent
+            .split('/')
+            .next()
+            .unwrap_or(user_agent)
+            .trim()
+            .to_owned();
+        Self {
+            robots: None,
+            product_token,
+            // Expire after the longest `Retry-After` so that origins which are never fetched again are evicted
+            retry_after: moka::sync::Cache::builder()
+                .max_capacity(Self::RETRY_AFTER_CAPACITY)
+                .time_to_live(Self::MAX_RETRY_AFTER)
+                .build(),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_robots_txt(self, enable: bool) -> Self {
+        let robots = enable.then(|| {
+            moka::future::Cache::builder()
+                .max_capacity(Self::ROBOTS_TXT_CACHE_CAPACITY)
+                .time_to_live(Self::ROBOTS_TXT_TTL)
+                .build()
+        });
+        Self { robots, ..self }
+    }
+
+    /// Check whether given url could be fetched now
+    pub(crate) async fn check(&self, http: &reqwest::Client, url: &Url) -> FetchFeedResult<()> {
+        let origin = url.origin().ascii_serialization();
+
+        if let Some(remaining) = self.remaining_retry_after(&origin) {
+            return Err(FetchFeedError::RetryAfter(remaining));
+        }
+
+        if let Some(robots) = self.robots.as_ref() {
+            // Unreachable robots.txt is not cached so that it is fetched again next time
+            let Ok(robots_txt) = robots
+                .try_get_with_by_ref(&origin, self.fetch_robots_txt(http, url))
+                .await
+            else {
+                return Err(FetchFeedError::DisallowedByRobotsTxt);
+            };
+            let path = match url.query() {
+                Some(query) => format!("{}?{query}", url.path()),
+                None => url.path().to_owned(),
+            };
+            if !robots_txt.is_allowed(&path) {
+                return Err(FetchFeedError::DisallowedByRobotsTxt);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Inspect the response status and record `Retry-After` if the server requested
+    pub(crate) fn inspect_response(
+        &self,
+        url: &Url,
+        status: StatusCode,
+        headers: &HeaderMap,
+    ) -> FetchFeedResult<()> {
+        if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+            return Ok(());
+        }
+        let Some(retry_after) = headers
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()))
+        else {
+            return Ok(());
+        };
+        let retry_after = retry_after.min(Self::MAX_RETRY_AFTER);
+
+        self.retry_after.insert(
+            url.origin().ascii_serialization(),
+            Instant::now() + retry_after,
+        );
+
+        Err(FetchFeedError::RetryAfter(retry_after))
+    }
+
+    fn remaining_retry_after(&self, origin: &str) -> Option<Duration> {
+        let until = self.retry_after.get(origin)?;
+        let now = Instant::now();
+        if until > now {
+            Some(until - now)
+        } else {
+            self.retry_after.invalidate(origin);
+            None
+        }
+    }
+
+    /// Fetch robots.txt of the origin of given url.
+    /// Return error if robots.txt is unreachable, in which case the crawler must assume complete disallow
+    /// (RFC 9309 2.3.1.4)
+    async fn fetch_robots_txt(
+        &self,
+        http: &reqwest::Client,
+        url: &Url,
+    ) -> Result<Arc<RobotsTxt>, Unreachable> {
+        let mut robots_url = url.clone();
+        robots_url.set_path("/robots.txt");
+        robots_url.set_query(None);
+        robots_url.set_fragment(None);
+
+        let response = match http.get(robots_url).send().await {
+            Ok(response) => response,
+            Err(err) => {
+                tracing::warn!(url = url.as_str(), "Failed to fetch robots.txt: {err}");
+                return Err(Unreachable);
+            }
+        };
+        let status = response.status();
+        if status.is_server_error() {
+            tracing::warn!(url = url.as_str(), %status, "robots.txt is unreachable");
+            return Err(Unreachable);
+        }
+        // 4xx means robots.txt is unavailable, crawler may access any resources (RFC 9309 2.3.1.3)
+        if !status.is_success() {
+            return Ok(Arc::new(RobotsTxt::allow_all()));
+        }
+
+        let mut buff = Vec::new();
+        let mut stream = response.bytes_stream();
+        while let Some(Ok(chunk)) = stream.next().await {
+            buff.extend_from_slice(&chunk);
+            if buff.len() >= Self::ROBOTS_TXT_LIMIT {
+                buff.truncate(Self::ROBOTS_TXT_LIMIT);
+                break;
+            }
+        }
+
+        Ok(Arc::new(RobotsTxt::parse(
+            &String::from_utf8_lossy(&buff),
+            &self.product_token,
+        )))
+    }
+}
+
+/// robots.txt could not be fetched due to server or network errors
+#[derive(Debug)]
+struct Unreachable;
+
+/// Parse `Retry-After` header value which is either delay seconds or http date
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        date.with_timezone(&Utc)
+            .signed_duration_since(now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+/// Rules of robots.txt which apply to the user agent
+#[derive(Debug, Default)]
+pub(crate) struct RobotsTxt {
+    rules: Vec<Rule>,
+}
+
+impl RobotsTxt {
+    fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Parse robots.txt and retain the rules of the group which match given product token.
+    /// If there is no matching group, rules of `*` group are used.
+    pub(crate) fn parse(content: &str, product_token: &str) -> Self {
+        let mut matched = Vec::new();
+        let mut wildcard = Vec::new();
+
+        // user agents of current group
+        let mut agents: Vec<String> = Vec::new();
+        // whether the previous line is user-agent line
+        let mut in_agents = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            if key.eq_ignore_ascii_case("user-agent") {
+                if !in_agents {
+                    agents.clear();
+                }
+                agents.push(value.to_ascii_lowercase());
+                in_agents = true;
+                continue;
+            }
+            in_agents = false;
+
+            let allow = if key.eq_ignore_ascii_case("allow") {
+                true
+            } else if key.eq_ignore_ascii_case("disallow") {
+                false
+            } else {
+                continue;
+            };
+            // Empty disallow means nothing is disallowed
+            if value.is_empty() {
+                continue;
+            }
+            let rule = Rule {
+                allow,
+                pattern: value.to_owned(),
+            };
+
+            if agents
+                .iter()
+                .any(|agent| agent.eq_ignore_ascii_case(product_token))
+            {
+                matched.push(rule.clone());
+            }
+            if agents.iter().any(|agent| agent == "*") {
+                wildcard.push(rule);
+            }
+        }
+
+        let rules = if matched.is_empty() {
+            wildcard
+        } else {
+            matched
+        };
+        Self { rules }
+    }
+
+    /// The most specific(longest) matching rule is used.
+    /// If allow and disallow rules are equally specific, allow is used.
+    pub(crate) fn is_allowed(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| matches(&rule.pattern, path))
+            .max_by(|a, b| {
+                a.pattern
+                    .len()
+                    .cmp(&b.pattern.len())
+                    .then(a.allow.cmp(&b.allow))
+            })
+            .map_or(true, |rule| rule.allow)
+    }
+}
+
+/// Match path against robots.txt pattern which could contain `*` and `$`
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        return !anchored || rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+
+    if anchored {
+        rest.ends_with(last)
+    } else {
+        rest.contains(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    // Respond to robots.txt request with given status and return the url of the feed
+    async fn serve_robots_txt(status: &'static str) -> Url {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf).await.unwrap();
+            let response =
+                format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+        });
+        Url::parse(&format!("http://{addr}/feed.xml")).unwrap()
+    }
+
+    #[tokio::test]
+    async fn robots_txt_server_error_disallow_all() {
+        let politeness = Politeness::new("synd-test").with_robots_txt(true);
+        let http = reqwest::Client::new();
+
+        let url = serve_robots_txt("503 Service Unavailable").await;
+        assert!(matches!(
+            politeness.check(&http, &url).await,
+            Err(FetchFeedError::DisallowedByRobotsTxt)
+        ));
+        assert!(politeness
+            .robots
+            .as_ref()
+            .unwrap()
+            .get(&url.origin().ascii_serialization())
+            .await
+            .is_none());
+
+        let url = serve_robots_txt("500 Internal Server Error").await;
+        assert!(matches!(
+            politeness.check(&http, &url).await,
+            Err(FetchFeedError::DisallowedByRobotsTxt)
+        ));
+    }
+
+    #[tokio::test]
+    async fn robots_txt_not_found_allow_all() {
+        let politeness = Politeness::new("synd-test").with_robots_txt(true);
+        let http = reqwest::Client::new();
+
+        let url = serve_robots_txt("404 Not Found").await;
+        assert!(politeness.check(&http, &url).await.is_ok());
+    }
+
+    #[test]
+    fn match_pattern() {
+        assert!(matches("/", "/feed.xml"));
+        assert!(matches("/feed", "/feed.xml"));
+        assert!(!matches("/private", "/feed.xml"));
+        assert!(matches("/*.xml", "/blog/feed.xml"));
+        assert!(matches("/*.xml$", "/blog/feed.xml"));
+        assert!(!matches("/*.xml$", "/blog/feed.xml?page=2"));
+        assert!(matches("/feed.xml$", "/feed.xml"));
+        assert!(!matches("/feed$", "/feed.xml"));
+        assert!(matches("/a*b*c", "/a-b-c-d"));
+    }
+
+    #[test]
+    fn parse_robots_txt() {
+        let content = r"
+# comment
+User-agent: *
+Disallow: /private
+Allow: /private/feed.xml
+
+User-agent: badbot
+User-agent: other
+Disallow: /
+";
+        let robots = RobotsTxt::parse(content, "synd-api");
+        assert!(robots.is_allowed("/feed.xml"));
+        assert!(!robots.is_allowed("/private/index.html"));
+        assert!(robots.is_allowed("/private/feed.xml"));
+
+        let robots = RobotsTxt::parse(content, "BadBot");
+        assert!(!robots.is_allowed("/feed.xml"));
+    }
+
+    #[test]
+    fn specific_group_take_precedence() {
+        let content = "User-agent: *\nDisallow: /\n\nUser-agent: synd-api\nAllow: /\n";
+        let robots = RobotsTxt::parse(content, "synd-api");
+        assert!(robots.is_allowed("/feed.xml"));
+    }
+
+    #[test]
+    fn empty_disallow_allow_all() {
+        let robots = RobotsTxt::parse("User-agent: *\nDisallow:\n", "synd-api");
+        assert!(robots.is_allowed("/feed.xml"));
+    }
+
+    #[test]
+    fn parse_retry_after_header() {
+        let now = DateTime::parse_from_rfc3339("2015-10-21T07:28:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:30:00 GMT", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
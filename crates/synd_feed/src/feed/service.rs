@@ -1,9 +1,31 @@
-use std::{sync::Arc, time::Duration};
+use std::{
+    borrow::Borrow,
+    io,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
+use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder};
 use async_trait::async_trait;
 use feed_rs::parser::{ParseErrorKind, ParseFeedError, Parser};
+use futures_util::StreamExt;
+use reqwest::header::{HeaderMap, ACCEPT_ENCODING, CONTENT_ENCODING};
+use synd_o11y::metric;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::io::StreamReader;
+use url::Url;
 
-use crate::types::{Feed, FeedUrl};
+use crate::{
+    feed::{
+        health::{FeedHealth, FetchErrorKind, HealthRecorder},
+        politeness::Politeness,
+    },
+    types::{Feed, FeedUrl},
+};
 
 pub type FetchFeedResult<T> = std::result::Result<T, FetchFeedError>;
 
@@ -23,15 +45,52 @@ pub enum FetchFeedError {
     JsonUnsupportedVersion(String),
     #[error("xml format error: {0}")]
     XmlFormat(String),
+    #[error("fetching feed is disallowed by robots.txt")]
+    DisallowedByRobotsTxt,
+    #[error("server requested to retry after {}s", .0.as_secs())]
+    RetryAfter(Duration),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+impl FetchFeedError {
+    pub fn kind(&self) -> FetchErrorKind {
+        match self {
+            FetchFeedError::Fetch(err) if err.is_status() => FetchErrorKind::HttpStatus,
+            FetchFeedError::Fetch(_) | FetchFeedError::Io(_) => FetchErrorKind::Network,
+            FetchFeedError::ResponseLimitExceed => FetchErrorKind::ResponseLimitExceeded,
+            FetchFeedError::InvalidFeed(_)
+            | FetchFeedError::JsonFormat(_)
+            | FetchFeedError::JsonUnsupportedVersion(_)
+            | FetchFeedError::XmlFormat(_) => FetchErrorKind::InvalidFeed,
+            FetchFeedError::DisallowedByRobotsTxt => FetchErrorKind::DisallowedByRobotsTxt,
+            FetchFeedError::RetryAfter(_) => FetchErrorKind::RetryAfter,
+            FetchFeedError::Other(_) => FetchErrorKind::Other,
+        }
+    }
+
+    // Recover the reqwest error wrapped to read the body as `AsyncRead`
+    fn from_body_io(err: io::Error) -> Self {
+        if !matches!(err.get_ref(), Some(inner) if inner.is::<reqwest::Error>()) {
+            return FetchFeedError::Io(err);
+        }
+        let inner = err
+            .into_inner()
+            .and_then(|inner| inner.downcast::<reqwest::Error>().ok())
+            .expect("inner error is reqwest error");
+        FetchFeedError::Fetch(*inner)
+    }
+}
+
 #[async_trait]
 pub trait FetchFeed: Send + Sync {
     async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed>;
     /// Fetch feeds by spawning tasks
     async fn fetch_feeds_parallel(&self, urls: &[FeedUrl]) -> FetchFeedResult<Vec<Feed>>;
+    /// Return the health of the feed. None if the health is not recorded
+    fn health(&self, _url: &FeedUrl) -> Option<FeedHealth> {
+        None
+    }
 }
 
 #[async_trait]
@@ -40,11 +99,14 @@ where
     T: FetchFeed,
 {
     async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
-        self.fetch_feed(url).await
+        (**self).fetch_feed(url).await
     }
     /// Fetch feeds by spawning tasks
     async fn fetch_feeds_parallel(&self, urls: &[FeedUrl]) -> FetchFeedResult<Vec<Feed>> {
-        self.fetch_feeds_parallel(urls).await
+        (**self).fetch_feeds_parallel(urls).await
+    }
+    fn health(&self, url: &FeedUrl) -> Option<FeedHealth> {
+        (**self).health(url)
     }
 }
 
@@ -53,33 +115,19 @@ where
 pub struct FeedService {
     http: reqwest::Client,
     buff_limit: usize,
+    politeness: Politeness,
+    health: HealthRecorder,
 }
 
 #[async_trait]
 impl FetchFeed for FeedService {
     async fn fetch_feed(&self, url: FeedUrl) -> FetchFeedResult<Feed> {
-        use futures_util::StreamExt;
-        let mut stream = self
-            .http
-            .get(url.clone().into_inner())
-            .send()
-            .await
-            .map_err(FetchFeedError::Fetch)?
-            .error_for_status()
-            .map_err(FetchFeedError::Fetch)?
-            .bytes_stream();
-
-        let mut buff = Vec::new();
-
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(FetchFeedError::Fetch)?;
-            if buff.len() + chunk.len() > self.buff_limit {
-                return Err(FetchFeedError::ResponseLimitExceed);
-            }
-            buff.extend(chunk);
-        }
-
-        self.parse(url, buff.as_slice())
+        let start = Instant::now();
+        let mut http_status = None;
+        let result = self.fetch(url.clone(), &mut http_status).await;
+        self.health
+            .record(&url, &result, http_status, start.elapsed());
+        result
     }
 
     async fn fetch_feeds_parallel(&self, urls: &[FeedUrl]) -> FetchFeedResult<Vec<Feed>> {
@@ -101,6 +149,10 @@ impl FetchFeed for FeedService {
 
         Ok(feeds)
     }
+
+    fn health(&self, url: &FeedUrl) -> Option<FeedHealth> {
+        self.health.health(url)
+    }
 }
 
 impl FeedService {
@@ -112,7 +164,70 @@ impl FeedService {
             .build()
             .unwrap();
 
-        Self { http, buff_limit }
+        Self {
+            http,
+            buff_limit,
+            politeness: Politeness::new(user_agent),
+            health: HealthRecorder::default(),
+        }
+    }
+
+    /// Check robots.txt of the feed origin before fetching
+    #[must_use]
+    pub fn with_robots_txt(self, enable: bool) -> Self {
+        Self {
+            politeness: self.politeness.with_robots_txt(enable),
+            ..self
+        }
+    }
+
+    /// Fetch the feed and report the status of the response received
+    async fn fetch(&self, url: FeedUrl, http_status: &mut Option<u16>) -> FetchFeedResult<Feed> {
+        let target: &Url = url.borrow();
+        self.politeness.check(&self.http, target).await?;
+
+        let response = self
+            .http
+            .get(target.clone())
+            .header(ACCEPT_ENCODING, ContentEncoding::ACCEPT)
+            .send()
+            .await
+            .map_err(FetchFeedError::Fetch)?;
+        *http_status = Some(response.status().as_u16());
+        self.politeness
+            .inspect_response(target, response.status(), response.headers())?;
+        let response = response.error_for_status().map_err(FetchFeedError::Fetch)?;
+
+        let encoding = ContentEncoding::from_headers(response.headers());
+        let received = AtomicUsize::new(0);
+        let stream = response.bytes_stream().map(|chunk| {
+            chunk
+                .inspect(|chunk| {
+                    received.fetch_add(chunk.len(), Ordering::Relaxed);
+                })
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        });
+        let reader = StreamReader::new(stream);
+        let decoder: Pin<Box<dyn AsyncRead + Send + '_>> = match encoding {
+            ContentEncoding::Identity => Box::pin(reader),
+            ContentEncoding::Gzip => Box::pin(GzipDecoder::new(reader)),
+            ContentEncoding::Brotli => Box::pin(BrotliDecoder::new(reader)),
+        };
+
+        // Enforce the limit on decompressed size to protect against decompression bombs
+        let mut buff = Vec::new();
+        decoder
+            .take(self.buff_limit as u64 + 1)
+            .read_to_end(&mut buff)
+            .await
+            .map_err(FetchFeedError::from_body_io)?;
+        if buff.len() > self.buff_limit {
+            return Err(FetchFeedError::ResponseLimitExceed);
+        }
+
+        Self::emit_metrics(encoding, received.load(Ordering::Relaxed), buff.len());
+
+        self.parse(url, buff.as_slice())
     }
 
     pub fn parse<S>(&self, url: FeedUrl, source: S) -> FetchFeedResult<Feed>
@@ -137,9 +252,174 @@ impl FeedService {
             })
     }
 
+    fn emit_metrics(encoding: ContentEncoding, received: usize, decoded: usize) {
+        if encoding == ContentEncoding::Identity || received == 0 {
+            return;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let ratio = decoded as f64 / received as f64;
+        let encoding = encoding.as_str();
+
+        metric!(histogram.feed.fetch.compression_ratio = ratio, encoding);
+    }
+
     fn build_parser(base_uri: impl AsRef<str>) -> Parser {
         feed_rs::parser::Builder::new()
             .base_uri(Some(base_uri))
             .build()
     }
 }
+
+/// Content encodings which `FeedService` could decode.
+/// Decoding is handled by `FeedService` instead of reqwest
+/// to observe the size of both compressed and decompressed body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentEncoding {
+    Identity,
+    Gzip,
+    Brotli,
+}
+
+impl ContentEncoding {
+    const ACCEPT: &'static str = "gzip, br";
+
+    fn from_headers(headers: &HeaderMap) -> Self {
+        match headers
+            .get(CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+        {
+            Some(encoding) if encoding.eq_ignore_ascii_case("gzip") => ContentEncoding::Gzip,
+            Some(encoding) if encoding.eq_ignore_ascii_case("x-gzip") => ContentEncoding::Gzip,
+            Some(encoding) if encoding.eq_ignore_ascii_case("br") => ContentEncoding::Brotli,
+            _ => ContentEncoding::Identity,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ContentEncoding::Identity => "identity",
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Brotli => "br",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_compression::tokio::bufread::GzipEncoder;
+    use tokio::{io::AsyncWriteExt, net::TcpListener};
+
+    use super::*;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>urn:synd:test</id>
+  <title>test</title>
+  <updated>2024-01-01T00:00:00Z</updated>
+  <entry>
+    <id>urn:synd:test:1</id>
+    <title>entry1</title>
+    <updated>2024-01-01T00:00:00Z</updated>
+  </entry>
+</feed>"#;
+
+    async fn gzip(body: &[u8]) -> Vec<u8> {
+        let mut compressed = Vec::new();
+        GzipEncoder::new(body)
+            .read_to_end(&mut compressed)
+            .await
+            .unwrap();
+        compressed
+    }
+
+    // Serve given response once and return its url
+    async fn serve_once(status: &'static str, headers: &'static str, body: Vec<u8>) -> FeedUrl {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0; 1024];
+            let _ = stream.read(&mut buf).await.unwrap();
+            let header = format!(
+                "HTTP/1.1 {status}\r\n{headers}content-length: {}\r\nconnection: close\r\n\r\n",
+                body.len()
+            );
+            stream.write_all(header.as_bytes()).await.unwrap();
+            stream.write_all(&body).await.unwrap();
+            stream.shutdown().await.unwrap();
+        });
+        FeedUrl::try_from(format!("http://{addr}/feed.xml").as_str()).unwrap()
+    }
+
+    async fn serve_gzip(body: Vec<u8>) -> FeedUrl {
+        serve_once(
+            "200 OK",
+            "content-type: application/atom+xml\r\ncontent-encoding: gzip\r\n",
+            body,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn decode_gzip_response() {
+        let url = serve_gzip(gzip(ATOM.as_bytes()).await).await;
+        let service = FeedService::new("synd-test", 1024 * 1024);
+
+        let feed = service.fetch_feed(url).await.unwrap();
+
+        assert_eq!(feed.meta().title(), Some("test"));
+        assert_eq!(feed.entries().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn limit_decompressed_size() {
+        let limit = 1024 * 1024;
+        let body = vec![b' '; limit * 4];
+        let compressed = gzip(&body).await;
+        assert!(compressed.len() < limit);
+
+        let url = serve_gzip(compressed).await;
+        let service = FeedService::new("synd-test", limit);
+
+        assert!(matches!(
+            service.fetch_feed(url).await,
+            Err(FetchFeedError::ResponseLimitExceed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn record_health() {
+        let service = FeedService::new("synd-test", 1024);
+
+        let url = serve_once("500 Internal Server Error", "", Vec::new()).await;
+        assert!(service.fetch_feed(url.clone()).await.is_err());
+        let health = service.health(&url).unwrap();
+        assert_eq!(health.consecutive_failures, 1);
+        assert_eq!(health.last_error, Some(FetchErrorKind::HttpStatus));
+        assert_eq!(health.last_http_status, Some(500));
+        assert!(health.average_latency.is_some());
+
+        let url = serve_once("200 OK", "", ATOM.as_bytes().to_vec()).await;
+        service.fetch_feed(url.clone()).await.unwrap();
+        let health = service.health(&url).unwrap();
+        assert_eq!(health.consecutive_failures, 0);
+        assert!(health.last_success.is_some());
+    }
+
+    #[tokio::test]
+    async fn honour_retry_after() {
+        let url = serve_once("429 Too Many Requests", "retry-after: 60\r\n", Vec::new()).await;
+        let service = FeedService::new("synd-test", 1024);
+
+        assert!(matches!(
+            service.fetch_feed(url.clone()).await,
+            Err(FetchFeedError::RetryAfter(retry_after)) if retry_after == Duration::from_secs(60)
+        ));
+        // Deferred without sending request
+        assert!(matches!(
+            service.fetch_feed(url).await,
+            Err(FetchFeedError::RetryAfter(retry_after)) if retry_after <= Duration::from_secs(60)
+        ));
+    }
+}
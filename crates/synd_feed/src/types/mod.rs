@@ -1,4 +1,4 @@
-use std::{borrow::Cow, fmt::Display};
+use std::{borrow::Cow, collections::BTreeSet, fmt::Display};
 
 use chrono::{DateTime, Utc};
 use feed_rs::model::{self as feedrs, Generator, Link, Person, Text};
@@ -17,7 +17,7 @@ pub use url::FeedUrl;
 mod feed_type;
 pub use feed_type::FeedType;
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone)]
 pub struct EntryId<'a>(Cow<'a, str>);
 
 impl<'a, T> From<T> for EntryId<'a>
@@ -112,6 +112,12 @@ pub struct Annotated<T> {
     pub feed: T,
     pub requirement: Option<Requirement>,
     pub category: Option<Category<'static>>,
+    /// Title given by the user which overrides the title of the feed
+    pub title: Option<String>,
+    /// Free text note by the user
+    pub note: Option<String>,
+    /// Tags in addition to the category
+    pub tags: BTreeSet<Category<'static>>,
 }
 
 impl<T> Annotated<T> {
@@ -120,6 +126,9 @@ impl<T> Annotated<T> {
             feed: f(&self.feed),
             requirement: self.requirement,
             category: self.category.clone(),
+            title: self.title.clone(),
+            note: self.note.clone(),
+            tags: self.tags.clone(),
         }
     }
 }
@@ -130,6 +139,9 @@ impl<T> Annotated<T> {
             feed,
             requirement: None,
             category: None,
+            title: None,
+            note: None,
+            tags: BTreeSet::new(),
         }
     }
 }
@@ -106,6 +106,29 @@ fn view() -> impl View {
                     // https://opentelemetry.io/docs/specs/semconv/general/metrics/#instrument-units
                     .unit(Unit::new("s")),
             ),
+            "feed.fetch.compression_ratio" => Some(
+                Stream::new()
+                    .name(instrument.name.clone())
+                    .description("ratio of decompressed size to transferred size of feed response")
+                    .aggregation(
+                        opentelemetry_sdk::metrics::Aggregation::ExplicitBucketHistogram {
+                            boundaries: vec![1.0, 1.5, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0, 16.0, 32.0],
+                            record_min_max: false,
+                        },
+                    ),
+            ),
+            "kvsd.pool.wait" => Some(
+                Stream::new()
+                    .name(instrument.name.clone())
+                    .description("duration waiting for a pooled kvsd connection")
+                    .aggregation(
+                        opentelemetry_sdk::metrics::Aggregation::ExplicitBucketHistogram {
+                            boundaries: vec![0.0001, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0],
+                            record_min_max: false,
+                        },
+                    )
+                    .unit(Unit::new("s")),
+            ),
             name => {
                 tracing::debug!(name, "There is no explicit view");
                 None
@@ -13,10 +13,17 @@ pub type RequestSequence = u64;
 pub enum RequestId {
     DeviceFlowDeviceAuthorize,
     DeviceFlowPollAccessToken,
+    LocalLogin,
     FetchEntries,
     FetchSubscription,
     SubscribeFeed,
     UnsubscribeFeed,
+    MoveFeed,
+    CreateFolder,
+    RenameFolder,
+    FetchStarredEntries,
+    StarEntry,
+    UnstarEntry,
 }
 
 /// Mangae in flight requests state
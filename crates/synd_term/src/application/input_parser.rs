@@ -1,4 +1,7 @@
+use std::fmt::Write as _;
+
 use nom::error::VerboseErrorKind;
+use synd_feed::types::Category;
 use thiserror::Error;
 
 use crate::{
@@ -13,6 +16,7 @@ const CTX_REQUIREMENT: &str = "requirement";
 const CTX_CATEGORY: &str = "category";
 const CTX_CATEGORY_POST: &str = "category_post";
 const CTX_URL: &str = "url";
+const CTX_TAG: &str = "tag";
 
 #[derive(Error, Debug, PartialEq, Eq)]
 pub(super) enum ParseFeedError {
@@ -36,10 +40,17 @@ impl<'a> InputParser<'a> {
 #     * \"MAY\"
 #   * For the category, please choose one category of the feed(for example, \"rust\")
 #
+# Optionally, the following lines can follow
+#
+# title: <title to display instead of the feed title>
+# note: <note of the feed>
+# tags: <tag> <tag> ...
+#
 # with '#' will be ignored, and an empty URL aborts the subscription.
 #
 # Example:
 # MUST rust https://this-week-in-rust.org/atom.xml
+# tags: weekly news
 ";
 
     pub(super) fn new(input: &'a str) -> Self {
@@ -55,6 +66,11 @@ impl<'a> InputParser<'a> {
                 if let Some(category) = input.category {
                     input.category = Some(categories.normalize(category));
                 }
+                input.tags = input.tags.map(|tags| {
+                    tags.into_iter()
+                        .map(|tag| categories.normalize(tag))
+                        .collect()
+                });
                 input
             })
             .map_err(|mut verbose_err: NomError| {
@@ -70,6 +86,9 @@ impl<'a> InputParser<'a> {
                     Some((input, VerboseErrorKind::Context(CTX_URL))) => {
                         format!("Invalid url: {input}")
                     }
+                    Some((input, VerboseErrorKind::Context(CTX_TAG))) => {
+                        format!("Invalid tag: {input}")
+                    }
                     Some((input, _)) => format!("Failed to parse input: {input}"),
                     None => "Failed to parse input".to_owned(),
                 };
@@ -78,13 +97,105 @@ impl<'a> InputParser<'a> {
     }
 
     pub(super) fn edit_feed_prompt(feed: &types::Feed) -> String {
-        format!(
-            "{}\n{requirement} {category} {feed_url}",
+        let mut prompt = format!(
+            "{}\n{requirement} {category} {feed_url}\n",
             Self::SUSBSCRIBE_FEED_PROMPT,
             requirement = feed.requirement(),
             category = feed.category(),
             feed_url = feed.url,
-        )
+        );
+        // Annotations not written back are cleared on resubscription
+        if let Some(title) = feed.custom_title.as_deref() {
+            writeln!(prompt, "title: {title}").ok();
+        }
+        if let Some(note) = feed.note.as_deref() {
+            writeln!(prompt, "note: {note}").ok();
+        }
+        if !feed.tags.is_empty() {
+            let tags = feed
+                .tags
+                .iter()
+                .map(Category::as_str)
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(prompt, "tags: {tags}").ok();
+        }
+        prompt
+    }
+}
+
+impl<'a> InputParser<'a> {
+    pub(super) const MOVE_FEED_PROMPT: &'static str =
+        "# Please enter the folder to move the feed into
+#
+#   * Nested folders are separated by '/' (for example, \"blogs/rust\")
+#   * An empty folder moves the feed to the top level
+#
+# with '#' will be ignored.
+#
+# Folders:
+";
+
+    pub(super) const CREATE_FOLDER_PROMPT: &'static str = "# Please enter the folder to create
+#
+#   * Nested folders are separated by '/' (for example, \"blogs/rust\")
+#   * The parent folders must already exist
+#
+# with '#' will be ignored, and an empty folder aborts the creation.
+";
+
+    pub(super) const RENAME_FOLDER_PROMPT: &'static str = "# Please enter the new folder name
+#
+# with '#' will be ignored, and an empty name aborts the renaming.
+";
+
+    pub(super) fn move_feed_prompt(current: Option<&str>, folders: &[String]) -> String {
+        let mut prompt = Self::MOVE_FEED_PROMPT.to_owned();
+        for folder in folders {
+            writeln!(prompt, "# {folder}").ok();
+        }
+        writeln!(prompt, "{}", current.unwrap_or_default()).ok();
+        prompt
+    }
+
+    pub(super) fn rename_folder_prompt(name: &str) -> String {
+        format!("{}{name}\n", Self::RENAME_FOLDER_PROMPT)
+    }
+
+    /// Parse the first line which is not a comment as the '/' separated folder names
+    pub(super) fn parse_folder_path(&self) -> Vec<String> {
+        self.input
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.split('/')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_folder_path() {
+        let prompt = InputParser::move_feed_prompt(Some("blogs/rust"), &["blogs".into()]);
+        assert_eq!(
+            InputParser::new(&prompt).parse_folder_path(),
+            vec!["blogs".to_owned(), "rust".to_owned()]
+        );
+        assert_eq!(
+            InputParser::new(" blogs / rust/ \n").parse_folder_path(),
+            vec!["blogs".to_owned(), "rust".to_owned()]
+        );
+        let prompt = InputParser::move_feed_prompt(None, &[]);
+        assert!(InputParser::new(&prompt).parse_folder_path().is_empty());
     }
 }
 
@@ -92,10 +203,11 @@ mod feed {
     use nom::{
         branch::alt,
         bytes::complete::{tag_no_case, take_while, take_while_m_n},
-        character::complete::{multispace0, multispace1},
+        character::complete::{multispace0, multispace1, not_line_ending, space0, space1},
         combinator::{map, value},
         error::context,
-        sequence::{delimited, Tuple},
+        multi::{fold_many0, separated_list0},
+        sequence::{delimited, preceded, terminated, Tuple},
         AsChar, Finish, IResult, Parser,
     };
     use synd_feed::types::{Category, FeedUrl};
@@ -104,7 +216,7 @@ mod feed {
     use super::NomError;
     use crate::{
         application::input_parser::{
-            comment, CTX_CATEGORY, CTX_CATEGORY_POST, CTX_REQUIREMENT, CTX_URL,
+            comment, CTX_CATEGORY, CTX_CATEGORY_POST, CTX_REQUIREMENT, CTX_TAG, CTX_URL,
         },
         client::mutation::subscribe_feed::{Requirement, SubscribeFeedInput},
     };
@@ -117,7 +229,7 @@ mod feed {
     }
 
     fn feed_input(s: &str) -> IResult<&str, SubscribeFeedInput, NomError> {
-        let (remain, (_, requirement, _, category, _, feed_url, _)) = (
+        let (remain, (_, requirement, _, category, _, feed_url, _, annotations)) = (
             multispace0,
             requirement,
             multispace1,
@@ -125,16 +237,88 @@ mod feed {
             context(CTX_CATEGORY_POST, multispace1),
             url,
             multispace0,
+            annotations,
         )
             .parse(s)?;
+        let Annotations { title, note, tags } = annotations;
         Ok((
             remain,
             SubscribeFeedInput {
                 url: feed_url,
                 requirement: Some(requirement),
                 category: Some(category),
+                title,
+                note,
+                tags,
+            },
+        ))
+    }
+
+    #[derive(Default)]
+    struct Annotations {
+        title: Option<String>,
+        note: Option<String>,
+        tags: Option<Vec<Category<'static>>>,
+    }
+
+    enum Annotation {
+        Title(String),
+        Note(String),
+        Tags(Vec<Category<'static>>),
+    }
+
+    /// Optional lines following the url
+    fn annotations(s: &str) -> IResult<&str, Annotations, NomError> {
+        fold_many0(
+            terminated(annotation, multispace0),
+            Annotations::default,
+            |mut acc, annotation| {
+                match annotation {
+                    Annotation::Title(title) => acc.title = non_empty(title),
+                    Annotation::Note(note) => acc.note = non_empty(note),
+                    Annotation::Tags(tags) => acc.tags = Some(tags),
+                }
+                acc
             },
+        )
+        .parse(s)
+    }
+
+    fn annotation(s: &str) -> IResult<&str, Annotation, NomError> {
+        alt((
+            map(preceded(tag_no_case("title:"), text), Annotation::Title),
+            map(preceded(tag_no_case("note:"), text), Annotation::Note),
+            map(preceded(tag_no_case("tags:"), tags), Annotation::Tags),
         ))
+        .parse(s)
+    }
+
+    fn text(s: &str) -> IResult<&str, String, NomError> {
+        map(not_line_ending, |text: &str| text.trim().to_owned()).parse(s)
+    }
+
+    fn tags(s: &str) -> IResult<&str, Vec<Category<'static>>, NomError> {
+        let (remain, tags) = delimited(
+            space0,
+            separated_list0(space1, context(CTX_TAG, category)),
+            space0,
+        )
+        .parse(s)?;
+        // Anything other than tags remains in the line
+        match not_line_ending::<_, NomError>(remain) {
+            Ok((_, rest)) if !rest.is_empty() => Err(nom::Err::Failure(nom::error::VerboseError {
+                errors: vec![(rest, nom::error::VerboseErrorKind::Context(CTX_TAG))],
+            })),
+            _ => Ok((remain, tags)),
+        }
+    }
+
+    fn non_empty(s: String) -> Option<String> {
+        if s.is_empty() {
+            None
+        } else {
+            Some(s)
+        }
     }
 
     pub fn requirement(s: &str) -> IResult<&str, Requirement, NomError> {
@@ -216,10 +400,52 @@ mod feed {
                     SubscribeFeedInput {
                         url: "https://example.ymgyt.io/atom.xml".try_into().unwrap(),
                         requirement: Some(Requirement::MUST),
-                        category: Some(Category::new("rust").unwrap())
+                        category: Some(Category::new("rust").unwrap()),
+                        title: None,
+                        note: None,
+                        tags: None,
+                    }
+                ))
+            );
+        }
+
+        #[test]
+        fn parse_feed_input_with_annotations() {
+            let input = "MUST rust https://example.ymgyt.io/atom.xml
+title: Example blog
+note:  read on weekends
+tags: blog Weekly
+";
+            assert_eq!(
+                feed_input(input),
+                Ok((
+                    "",
+                    SubscribeFeedInput {
+                        url: "https://example.ymgyt.io/atom.xml".try_into().unwrap(),
+                        requirement: Some(Requirement::MUST),
+                        category: Some(Category::new("rust").unwrap()),
+                        title: Some("Example blog".into()),
+                        note: Some("read on weekends".into()),
+                        tags: Some(vec![
+                            Category::new("blog").unwrap(),
+                            Category::new("weekly").unwrap(),
+                        ]),
                     }
                 ))
             );
+
+            let (_, input) =
+                feed_input("MAY rust https://example.ymgyt.io/atom.xml\ntitle:\ntags:\n").unwrap();
+            assert_eq!(input.title, None);
+            assert_eq!(input.tags, Some(Vec::new()));
+
+            let (_, kind) = feed_input("MAY rust https://example.ymgyt.io/atom.xml\ntags: a-b\n")
+                .finish()
+                .unwrap_err()
+                .errors
+                .pop()
+                .unwrap();
+            assert_eq!(kind, VerboseErrorKind::Context(CTX_TAG));
         }
 
         #[test]
@@ -26,10 +26,11 @@ use crate::{
     job::Jobs,
     keymap::{KeymapId, Keymaps},
     terminal::Terminal,
+    types,
     ui::{
         self,
         components::{
-            authentication::AuthenticateState, filter::FeedFilter, root::Root,
+            authentication::AuthenticateState, entries::Entries, filter::FeedFilter, root::Root,
             subscription::UnsubscribeSelection, tabs::Tab, Components,
         },
         theme::{Palette, Theme},
@@ -140,15 +141,16 @@ impl Application {
             key_handlers.push(event::KeyHandler::Keymaps(keymaps));
             key_handlers
         };
+        let authenticator = authenticator.unwrap_or_else(Authenticator::new);
 
         Self {
             clock: Box::new(SystemClock),
             terminal,
             client,
             jobs: Jobs::new(),
-            components: Components::new(),
+            components: Components::new(authenticator.providers()),
             interactor: interactor.unwrap_or_else(Interactor::new),
-            authenticator: authenticator.unwrap_or_else(Authenticator::new),
+            authenticator,
             in_flight: InFlight::new().with_throbber_timer_interval(config.throbber_timer_interval),
             cache,
             theme,
@@ -273,6 +275,9 @@ impl Application {
                 Some(command) = self.jobs.futures.next() => {
                     Some(command.unwrap())
                 }
+                Some(command) = self.jobs.scheduled.next() => {
+                    Some(command.unwrap())
+                }
                 ()  = self.in_flight.throbber_timer() => {
                     Some(Command::RenderThrobber)
                 }
@@ -318,17 +323,43 @@ impl Application {
                 Command::Idle => {
                     self.handle_idle();
                 }
-                Command::Authenticate => {
-                    if self.components.auth.state() != &AuthenticateState::NotAuthenticated {
-                        continue;
+                Command::Authenticate => match self.components.auth.state() {
+                    AuthenticateState::NotAuthenticated => {
+                        match self.components.auth.selected_provider() {
+                            AuthenticationProvider::Local => {
+                                let prompt = self.components.auth.start_local_login();
+                                self.key_handlers.push(event::KeyHandler::Prompt(prompt));
+                                self.should_render();
+                            }
+                            provider => self.init_device_flow(provider),
+                        }
                     }
-                    let provider = self.components.auth.selected_provider();
-                    self.init_device_flow(provider);
-                }
+                    AuthenticateState::LocalLogin => self.submit_local_login(),
+                    _ => continue,
+                },
                 Command::MoveAuthenticationProvider(direction) => {
-                    self.components.auth.move_selection(direction);
+                    if self.components.auth.state() == &AuthenticateState::LocalLogin {
+                        self.move_local_login_field();
+                    } else {
+                        self.components.auth.move_selection(direction);
+                    }
                     self.should_render();
                 }
+                Command::CancelLocalLogin => {
+                    if self.components.auth.state() == &AuthenticateState::LocalLogin {
+                        self.components.auth.cancel_local_login();
+                        self.key_handlers.remove_prompt();
+                        self.should_render();
+                    }
+                }
+                Command::CompleteLocalLogin {
+                    credential,
+                    request_seq,
+                } => {
+                    self.in_flight.remove(request_seq);
+                    self.key_handlers.remove_prompt();
+                    self.complete_device_authroize_flow(credential);
+                }
                 Command::HandleDeviceFlowAuthorizationResponse {
                     provider,
                     device_authorization,
@@ -346,17 +377,30 @@ impl Application {
                     self.set_credential(credential);
                 }
                 Command::MoveTabSelection(direction) => {
-                    self.keymaps().toggle(KeymapId::Entries);
-                    self.keymaps().toggle(KeymapId::Subscription);
+                    self.keymaps().disable(KeymapId::Entries);
+                    self.keymaps().disable(KeymapId::Subscription);
+                    self.keymaps().disable(KeymapId::Starred);
 
                     match self.components.tabs.move_selection(direction) {
-                        Tab::Feeds if !self.components.subscription.has_subscription() => {
-                            next = Some(Command::FetchSubscription {
-                                after: None,
-                                first: config::client::INITIAL_FEEDS_TO_FETCH,
-                            });
+                        Tab::Entries => self.keymaps().enable(KeymapId::Entries),
+                        Tab::Feeds => {
+                            self.keymaps().enable(KeymapId::Subscription);
+                            if !self.components.subscription.has_subscription() {
+                                next = Some(Command::FetchSubscription {
+                                    after: None,
+                                    first: config::client::INITIAL_FEEDS_TO_FETCH,
+                                });
+                            }
+                        }
+                        Tab::Starred => {
+                            self.keymaps().enable(KeymapId::Starred);
+                            if self.components.starred.count() == 0 {
+                                next = Some(Command::FetchStarredEntries {
+                                    after: None,
+                                    first: config::client::INITIAL_ENTRIES_TO_FETCH,
+                                });
+                            }
                         }
-                        _ => {}
                     }
                     self.should_render();
                 }
@@ -380,6 +424,60 @@ impl Application {
                     self.prompt_feed_edition();
                     self.should_render();
                 }
+                Command::PromptFeedMove => {
+                    self.prompt_feed_move();
+                    self.should_render();
+                }
+                Command::PromptFolderCreation => {
+                    self.prompt_folder_creation();
+                    self.should_render();
+                }
+                Command::ToggleFeedHealthSort => {
+                    self.components.subscription.toggle_sort_by_health();
+                    self.should_render();
+                }
+                Command::ReorderSubscribedFeed(direction) => {
+                    next = self
+                        .components
+                        .subscription
+                        .reorder_selected_feed(direction)
+                        .map(|(url, folder_id, position)| Command::MoveFeed {
+                            url,
+                            folder_id,
+                            position: Some(position),
+                        });
+                }
+                Command::MoveFeed {
+                    url,
+                    folder_id,
+                    position,
+                } => {
+                    self.components
+                        .subscription
+                        .move_feed(&url, folder_id, position);
+                    self.move_feed(url, folder_id, position);
+                    self.should_render();
+                }
+                Command::CompleteMoveFeed { request_seq } => {
+                    self.in_flight.remove(request_seq);
+                    self.should_render();
+                }
+                Command::CreateFolder { name, parent_id } => {
+                    self.create_folder(name, parent_id);
+                    self.should_render();
+                }
+                Command::RenameFolder { folder_id, name } => {
+                    self.rename_folder(folder_id, name);
+                    self.should_render();
+                }
+                Command::CompleteUpsertFolder {
+                    folder,
+                    request_seq,
+                } => {
+                    self.in_flight.remove(request_seq);
+                    self.components.subscription.upsert_folder(folder);
+                    self.should_render();
+                }
                 Command::PromptFeedUnsubscription => {
                     if self.components.subscription.selected_feed().is_some() {
                         self.components.subscription.show_unsubscribe_popup(true);
@@ -466,7 +564,11 @@ impl Application {
                     self.should_render();
                 }
                 Command::OpenFeed => {
-                    self.open_feed();
+                    if self.components.subscription.toggle_selected_folder() {
+                        self.should_render();
+                    } else {
+                        self.open_feed();
+                    }
                 }
                 Command::FetchEntries { after, first } => {
                     self.fetch_entries(Populate::Append, after, first);
@@ -510,20 +612,76 @@ impl Application {
                     self.should_render();
                 }
                 Command::MoveEntry(direction) => {
-                    self.components.entries.move_selection(direction);
+                    self.current_entries().move_selection(direction);
                     self.should_render();
                 }
                 Command::MoveEntryFirst => {
-                    self.components.entries.move_first();
+                    self.current_entries().move_first();
                     self.should_render();
                 }
                 Command::MoveEntryLast => {
-                    self.components.entries.move_last();
+                    self.current_entries().move_last();
                     self.should_render();
                 }
                 Command::OpenEntry => {
                     self.open_entry();
                 }
+                Command::StarEntry => {
+                    self.star_entry();
+                }
+                Command::UnstarEntry => {
+                    self.unstar_entry();
+                }
+                Command::CompleteStarEntry { entry, request_seq } => {
+                    self.in_flight.remove(request_seq);
+                    self.components.starred.upsert_entry(entry);
+                    self.should_render();
+                }
+                Command::CompleteUnstarEntry {
+                    feed_url,
+                    id,
+                    request_seq,
+                } => {
+                    self.in_flight.remove(request_seq);
+                    self.components.starred.remove_entry(&feed_url, &id);
+                    self.should_render();
+                }
+                Command::FetchStarredEntries { after, first } => {
+                    self.fetch_starred_entries(Populate::Append, after, first);
+                }
+                Command::PopulateFetchedStarredEntries {
+                    populate,
+                    payload,
+                    request_seq,
+                } => {
+                    self.in_flight.remove(request_seq);
+                    // paginate
+                    next = payload
+                        .page_info
+                        .has_next_page
+                        .then(|| Command::FetchStarredEntries {
+                            after: payload.page_info.end_cursor.clone(),
+                            first: self
+                                .config
+                                .entries_limit
+                                .saturating_sub(
+                                    self.components.starred.count() + payload.entries.len(),
+                                )
+                                .min(payload.entries.len())
+                                .try_into()
+                                .unwrap_or(0),
+                        });
+                    self.components.starred.update_entries(populate, payload);
+                    self.should_render();
+                }
+                Command::ReloadStarredEntries => {
+                    self.fetch_starred_entries(
+                        Populate::Replace,
+                        None,
+                        config::client::INITIAL_ENTRIES_TO_FETCH,
+                    );
+                    self.should_render();
+                }
                 Command::MoveFilterRequirement(direction) => {
                     let filter = self.components.filter.move_requirement(direction);
                     self.apply_feed_filter(filter);
@@ -590,6 +748,13 @@ impl Application {
                                 url.map(|url| url.to_string()).unwrap_or_default(),
                             )
                         }
+                        SyndApiError::RateLimited { retry_after } => match retry_after {
+                            Some(retry_after) => format!(
+                                "too many requests. please retry after {}s",
+                                retry_after.as_secs()
+                            ),
+                            None => "too many requests. please retry later".to_owned(),
+                        },
                         SyndApiError::BuildRequest(err) => {
                             format!("build request failed: {err} this is a BUG")
                         }
@@ -710,6 +875,26 @@ impl Application {
     }
 
     fn prompt_feed_edition(&mut self) {
+        if let Some(folder) = self.components.subscription.selected_folder() {
+            let folder_id = folder.id;
+            let input = self
+                .interactor
+                .open_editor(InputParser::rename_folder_prompt(&folder.name));
+            // the terminal state becomes strange after editing in the editor
+            self.terminal.force_redraw();
+
+            // folder names do not contain '/' as it separates folders
+            if let Some(name) = InputParser::new(input.as_str())
+                .parse_folder_path()
+                .into_iter()
+                .next()
+            {
+                self.jobs
+                    .futures
+                    .push(future::ready(Ok(Command::RenameFolder { folder_id, name })).boxed());
+            }
+            return;
+        }
         let Some(feed) = self.components.subscription.selected_feed() else {
             return;
         };
@@ -736,6 +921,132 @@ impl Application {
         self.jobs.futures.push(fut);
     }
 
+    fn prompt_feed_move(&mut self) {
+        let subscription = &self.components.subscription;
+        let Some(feed) = subscription.selected_feed() else {
+            return;
+        };
+        let (url, current) = (feed.url.clone(), feed.folder_id);
+        let prompt = InputParser::move_feed_prompt(
+            current
+                .and_then(|id| subscription.folder_path(id))
+                .as_deref(),
+            &subscription.folder_paths(),
+        );
+
+        let input = self.interactor.open_editor(prompt);
+        // the terminal state becomes strange after editing in the editor
+        self.terminal.force_redraw();
+
+        let path = InputParser::new(input.as_str()).parse_folder_path();
+        let folder_id = if path.is_empty() {
+            None
+        } else {
+            let Some(folder_id) = self.components.subscription.folder_by_path(&path) else {
+                let message = format!("Folder not found: {}", path.join("/"));
+                self.jobs
+                    .futures
+                    .push(future::ready(Ok(Command::HandleError { message })).boxed());
+                return;
+            };
+            Some(folder_id)
+        };
+        // moving into the same folder keeps the current position
+        if folder_id == current {
+            return;
+        }
+
+        self.jobs.futures.push(
+            future::ready(Ok(Command::MoveFeed {
+                url,
+                folder_id,
+                position: None,
+            }))
+            .boxed(),
+        );
+    }
+
+    fn prompt_folder_creation(&mut self) {
+        let input = self
+            .interactor
+            .open_editor(InputParser::CREATE_FOLDER_PROMPT);
+        // the terminal state becomes strange after editing in the editor
+        self.terminal.force_redraw();
+
+        let mut path = InputParser::new(input.as_str()).parse_folder_path();
+        let Some(name) = path.pop() else {
+            return;
+        };
+        let command = if path.is_empty() {
+            Command::CreateFolder {
+                name,
+                parent_id: None,
+            }
+        } else {
+            match self.components.subscription.folder_by_path(&path) {
+                Some(parent_id) => Command::CreateFolder {
+                    name,
+                    parent_id: Some(parent_id),
+                },
+                None => Command::HandleError {
+                    message: format!("Folder not found: {}", path.join("/")),
+                },
+            }
+        };
+
+        self.jobs.futures.push(future::ready(Ok(command)).boxed());
+    }
+
+    fn move_feed(
+        &mut self,
+        url: FeedUrl,
+        folder_id: Option<types::FolderId>,
+        position: Option<usize>,
+    ) {
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::MoveFeed);
+        let fut = async move {
+            match client.move_feed(url, folder_id, position).await {
+                Ok(()) => Ok(Command::CompleteMoveFeed { request_seq }),
+                Err(err) => Ok(Command::api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.futures.push(fut);
+    }
+
+    fn create_folder(&mut self, name: String, parent_id: Option<types::FolderId>) {
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::CreateFolder);
+        let fut = async move {
+            match client.create_folder(name, parent_id).await {
+                Ok(folder) => Ok(Command::CompleteUpsertFolder {
+                    folder,
+                    request_seq,
+                }),
+                Err(err) => Ok(Command::api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.futures.push(fut);
+    }
+
+    fn rename_folder(&mut self, folder_id: types::FolderId, name: String) {
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::RenameFolder);
+        let fut = async move {
+            match client.rename_folder(folder_id, name).await {
+                Ok(folder) => Ok(Command::CompleteUpsertFolder {
+                    folder,
+                    request_seq,
+                }),
+                Err(err) => Ok(Command::api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.futures.push(fut);
+    }
+
     fn subscribe_feed(&mut self, input: SubscribeFeedInput) {
         let client = self.client.clone();
         let request_seq = self.in_flight.add(RequestId::SubscribeFeed);
@@ -749,6 +1060,44 @@ impl Application {
         self.jobs.futures.push(fut);
     }
 
+    fn star_entry(&mut self) {
+        let Some(entry) = self.components.entries.selected_entry() else {
+            return;
+        };
+        let (feed_url, id) = (entry.feed_url.clone(), entry.id.clone());
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::StarEntry);
+        let fut = async move {
+            match client.star_entry(feed_url, id).await {
+                Ok(entry) => Ok(Command::CompleteStarEntry { entry, request_seq }),
+                Err(err) => Ok(Command::api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.futures.push(fut);
+    }
+
+    fn unstar_entry(&mut self) {
+        let Some(entry) = self.components.starred.selected_entry() else {
+            return;
+        };
+        let (feed_url, id) = (entry.feed_url.clone(), entry.id.clone());
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::UnstarEntry);
+        let fut = async move {
+            match client.unstar_entry(feed_url.clone(), id.clone()).await {
+                Ok(()) => Ok(Command::CompleteUnstarEntry {
+                    feed_url,
+                    id,
+                    request_seq,
+                }),
+                Err(err) => Ok(Command::api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.futures.push(fut);
+    }
+
     fn unsubscribe_feed(&mut self, url: FeedUrl) {
         let client = self.client.clone();
         let request_seq = self.in_flight.add(RequestId::UnsubscribeFeed);
@@ -777,11 +1126,20 @@ impl Application {
     }
 
     fn open_entry(&mut self) {
-        let Some(entry_website_url) = self.components.entries.selected_entry_website_url() else {
+        let Some(entry_website_url) = self.current_entries().selected_entry_website_url() else {
             return;
         };
+        let entry_website_url = entry_website_url.to_owned();
         self.interactor.open_browser(entry_website_url);
     }
+
+    /// Return entries component of the current tab
+    fn current_entries(&mut self) -> &mut Entries {
+        match self.components.tabs.current() {
+            Tab::Starred => &mut self.components.starred,
+            Tab::Entries | Tab::Feeds => &mut self.components.entries,
+        }
+    }
 }
 
 impl Application {
@@ -831,6 +1189,29 @@ impl Application {
     }
 }
 
+impl Application {
+    #[tracing::instrument(skip(self))]
+    fn fetch_starred_entries(&mut self, populate: Populate, after: Option<String>, first: i64) {
+        if first <= 0 {
+            return;
+        }
+        let client = self.client.clone();
+        let request_seq = self.in_flight.add(RequestId::FetchStarredEntries);
+        let fut = async move {
+            match client.fetch_starred_entries(after, first).await {
+                Ok(payload) => Ok(Command::PopulateFetchedStarredEntries {
+                    populate,
+                    payload,
+                    request_seq,
+                }),
+                Err(err) => Ok(Command::api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.futures.push(fut);
+    }
+}
+
 impl Application {
     #[tracing::instrument(skip(self))]
     fn init_device_flow(&mut self, provider: AuthenticationProvider) {
@@ -884,6 +1265,37 @@ impl Application {
         self.jobs.futures.push(fut);
     }
 
+    fn move_local_login_field(&mut self) {
+        if let Some(prompt) = self.components.auth.move_local_login_field() {
+            self.key_handlers.remove_prompt();
+            self.key_handlers.push(event::KeyHandler::Prompt(prompt));
+        }
+    }
+
+    fn submit_local_login(&mut self) {
+        let Some((username, password)) = self.components.auth.local_login_credential() else {
+            self.move_local_login_field();
+            self.should_render();
+            return;
+        };
+        tracing::info!("Start local login");
+
+        let client = self.client.clone();
+        let now = self.now();
+        let request_seq = self.in_flight.add(RequestId::LocalLogin);
+        let fut = async move {
+            match Authenticator::login_local(&client, &username, &password, now).await {
+                Ok(credential) => Ok(Command::CompleteLocalLogin {
+                    credential,
+                    request_seq,
+                }),
+                Err(err) => Ok(Command::oauth_api_error(err, request_seq)),
+            }
+        }
+        .boxed();
+        self.jobs.futures.push(fut);
+    }
+
     fn complete_device_authroize_flow(&mut self, cred: Verified<Credential>) {
         if let Err(err) = self.cache.persist_credential(&cred) {
             tracing::error!("Failed to save credential to cache: {err}");
@@ -894,7 +1306,13 @@ impl Application {
 
     fn schedule_credential_refreshing(&mut self, cred: &Verified<Credential>) {
         match &**cred {
-            Credential::Github { .. } => {}
+            // Without the refresh token, login again after expiration
+            Credential::Github { .. }
+            | Credential::Local { .. }
+            | Credential::Oidc {
+                refresh_token: None,
+                ..
+            } => {}
             Credential::Google {
                 refresh_token,
                 expired_at,
@@ -919,7 +1337,33 @@ impl Application {
                     }
                 }
                 .boxed();
-                self.jobs.futures.push(fut);
+                self.jobs.scheduled.push(fut);
+            }
+            Credential::Oidc {
+                refresh_token: Some(refresh_token),
+                expired_at,
+                ..
+            } => {
+                let until_expire = expired_at
+                    .sub(config::credential::EXPIRE_MARGIN)
+                    .sub(self.now())
+                    .to_std()
+                    .unwrap_or(config::credential::FALLBACK_EXPIRE);
+                let jwt_service = self.jwt_service().clone();
+                let refresh_token = refresh_token.clone();
+                let fut = async move {
+                    tokio::time::sleep(until_expire).await;
+
+                    tracing::debug!("Refresh oidc credential");
+                    match jwt_service.refresh_oidc_id_token(&refresh_token).await {
+                        Ok(credential) => Ok(Command::RefreshCredential { credential }),
+                        Err(err) => Ok(Command::HandleError {
+                            message: err.to_string(),
+                        }),
+                    }
+                }
+                .boxed();
+                self.jobs.scheduled.push(fut);
             }
         }
     }
@@ -928,6 +1372,7 @@ impl Application {
 impl Application {
     fn apply_feed_filter(&mut self, filter: FeedFilter) {
         self.components.entries.update_filter(filter.clone());
+        self.components.starred.update_filter(filter.clone());
         self.components.subscription.update_filter(filter);
     }
 
@@ -1008,6 +1453,7 @@ impl Application {
         self.terminal.buffer()
     }
 
+    /// Scheduled jobs like credential refreshing are not waited
     pub async fn wait_until_jobs_completed<S>(&mut self, input: &mut S)
     where
         S: Stream<Item = std::io::Result<CrosstermEvent>> + Unpin,
@@ -1,4 +1,6 @@
 use std::ops::Add;
+
+use anyhow::anyhow;
 use synd_auth::{
     device_flow::{provider, DeviceAuthorizationResponse, DeviceFlow},
     jwt,
@@ -6,6 +8,7 @@ use synd_auth::{
 
 use crate::{
     auth::{AuthenticationProvider, Credential, CredentialError, Verified},
+    client::{Client, SyndApiError},
     config,
     types::Time,
 };
@@ -19,15 +22,30 @@ pub struct DeviceFlows {
 #[derive(Clone)]
 pub struct JwtService {
     pub google: jwt::google::JwtService,
+    /// Configured only when the generic `OpenID` Connect provider is specified
+    pub oidc: Option<jwt::oidc::JwtService>,
 }
 
 impl JwtService {
     pub fn new() -> Self {
         Self {
             google: jwt::google::JwtService::default(),
+            oidc: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_oidc(self, oidc: jwt::oidc::JwtService) -> Self {
+        Self {
+            oidc: Some(oidc),
+            ..self
         }
     }
 
+    pub(crate) fn oidc(&self) -> Result<&jwt::oidc::JwtService, CredentialError> {
+        self.oidc.as_ref().ok_or(CredentialError::OidcNotConfigured)
+    }
+
     pub(crate) async fn refresh_google_id_token(
         &self,
         refresh_token: &str,
@@ -49,6 +67,29 @@ impl JwtService {
         };
         Ok(Verified(credential))
     }
+
+    pub(crate) async fn refresh_oidc_id_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<Verified<Credential>, CredentialError> {
+        let oidc = self.oidc()?;
+        let tokens = oidc
+            .refresh_id_token(refresh_token)
+            .await
+            .map_err(CredentialError::RefreshJwt)?;
+        let expired_at = oidc
+            .decode_id_token_insecure(&tokens.id_token, false)
+            .map_err(CredentialError::DecodeJwt)?
+            .expired_at();
+        let credential = Credential::Oidc {
+            id_token: tokens.id_token,
+            refresh_token: tokens
+                .refresh_token
+                .or_else(|| Some(refresh_token.to_owned())),
+            expired_at,
+        };
+        Ok(Verified(credential))
+    }
 }
 
 #[derive(Clone)]
@@ -76,6 +117,36 @@ impl Authenticator {
         }
     }
 
+    /// Enable login with the generic `OpenID` Connect provider
+    #[must_use]
+    pub fn with_oidc(self, oidc: jwt::oidc::JwtService) -> Self {
+        Self {
+            jwt_service: self.jwt_service.with_oidc(oidc),
+            ..self
+        }
+    }
+
+    /// Return available authentication providers
+    pub fn providers(&self) -> Vec<AuthenticationProvider> {
+        let mut providers = vec![
+            AuthenticationProvider::Github,
+            AuthenticationProvider::Google,
+        ];
+        if self.jwt_service.oidc.is_some() {
+            providers.push(AuthenticationProvider::Oidc);
+        }
+        providers.push(AuthenticationProvider::Local);
+        providers
+    }
+
+    /// Device flow of the oidc provider is constructed from the discovered metadata
+    async fn oidc_device_flow(&self) -> anyhow::Result<DeviceFlow<provider::Oidc>> {
+        let oidc = self.jwt_service.oidc()?;
+        let metadata = oidc.metadata().await?;
+        let provider = provider::Oidc::new(&metadata, oidc.client_id().to_owned())?;
+        Ok(DeviceFlow::new(provider))
+    }
+
     pub(crate) async fn init_device_flow(
         &self,
         provider: AuthenticationProvider,
@@ -88,6 +159,15 @@ impl Authenticator {
             AuthenticationProvider::Google => {
                 self.device_flows.google.device_authorize_request().await
             }
+            AuthenticationProvider::Oidc => {
+                self.oidc_device_flow()
+                    .await?
+                    .device_authorize_request()
+                    .await
+            }
+            AuthenticationProvider::Local => {
+                Err(anyhow!("local provider does not support device flow"))
+            }
         }
     }
 
@@ -133,6 +213,53 @@ impl Authenticator {
                     expired_at,
                 }))
             }
+            AuthenticationProvider::Oidc => {
+                let token_response = self
+                    .oidc_device_flow()
+                    .await?
+                    .poll_device_access_token(response.device_code, response.interval)
+                    .await?;
+
+                let id_token = token_response
+                    .id_token
+                    .ok_or_else(|| anyhow!("id token not found"))?;
+                let expired_at = self
+                    .jwt_service
+                    .oidc()?
+                    .decode_id_token_insecure(&id_token, false)
+                    .ok()
+                    .map_or(now.add(config::credential::FALLBACK_EXPIRE), |claims| {
+                        claims.expired_at()
+                    });
+                Ok(Verified(Credential::Oidc {
+                    id_token,
+                    refresh_token: token_response.refresh_token,
+                    expired_at,
+                }))
+            }
+            AuthenticationProvider::Local => {
+                Err(anyhow!("local provider does not support device flow"))
+            }
         }
     }
+
+    /// Login as the local user of synd-api and exchange the password for a session token.
+    /// The password is not persisted
+    pub(crate) async fn login_local(
+        client: &Client,
+        username: &str,
+        password: &str,
+        now: Time,
+    ) -> anyhow::Result<Verified<Credential>> {
+        let expired_at = now.add(config::credential::LOCAL_TOKEN_TTL);
+        let token = client
+            .issue_local_api_token(username, password, expired_at)
+            .await
+            .map_err(|err| match err {
+                SyndApiError::Unauthorized { .. } => anyhow!("invalid username or password"),
+                err => anyhow!(err),
+            })?;
+
+        Ok(Verified(Credential::Local { token, expired_at }))
+    }
 }
@@ -24,6 +24,10 @@ pub mod authenticator;
 pub enum AuthenticationProvider {
     Github,
     Google,
+    /// Generic `OpenID` Connect provider configured by the issuer
+    Oidc,
+    /// Users defined by the self hosted synd-api
+    Local,
 }
 
 #[derive(Debug, Error)]
@@ -32,6 +36,12 @@ pub enum CredentialError {
     GoogleJwtExpired { refresh_token: String },
     #[error("google jwt email not verified")]
     GoogleJwtEmailNotVerified,
+    #[error("oidc jwt expired and no refresh token")]
+    OidcJwtExpired,
+    #[error("oidc provider is not configured")]
+    OidcNotConfigured,
+    #[error("local api token expired")]
+    LocalTokenExpired,
     #[error("failed to open: {path} :{io_err}")]
     Open {
         #[source]
@@ -64,6 +74,17 @@ pub enum Credential {
         refresh_token: String,
         expired_at: DateTime<Utc>,
     },
+    Oidc {
+        id_token: String,
+        /// Provider may not issue the refresh token
+        refresh_token: Option<String>,
+        expired_at: DateTime<Utc>,
+    },
+    /// Session token issued in exchange for the local user's password
+    Local {
+        token: String,
+        expired_at: DateTime<Utc>,
+    },
 }
 
 impl fmt::Debug for Credential {
@@ -124,6 +145,14 @@ impl Unverified<Credential> {
         let credential = self.0;
         match &credential {
             Credential::Github { .. } => Ok(VerifyResult::Verified(Verified(credential))),
+            // Local api token cannot be refreshed without the password, so login again
+            Credential::Local { expired_at, .. } => {
+                if (*expired_at).sub(config::credential::EXPIRE_MARGIN) <= now {
+                    Err(CredentialError::LocalTokenExpired)
+                } else {
+                    Ok(VerifyResult::Verified(Verified(credential)))
+                }
+            }
             Credential::Google { id_token, .. } => {
                 let claims = jwt_service
                     .google
@@ -147,6 +176,25 @@ impl Unverified<Credential> {
                     Ordering::Greater => Ok(VerifyResult::Verified(Verified(credential))),
                 }
             }
+            Credential::Oidc {
+                id_token,
+                refresh_token,
+                ..
+            } => {
+                let claims = jwt_service
+                    .oidc()?
+                    .decode_id_token_insecure(id_token, false)
+                    .map_err(CredentialError::DecodeJwt)?;
+                if claims.expired_at().sub(config::credential::EXPIRE_MARGIN) > now {
+                    Ok(VerifyResult::Verified(Verified(credential)))
+                } else if refresh_token.is_some() {
+                    debug!("Oidc jwt expired, trying to refresh");
+
+                    Ok(VerifyResult::Expired(Expired(credential)))
+                } else {
+                    Err(CredentialError::OidcJwtExpired)
+                }
+            }
         }
     }
 }
@@ -180,6 +228,18 @@ impl<'a> Restore<'a> {
 
                 Ok(cred)
             }
+            VerifyResult::Expired(Expired(Credential::Oidc {
+                refresh_token: Some(refresh_token),
+                ..
+            })) => {
+                let cred = jwt_service.refresh_oidc_id_token(&refresh_token).await?;
+
+                if persist_when_refreshed {
+                    cache.persist_credential(&cred)?;
+                }
+
+                Ok(cred)
+            }
             VerifyResult::Expired(_) => panic!("Unexpected verify result. this is bug"),
         }
     }
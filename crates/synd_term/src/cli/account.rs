@@ -0,0 +1,121 @@
+use std::{
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use clap::{Args, Subcommand};
+use url::Url;
+
+use crate::{
+    application::{Cache, Clock, JwtService, SystemClock},
+    auth,
+    client::Client,
+    config,
+};
+
+/// Manage the account on the api
+#[derive(Args, Debug)]
+pub struct AccountCommand {
+    #[command(subcommand)]
+    command: AccountSubcommand,
+    /// Cache directory
+    #[arg(
+        long,
+        default_value = config::cache::dir().to_path_buf().into_os_string(),
+    )]
+    cache_dir: PathBuf,
+}
+
+#[derive(Subcommand, Debug)]
+enum AccountSubcommand {
+    /// Print subscriptions, annotations, read states and starred entries as json
+    Export,
+    /// Delete all data of the account. This cannot be undone
+    Delete {
+        /// Skip the confirmation prompt
+        #[arg(long, default_value_t = false)]
+        yes: bool,
+    },
+}
+
+impl AccountCommand {
+    pub async fn run(self, endpoint: Url, jwt_service: JwtService) -> i32 {
+        if let Err(err) = self.account(endpoint, jwt_service).await {
+            tracing::error!("{err:?}");
+            1
+        } else {
+            0
+        }
+    }
+
+    async fn account(self, endpoint: Url, jwt_service: JwtService) -> anyhow::Result<()> {
+        let mut client = Client::new(endpoint.clone(), Duration::from_secs(10))?;
+        let cache = Cache::new(self.cache_dir);
+        let restore = auth::Restore {
+            jwt_service: &jwt_service,
+            cache: &cache,
+            now: SystemClock.now(),
+            persist_when_refreshed: false,
+        };
+        let credential = restore
+            .restore()
+            .await
+            .map_err(|_| anyhow!("You are not authenticated, try login in first"))?;
+        client.set_credential(credential);
+
+        match self.command {
+            AccountSubcommand::Export => {
+                let data = client.export_my_data().await?;
+                let data: serde_json::Value = serde_json::from_str(&data)?;
+                serde_json::to_writer_pretty(io::stdout(), &data)?;
+            }
+            AccountSubcommand::Delete { yes } => {
+                if !yes && !confirm(&endpoint, io::stdin().lock(), io::stderr())? {
+                    anyhow::bail!("Account deletion is cancelled");
+                }
+                client.delete_my_account().await?;
+                eprintln!("Account deleted");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Ask the user to type `delete` to confirm the deletion
+fn confirm(endpoint: &Url, mut input: impl BufRead, mut output: impl Write) -> io::Result<bool> {
+    write!(
+        output,
+        "All your data on {endpoint} will be deleted. Type 'delete' to confirm: "
+    )?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(answer.trim() == "delete")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirm_deletion() {
+        let endpoint = Url::parse("https://localhost:5959").unwrap();
+        for (answer, expected) in [
+            ("delete\n", true),
+            ("  delete \r\n", true),
+            ("y\n", false),
+            ("", false),
+        ] {
+            let mut output = Vec::new();
+            assert_eq!(
+                confirm(&endpoint, answer.as_bytes(), &mut output).unwrap(),
+                expected
+            );
+            assert!(String::from_utf8(output).unwrap().contains("localhost"));
+        }
+    }
+}
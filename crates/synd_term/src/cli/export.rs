@@ -40,11 +40,11 @@ pub struct ExportCommand {
 
 impl ExportCommand {
     #[allow(clippy::unused_self)]
-    pub async fn run(self, endpoint: Url) -> i32 {
+    pub async fn run(self, endpoint: Url, jwt_service: JwtService) -> i32 {
         let err = if self.print_schema {
             Self::print_json_schema()
         } else {
-            self.export(endpoint).await
+            self.export(endpoint, jwt_service).await
         };
         if let Err(err) = err {
             tracing::error!("{err:?}");
@@ -59,9 +59,8 @@ impl ExportCommand {
         serde_json::to_writer_pretty(std::io::stdout(), &schema).map_err(anyhow::Error::from)
     }
 
-    async fn export(self, endpoint: Url) -> anyhow::Result<()> {
+    async fn export(self, endpoint: Url, jwt_service: JwtService) -> anyhow::Result<()> {
         let mut client = Client::new(endpoint, Duration::from_secs(10))?;
-        let jwt_service = JwtService::new();
         let cache = Cache::new(self.cache_dir);
         let restore = auth::Restore {
             jwt_service: &jwt_service,
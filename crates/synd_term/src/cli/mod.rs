@@ -1,13 +1,16 @@
 use std::{path::PathBuf, time::Duration};
 
 use clap::{Parser, Subcommand};
+use synd_auth::jwt;
 use url::Url;
 
 use crate::{config, ui::theme};
 
+mod account;
 mod check;
 mod clean;
 mod export;
+mod token;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, clap::ValueEnum)]
 pub enum Palette {
@@ -47,6 +50,8 @@ pub struct Args {
     pub api: ApiOptions,
     #[command(flatten)]
     pub feed: FeedOptions,
+    #[command(flatten)]
+    pub auth: AuthOptions,
 }
 
 #[derive(clap::Args, Debug)]
@@ -60,6 +65,30 @@ pub struct ApiOptions {
     pub client_timeout: Duration,
 }
 
+#[derive(clap::Args, Debug)]
+#[command(next_help_heading = "Auth options")]
+pub struct AuthOptions {
+    /// `OpenID` Connect issuer url to enable login with the provider(e.g. Keycloak, Dex)
+    #[arg(long, global = true, env = config::env::OIDC_ISSUER, requires = "oidc_client_id")]
+    pub oidc_issuer: Option<Url>,
+    /// `OpenID` Connect client id registered for synd
+    #[arg(long, global = true, env = config::env::OIDC_CLIENT_ID, requires = "oidc_issuer")]
+    pub oidc_client_id: Option<String>,
+}
+
+impl AuthOptions {
+    /// Return the jwt service of the configured oidc provider
+    pub fn oidc(&self) -> Option<jwt::oidc::JwtService> {
+        match (self.oidc_issuer.as_ref(), self.oidc_client_id.as_ref()) {
+            (Some(issuer), Some(client_id)) => Some(jwt::oidc::JwtService::new(
+                issuer.as_str().trim_end_matches('/').to_owned(),
+                client_id.clone(),
+            )),
+            _ => None,
+        }
+    }
+}
+
 #[derive(clap::Args, Debug)]
 #[command(next_help_heading = "Feed options")]
 pub struct FeedOptions {
@@ -73,10 +102,12 @@ pub struct FeedOptions {
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    Account(account::AccountCommand),
     #[command(alias = "clear")]
     Clean(clean::CleanCommand),
     Check(check::CheckCommand),
     Export(export::ExportCommand),
+    Token(token::TokenCommand),
 }
 
 pub fn parse() -> Args {
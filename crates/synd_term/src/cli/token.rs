@@ -0,0 +1,100 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::anyhow;
+use clap::{Args, Subcommand};
+use url::Url;
+
+use crate::{
+    application::{Cache, Clock, JwtService, SystemClock},
+    auth,
+    client::Client,
+    config,
+};
+
+/// Manage personal access tokens to call the api from scripts.
+/// Send the token as `Authorization: synd <token>`
+#[derive(Args, Debug)]
+pub struct TokenCommand {
+    #[command(subcommand)]
+    command: TokenSubcommand,
+    /// Cache directory
+    #[arg(
+        long,
+        default_value = config::cache::dir().to_path_buf().into_os_string(),
+    )]
+    cache_dir: PathBuf,
+}
+
+#[derive(Subcommand, Debug)]
+enum TokenSubcommand {
+    /// Create a token and print it. The token cannot be shown again
+    Create {
+        /// Name to identify the token
+        #[arg(long)]
+        name: String,
+        /// Allow only queries with the token
+        #[arg(long, default_value_t = false)]
+        read_only: bool,
+        /// Expire the token after the duration(e.g. 30d). The token never expires if omitted
+        #[arg(long, value_parser = parse_duration::parse)]
+        expires_in: Option<Duration>,
+    },
+    /// Print tokens as json
+    List,
+    /// Revoke the token
+    Revoke {
+        /// Id of the token
+        id: String,
+    },
+}
+
+impl TokenCommand {
+    pub async fn run(self, endpoint: Url, jwt_service: JwtService) -> i32 {
+        if let Err(err) = self.token(endpoint, jwt_service).await {
+            tracing::error!("{err:?}");
+            1
+        } else {
+            0
+        }
+    }
+
+    async fn token(self, endpoint: Url, jwt_service: JwtService) -> anyhow::Result<()> {
+        let mut client = Client::new(endpoint, Duration::from_secs(10))?;
+        let cache = Cache::new(self.cache_dir);
+        let now = SystemClock.now();
+        let restore = auth::Restore {
+            jwt_service: &jwt_service,
+            cache: &cache,
+            now,
+            persist_when_refreshed: false,
+        };
+        let credential = restore
+            .restore()
+            .await
+            .map_err(|_| anyhow!("You are not authenticated, try login in first"))?;
+        client.set_credential(credential);
+
+        match self.command {
+            TokenSubcommand::Create {
+                name,
+                read_only,
+                expires_in,
+            } => {
+                let expires_at = expires_in
+                    .map(|expires_in| chrono::Duration::from_std(expires_in).map(|d| now + d))
+                    .transpose()?;
+                let (_, token) = client.create_api_token(name, read_only, expires_at).await?;
+                println!("{token}");
+            }
+            TokenSubcommand::List => {
+                let tokens = client.fetch_api_tokens().await?;
+                serde_json::to_writer_pretty(std::io::stdout(), &tokens)?;
+            }
+            TokenSubcommand::Revoke { id } => {
+                client.revoke_api_token(id).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+#![allow(clippy::all, warnings)]
+pub struct ExportMyData;
+pub mod export_my_data {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "ExportMyData";
+    pub const QUERY : & str = "# Account operations are separated from query.gql and mutation.gql\n# since the complexity is calculated over the whole document\n\nmutation ExportMyData {\n  exportMyData {\n    __typename\n    ... on ExportMyDataSuccess {\n      data\n      status {\n        code\n      }\n    }\n    ... on AccountError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation DeleteMyAccount {\n  deleteMyAccount {\n    __typename\n    ... on DeleteMyAccountSuccess {\n      status {\n        code\n      }\n    }\n    ... on AccountError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables;
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "exportMyData")]
+        pub export_my_data: ExportMyDataExportMyData,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum ExportMyDataExportMyData {
+        ExportMyDataSuccess(ExportMyDataExportMyDataOnExportMyDataSuccess),
+        AccountError(ExportMyDataExportMyDataOnAccountError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ExportMyDataExportMyDataOnExportMyDataSuccess {
+        pub data: String,
+        pub status: ExportMyDataExportMyDataOnExportMyDataSuccessStatus,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ExportMyDataExportMyDataOnExportMyDataSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ExportMyDataExportMyDataOnAccountError {
+        pub status: ExportMyDataExportMyDataOnAccountErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ExportMyDataExportMyDataOnAccountErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for ExportMyData {
+    type Variables = export_my_data::Variables;
+    type ResponseData = export_my_data::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: export_my_data::QUERY,
+            operation_name: export_my_data::OPERATION_NAME,
+        }
+    }
+}
+pub struct DeleteMyAccount;
+pub mod delete_my_account {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "DeleteMyAccount";
+    pub const QUERY : & str = "# Account operations are separated from query.gql and mutation.gql\n# since the complexity is calculated over the whole document\n\nmutation ExportMyData {\n  exportMyData {\n    __typename\n    ... on ExportMyDataSuccess {\n      data\n      status {\n        code\n      }\n    }\n    ... on AccountError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation DeleteMyAccount {\n  deleteMyAccount {\n    __typename\n    ... on DeleteMyAccountSuccess {\n      status {\n        code\n      }\n    }\n    ... on AccountError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables;
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "deleteMyAccount")]
+        pub delete_my_account: DeleteMyAccountDeleteMyAccount,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum DeleteMyAccountDeleteMyAccount {
+        DeleteMyAccountSuccess(DeleteMyAccountDeleteMyAccountOnDeleteMyAccountSuccess),
+        AccountError(DeleteMyAccountDeleteMyAccountOnAccountError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct DeleteMyAccountDeleteMyAccountOnDeleteMyAccountSuccess {
+        pub status: DeleteMyAccountDeleteMyAccountOnDeleteMyAccountSuccessStatus,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct DeleteMyAccountDeleteMyAccountOnDeleteMyAccountSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct DeleteMyAccountDeleteMyAccountOnAccountError {
+        pub status: DeleteMyAccountDeleteMyAccountOnAccountErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct DeleteMyAccountDeleteMyAccountOnAccountErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for DeleteMyAccount {
+    type Variables = delete_my_account::Variables;
+    type ResponseData = delete_my_account::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: delete_my_account::QUERY,
+            operation_name: delete_my_account::OPERATION_NAME,
+        }
+    }
+}
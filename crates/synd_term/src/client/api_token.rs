@@ -0,0 +1,322 @@
+#![allow(clippy::all, warnings)]
+pub struct ApiTokens;
+pub mod api_tokens {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "ApiTokens";
+    pub const QUERY : & str = "# Personal access token operations are separated from query.gql and mutation.gql\n# since the complexity is calculated over the whole document\n\nquery ApiTokens {\n  output: subscription {\n    apiTokens {\n      ...ApiToken\n    }\n  }\n}\n\nmutation CreateApiToken($createApiTokenInput: CreateApiTokenInput!) {\n  createApiToken(input: $createApiTokenInput) {\n    __typename\n    ... on CreateApiTokenSuccess {\n      apiToken {\n        ...ApiToken\n      }\n      token\n      status {\n        code\n      }\n    }\n    ... on ApiTokenError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RevokeApiToken($revokeApiTokenInput: RevokeApiTokenInput!) {\n  revokeApiToken(input: $revokeApiTokenInput) {\n    __typename\n    ... on RevokeApiTokenSuccess {\n      id\n      status {\n        code\n      }\n    }\n    ... on ApiTokenError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment ApiToken on ApiToken {\n  id\n  name\n  readOnly\n  createdAt\n  expiresAt\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    type Rfc3339Time = crate::client::scalar::Rfc3339Time;
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables;
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ApiToken {
+        pub id: String,
+        pub name: String,
+        #[serde(rename = "readOnly")]
+        pub read_only: Boolean,
+        #[serde(rename = "createdAt")]
+        pub created_at: Rfc3339Time,
+        #[serde(rename = "expiresAt")]
+        pub expires_at: Option<Rfc3339Time>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        pub output: ApiTokensOutput,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ApiTokensOutput {
+        #[serde(rename = "apiTokens")]
+        pub api_tokens: Vec<ApiTokensOutputApiTokens>,
+    }
+    pub type ApiTokensOutputApiTokens = ApiToken;
+}
+impl graphql_client::GraphQLQuery for ApiTokens {
+    type Variables = api_tokens::Variables;
+    type ResponseData = api_tokens::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: api_tokens::QUERY,
+            operation_name: api_tokens::OPERATION_NAME,
+        }
+    }
+}
+pub struct CreateApiToken;
+pub mod create_api_token {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "CreateApiToken";
+    pub const QUERY : & str = "# Personal access token operations are separated from query.gql and mutation.gql\n# since the complexity is calculated over the whole document\n\nquery ApiTokens {\n  output: subscription {\n    apiTokens {\n      ...ApiToken\n    }\n  }\n}\n\nmutation CreateApiToken($createApiTokenInput: CreateApiTokenInput!) {\n  createApiToken(input: $createApiTokenInput) {\n    __typename\n    ... on CreateApiTokenSuccess {\n      apiToken {\n        ...ApiToken\n      }\n      token\n      status {\n        code\n      }\n    }\n    ... on ApiTokenError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RevokeApiToken($revokeApiTokenInput: RevokeApiTokenInput!) {\n  revokeApiToken(input: $revokeApiTokenInput) {\n    __typename\n    ... on RevokeApiTokenSuccess {\n      id\n      status {\n        code\n      }\n    }\n    ... on ApiTokenError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment ApiToken on ApiToken {\n  id\n  name\n  readOnly\n  createdAt\n  expiresAt\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    type Rfc3339Time = crate::client::scalar::Rfc3339Time;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateApiTokenInput {
+        pub name: String,
+        #[serde(rename = "readOnly")]
+        pub read_only: Boolean,
+        #[serde(rename = "expiresAt")]
+        pub expires_at: Option<Rfc3339Time>,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "createApiTokenInput")]
+        pub create_api_token_input: CreateApiTokenInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ApiToken {
+        pub id: String,
+        pub name: String,
+        #[serde(rename = "readOnly")]
+        pub read_only: Boolean,
+        #[serde(rename = "createdAt")]
+        pub created_at: Rfc3339Time,
+        #[serde(rename = "expiresAt")]
+        pub expires_at: Option<Rfc3339Time>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "createApiToken")]
+        pub create_api_token: CreateApiTokenCreateApiToken,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum CreateApiTokenCreateApiToken {
+        CreateApiTokenSuccess(CreateApiTokenCreateApiTokenOnCreateApiTokenSuccess),
+        ApiTokenError(CreateApiTokenCreateApiTokenOnApiTokenError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateApiTokenCreateApiTokenOnCreateApiTokenSuccess {
+        #[serde(rename = "apiToken")]
+        pub api_token: CreateApiTokenCreateApiTokenOnCreateApiTokenSuccessApiToken,
+        pub token: String,
+        pub status: CreateApiTokenCreateApiTokenOnCreateApiTokenSuccessStatus,
+    }
+    pub type CreateApiTokenCreateApiTokenOnCreateApiTokenSuccessApiToken = ApiToken;
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateApiTokenCreateApiTokenOnCreateApiTokenSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateApiTokenCreateApiTokenOnApiTokenError {
+        pub status: CreateApiTokenCreateApiTokenOnApiTokenErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateApiTokenCreateApiTokenOnApiTokenErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for CreateApiToken {
+    type Variables = create_api_token::Variables;
+    type ResponseData = create_api_token::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: create_api_token::QUERY,
+            operation_name: create_api_token::OPERATION_NAME,
+        }
+    }
+}
+pub struct RevokeApiToken;
+pub mod revoke_api_token {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "RevokeApiToken";
+    pub const QUERY : & str = "# Personal access token operations are separated from query.gql and mutation.gql\n# since the complexity is calculated over the whole document\n\nquery ApiTokens {\n  output: subscription {\n    apiTokens {\n      ...ApiToken\n    }\n  }\n}\n\nmutation CreateApiToken($createApiTokenInput: CreateApiTokenInput!) {\n  createApiToken(input: $createApiTokenInput) {\n    __typename\n    ... on CreateApiTokenSuccess {\n      apiToken {\n        ...ApiToken\n      }\n      token\n      status {\n        code\n      }\n    }\n    ... on ApiTokenError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RevokeApiToken($revokeApiTokenInput: RevokeApiTokenInput!) {\n  revokeApiToken(input: $revokeApiTokenInput) {\n    __typename\n    ... on RevokeApiTokenSuccess {\n      id\n      status {\n        code\n      }\n    }\n    ... on ApiTokenError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment ApiToken on ApiToken {\n  id\n  name\n  readOnly\n  createdAt\n  expiresAt\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RevokeApiTokenInput {
+        pub id: String,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "revokeApiTokenInput")]
+        pub revoke_api_token_input: RevokeApiTokenInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "revokeApiToken")]
+        pub revoke_api_token: RevokeApiTokenRevokeApiToken,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum RevokeApiTokenRevokeApiToken {
+        RevokeApiTokenSuccess(RevokeApiTokenRevokeApiTokenOnRevokeApiTokenSuccess),
+        ApiTokenError(RevokeApiTokenRevokeApiTokenOnApiTokenError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RevokeApiTokenRevokeApiTokenOnRevokeApiTokenSuccess {
+        pub id: String,
+        pub status: RevokeApiTokenRevokeApiTokenOnRevokeApiTokenSuccessStatus,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RevokeApiTokenRevokeApiTokenOnRevokeApiTokenSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RevokeApiTokenRevokeApiTokenOnApiTokenError {
+        pub status: RevokeApiTokenRevokeApiTokenOnApiTokenErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RevokeApiTokenRevokeApiTokenOnApiTokenErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for RevokeApiToken {
+    type Variables = revoke_api_token::Variables;
+    type ResponseData = revoke_api_token::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: revoke_api_token::QUERY,
+            operation_name: revoke_api_token::OPERATION_NAME,
+        }
+    }
+}
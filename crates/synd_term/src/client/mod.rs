@@ -1,8 +1,12 @@
 use std::{fmt::Debug, time::Duration};
 
 use anyhow::anyhow;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use graphql_client::{GraphQLQuery, Response};
-use reqwest::header::{self, HeaderValue};
+use reqwest::{
+    header::{self, HeaderValue},
+    StatusCode,
+};
 use serde::{de::DeserializeOwned, Serialize};
 use synd_o11y::{health_check::Health, opentelemetry::extension::*};
 use thiserror::Error;
@@ -19,6 +23,8 @@ use self::query::subscription::SubscriptionOutput;
 
 mod scalar;
 pub use scalar::*;
+pub mod account;
+pub mod api_token;
 pub mod mutation;
 pub mod payload;
 pub mod query;
@@ -27,12 +33,16 @@ pub mod query;
 pub enum SubscribeFeedError {
     #[error("invalid feed url: `{feed_url}` ({message})`")]
     InvalidFeedUrl { feed_url: FeedUrl, message: String },
+    #[error("feed unavailable: `{feed_url}` ({message})")]
+    FeedUnavailable { feed_url: FeedUrl, message: String },
 }
 
 #[derive(Error, Debug)]
 pub enum SyndApiError {
     #[error("unauthorized")]
     Unauthorized { url: Option<Url> },
+    #[error("rate limited")]
+    RateLimited { retry_after: Option<Duration> },
     #[error(transparent)]
     BuildRequest(#[from] reqwest::Error),
     #[error("graphql error: {errors:?}")]
@@ -75,6 +85,8 @@ impl Client {
         let mut token = HeaderValue::try_from(match cred.into_inner() {
             Credential::Github { access_token } => format!("github {access_token}"),
             Credential::Google { id_token, .. } => format!("google {id_token}"),
+            Credential::Oidc { id_token, .. } => format!("oidc {id_token}"),
+            Credential::Local { token, .. } => format!("synd {token}"),
         })
         .unwrap();
         token.set_sensitive(true);
@@ -119,6 +131,12 @@ impl Client {
                             message: err.message,
                         },
                     )),
+                    ResponseCode::FEED_UNAVAILABLE => Err(SyndApiError::SubscribeFeed(
+                        SubscribeFeedError::FeedUnavailable {
+                            feed_url: url,
+                            message: err.message,
+                        },
+                    )),
                     err_code => Err(SyndApiError::Internal(anyhow::anyhow!(
                         "Unexpected subscribe_feed error code: {err_code:?}"
                     ))),
@@ -147,6 +165,75 @@ impl Client {
         }
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn move_feed(
+        &self,
+        url: FeedUrl,
+        folder_id: Option<types::FolderId>,
+        position: Option<usize>,
+    ) -> Result<(), SyndApiError> {
+        let var = mutation::move_feed::Variables {
+            move_feed_input: mutation::move_feed::MoveFeedInput {
+                url,
+                folder_id,
+                position: position.and_then(|position| i64::try_from(position).ok()),
+            },
+        };
+        let request = mutation::MoveFeed::build_query(var);
+        let response: mutation::move_feed::ResponseData = self.request(&request).await?;
+
+        match response.move_feed {
+            mutation::move_feed::MoveFeedMoveFeed::MoveFeedSuccess(_) => Ok(()),
+            mutation::move_feed::MoveFeedMoveFeed::MoveFeedError(err) => Err(
+                SyndApiError::Internal(anyhow!("Failed to mutate move_feed: {}", err.message)),
+            ),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn create_folder(
+        &self,
+        name: String,
+        parent_id: Option<types::FolderId>,
+    ) -> Result<types::Folder, SyndApiError> {
+        let var = mutation::create_folder::Variables {
+            create_folder_input: mutation::create_folder::CreateFolderInput { name, parent_id },
+        };
+        let request = mutation::CreateFolder::build_query(var);
+        let response: mutation::create_folder::ResponseData = self.request(&request).await?;
+
+        match response.create_folder {
+            mutation::create_folder::CreateFolderCreateFolder::FolderSuccess(success) => {
+                Ok(types::Folder::from(success.folder))
+            }
+            mutation::create_folder::CreateFolderCreateFolder::FolderError(err) => Err(
+                SyndApiError::Internal(anyhow!("Failed to mutate create_folder: {}", err.message)),
+            ),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn rename_folder(
+        &self,
+        folder_id: types::FolderId,
+        name: String,
+    ) -> Result<types::Folder, SyndApiError> {
+        let var = mutation::rename_folder::Variables {
+            rename_folder_input: mutation::rename_folder::RenameFolderInput { folder_id, name },
+        };
+        let request = mutation::RenameFolder::build_query(var);
+        let response: mutation::rename_folder::ResponseData = self.request(&request).await?;
+
+        match response.rename_folder {
+            mutation::rename_folder::RenameFolderRenameFolder::FolderSuccess(success) => {
+                Ok(types::Folder::from(success.folder))
+            }
+            mutation::rename_folder::RenameFolderRenameFolder::FolderError(err) => Err(
+                SyndApiError::Internal(anyhow!("Failed to mutate rename_folder: {}", err.message)),
+            ),
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn fetch_entries(
         &self,
@@ -164,6 +251,191 @@ impl Client {
         Ok(response.output.into())
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn fetch_starred_entries(
+        &self,
+        after: Option<String>,
+        first: i64,
+    ) -> Result<payload::FetchEntriesPayload, SyndApiError> {
+        let var = query::starred_entries::Variables { after, first };
+        let request = query::StarredEntries::build_query(var);
+        let response: query::starred_entries::ResponseData = self.request(&request).await?;
+
+        Ok(response.output.into())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn star_entry(
+        &self,
+        feed_url: FeedUrl,
+        entry_id: String,
+    ) -> Result<types::Entry, SyndApiError> {
+        let var = mutation::star_entry::Variables {
+            star_input: mutation::star_entry::StarEntryInput {
+                entry: mutation::star_entry::EntryReferenceInput { feed_url, entry_id },
+            },
+        };
+        let request = mutation::StarEntry::build_query(var);
+        let response: mutation::star_entry::ResponseData = self.request(&request).await?;
+
+        match response.star_entry {
+            mutation::star_entry::StarEntryStarEntry::StarEntrySuccess(success) => {
+                Ok(types::Entry::from(success.entry))
+            }
+            mutation::star_entry::StarEntryStarEntry::StarEntryError(err) => Err(
+                SyndApiError::Internal(anyhow!("Failed to mutate star_entry: {}", err.message)),
+            ),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn unstar_entry(
+        &self,
+        feed_url: FeedUrl,
+        entry_id: String,
+    ) -> Result<(), SyndApiError> {
+        let var = mutation::unstar_entry::Variables {
+            unstar_input: mutation::unstar_entry::StarEntryInput {
+                entry: mutation::unstar_entry::EntryReferenceInput { feed_url, entry_id },
+            },
+        };
+        let request = mutation::UnstarEntry::build_query(var);
+        let response: mutation::unstar_entry::ResponseData = self.request(&request).await?;
+
+        match response.unstar_entry {
+            mutation::unstar_entry::UnstarEntryUnstarEntry::UnstarEntrySuccess(_) => Ok(()),
+            mutation::unstar_entry::UnstarEntryUnstarEntry::UnstarEntryError(err) => Err(
+                SyndApiError::Internal(anyhow!("Failed to mutate unstar_entry {err:?}")),
+            ),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn fetch_api_tokens(&self) -> Result<Vec<types::ApiToken>, SyndApiError> {
+        let request = api_token::ApiTokens::build_query(api_token::api_tokens::Variables);
+        let response: api_token::api_tokens::ResponseData = self.request(&request).await?;
+
+        Ok(response
+            .output
+            .api_tokens
+            .into_iter()
+            .map(types::ApiToken::from)
+            .collect())
+    }
+
+    /// Create api token and return it with its plaintext
+    #[tracing::instrument(skip(self))]
+    pub async fn create_api_token(
+        &self,
+        name: String,
+        read_only: bool,
+        expires_at: Option<types::Time>,
+    ) -> Result<(types::ApiToken, String), SyndApiError> {
+        let var = api_token::create_api_token::Variables {
+            create_api_token_input: api_token::create_api_token::CreateApiTokenInput {
+                name,
+                read_only,
+                expires_at: expires_at.map(|t| t.to_rfc3339()),
+            },
+        };
+        let request = api_token::CreateApiToken::build_query(var);
+        let response: api_token::create_api_token::ResponseData = self.request(&request).await?;
+
+        match response.create_api_token {
+            api_token::create_api_token::CreateApiTokenCreateApiToken::CreateApiTokenSuccess(
+                success,
+            ) => Ok((types::ApiToken::from(success.api_token), success.token)),
+            api_token::create_api_token::CreateApiTokenCreateApiToken::ApiTokenError(err) => {
+                Err(SyndApiError::Internal(anyhow!(
+                    "Failed to mutate create_api_token: {}",
+                    err.message
+                )))
+            }
+        }
+    }
+
+    /// Issue a session token for the local user in exchange for the password
+    #[tracing::instrument(skip(self, password))]
+    pub async fn issue_local_api_token(
+        &self,
+        username: &str,
+        password: &str,
+        expires_at: types::Time,
+    ) -> Result<String, SyndApiError> {
+        let credential = BASE64.encode(format!("{username}:{password}"));
+        let mut credential = HeaderValue::try_from(format!("local {credential}"))
+            .map_err(|err| SyndApiError::Internal(err.into()))?;
+        credential.set_sensitive(true);
+        let client = Self {
+            credential: Some(credential),
+            ..self.clone()
+        };
+
+        let (_, token) = client
+            .create_api_token(
+                config::client::LOCAL_API_TOKEN_NAME.to_owned(),
+                false,
+                Some(expires_at),
+            )
+            .await?;
+        Ok(token)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn revoke_api_token(&self, id: String) -> Result<(), SyndApiError> {
+        let var = api_token::revoke_api_token::Variables {
+            revoke_api_token_input: api_token::revoke_api_token::RevokeApiTokenInput { id },
+        };
+        let request = api_token::RevokeApiToken::build_query(var);
+        let response: api_token::revoke_api_token::ResponseData = self.request(&request).await?;
+
+        match response.revoke_api_token {
+            api_token::revoke_api_token::RevokeApiTokenRevokeApiToken::RevokeApiTokenSuccess(_) => {
+                Ok(())
+            }
+            api_token::revoke_api_token::RevokeApiTokenRevokeApiToken::ApiTokenError(err) => {
+                Err(SyndApiError::Internal(anyhow!(
+                    "Failed to mutate revoke_api_token: {}",
+                    err.message
+                )))
+            }
+        }
+    }
+
+    /// Return all data of the user as JSON
+    #[tracing::instrument(skip(self))]
+    pub async fn export_my_data(&self) -> Result<String, SyndApiError> {
+        let request = account::ExportMyData::build_query(account::export_my_data::Variables);
+        let response: account::export_my_data::ResponseData = self.request(&request).await?;
+
+        match response.export_my_data {
+            account::export_my_data::ExportMyDataExportMyData::ExportMyDataSuccess(success) => {
+                Ok(success.data)
+            }
+            account::export_my_data::ExportMyDataExportMyData::AccountError(err) => Err(
+                SyndApiError::Internal(anyhow!("Failed to mutate export_my_data: {}", err.message)),
+            ),
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_my_account(&self) -> Result<(), SyndApiError> {
+        let request = account::DeleteMyAccount::build_query(account::delete_my_account::Variables);
+        let response: account::delete_my_account::ResponseData = self.request(&request).await?;
+
+        match response.delete_my_account {
+            account::delete_my_account::DeleteMyAccountDeleteMyAccount::DeleteMyAccountSuccess(
+                _,
+            ) => Ok(()),
+            account::delete_my_account::DeleteMyAccountDeleteMyAccount::AccountError(err) => {
+                Err(SyndApiError::Internal(anyhow!(
+                    "Failed to mutate delete_my_account: {}",
+                    err.message
+                )))
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn export_subscription(
         &self,
@@ -205,10 +477,17 @@ impl Client {
 
         tracing::debug!(url = request.url().as_str(), "Send request");
 
-        let response: Response<ResponseData> = self
-            .client
-            .execute(request)
-            .await?
+        let response = self.client.execute(request).await?;
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse().ok())
+                .map(Duration::from_secs);
+            return Err(SyndApiError::RateLimited { retry_after });
+        }
+        let response: Response<ResponseData> = response
             .error_for_status()
             .map_err(|err| match err.status().map(|s| s.as_u16()) {
                 Some(401) => SyndApiError::Unauthorized {
@@ -4,7 +4,7 @@ pub mod subscribe_feed {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "SubscribeFeed";
-    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n" ;
+    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation StarEntry($starInput: StarEntryInput!) {\n  starEntry(input: $starInput) {\n    __typename\n    ... on StarEntrySuccess {\n      entry {\n        ...StarredEntry\n      }\n      status {\n        code\n      }\n    }\n    ... on StarEntryError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnstarEntry($unstarInput: StarEntryInput!) {\n  unstarEntry(input: $unstarInput) {\n    __typename\n    ... on UnstarEntrySuccess {\n      status {\n        code\n      }\n    }\n    ... on UnstarEntryError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation MoveFeed($moveFeedInput: MoveFeedInput!) {\n  moveFeed(input: $moveFeedInput) {\n    __typename\n    ... on MoveFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on MoveFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation CreateFolder($createFolderInput: CreateFolderInput!) {\n  createFolder(input: $createFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RenameFolder($renameFolderInput: RenameFolderInput!) {\n  renameFolder(input: $renameFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n" ;
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -86,6 +86,15 @@ pub mod subscribe_feed {
         UNAUTHORIZED,
         INVALID_FEED_URL,
         INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
         Other(String),
     }
     impl ::serde::Serialize for ResponseCode {
@@ -95,6 +104,15 @@ pub mod subscribe_feed {
                 ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
                 ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
                 ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
                 ResponseCode::Other(ref s) => &s,
             })
         }
@@ -107,6 +125,15 @@ pub mod subscribe_feed {
                 "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
                 "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
                 "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
                 _ => Ok(ResponseCode::Other(s)),
             }
         }
@@ -116,6 +143,9 @@ pub mod subscribe_feed {
         pub url: FeedUrl,
         pub requirement: Option<Requirement>,
         pub category: Option<Category>,
+        pub title: Option<String>,
+        pub note: Option<String>,
+        pub tags: Option<Vec<Category>>,
     }
     #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
     pub struct Variables {
@@ -137,6 +167,12 @@ pub mod subscribe_feed {
         pub generator: Option<String>,
         pub requirement: Option<Requirement>,
         pub category: Option<Category>,
+        #[serde(rename = "customTitle")]
+        pub custom_title: Option<String>,
+        pub note: Option<String>,
+        pub tags: Vec<Category>,
+        #[serde(rename = "folderId")]
+        pub folder_id: Option<Int>,
         pub entries: FeedEntries,
         pub links: FeedLinks,
         pub authors: FeedAuthors,
@@ -217,7 +253,7 @@ pub mod unsubscribe_feed {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "UnsubscribeFeed";
-    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n" ;
+    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation StarEntry($starInput: StarEntryInput!) {\n  starEntry(input: $starInput) {\n    __typename\n    ... on StarEntrySuccess {\n      entry {\n        ...StarredEntry\n      }\n      status {\n        code\n      }\n    }\n    ... on StarEntryError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnstarEntry($unstarInput: StarEntryInput!) {\n  unstarEntry(input: $unstarInput) {\n    __typename\n    ... on UnstarEntrySuccess {\n      status {\n        code\n      }\n    }\n    ... on UnstarEntryError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation MoveFeed($moveFeedInput: MoveFeedInput!) {\n  moveFeed(input: $moveFeedInput) {\n    __typename\n    ... on MoveFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on MoveFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation CreateFolder($createFolderInput: CreateFolderInput!) {\n  createFolder(input: $createFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RenameFolder($renameFolderInput: RenameFolderInput!) {\n  renameFolder(input: $renameFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n" ;
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -235,6 +271,15 @@ pub mod unsubscribe_feed {
         UNAUTHORIZED,
         INVALID_FEED_URL,
         INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
         Other(String),
     }
     impl ::serde::Serialize for ResponseCode {
@@ -244,6 +289,15 @@ pub mod unsubscribe_feed {
                 ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
                 ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
                 ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
                 ResponseCode::Other(ref s) => &s,
             })
         }
@@ -256,6 +310,15 @@ pub mod unsubscribe_feed {
                 "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
                 "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
                 "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
                 _ => Ok(ResponseCode::Other(s)),
             }
         }
@@ -309,3 +372,712 @@ impl graphql_client::GraphQLQuery for UnsubscribeFeed {
         }
     }
 }
+pub struct StarEntry;
+pub mod star_entry {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "StarEntry";
+    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation StarEntry($starInput: StarEntryInput!) {\n  starEntry(input: $starInput) {\n    __typename\n    ... on StarEntrySuccess {\n      entry {\n        ...StarredEntry\n      }\n      status {\n        code\n      }\n    }\n    ... on StarEntryError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnstarEntry($unstarInput: StarEntryInput!) {\n  unstarEntry(input: $unstarInput) {\n    __typename\n    ... on UnstarEntrySuccess {\n      status {\n        code\n      }\n    }\n    ... on UnstarEntryError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation MoveFeed($moveFeedInput: MoveFeedInput!) {\n  moveFeed(input: $moveFeedInput) {\n    __typename\n    ... on MoveFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on MoveFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation CreateFolder($createFolderInput: CreateFolderInput!) {\n  createFolder(input: $createFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RenameFolder($renameFolderInput: RenameFolderInput!) {\n  renameFolder(input: $renameFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    type Category = crate::client::scalar::Category;
+    type FeedUrl = crate::client::scalar::FeedUrl;
+    type Rfc3339Time = crate::client::scalar::Rfc3339Time;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum Requirement {
+        MUST,
+        SHOULD,
+        MAY,
+        Other(String),
+    }
+    impl ::serde::Serialize for Requirement {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                Requirement::MUST => "MUST",
+                Requirement::SHOULD => "SHOULD",
+                Requirement::MAY => "MAY",
+                Requirement::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for Requirement {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "MUST" => Ok(Requirement::MUST),
+                "SHOULD" => Ok(Requirement::SHOULD),
+                "MAY" => Ok(Requirement::MAY),
+                _ => Ok(Requirement::Other(s)),
+            }
+        }
+    }
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct EntryReferenceInput {
+        #[serde(rename = "feedUrl")]
+        pub feed_url: FeedUrl,
+        #[serde(rename = "entryId")]
+        pub entry_id: String,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarEntryInput {
+        pub entry: EntryReferenceInput,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "starInput")]
+        pub star_input: StarEntryInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarredEntry {
+        pub id: String,
+        pub title: Option<String>,
+        pub published: Option<Rfc3339Time>,
+        pub updated: Option<Rfc3339Time>,
+        pub summary: Option<String>,
+        #[serde(rename = "websiteUrl")]
+        pub website_url: Option<String>,
+        #[serde(rename = "feedUrl")]
+        pub feed_url: FeedUrl,
+        #[serde(rename = "feedTitle")]
+        pub feed_title: Option<String>,
+        pub requirement: Option<Requirement>,
+        pub category: Option<Category>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "starEntry")]
+        pub star_entry: StarEntryStarEntry,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum StarEntryStarEntry {
+        StarEntrySuccess(StarEntryStarEntryOnStarEntrySuccess),
+        StarEntryError(StarEntryStarEntryOnStarEntryError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarEntryStarEntryOnStarEntrySuccess {
+        pub entry: StarEntryStarEntryOnStarEntrySuccessEntry,
+        pub status: StarEntryStarEntryOnStarEntrySuccessStatus,
+    }
+    pub type StarEntryStarEntryOnStarEntrySuccessEntry = StarredEntry;
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarEntryStarEntryOnStarEntrySuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarEntryStarEntryOnStarEntryError {
+        pub status: StarEntryStarEntryOnStarEntryErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarEntryStarEntryOnStarEntryErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for StarEntry {
+    type Variables = star_entry::Variables;
+    type ResponseData = star_entry::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: star_entry::QUERY,
+            operation_name: star_entry::OPERATION_NAME,
+        }
+    }
+}
+pub struct UnstarEntry;
+pub mod unstar_entry {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "UnstarEntry";
+    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation StarEntry($starInput: StarEntryInput!) {\n  starEntry(input: $starInput) {\n    __typename\n    ... on StarEntrySuccess {\n      entry {\n        ...StarredEntry\n      }\n      status {\n        code\n      }\n    }\n    ... on StarEntryError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnstarEntry($unstarInput: StarEntryInput!) {\n  unstarEntry(input: $unstarInput) {\n    __typename\n    ... on UnstarEntrySuccess {\n      status {\n        code\n      }\n    }\n    ... on UnstarEntryError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation MoveFeed($moveFeedInput: MoveFeedInput!) {\n  moveFeed(input: $moveFeedInput) {\n    __typename\n    ... on MoveFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on MoveFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation CreateFolder($createFolderInput: CreateFolderInput!) {\n  createFolder(input: $createFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RenameFolder($renameFolderInput: RenameFolderInput!) {\n  renameFolder(input: $renameFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    type FeedUrl = crate::client::scalar::FeedUrl;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct EntryReferenceInput {
+        #[serde(rename = "feedUrl")]
+        pub feed_url: FeedUrl,
+        #[serde(rename = "entryId")]
+        pub entry_id: String,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarEntryInput {
+        pub entry: EntryReferenceInput,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "unstarInput")]
+        pub unstar_input: StarEntryInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "unstarEntry")]
+        pub unstar_entry: UnstarEntryUnstarEntry,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum UnstarEntryUnstarEntry {
+        UnstarEntrySuccess(UnstarEntryUnstarEntryOnUnstarEntrySuccess),
+        UnstarEntryError(UnstarEntryUnstarEntryOnUnstarEntryError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UnstarEntryUnstarEntryOnUnstarEntrySuccess {
+        pub status: UnstarEntryUnstarEntryOnUnstarEntrySuccessStatus,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UnstarEntryUnstarEntryOnUnstarEntrySuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UnstarEntryUnstarEntryOnUnstarEntryError {
+        pub status: UnstarEntryUnstarEntryOnUnstarEntryErrorStatus,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct UnstarEntryUnstarEntryOnUnstarEntryErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for UnstarEntry {
+    type Variables = unstar_entry::Variables;
+    type ResponseData = unstar_entry::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: unstar_entry::QUERY,
+            operation_name: unstar_entry::OPERATION_NAME,
+        }
+    }
+}
+pub struct MoveFeed;
+pub mod move_feed {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "MoveFeed";
+    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation StarEntry($starInput: StarEntryInput!) {\n  starEntry(input: $starInput) {\n    __typename\n    ... on StarEntrySuccess {\n      entry {\n        ...StarredEntry\n      }\n      status {\n        code\n      }\n    }\n    ... on StarEntryError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnstarEntry($unstarInput: StarEntryInput!) {\n  unstarEntry(input: $unstarInput) {\n    __typename\n    ... on UnstarEntrySuccess {\n      status {\n        code\n      }\n    }\n    ... on UnstarEntryError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation MoveFeed($moveFeedInput: MoveFeedInput!) {\n  moveFeed(input: $moveFeedInput) {\n    __typename\n    ... on MoveFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on MoveFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation CreateFolder($createFolderInput: CreateFolderInput!) {\n  createFolder(input: $createFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RenameFolder($renameFolderInput: RenameFolderInput!) {\n  renameFolder(input: $renameFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    type FeedUrl = crate::client::scalar::FeedUrl;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct MoveFeedInput {
+        pub url: FeedUrl,
+        #[serde(rename = "folderId")]
+        pub folder_id: Option<Int>,
+        pub position: Option<Int>,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "moveFeedInput")]
+        pub move_feed_input: MoveFeedInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "moveFeed")]
+        pub move_feed: MoveFeedMoveFeed,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum MoveFeedMoveFeed {
+        MoveFeedSuccess(MoveFeedMoveFeedOnMoveFeedSuccess),
+        MoveFeedError(MoveFeedMoveFeedOnMoveFeedError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct MoveFeedMoveFeedOnMoveFeedSuccess {
+        pub status: MoveFeedMoveFeedOnMoveFeedSuccessStatus,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct MoveFeedMoveFeedOnMoveFeedSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct MoveFeedMoveFeedOnMoveFeedError {
+        pub status: MoveFeedMoveFeedOnMoveFeedErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct MoveFeedMoveFeedOnMoveFeedErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for MoveFeed {
+    type Variables = move_feed::Variables;
+    type ResponseData = move_feed::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: move_feed::QUERY,
+            operation_name: move_feed::OPERATION_NAME,
+        }
+    }
+}
+pub struct CreateFolder;
+pub mod create_folder {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "CreateFolder";
+    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation StarEntry($starInput: StarEntryInput!) {\n  starEntry(input: $starInput) {\n    __typename\n    ... on StarEntrySuccess {\n      entry {\n        ...StarredEntry\n      }\n      status {\n        code\n      }\n    }\n    ... on StarEntryError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnstarEntry($unstarInput: StarEntryInput!) {\n  unstarEntry(input: $unstarInput) {\n    __typename\n    ... on UnstarEntrySuccess {\n      status {\n        code\n      }\n    }\n    ... on UnstarEntryError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation MoveFeed($moveFeedInput: MoveFeedInput!) {\n  moveFeed(input: $moveFeedInput) {\n    __typename\n    ... on MoveFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on MoveFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation CreateFolder($createFolderInput: CreateFolderInput!) {\n  createFolder(input: $createFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RenameFolder($renameFolderInput: RenameFolderInput!) {\n  renameFolder(input: $renameFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateFolderInput {
+        pub name: String,
+        #[serde(rename = "parentId")]
+        pub parent_id: Option<Int>,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "createFolderInput")]
+        pub create_folder_input: CreateFolderInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Folder {
+        pub id: Int,
+        pub name: String,
+        #[serde(rename = "parentId")]
+        pub parent_id: Option<Int>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "createFolder")]
+        pub create_folder: CreateFolderCreateFolder,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum CreateFolderCreateFolder {
+        FolderSuccess(CreateFolderCreateFolderOnFolderSuccess),
+        FolderError(CreateFolderCreateFolderOnFolderError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateFolderCreateFolderOnFolderSuccess {
+        pub folder: CreateFolderCreateFolderOnFolderSuccessFolder,
+        pub status: CreateFolderCreateFolderOnFolderSuccessStatus,
+    }
+    pub type CreateFolderCreateFolderOnFolderSuccessFolder = Folder;
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateFolderCreateFolderOnFolderSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateFolderCreateFolderOnFolderError {
+        pub status: CreateFolderCreateFolderOnFolderErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct CreateFolderCreateFolderOnFolderErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for CreateFolder {
+    type Variables = create_folder::Variables;
+    type ResponseData = create_folder::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: create_folder::QUERY,
+            operation_name: create_folder::OPERATION_NAME,
+        }
+    }
+}
+pub struct RenameFolder;
+pub mod rename_folder {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "RenameFolder";
+    pub const QUERY : & str = "mutation SubscribeFeed($subscribeInput: SubscribeFeedInput!) {\n  subscribeFeed(input: $subscribeInput) {\n    __typename\n    ... on SubscribeFeedSuccess {\n      feed {\n        ...Feed\n      }\n      status {\n        code\n      }\n    }\n    ... on SubscribeFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnsubscribeFeed($unsubscribeInput: UnsubscribeFeedInput!) {\n  unsubscribeFeed(input: $unsubscribeInput) {\n    __typename\n    ... on UnsubscribeFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on UnsubscribeFeedError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation StarEntry($starInput: StarEntryInput!) {\n  starEntry(input: $starInput) {\n    __typename\n    ... on StarEntrySuccess {\n      entry {\n        ...StarredEntry\n      }\n      status {\n        code\n      }\n    }\n    ... on StarEntryError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation UnstarEntry($unstarInput: StarEntryInput!) {\n  unstarEntry(input: $unstarInput) {\n    __typename\n    ... on UnstarEntrySuccess {\n      status {\n        code\n      }\n    }\n    ... on UnstarEntryError {\n      status {\n        code\n      }\n    }\n  }\n}\n\nmutation MoveFeed($moveFeedInput: MoveFeedInput!) {\n  moveFeed(input: $moveFeedInput) {\n    __typename\n    ... on MoveFeedSuccess {\n      status {\n        code\n      }\n    }\n    ... on MoveFeedError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation CreateFolder($createFolderInput: CreateFolderInput!) {\n  createFolder(input: $createFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nmutation RenameFolder($renameFolderInput: RenameFolderInput!) {\n  renameFolder(input: $renameFolderInput) {\n    __typename\n    ... on FolderSuccess {\n      folder {\n        ...Folder\n      }\n      status {\n        code\n      }\n    }\n    ... on FolderError {\n      status {\n        code\n      }\n      message\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  entries(first: 20) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum ResponseCode {
+        OK,
+        UNAUTHORIZED,
+        INVALID_FEED_URL,
+        INTERNAL_ERROR,
+        FEED_UNAVAILABLE,
+        ENTRY_NOT_FOUND,
+        FEED_NOT_SUBSCRIBED,
+        FOLDER_NOT_FOUND,
+        INVALID_FOLDER_NAME,
+        API_TOKEN_NOT_FOUND,
+        INVALID_API_TOKEN,
+        QUOTA_EXCEEDED,
+        RATE_LIMITED,
+        Other(String),
+    }
+    impl ::serde::Serialize for ResponseCode {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                ResponseCode::OK => "OK",
+                ResponseCode::UNAUTHORIZED => "UNAUTHORIZED",
+                ResponseCode::INVALID_FEED_URL => "INVALID_FEED_URL",
+                ResponseCode::INTERNAL_ERROR => "INTERNAL_ERROR",
+                ResponseCode::FEED_UNAVAILABLE => "FEED_UNAVAILABLE",
+                ResponseCode::ENTRY_NOT_FOUND => "ENTRY_NOT_FOUND",
+                ResponseCode::FEED_NOT_SUBSCRIBED => "FEED_NOT_SUBSCRIBED",
+                ResponseCode::FOLDER_NOT_FOUND => "FOLDER_NOT_FOUND",
+                ResponseCode::INVALID_FOLDER_NAME => "INVALID_FOLDER_NAME",
+                ResponseCode::API_TOKEN_NOT_FOUND => "API_TOKEN_NOT_FOUND",
+                ResponseCode::INVALID_API_TOKEN => "INVALID_API_TOKEN",
+                ResponseCode::QUOTA_EXCEEDED => "QUOTA_EXCEEDED",
+                ResponseCode::RATE_LIMITED => "RATE_LIMITED",
+                ResponseCode::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for ResponseCode {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "OK" => Ok(ResponseCode::OK),
+                "UNAUTHORIZED" => Ok(ResponseCode::UNAUTHORIZED),
+                "INVALID_FEED_URL" => Ok(ResponseCode::INVALID_FEED_URL),
+                "INTERNAL_ERROR" => Ok(ResponseCode::INTERNAL_ERROR),
+                "FEED_UNAVAILABLE" => Ok(ResponseCode::FEED_UNAVAILABLE),
+                "ENTRY_NOT_FOUND" => Ok(ResponseCode::ENTRY_NOT_FOUND),
+                "FEED_NOT_SUBSCRIBED" => Ok(ResponseCode::FEED_NOT_SUBSCRIBED),
+                "FOLDER_NOT_FOUND" => Ok(ResponseCode::FOLDER_NOT_FOUND),
+                "INVALID_FOLDER_NAME" => Ok(ResponseCode::INVALID_FOLDER_NAME),
+                "API_TOKEN_NOT_FOUND" => Ok(ResponseCode::API_TOKEN_NOT_FOUND),
+                "INVALID_API_TOKEN" => Ok(ResponseCode::INVALID_API_TOKEN),
+                "QUOTA_EXCEEDED" => Ok(ResponseCode::QUOTA_EXCEEDED),
+                "RATE_LIMITED" => Ok(ResponseCode::RATE_LIMITED),
+                _ => Ok(ResponseCode::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RenameFolderInput {
+        #[serde(rename = "folderId")]
+        pub folder_id: Int,
+        pub name: String,
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        #[serde(rename = "renameFolderInput")]
+        pub rename_folder_input: RenameFolderInput,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Folder {
+        pub id: Int,
+        pub name: String,
+        #[serde(rename = "parentId")]
+        pub parent_id: Option<Int>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        #[serde(rename = "renameFolder")]
+        pub rename_folder: RenameFolderRenameFolder,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    #[serde(tag = "__typename")]
+    pub enum RenameFolderRenameFolder {
+        FolderSuccess(RenameFolderRenameFolderOnFolderSuccess),
+        FolderError(RenameFolderRenameFolderOnFolderError),
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RenameFolderRenameFolderOnFolderSuccess {
+        pub folder: RenameFolderRenameFolderOnFolderSuccessFolder,
+        pub status: RenameFolderRenameFolderOnFolderSuccessStatus,
+    }
+    pub type RenameFolderRenameFolderOnFolderSuccessFolder = Folder;
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RenameFolderRenameFolderOnFolderSuccessStatus {
+        pub code: ResponseCode,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RenameFolderRenameFolderOnFolderError {
+        pub status: RenameFolderRenameFolderOnFolderErrorStatus,
+        pub message: String,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct RenameFolderRenameFolderOnFolderErrorStatus {
+        pub code: ResponseCode,
+    }
+}
+impl graphql_client::GraphQLQuery for RenameFolder {
+    type Variables = rename_folder::Variables;
+    type ResponseData = rename_folder::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: rename_folder::QUERY,
+            operation_name: rename_folder::OPERATION_NAME,
+        }
+    }
+}
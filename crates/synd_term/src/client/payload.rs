@@ -15,6 +15,20 @@ impl From<query::entries::EntriesOutput> for FetchEntriesPayload {
     }
 }
 
+impl From<query::starred_entries::StarredEntriesOutput> for FetchEntriesPayload {
+    fn from(v: query::starred_entries::StarredEntriesOutput) -> Self {
+        let page_info = v.starred_entries.page_info.into();
+        let entries = v
+            .starred_entries
+            .nodes
+            .into_iter()
+            .map(Into::into)
+            .collect();
+
+        Self { entries, page_info }
+    }
+}
+
 pub struct ExportSubscriptionPayload {
     pub feeds: Vec<types::ExportedFeed>,
     pub page_info: types::PageInfo,
@@ -4,7 +4,7 @@ pub mod subscription {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "Subscription";
-    pub const QUERY : & str = "query Subscription($after: String, $first: Int) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      nodes {\n        ...Feed\n      }\n      pageInfo {\n        ...PageInfo\n      }\n      errors {\n        url\n        errorMessage\n      }\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 10) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nquery Entries($after: String, $first: Int!) {\n  output: subscription {\n    entries(after: $after, first: $first) {\n      nodes {\n        ...Entry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment Entry on Entry {\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feed {\n    ...FeedMeta\n  }\n}\n\nfragment FeedMeta on FeedMeta {\n  title\n  url\n  requirement\n  category\n}\n\nfragment PageInfo on PageInfo {\n  hasNextPage\n  endCursor\n}\n\nquery ExportSubscription($after: String, $first: Int!) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      pageInfo {\n        hasNextPage\n        endCursor\n      }\n      nodes {\n        title\n        url\n      }\n    }\n  }\n}\n" ;
+    pub const QUERY : & str = "query Subscription($after: String, $first: Int) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      nodes {\n        ...Feed\n      }\n      pageInfo {\n        ...PageInfo\n      }\n      errors {\n        url\n        errorMessage\n      }\n      folders {\n        ...Folder\n      }\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  health {\n    lastSuccess\n    lastError\n    consecutiveFailures\n    lastHttpStatus\n    averageLatencyMs\n  }\n  entries(first: 10) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nquery Entries($after: String, $first: Int!) {\n  output: subscription {\n    entries(after: $after, first: $first) {\n      nodes {\n        ...Entry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment Entry on Entry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feed {\n    ...FeedMeta\n  }\n}\n\nfragment FeedMeta on FeedMeta {\n  title\n  url\n  requirement\n  category\n  customTitle\n  tags\n}\n\nfragment PageInfo on PageInfo {\n  hasNextPage\n  endCursor\n}\n\nquery StarredEntries($after: String, $first: Int!) {\n  output: subscription {\n    starredEntries(after: $after, first: $first) {\n      nodes {\n        ...StarredEntry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n\nquery ExportSubscription($after: String, $first: Int!) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      pageInfo {\n        hasNextPage\n        endCursor\n      }\n      nodes {\n        title\n        url\n      }\n    }\n  }\n}\n" ;
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -53,6 +53,46 @@ pub mod subscription {
         }
     }
     #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum FetchErrorKind {
+        NETWORK,
+        HTTP_STATUS,
+        RESPONSE_LIMIT_EXCEEDED,
+        INVALID_FEED,
+        DISALLOWED_BY_ROBOTS_TXT,
+        RETRY_AFTER,
+        OTHER,
+        Other(String),
+    }
+    impl ::serde::Serialize for FetchErrorKind {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                FetchErrorKind::NETWORK => "NETWORK",
+                FetchErrorKind::HTTP_STATUS => "HTTP_STATUS",
+                FetchErrorKind::RESPONSE_LIMIT_EXCEEDED => "RESPONSE_LIMIT_EXCEEDED",
+                FetchErrorKind::INVALID_FEED => "INVALID_FEED",
+                FetchErrorKind::DISALLOWED_BY_ROBOTS_TXT => "DISALLOWED_BY_ROBOTS_TXT",
+                FetchErrorKind::RETRY_AFTER => "RETRY_AFTER",
+                FetchErrorKind::OTHER => "OTHER",
+                FetchErrorKind::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for FetchErrorKind {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "NETWORK" => Ok(FetchErrorKind::NETWORK),
+                "HTTP_STATUS" => Ok(FetchErrorKind::HTTP_STATUS),
+                "RESPONSE_LIMIT_EXCEEDED" => Ok(FetchErrorKind::RESPONSE_LIMIT_EXCEEDED),
+                "INVALID_FEED" => Ok(FetchErrorKind::INVALID_FEED),
+                "DISALLOWED_BY_ROBOTS_TXT" => Ok(FetchErrorKind::DISALLOWED_BY_ROBOTS_TXT),
+                "RETRY_AFTER" => Ok(FetchErrorKind::RETRY_AFTER),
+                "OTHER" => Ok(FetchErrorKind::OTHER),
+                _ => Ok(FetchErrorKind::Other(s)),
+            }
+        }
+    }
+    #[derive(Clone, Debug, Eq, PartialEq)]
     pub enum Requirement {
         MUST,
         SHOULD,
@@ -87,6 +127,13 @@ pub mod subscription {
     }
     impl Variables {}
     #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Folder {
+        pub id: Int,
+        pub name: String,
+        #[serde(rename = "parentId")]
+        pub parent_id: Option<Int>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
     pub struct Feed {
         pub id: ID,
         #[serde(rename = "type")]
@@ -100,11 +147,31 @@ pub mod subscription {
         pub generator: Option<String>,
         pub requirement: Option<Requirement>,
         pub category: Option<Category>,
+        #[serde(rename = "customTitle")]
+        pub custom_title: Option<String>,
+        pub note: Option<String>,
+        pub tags: Vec<Category>,
+        #[serde(rename = "folderId")]
+        pub folder_id: Option<Int>,
+        pub health: Option<FeedHealth>,
         pub entries: FeedEntries,
         pub links: FeedLinks,
         pub authors: FeedAuthors,
     }
     #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct FeedHealth {
+        #[serde(rename = "lastSuccess")]
+        pub last_success: Option<Rfc3339Time>,
+        #[serde(rename = "lastError")]
+        pub last_error: Option<FetchErrorKind>,
+        #[serde(rename = "consecutiveFailures")]
+        pub consecutive_failures: Int,
+        #[serde(rename = "lastHttpStatus")]
+        pub last_http_status: Option<Int>,
+        #[serde(rename = "averageLatencyMs")]
+        pub average_latency_ms: Option<Int>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
     pub struct FeedEntries {
         pub nodes: Vec<FeedEntriesNodes>,
     }
@@ -154,6 +221,7 @@ pub mod subscription {
         #[serde(rename = "pageInfo")]
         pub page_info: SubscriptionOutputFeedsPageInfo,
         pub errors: Vec<SubscriptionOutputFeedsErrors>,
+        pub folders: Vec<SubscriptionOutputFeedsFolders>,
     }
     pub type SubscriptionOutputFeedsNodes = Feed;
     pub type SubscriptionOutputFeedsPageInfo = PageInfo;
@@ -163,6 +231,7 @@ pub mod subscription {
         #[serde(rename = "errorMessage")]
         pub error_message: String,
     }
+    pub type SubscriptionOutputFeedsFolders = Folder;
 }
 impl graphql_client::GraphQLQuery for Subscription {
     type Variables = subscription::Variables;
@@ -180,7 +249,7 @@ pub mod entries {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "Entries";
-    pub const QUERY : & str = "query Subscription($after: String, $first: Int) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      nodes {\n        ...Feed\n      }\n      pageInfo {\n        ...PageInfo\n      }\n      errors {\n        url\n        errorMessage\n      }\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 10) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nquery Entries($after: String, $first: Int!) {\n  output: subscription {\n    entries(after: $after, first: $first) {\n      nodes {\n        ...Entry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment Entry on Entry {\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feed {\n    ...FeedMeta\n  }\n}\n\nfragment FeedMeta on FeedMeta {\n  title\n  url\n  requirement\n  category\n}\n\nfragment PageInfo on PageInfo {\n  hasNextPage\n  endCursor\n}\n\nquery ExportSubscription($after: String, $first: Int!) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      pageInfo {\n        hasNextPage\n        endCursor\n      }\n      nodes {\n        title\n        url\n      }\n    }\n  }\n}\n" ;
+    pub const QUERY : & str = "query Subscription($after: String, $first: Int) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      nodes {\n        ...Feed\n      }\n      pageInfo {\n        ...PageInfo\n      }\n      errors {\n        url\n        errorMessage\n      }\n      folders {\n        ...Folder\n      }\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  health {\n    lastSuccess\n    lastError\n    consecutiveFailures\n    lastHttpStatus\n    averageLatencyMs\n  }\n  entries(first: 10) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nquery Entries($after: String, $first: Int!) {\n  output: subscription {\n    entries(after: $after, first: $first) {\n      nodes {\n        ...Entry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment Entry on Entry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feed {\n    ...FeedMeta\n  }\n}\n\nfragment FeedMeta on FeedMeta {\n  title\n  url\n  requirement\n  category\n  customTitle\n  tags\n}\n\nfragment PageInfo on PageInfo {\n  hasNextPage\n  endCursor\n}\n\nquery StarredEntries($after: String, $first: Int!) {\n  output: subscription {\n    starredEntries(after: $after, first: $first) {\n      nodes {\n        ...StarredEntry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n\nquery ExportSubscription($after: String, $first: Int!) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      pageInfo {\n        hasNextPage\n        endCursor\n      }\n      nodes {\n        title\n        url\n      }\n    }\n  }\n}\n" ;
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -230,6 +299,7 @@ pub mod entries {
     impl Variables {}
     #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
     pub struct Entry {
+        pub id: String,
         pub title: Option<String>,
         pub published: Option<Rfc3339Time>,
         pub updated: Option<Rfc3339Time>,
@@ -245,6 +315,9 @@ pub mod entries {
         pub url: FeedUrl,
         pub requirement: Option<Requirement>,
         pub category: Option<Category>,
+        #[serde(rename = "customTitle")]
+        pub custom_title: Option<String>,
+        pub tags: Vec<Category>,
     }
     #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
     pub struct PageInfo {
@@ -281,12 +354,117 @@ impl graphql_client::GraphQLQuery for Entries {
         }
     }
 }
+pub struct StarredEntries;
+pub mod starred_entries {
+    #![allow(dead_code)]
+    use std::result::Result;
+    pub const OPERATION_NAME: &str = "StarredEntries";
+    pub const QUERY : & str = "query Subscription($after: String, $first: Int) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      nodes {\n        ...Feed\n      }\n      pageInfo {\n        ...PageInfo\n      }\n      errors {\n        url\n        errorMessage\n      }\n      folders {\n        ...Folder\n      }\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  health {\n    lastSuccess\n    lastError\n    consecutiveFailures\n    lastHttpStatus\n    averageLatencyMs\n  }\n  entries(first: 10) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nquery Entries($after: String, $first: Int!) {\n  output: subscription {\n    entries(after: $after, first: $first) {\n      nodes {\n        ...Entry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment Entry on Entry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feed {\n    ...FeedMeta\n  }\n}\n\nfragment FeedMeta on FeedMeta {\n  title\n  url\n  requirement\n  category\n  customTitle\n  tags\n}\n\nfragment PageInfo on PageInfo {\n  hasNextPage\n  endCursor\n}\n\nquery StarredEntries($after: String, $first: Int!) {\n  output: subscription {\n    starredEntries(after: $after, first: $first) {\n      nodes {\n        ...StarredEntry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n\nquery ExportSubscription($after: String, $first: Int!) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      pageInfo {\n        hasNextPage\n        endCursor\n      }\n      nodes {\n        title\n        url\n      }\n    }\n  }\n}\n" ;
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    #[allow(dead_code)]
+    type Boolean = bool;
+    #[allow(dead_code)]
+    type Float = f64;
+    #[allow(dead_code)]
+    type Int = i64;
+    #[allow(dead_code)]
+    type ID = String;
+    type Category = crate::client::scalar::Category;
+    type FeedUrl = crate::client::scalar::FeedUrl;
+    type Rfc3339Time = crate::client::scalar::Rfc3339Time;
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub enum Requirement {
+        MUST,
+        SHOULD,
+        MAY,
+        Other(String),
+    }
+    impl ::serde::Serialize for Requirement {
+        fn serialize<S: serde::Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+            ser.serialize_str(match *self {
+                Requirement::MUST => "MUST",
+                Requirement::SHOULD => "SHOULD",
+                Requirement::MAY => "MAY",
+                Requirement::Other(ref s) => &s,
+            })
+        }
+    }
+    impl<'de> ::serde::Deserialize<'de> for Requirement {
+        fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let s: String = ::serde::Deserialize::deserialize(deserializer)?;
+            match s.as_str() {
+                "MUST" => Ok(Requirement::MUST),
+                "SHOULD" => Ok(Requirement::SHOULD),
+                "MAY" => Ok(Requirement::MAY),
+                _ => Ok(Requirement::Other(s)),
+            }
+        }
+    }
+    #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+    pub struct Variables {
+        pub after: Option<String>,
+        pub first: Int,
+    }
+    impl Variables {}
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct PageInfo {
+        #[serde(rename = "hasNextPage")]
+        pub has_next_page: Boolean,
+        #[serde(rename = "endCursor")]
+        pub end_cursor: Option<String>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarredEntry {
+        pub id: String,
+        pub title: Option<String>,
+        pub published: Option<Rfc3339Time>,
+        pub updated: Option<Rfc3339Time>,
+        pub summary: Option<String>,
+        #[serde(rename = "websiteUrl")]
+        pub website_url: Option<String>,
+        #[serde(rename = "feedUrl")]
+        pub feed_url: FeedUrl,
+        #[serde(rename = "feedTitle")]
+        pub feed_title: Option<String>,
+        pub requirement: Option<Requirement>,
+        pub category: Option<Category>,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct ResponseData {
+        pub output: StarredEntriesOutput,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarredEntriesOutput {
+        #[serde(rename = "starredEntries")]
+        pub starred_entries: StarredEntriesOutputStarredEntries,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+    pub struct StarredEntriesOutputStarredEntries {
+        pub nodes: Vec<StarredEntriesOutputStarredEntriesNodes>,
+        #[serde(rename = "pageInfo")]
+        pub page_info: StarredEntriesOutputStarredEntriesPageInfo,
+    }
+    pub type StarredEntriesOutputStarredEntriesNodes = StarredEntry;
+    pub type StarredEntriesOutputStarredEntriesPageInfo = PageInfo;
+}
+impl graphql_client::GraphQLQuery for StarredEntries {
+    type Variables = starred_entries::Variables;
+    type ResponseData = starred_entries::ResponseData;
+    fn build_query(variables: Self::Variables) -> ::graphql_client::QueryBody<Self::Variables> {
+        graphql_client::QueryBody {
+            variables,
+            query: starred_entries::QUERY,
+            operation_name: starred_entries::OPERATION_NAME,
+        }
+    }
+}
 pub struct ExportSubscription;
 pub mod export_subscription {
     #![allow(dead_code)]
     use std::result::Result;
     pub const OPERATION_NAME: &str = "ExportSubscription";
-    pub const QUERY : & str = "query Subscription($after: String, $first: Int) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      nodes {\n        ...Feed\n      }\n      pageInfo {\n        ...PageInfo\n      }\n      errors {\n        url\n        errorMessage\n      }\n    }\n  }\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  entries(first: 10) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nquery Entries($after: String, $first: Int!) {\n  output: subscription {\n    entries(after: $after, first: $first) {\n      nodes {\n        ...Entry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment Entry on Entry {\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feed {\n    ...FeedMeta\n  }\n}\n\nfragment FeedMeta on FeedMeta {\n  title\n  url\n  requirement\n  category\n}\n\nfragment PageInfo on PageInfo {\n  hasNextPage\n  endCursor\n}\n\nquery ExportSubscription($after: String, $first: Int!) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      pageInfo {\n        hasNextPage\n        endCursor\n      }\n      nodes {\n        title\n        url\n      }\n    }\n  }\n}\n" ;
+    pub const QUERY : & str = "query Subscription($after: String, $first: Int) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      nodes {\n        ...Feed\n      }\n      pageInfo {\n        ...PageInfo\n      }\n      errors {\n        url\n        errorMessage\n      }\n      folders {\n        ...Folder\n      }\n    }\n  }\n}\n\nfragment Folder on Folder {\n  id\n  name\n  parentId\n}\n\nfragment Feed on Feed {\n  id\n  type\n  title\n  url\n  updated\n  websiteUrl\n  description\n  generator\n  requirement\n  category\n  customTitle\n  note\n  tags\n  folderId\n  health {\n    lastSuccess\n    lastError\n    consecutiveFailures\n    lastHttpStatus\n    averageLatencyMs\n  }\n  entries(first: 10) {\n    nodes {\n      ...EntryMeta\n    }\n  }\n  links {\n    nodes {\n      ...Link\n    }\n  }\n  authors {\n    nodes\n  }\n}\n\nfragment EntryMeta on Entry {\n    title\n    published\n    updated\n    summary\n}\n\nfragment Link on Link {\n  href\n  rel\n  mediaType\n  title  \n}\n\nquery Entries($after: String, $first: Int!) {\n  output: subscription {\n    entries(after: $after, first: $first) {\n      nodes {\n        ...Entry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment Entry on Entry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feed {\n    ...FeedMeta\n  }\n}\n\nfragment FeedMeta on FeedMeta {\n  title\n  url\n  requirement\n  category\n  customTitle\n  tags\n}\n\nfragment PageInfo on PageInfo {\n  hasNextPage\n  endCursor\n}\n\nquery StarredEntries($after: String, $first: Int!) {\n  output: subscription {\n    starredEntries(after: $after, first: $first) {\n      nodes {\n        ...StarredEntry\n      }\n      pageInfo {\n        ...PageInfo\n      }\n    }\n  }\n}\n\nfragment StarredEntry on StarredEntry {\n  id\n  title\n  published\n  updated\n  summary\n  websiteUrl\n  feedUrl\n  feedTitle\n  requirement\n  category\n}\n\nquery ExportSubscription($after: String, $first: Int!) {\n  output: subscription {\n    feeds(after: $after, first: $first) {\n      pageInfo {\n        hasNextPage\n        endCursor\n      }\n      nodes {\n        title\n        url\n      }\n    }\n  }\n}\n" ;
     use super::*;
     use serde::{Deserialize, Serialize};
     #[allow(dead_code)]
@@ -9,7 +9,7 @@ use crate::{
         mutation::subscribe_feed::SubscribeFeedInput, payload,
         query::subscription::SubscriptionOutput, SyndApiError,
     },
-    types::Feed,
+    types::{self, Feed},
 };
 
 #[derive(Debug, Clone)]
@@ -34,6 +34,11 @@ pub(crate) enum Command {
         credential: Verified<Credential>,
         request_seq: RequestSequence,
     },
+    CancelLocalLogin,
+    CompleteLocalLogin {
+        credential: Verified<Credential>,
+        request_seq: RequestSequence,
+    },
     RefreshCredential {
         credential: Verified<Credential>,
     },
@@ -47,6 +52,10 @@ pub(crate) enum Command {
     PromptFeedSubscription,
     PromptFeedEdition,
     PromptFeedUnsubscription,
+    PromptFeedMove,
+    PromptFolderCreation,
+    ReorderSubscribedFeed(Direction),
+    ToggleFeedHealthSort,
     MoveFeedUnsubscriptionPopupSelection(Direction),
     SelectFeedUnsubscriptionPopup,
     CancelFeedUnsubscriptionPopup,
@@ -61,6 +70,26 @@ pub(crate) enum Command {
         url: FeedUrl,
         request_seq: RequestSequence,
     },
+    MoveFeed {
+        url: FeedUrl,
+        folder_id: Option<types::FolderId>,
+        position: Option<usize>,
+    },
+    CompleteMoveFeed {
+        request_seq: RequestSequence,
+    },
+    CreateFolder {
+        name: String,
+        parent_id: Option<types::FolderId>,
+    },
+    RenameFolder {
+        folder_id: types::FolderId,
+        name: String,
+    },
+    CompleteUpsertFolder {
+        folder: types::Folder,
+        request_seq: RequestSequence,
+    },
     FetchSubscription {
         after: Option<String>,
         first: i64,
@@ -89,6 +118,29 @@ pub(crate) enum Command {
     MoveEntryLast,
     OpenEntry,
 
+    // Starred entries
+    StarEntry,
+    UnstarEntry,
+    CompleteStarEntry {
+        entry: types::Entry,
+        request_seq: RequestSequence,
+    },
+    CompleteUnstarEntry {
+        feed_url: FeedUrl,
+        id: String,
+        request_seq: RequestSequence,
+    },
+    FetchStarredEntries {
+        after: Option<String>,
+        first: i64,
+    },
+    PopulateFetchedStarredEntries {
+        populate: Populate,
+        payload: payload::FetchEntriesPayload,
+        request_seq: RequestSequence,
+    },
+    ReloadStarredEntries,
+
     // Filter
     MoveFilterRequirement(Direction),
     ActivateCategoryFilterling,
@@ -130,9 +182,13 @@ impl Display for Command {
                 f.write_str("PopulateFetchedSubscription")
             }
             Command::PopulateFetchedEntries { .. } => f.write_str("PopulateFetchedEntries"),
+            Command::PopulateFetchedStarredEntries { .. } => {
+                f.write_str("PopulateFetchedStarredEntries")
+            }
             Command::CompleteDevieAuthorizationFlow { .. } => {
                 f.write_str("CompleteDeviceAuthorizationFlow")
             }
+            Command::CompleteLocalLogin { .. } => f.write_str("CompleteLocalLogin"),
             cmd => write!(f, "{cmd:?}"),
         }
     }
@@ -160,6 +216,9 @@ impl Command {
     pub fn authenticate() -> Self {
         Command::Authenticate
     }
+    pub fn cancel_local_login() -> Self {
+        Command::CancelLocalLogin
+    }
     pub fn move_right_tab_selection() -> Self {
         Command::MoveTabSelection(Direction::Right)
     }
@@ -190,6 +249,15 @@ impl Command {
     pub fn move_entry_last() -> Self {
         Command::MoveEntryLast
     }
+    pub fn star_entry() -> Self {
+        Command::StarEntry
+    }
+    pub fn unstar_entry() -> Self {
+        Command::UnstarEntry
+    }
+    pub fn reload_starred_entries() -> Self {
+        Command::ReloadStarredEntries
+    }
     pub fn prompt_feed_subscription() -> Self {
         Command::PromptFeedSubscription
     }
@@ -199,6 +267,21 @@ impl Command {
     pub fn prompt_feed_unsubscription() -> Self {
         Command::PromptFeedUnsubscription
     }
+    pub fn prompt_feed_move() -> Self {
+        Command::PromptFeedMove
+    }
+    pub fn prompt_folder_creation() -> Self {
+        Command::PromptFolderCreation
+    }
+    pub fn toggle_feed_health_sort() -> Self {
+        Command::ToggleFeedHealthSort
+    }
+    pub fn reorder_up_subscribed_feed() -> Self {
+        Command::ReorderSubscribedFeed(Direction::Up)
+    }
+    pub fn reorder_down_subscribed_feed() -> Self {
+        Command::ReorderSubscribedFeed(Direction::Down)
+    }
     pub fn move_feed_unsubscription_popup_selection_left() -> Self {
         Command::MoveFeedUnsubscriptionPopupSelection(Direction::Left)
     }
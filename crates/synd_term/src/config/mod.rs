@@ -21,6 +21,8 @@ pub mod env {
     pub const ENDPOINT: &str = env_key!("ENDPOINT");
     pub const LOG_PATH: &str = env_key!("LOG_PATH");
     pub const THEME: &str = env_key!("THEME");
+    pub const OIDC_ISSUER: &str = env_key!("OIDC_ISSUER");
+    pub const OIDC_CLIENT_ID: &str = env_key!("OIDC_CLIENT_ID");
 }
 
 pub mod client {
@@ -31,6 +33,8 @@ pub mod client {
     pub const INITIAL_ENTRIES_TO_FETCH: i64 = 200;
     /// Number of feeds to fetch
     pub const INITIAL_FEEDS_TO_FETCH: i64 = 50;
+    /// Name of the session token issued by the local login
+    pub const LOCAL_API_TOKEN_NAME: &str = "synd-term";
 }
 
 pub mod credential {
@@ -38,6 +42,8 @@ pub mod credential {
     /// The `Duration` considered as expired before actually performing the refresh process
     pub const EXPIRE_MARGIN: Duration = Duration::from_secs(60);
     pub const FALLBACK_EXPIRE: Duration = Duration::from_secs(60 * 15);
+    /// Lifetime of the session token issued by the local login
+    pub const LOCAL_TOKEN_TTL: Duration = Duration::from_secs(60 * 60 * 24 * 30);
 }
 
 pub mod feed {
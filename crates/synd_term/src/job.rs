@@ -6,12 +6,15 @@ pub(crate) type JobFuture = BoxFuture<'static, anyhow::Result<Command>>;
 
 pub(crate) struct Jobs {
     pub futures: FuturesUnordered<JobFuture>,
+    /// Jobs which wait for a long time before running like credential refreshing
+    pub scheduled: FuturesUnordered<JobFuture>,
 }
 
 impl Jobs {
     pub fn new() -> Self {
         Self {
             futures: FuturesUnordered::new(),
+            scheduled: FuturesUnordered::new(),
         }
     }
 }
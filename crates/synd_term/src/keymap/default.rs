@@ -3,8 +3,9 @@ use crate::keymap::{macros::keymap, KeymapsConfig};
 pub fn default() -> KeymapsConfig {
     let login = keymap!({
         "enter" => authenticate,
-        "k" | "up" => move_up_authentication_provider,
-        "j" | "down" => move_down_authentication_provider,
+        "k" | "up" | "backtab" => move_up_authentication_provider,
+        "j" | "down" | "tab" => move_down_authentication_provider,
+        "esc" => cancel_local_login,
     });
     let tabs = keymap!({
         "tab" => move_right_tab_selection,
@@ -15,6 +16,18 @@ pub fn default() -> KeymapsConfig {
         "j" | "down" => move_down_entry,
         "r" => reload_entries,
         "enter" => open_entry,
+        "s" => star_entry,
+        "g" => {
+           "g" => move_entry_first,
+           "e" => move_entry_last,
+        },
+    });
+    let starred = keymap!({
+        "k" | "up" => move_up_entry,
+        "j" | "down" => move_down_entry,
+        "r" => reload_starred_entries,
+        "enter" => open_entry,
+        "d" => unstar_entry,
         "g" => {
            "g" => move_entry_first,
            "e" => move_entry_last,
@@ -24,6 +37,11 @@ pub fn default() -> KeymapsConfig {
         "a" => prompt_feed_subscription,
         "e" => prompt_feed_edition,
         "d" => prompt_feed_unsubscription,
+        "m" => prompt_feed_move,
+        "f" => prompt_folder_creation,
+        "o" => toggle_feed_health_sort,
+        "S-k" => reorder_up_subscribed_feed,
+        "S-j" => reorder_down_subscribed_feed,
         "k" | "up" => move_up_subscribed_feed,
         "j" | "down" => move_down_subscribed_feed,
         "r" => reload_subscription,
@@ -58,6 +76,7 @@ pub fn default() -> KeymapsConfig {
         subscription,
         filter,
         unsubscribe_popup,
+        starred,
         global,
     }
 }
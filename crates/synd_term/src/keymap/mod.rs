@@ -19,6 +19,7 @@ pub(crate) enum KeymapId {
     Filter = 5,
     CategoryFiltering = 6,
     UnsubscribePopupSelection = 7,
+    Starred = 8,
 }
 
 #[derive(Debug)]
@@ -75,6 +76,7 @@ pub(crate) struct KeymapsConfig {
     pub subscription: KeyTrie,
     pub filter: KeyTrie,
     pub unsubscribe_popup: KeyTrie,
+    pub starred: KeyTrie,
     pub global: KeyTrie,
 }
 
@@ -104,6 +106,7 @@ impl Keymaps {
                 KeymapId::UnsubscribePopupSelection,
                 config.unsubscribe_popup,
             ),
+            Keymap::new(KeymapId::Starred, config.starred),
         ];
 
         Self { keymaps }
@@ -117,11 +120,6 @@ impl Keymaps {
         self.keymaps[id as usize].enable = false;
     }
 
-    pub fn toggle(&mut self, id: KeymapId) {
-        let enable = self.keymaps[id as usize].enable;
-        self.keymaps[id as usize].enable = !enable;
-    }
-
     pub fn update(&mut self, id: KeymapId, keymap: Keymap) {
         let mut keymap = keymap;
         keymap.enable = true;
@@ -210,3 +208,66 @@ fn parse(s: &str) -> anyhow::Result<KeyEvent> {
     }
     Ok(KeyEvent::new(code, modifiers))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_shifted_keys() {
+        // terminals report shifted characters in uppercase with or without the shift modifier
+        for (key, code) in [("S-t", 'T'), ("S-k", 'K'), ("S-j", 'J')] {
+            let parsed = parse(key).unwrap();
+            assert_eq!(
+                parsed,
+                KeyEvent::new(KeyCode::Char(code), KeyModifiers::SHIFT)
+            );
+            assert_eq!(
+                parsed,
+                KeyEvent::new(KeyCode::Char(code), KeyModifiers::NONE)
+            );
+            assert_ne!(
+                parsed,
+                KeyEvent::new(KeyCode::Char(code.to_ascii_lowercase()), KeyModifiers::NONE)
+            );
+        }
+        assert_eq!(
+            parse("C-c").unwrap(),
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn default_keymaps_resolve_shifted_keys() {
+        let mut keymaps = Keymaps::default();
+        keymaps.enable(KeymapId::Global);
+        keymaps.enable(KeymapId::Subscription);
+        for (event, command) in [
+            (
+                KeyEvent::new(KeyCode::Char('K'), KeyModifiers::SHIFT),
+                Command::reorder_up_subscribed_feed(),
+            ),
+            (
+                KeyEvent::new(KeyCode::Char('J'), KeyModifiers::SHIFT),
+                Command::reorder_down_subscribed_feed(),
+            ),
+            (
+                KeyEvent::new(KeyCode::Char('T'), KeyModifiers::SHIFT),
+                Command::rotate_theme(),
+            ),
+            (
+                KeyEvent::new(KeyCode::Char('k'), KeyModifiers::NONE),
+                Command::move_up_subscribed_feed(),
+            ),
+        ] {
+            let KeyEventResult::Consumed {
+                command: Some(found),
+                ..
+            } = keymaps.search(&event)
+            else {
+                panic!("{event:?} is not bound");
+            };
+            assert_eq!(format!("{found:?}"), format!("{command:?}"));
+        }
+    }
+}
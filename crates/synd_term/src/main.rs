@@ -4,8 +4,8 @@ use anyhow::Context as _;
 use crossterm::event::EventStream;
 use futures_util::TryFutureExt;
 use synd_term::{
-    application::{Application, Cache, Config},
-    cli::{self, ApiOptions, Args, FeedOptions, Palette},
+    application::{Application, Authenticator, Cache, Config, JwtService},
+    cli::{self, ApiOptions, Args, AuthOptions, FeedOptions, Palette},
     client::Client,
     config::{self, Categories},
     terminal::Terminal,
@@ -68,7 +68,12 @@ fn build_app(
         entries_limit,
     }: FeedOptions,
     cache_dir: PathBuf,
+    auth: &AuthOptions,
 ) -> anyhow::Result<Application> {
+    let authenticator = match auth.oidc() {
+        Some(oidc) => Authenticator::new().with_oidc(oidc),
+        None => Authenticator::new(),
+    };
     let app = Application::builder()
         .terminal(Terminal::new().context("Failed to construct terminal")?)
         .client(Client::new(endpoint, timeout).context("Failed to construct client")?)
@@ -84,6 +89,7 @@ fn build_app(
         })
         .cache(Cache::new(cache_dir))
         .theme(Theme::with_palette(&palette.into()))
+        .authenticator(authenticator)
         .build();
 
     Ok(app)
@@ -101,6 +107,7 @@ async fn main() {
         cache_dir,
         command,
         palette,
+        auth,
     } = cli::parse();
 
     // Subcommand logs to the terminal, tui writes logs to a file.
@@ -108,10 +115,16 @@ async fn main() {
     let _guard = init_tracing(log).unwrap();
 
     if let Some(command) = command {
+        let jwt_service = match auth.oidc() {
+            Some(oidc) => JwtService::new().with_oidc(oidc),
+            None => JwtService::new(),
+        };
         let exit_code = match command {
+            cli::Command::Account(account) => account.run(endpoint, jwt_service).await,
             cli::Command::Clean(clean) => clean.run(),
             cli::Command::Check(check) => check.run(endpoint).await,
-            cli::Command::Export(export) => export.run(endpoint).await,
+            cli::Command::Export(export) => export.run(endpoint, jwt_service).await,
+            cli::Command::Token(token) => token.run(endpoint, jwt_service).await,
         };
 
         std::process::exit(exit_code);
@@ -125,6 +138,7 @@ async fn main() {
         palette,
         feed,
         cache_dir,
+        &auth,
     ))
     .and_then(|app| {
         tracing::info!("Running...");
@@ -5,7 +5,7 @@ use synd_feed::types::{Category, FeedType, FeedUrl, Requirement};
 
 use crate::{
     client::{
-        mutation,
+        api_token, mutation,
         query::{self},
     },
     ui,
@@ -103,11 +103,22 @@ pub struct Feed {
     pub generator: Option<String>,
     pub entries: Vec<EntryMeta>,
     pub authors: Vec<String>,
+    /// Title given by the user
+    pub custom_title: Option<String>,
+    pub note: Option<String>,
+    pub tags: Vec<Category<'static>>,
+    pub folder_id: Option<FolderId>,
+    pub health: Option<FeedHealth>,
     requirement: Option<Requirement>,
     category: Option<Category<'static>>,
 }
 
 impl Feed {
+    /// Title given by the user if any, otherwise the feed title
+    pub fn display_title(&self) -> Option<&str> {
+        self.custom_title.as_deref().or(self.title.as_deref())
+    }
+
     pub fn requirement(&self) -> Requirement {
         self.requirement.unwrap_or(ui::DEFAULT_REQUIREMNET)
     }
@@ -144,6 +155,11 @@ impl From<query::subscription::Feed> for Feed {
                 query::subscription::Requirement::Other(_) => None,
             }),
             category: f.category,
+            custom_title: f.custom_title,
+            note: f.note,
+            tags: f.tags,
+            folder_id: f.folder_id,
+            health: f.health.map(FeedHealth::from),
         }
     }
 }
@@ -175,12 +191,100 @@ impl From<mutation::subscribe_feed::Feed> for Feed {
                 mutation::subscribe_feed::Requirement::Other(_) => None,
             }),
             category: f.category,
+            custom_title: f.custom_title,
+            note: f.note,
+            tags: f.tags,
+            folder_id: f.folder_id,
+            health: None,
+        }
+    }
+}
+
+/// Outcomes of fetching the feed observed by the api
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(fake::Dummy))]
+pub struct FeedHealth {
+    pub last_success: Option<Time>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: i64,
+    pub last_http_status: Option<i64>,
+    pub average_latency_ms: Option<i64>,
+}
+
+impl FeedHealth {
+    /// Whether the feed has failed since the last success
+    pub fn is_failing(&self) -> bool {
+        self.consecutive_failures > 0
+    }
+}
+
+impl From<query::subscription::FeedHealth> for FeedHealth {
+    fn from(h: query::subscription::FeedHealth) -> Self {
+        use query::subscription::FetchErrorKind;
+        Self {
+            last_success: h.last_success.map(parse_time),
+            last_error: h.last_error.map(|kind| {
+                match kind {
+                    FetchErrorKind::NETWORK => "network error",
+                    FetchErrorKind::HTTP_STATUS => "http error status",
+                    FetchErrorKind::RESPONSE_LIMIT_EXCEEDED => "response too large",
+                    FetchErrorKind::INVALID_FEED => "invalid feed",
+                    FetchErrorKind::DISALLOWED_BY_ROBOTS_TXT => "disallowed by robots.txt",
+                    FetchErrorKind::RETRY_AFTER => "rate limited",
+                    FetchErrorKind::OTHER | FetchErrorKind::Other(_) => "error",
+                }
+                .to_owned()
+            }),
+            consecutive_failures: h.consecutive_failures,
+            last_http_status: h.last_http_status,
+            average_latency_ms: h.average_latency_ms,
+        }
+    }
+}
+
+pub type FolderId = i64;
+
+/// Named group of subscribed feeds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Folder {
+    pub id: FolderId,
+    pub name: String,
+    pub parent_id: Option<FolderId>,
+}
+
+impl From<query::subscription::Folder> for Folder {
+    fn from(v: query::subscription::Folder) -> Self {
+        Self {
+            id: v.id,
+            name: v.name,
+            parent_id: v.parent_id,
+        }
+    }
+}
+
+impl From<mutation::create_folder::Folder> for Folder {
+    fn from(v: mutation::create_folder::Folder) -> Self {
+        Self {
+            id: v.id,
+            name: v.name,
+            parent_id: v.parent_id,
+        }
+    }
+}
+
+impl From<mutation::rename_folder::Folder> for Folder {
+    fn from(v: mutation::rename_folder::Folder) -> Self {
+        Self {
+            id: v.id,
+            name: v.name,
+            parent_id: v.parent_id,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Entry {
+    pub id: String,
     pub title: Option<String>,
     pub published: Option<Time>,
     pub updated: Option<Time>,
@@ -210,16 +314,21 @@ impl Entry {
             .as_ref()
             .unwrap_or_else(|| ui::default_category())
     }
+
+    pub fn is(&self, feed_url: &FeedUrl, id: &str) -> bool {
+        &self.feed_url == feed_url && self.id == id
+    }
 }
 
 impl From<query::entries::Entry> for Entry {
     fn from(v: query::entries::Entry) -> Self {
         Self {
+            id: v.id,
             title: v.title,
             published: v.published.map(parse_time),
             updated: v.updated.map(parse_time),
             website_url: v.website_url,
-            feed_title: v.feed.title,
+            feed_title: v.feed.custom_title.or(v.feed.title),
             feed_url: v.feed.url,
             summary: v.summary,
             requirement: match v.feed.requirement {
@@ -233,6 +342,50 @@ impl From<query::entries::Entry> for Entry {
     }
 }
 
+impl From<query::starred_entries::StarredEntry> for Entry {
+    fn from(v: query::starred_entries::StarredEntry) -> Self {
+        Self {
+            id: v.id,
+            title: v.title,
+            published: v.published.map(parse_time),
+            updated: v.updated.map(parse_time),
+            website_url: v.website_url,
+            feed_title: v.feed_title,
+            feed_url: v.feed_url,
+            summary: v.summary,
+            requirement: match v.requirement {
+                Some(query::starred_entries::Requirement::MUST) => Some(Requirement::Must),
+                Some(query::starred_entries::Requirement::SHOULD) => Some(Requirement::Should),
+                Some(query::starred_entries::Requirement::MAY) => Some(Requirement::May),
+                _ => None,
+            },
+            category: v.category,
+        }
+    }
+}
+
+impl From<mutation::star_entry::StarredEntry> for Entry {
+    fn from(v: mutation::star_entry::StarredEntry) -> Self {
+        Self {
+            id: v.id,
+            title: v.title,
+            published: v.published.map(parse_time),
+            updated: v.updated.map(parse_time),
+            website_url: v.website_url,
+            feed_title: v.feed_title,
+            feed_url: v.feed_url,
+            summary: v.summary,
+            requirement: match v.requirement {
+                Some(mutation::star_entry::Requirement::MUST) => Some(Requirement::Must),
+                Some(mutation::star_entry::Requirement::SHOULD) => Some(Requirement::Should),
+                Some(mutation::star_entry::Requirement::MAY) => Some(Requirement::May),
+                _ => None,
+            },
+            category: v.category,
+        }
+    }
+}
+
 #[derive(Serialize, JsonSchema)]
 pub struct ExportedFeed {
     pub title: Option<String>,
@@ -248,6 +401,40 @@ impl From<query::export_subscription::ExportSubscriptionOutputFeedsNodes> for Ex
     }
 }
 
+/// Personal access token to call the api from scripts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ApiToken {
+    pub id: String,
+    pub name: String,
+    pub read_only: bool,
+    pub created_at: Time,
+    pub expires_at: Option<Time>,
+}
+
+impl From<api_token::api_tokens::ApiToken> for ApiToken {
+    fn from(v: api_token::api_tokens::ApiToken) -> Self {
+        Self {
+            id: v.id,
+            name: v.name,
+            read_only: v.read_only,
+            created_at: parse_time(v.created_at),
+            expires_at: v.expires_at.map(parse_time),
+        }
+    }
+}
+
+impl From<api_token::create_api_token::ApiToken> for ApiToken {
+    fn from(v: api_token::create_api_token::ApiToken) -> Self {
+        Self {
+            id: v.id,
+            name: v.name,
+            read_only: v.read_only,
+            created_at: parse_time(v.created_at),
+            expires_at: v.expires_at.map(parse_time),
+        }
+    }
+}
+
 fn parse_time(t: impl AsRef<str>) -> Time {
     DateTime::parse_from_rfc3339(t.as_ref())
         .expect("invalid rfc3339 time")
@@ -15,6 +15,15 @@ impl From<query::entries::PageInfo> for PageInfo {
     }
 }
 
+impl From<query::starred_entries::PageInfo> for PageInfo {
+    fn from(v: query::starred_entries::PageInfo) -> Self {
+        Self {
+            has_next_page: v.has_next_page,
+            end_cursor: v.end_cursor,
+        }
+    }
+}
+
 impl From<query::export_subscription::ExportSubscriptionOutputFeedsPageInfo> for PageInfo {
     fn from(v: query::export_subscription::ExportSubscriptionOutputFeedsPageInfo) -> Self {
         Self {
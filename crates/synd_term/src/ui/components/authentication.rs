@@ -1,3 +1,5 @@
+use std::{cell::RefCell, rc::Rc};
+
 use ratatui::{
     prelude::{Alignment, Buffer, Constraint, Layout, Rect},
     style::{Modifier, Style},
@@ -13,7 +15,12 @@ use tui_big_text::{BigText, PixelSize};
 use crate::{
     application::Direction,
     auth::AuthenticationProvider,
-    ui::{self, extension::RectExt, Context},
+    ui::{
+        self,
+        extension::RectExt,
+        widgets::prompt::{Prompt, RenderCursor},
+        Context,
+    },
 };
 
 /// Handle user authentication
@@ -21,13 +28,37 @@ use crate::{
 pub(crate) enum AuthenticateState {
     NotAuthenticated,
     DeviceFlow(DeviceAuthorizationResponse),
+    /// Inputting username and password of the local user
+    LocalLogin,
     Authenticated,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LocalLoginField {
+    Username,
+    Password,
+}
+
+struct LocalLoginForm {
+    username: Rc<RefCell<Prompt>>,
+    password: Rc<RefCell<Prompt>>,
+    focus: LocalLoginField,
+}
+
+impl LocalLoginForm {
+    fn focused(&self) -> Rc<RefCell<Prompt>> {
+        match self.focus {
+            LocalLoginField::Username => self.username.clone(),
+            LocalLoginField::Password => self.password.clone(),
+        }
+    }
+}
+
 pub(crate) struct Authentication {
     state: AuthenticateState,
     providers: Vec<AuthenticationProvider>,
     selected_provider_index: usize,
+    local_login: Option<LocalLoginForm>,
 }
 
 impl Authentication {
@@ -38,6 +69,7 @@ impl Authentication {
             state: AuthenticateState::NotAuthenticated,
             providers,
             selected_provider_index: 0,
+            local_login: None,
         }
     }
 
@@ -59,6 +91,44 @@ impl Authentication {
 
     pub fn authenticated(&mut self) {
         self.state = AuthenticateState::Authenticated;
+        self.local_login = None;
+    }
+
+    /// Show the local login form and return the prompt to input the username
+    pub fn start_local_login(&mut self) -> Rc<RefCell<Prompt>> {
+        let form = LocalLoginForm {
+            username: Rc::new(RefCell::new(Prompt::new())),
+            password: Rc::new(RefCell::new(Prompt::masked())),
+            focus: LocalLoginField::Username,
+        };
+        let prompt = form.focused();
+        self.state = AuthenticateState::LocalLogin;
+        self.local_login = Some(form);
+        prompt
+    }
+
+    /// Move the focus of the local login form and return the focused prompt
+    pub fn move_local_login_field(&mut self) -> Option<Rc<RefCell<Prompt>>> {
+        let form = self.local_login.as_mut()?;
+        form.focus = match form.focus {
+            LocalLoginField::Username => LocalLoginField::Password,
+            LocalLoginField::Password => LocalLoginField::Username,
+        };
+        Some(form.focused())
+    }
+
+    /// Return the username and password if the form is ready to submit
+    pub fn local_login_credential(&self) -> Option<(String, String)> {
+        let form = self.local_login.as_ref()?;
+        let username = form.username.borrow().line().trim().to_owned();
+        let password = form.password.borrow().line().to_owned();
+        (form.focus == LocalLoginField::Password && !username.is_empty() && !password.is_empty())
+            .then_some((username, password))
+    }
+
+    pub fn cancel_local_login(&mut self) {
+        self.state = AuthenticateState::NotAuthenticated;
+        self.local_login = None;
     }
 
     pub fn set_device_authorization_response(&mut self, response: DeviceAuthorizationResponse) {
@@ -68,7 +138,9 @@ impl Authentication {
     pub(super) fn should_render(&self) -> bool {
         matches!(
             self.state,
-            AuthenticateState::NotAuthenticated | AuthenticateState::DeviceFlow(_)
+            AuthenticateState::NotAuthenticated
+                | AuthenticateState::DeviceFlow(_)
+                | AuthenticateState::LocalLogin
         )
     }
 }
@@ -78,6 +150,7 @@ impl Authentication {
         match self.state {
             AuthenticateState::NotAuthenticated => self.render_login(area, buf, cx),
             AuthenticateState::DeviceFlow(ref res) => Self::render_device_flow(area, buf, cx, res),
+            AuthenticateState::LocalLogin => self.render_local_login(area, buf, cx),
             AuthenticateState::Authenticated => unreachable!(),
         }
     }
@@ -113,6 +186,8 @@ impl Authentication {
                 .map(|provider| match provider {
                     AuthenticationProvider::Github => Text::from("󰊤 GitHub"),
                     AuthenticationProvider::Google => Text::from("󰊭 Google"),
+                    AuthenticationProvider::Oidc => Text::from("󰌾 OIDC"),
+                    AuthenticationProvider::Local => Text::from("󰌆 Local"),
                 })
                 .map(ListItem::new);
 
@@ -165,6 +240,56 @@ impl Authentication {
         Widget::render(device_flow, device_flow_area, buf);
     }
 
+    fn render_local_login(&self, area: Rect, buf: &mut Buffer, cx: &Context<'_>) {
+        let Some(form) = self.local_login.as_ref() else {
+            return;
+        };
+        let area = area.centered(40, 50);
+
+        let vertical = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(1),
+        ]);
+
+        let [title_area, username_area, password_area, _, help_area] = vertical.areas(area);
+
+        let fields = [
+            (
+                "Username: ",
+                username_area,
+                &form.username,
+                LocalLoginField::Username,
+            ),
+            (
+                "Password: ",
+                password_area,
+                &form.password,
+                LocalLoginField::Password,
+            ),
+        ];
+        for (label, area, prompt, field) in fields {
+            let horizontal = Layout::horizontal([Constraint::Length(10), Constraint::Fill(1)]);
+            let [label_area, prompt_area] = horizontal.areas(area);
+            let cursor = if form.focus == field {
+                RenderCursor::Enable
+            } else {
+                RenderCursor::Disable
+            };
+
+            Widget::render(Span::from(label), label_area, buf);
+            prompt.borrow().render(prompt_area, buf, cursor);
+        }
+
+        let help = Paragraph::new(Line::from("Enter: next/login  Tab: switch  Esc: back"))
+            .style(cx.theme.prompt.key_desc);
+
+        Widget::render(Self::login_title(cx), title_area, buf);
+        Widget::render(help, help_area, buf);
+    }
+
     fn login_title(cx: &Context<'_>) -> Paragraph<'static> {
         Paragraph::new(Span::styled("Login", cx.theme.login.title))
             .alignment(Alignment::Center)
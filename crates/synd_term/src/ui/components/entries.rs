@@ -46,7 +46,18 @@ impl Entries {
 
     pub fn update_entries(&mut self, action: Populate, payload: payload::FetchEntriesPayload) {
         match action {
-            Populate::Append => self.entries.extend(payload.entries),
+            // Entry may be already upserted while fetching(e.g. starred during pagination)
+            Populate::Append => {
+                for entry in payload.entries {
+                    if !self
+                        .entries
+                        .iter()
+                        .any(|current| current.is(&entry.feed_url, &entry.id))
+                    {
+                        self.entries.push(entry);
+                    }
+                }
+            }
             Populate::Replace => self.entries = payload.entries,
         }
         self.apply_filter();
@@ -71,6 +82,19 @@ impl Entries {
             .min(self.effective_entries.len().saturating_sub(1));
     }
 
+    /// Insert given entry at the head. if the entry already exists, it is replaced
+    pub fn upsert_entry(&mut self, entry: types::Entry) {
+        self.entries
+            .retain(|current| !current.is(&entry.feed_url, &entry.id));
+        self.entries.insert(0, entry);
+        self.apply_filter();
+    }
+
+    pub fn remove_entry(&mut self, feed_url: &FeedUrl, id: &str) {
+        self.entries.retain(|entry| !entry.is(feed_url, id));
+        self.apply_filter();
+    }
+
     pub fn remove_unsubscribed_entries(&mut self, url: &FeedUrl) {
         self.entries.retain(|entry| &entry.feed_url != url);
         self.apply_filter();
@@ -103,7 +127,7 @@ impl Entries {
             .and_then(|entry| entry.website_url.as_deref())
     }
 
-    fn selected_entry(&self) -> Option<&types::Entry> {
+    pub fn selected_entry(&self) -> Option<&types::Entry> {
         self.effective_entries
             .get(self.selected_entry_index)
             .map(|&idx| self.entries.get(idx).unwrap())
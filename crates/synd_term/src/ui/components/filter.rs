@@ -88,9 +88,19 @@ impl FeedFilter {
         if self
             .matcher
             .r#match(feed.title.as_deref().unwrap_or_default())
+            || self
+                .matcher
+                .r#match(feed.custom_title.as_deref().unwrap_or_default())
+            || self
+                .matcher
+                .r#match(feed.note.as_deref().unwrap_or_default())
             || self
                 .matcher
                 .r#match(feed.website_url.as_deref().unwrap_or_default())
+            || feed
+                .tags
+                .iter()
+                .any(|tag| self.matcher.r#match(tag.as_str()))
         {
             return FilterResult::Use;
         }
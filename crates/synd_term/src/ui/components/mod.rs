@@ -20,21 +20,20 @@ pub(crate) struct Components {
     pub prompt: StatusLine,
     pub subscription: Subscription,
     pub entries: Entries,
+    pub starred: Entries,
     pub auth: Authentication,
 }
 
 impl Components {
-    pub fn new() -> Self {
+    pub fn new(providers: Vec<AuthenticationProvider>) -> Self {
         Self {
             tabs: Tabs::new(),
             filter: Filter::new(),
             prompt: StatusLine::new(),
             subscription: Subscription::new(),
             entries: Entries::new(),
-            auth: Authentication::new(vec![
-                AuthenticationProvider::Github,
-                AuthenticationProvider::Google,
-            ]),
+            starred: Entries::new(),
+            auth: Authentication::new(providers),
         }
     }
 }
@@ -64,7 +64,14 @@ impl StatusLine {
                 .iter()
                 .chain(&[("Ent", icon!(open)), ("a", "󰑫"), ("e", ""), ("d", "󰼡")])
                 .chain(suf_keys),
-            Some(Tab::Entries) => pre_keys.iter().chain(&[("Ent", "󰏌")]).chain(suf_keys),
+            Some(Tab::Entries) => pre_keys
+                .iter()
+                .chain(&[("Ent", "󰏌"), ("s", "󰓎")])
+                .chain(suf_keys),
+            Some(Tab::Starred) => pre_keys
+                .iter()
+                .chain(&[("Ent", "󰏌"), ("d", "󰓒")])
+                .chain(suf_keys),
             // Imply login
             None => [("j/k", "󰹹")][..]
                 .iter()
@@ -84,10 +91,17 @@ impl StatusLine {
                 let label = match in_flight {
                     RequestId::DeviceFlowDeviceAuthorize => "Request device authorization",
                     RequestId::DeviceFlowPollAccessToken => "Polling...",
+                    RequestId::LocalLogin => "Login...",
                     RequestId::FetchEntries => "Fetch entries...",
                     RequestId::FetchSubscription => "Fetch subscription...",
                     RequestId::SubscribeFeed => "Subscribe feed...",
                     RequestId::UnsubscribeFeed => "Unsubscribe feed...",
+                    RequestId::MoveFeed => "Move feed...",
+                    RequestId::CreateFolder => "Create folder...",
+                    RequestId::RenameFolder => "Rename folder...",
+                    RequestId::FetchStarredEntries => "Fetch starred entries...",
+                    RequestId::StarEntry => "Star entry...",
+                    RequestId::UnstarEntry => "Unstar entry...",
                 };
                 let horizontal = Layout::horizontal([
                     Constraint::Length(label.len() as u16 + 1),
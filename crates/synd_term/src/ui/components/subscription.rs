@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet, fmt::Write as _};
 
 use itertools::Itertools;
 use ratatui::{
@@ -10,12 +10,12 @@ use ratatui::{
         ScrollbarOrientation, ScrollbarState, StatefulWidget, Table, TableState, Tabs, Widget,
     },
 };
-use synd_feed::types::{FeedType, FeedUrl};
+use synd_feed::types::{Category, FeedType, FeedUrl};
 
 use crate::{
     application::{Direction, IndexOutOfRange, Populate},
     client::query::subscription::SubscriptionOutput,
-    types::{self, EntryMeta, Feed, RequirementExt, TimeExt},
+    types::{self, EntryMeta, Feed, FolderId, RequirementExt, TimeExt},
     ui::{
         self,
         components::filter::{FeedFilter, FilterResult},
@@ -25,14 +25,33 @@ use crate::{
 };
 
 pub struct Subscription {
-    selected_feed_index: usize,
+    selected_row_index: usize,
     feeds: Vec<types::Feed>,
-    effective_feeds: Vec<usize>,
+    folders: Vec<types::Folder>,
+    collapsed_folders: HashSet<FolderId>,
+    rows: Vec<TreeRow>,
     filter: FeedFilter,
+    /// Show failing feeds first instead of the order defined by the user
+    sort_by_health: bool,
 
     unsubscribe_popup: UnsubscribePopup,
 }
 
+/// Row of the feeds table
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum TreeRow {
+    Folder {
+        id: FolderId,
+        depth: usize,
+        /// Number of feeds in the folder passing the filter
+        feeds: usize,
+    },
+    Feed {
+        index: usize,
+        depth: usize,
+    },
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum UnsubscribeSelection {
     Yes,
@@ -56,10 +75,13 @@ struct UnsubscribePopup {
 impl Subscription {
     pub fn new() -> Self {
         Self {
-            selected_feed_index: 0,
+            selected_row_index: 0,
             feeds: Vec::new(),
-            effective_feeds: Vec::new(),
+            folders: Vec::new(),
+            collapsed_folders: HashSet::new(),
+            rows: Vec::new(),
             filter: FeedFilter::default(),
+            sort_by_health: false,
             unsubscribe_popup: UnsubscribePopup {
                 selection: UnsubscribeSelection::Yes,
                 selected_feed: None,
@@ -76,9 +98,55 @@ impl Subscription {
     }
 
     pub fn selected_feed(&self) -> Option<&types::Feed> {
-        self.effective_feeds
-            .get(self.selected_feed_index)
-            .map(|&idx| self.feeds.get(idx).unwrap())
+        match self.rows.get(self.selected_row_index) {
+            Some(TreeRow::Feed { index, .. }) => self.feeds.get(*index),
+            _ => None,
+        }
+    }
+
+    pub fn selected_folder(&self) -> Option<&types::Folder> {
+        match self.rows.get(self.selected_row_index) {
+            Some(TreeRow::Folder { id, .. }) => self.folder(*id),
+            _ => None,
+        }
+    }
+
+    fn folder(&self, id: FolderId) -> Option<&types::Folder> {
+        self.folders.iter().find(|folder| folder.id == id)
+    }
+
+    /// Return the '/' separated names from the top level folder
+    pub fn folder_path(&self, id: FolderId) -> Option<String> {
+        let mut names = Vec::new();
+        let mut current = self.folder(id);
+        while let Some(folder) = current {
+            // guard against a cyclic parent
+            if names.len() > self.folders.len() {
+                break;
+            }
+            names.push(folder.name.as_str());
+            current = folder.parent_id.and_then(|parent| self.folder(parent));
+        }
+        (!names.is_empty()).then(|| names.into_iter().rev().join("/"))
+    }
+
+    /// Resolve the folder from the '/' separated names
+    pub fn folder_by_path(&self, path: &[String]) -> Option<FolderId> {
+        path.iter().try_fold(None, |parent, name| {
+            self.folders
+                .iter()
+                .find(|folder| folder.parent_id == parent && &folder.name == name)
+                .map(|folder| Some(folder.id))
+        })?
+    }
+
+    /// Return the '/' separated paths of all folders
+    pub fn folder_paths(&self) -> Vec<String> {
+        self.folders
+            .iter()
+            .filter_map(|folder| self.folder_path(folder.id))
+            .sorted()
+            .collect()
     }
 
     pub fn show_unsubscribe_popup(&mut self, show: bool) {
@@ -98,6 +166,13 @@ impl Subscription {
 
     pub fn update_subscription(&mut self, populate: Populate, subscription: SubscriptionOutput) {
         let feed_metas = subscription.feeds.nodes.into_iter().map(types::Feed::from);
+        // Every page contains all folders of the user
+        self.folders = subscription
+            .feeds
+            .folders
+            .into_iter()
+            .map(types::Folder::from)
+            .collect();
         match populate {
             Populate::Append => self.feeds.extend(feed_metas),
             Populate::Replace => self.feeds = feed_metas.collect(),
@@ -111,17 +186,166 @@ impl Subscription {
     }
 
     fn apply_filter(&mut self) {
-        self.effective_feeds = self
+        let mut rows = Vec::with_capacity(self.feeds.len() + self.folders.len());
+        self.push_rows(None, 0, &mut rows);
+        self.rows = rows;
+        // prevent selection from out of index
+        self.selected_row_index = self
+            .selected_row_index
+            .min(self.rows.len().saturating_sub(1));
+    }
+
+    /// Push the rows of the folder contents, folders first then feeds.
+    /// Return the number of feeds passing the filter and all feeds in the folder
+    fn push_rows(
+        &self,
+        folder: Option<FolderId>,
+        depth: usize,
+        rows: &mut Vec<TreeRow>,
+    ) -> (usize, usize) {
+        let (mut visible, mut total) = (0, 0);
+        for child in self
+            .folders
+            .iter()
+            .filter(|child| self.parent_folder(child) == folder)
+        {
+            let mut children = Vec::new();
+            let (child_visible, child_total) =
+                self.push_rows(Some(child.id), depth + 1, &mut children);
+            visible += child_visible;
+            total += child_total;
+            // hide folders whose feeds are all filtered out
+            if child_visible == 0 && child_total > 0 {
+                continue;
+            }
+            rows.push(TreeRow::Folder {
+                id: child.id,
+                depth,
+                feeds: child_visible,
+            });
+            if !self.collapsed_folders.contains(&child.id) {
+                rows.extend(children);
+            }
+        }
+        let mut feeds = self
             .feeds
             .iter()
             .enumerate()
-            .filter(|(_idx, feed)| self.filter.feed(feed) == FilterResult::Use)
-            .map(|(idx, _)| idx)
-            .collect();
-        // prevent selection from out of index
-        self.selected_feed_index = self
-            .selected_feed_index
-            .min(self.effective_feeds.len().saturating_sub(1));
+            .filter(|(_, feed)| self.feed_folder(feed) == folder)
+            .collect::<Vec<_>>();
+        if self.sort_by_health {
+            feeds.sort_by_key(|(_, feed)| {
+                std::cmp::Reverse(feed.health.as_ref().map_or(0, |h| h.consecutive_failures))
+            });
+        }
+        for (index, feed) in feeds {
+            total += 1;
+            if self.filter.feed(feed) == FilterResult::Use {
+                visible += 1;
+                rows.push(TreeRow::Feed { index, depth });
+            }
+        }
+        (visible, total)
+    }
+
+    /// Folder containing the folder. Unknown folders are treated as the top level
+    fn parent_folder(&self, folder: &types::Folder) -> Option<FolderId> {
+        folder
+            .parent_id
+            .filter(|&parent| parent != folder.id && self.folder(parent).is_some())
+    }
+
+    /// Folder containing the feed. Unknown folders are treated as the top level
+    fn feed_folder(&self, feed: &types::Feed) -> Option<FolderId> {
+        feed.folder_id.filter(|&id| self.folder(id).is_some())
+    }
+
+    pub fn toggle_sort_by_health(&mut self) {
+        self.sort_by_health = !self.sort_by_health;
+        self.apply_filter();
+    }
+
+    /// Collapse or expand the selected folder. Return false if no folder is selected
+    pub fn toggle_selected_folder(&mut self) -> bool {
+        let Some(id) = self.selected_folder().map(|folder| folder.id) else {
+            return false;
+        };
+        if !self.collapsed_folders.remove(&id) {
+            self.collapsed_folders.insert(id);
+        }
+        self.apply_filter();
+        true
+    }
+
+    pub fn upsert_folder(&mut self, folder: types::Folder) {
+        match self.folders.iter_mut().find(|x| x.id == folder.id) {
+            Some(x) => *x = folder,
+            None => self.folders.push(folder),
+        }
+        self.apply_filter();
+    }
+
+    /// Move the feed in the same way as the api so that the order matches without reloading
+    pub fn move_feed(
+        &mut self,
+        url: &FeedUrl,
+        folder_id: Option<FolderId>,
+        position: Option<usize>,
+    ) {
+        let Some(current) = self.feeds.iter().position(|feed| &feed.url == url) else {
+            return;
+        };
+        let mut feed = self.feeds.remove(current);
+        feed.folder_id = folder_id;
+
+        let siblings = self
+            .feeds
+            .iter()
+            .positions(|sibling| sibling.folder_id == folder_id)
+            .collect::<Vec<_>>();
+        let index = match position.and_then(|position| siblings.get(position)) {
+            Some(&index) => index,
+            None => siblings.last().map_or(self.feeds.len(), |&last| last + 1),
+        };
+        self.feeds.insert(index, feed);
+        self.apply_filter();
+
+        // keep the moved feed selected
+        if let Some(row) = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, TreeRow::Feed { index: i, .. } if *i == index))
+        {
+            self.selected_row_index = row;
+        }
+    }
+
+    /// Return the destination when the selected feed is moved one up or down among the feeds in the same folder
+    pub fn reorder_selected_feed(
+        &self,
+        direction: Direction,
+    ) -> Option<(FeedUrl, Option<FolderId>, usize)> {
+        // the order defined by the user is not displayed
+        if self.sort_by_health {
+            return None;
+        }
+        let feed = self.selected_feed()?;
+        let siblings = self
+            .feeds
+            .iter()
+            .filter(|sibling| sibling.folder_id == feed.folder_id)
+            .count();
+        let position = self
+            .feeds
+            .iter()
+            .filter(|sibling| sibling.folder_id == feed.folder_id)
+            .position(|sibling| sibling.url == feed.url)?;
+        let position = match direction {
+            Direction::Up => position.checked_sub(1)?,
+            Direction::Down if position + 1 < siblings => position + 1,
+            _ => return None,
+        };
+        Some((feed.url.clone(), feed.folder_id, position))
     }
 
     pub fn upsert_subscribed_feed(&mut self, feed: types::Feed) {
@@ -139,20 +363,20 @@ impl Subscription {
     }
 
     pub fn move_selection(&mut self, direction: Direction) {
-        self.selected_feed_index = direction.apply(
-            self.selected_feed_index,
-            self.effective_feeds.len(),
+        self.selected_row_index = direction.apply(
+            self.selected_row_index,
+            self.rows.len(),
             IndexOutOfRange::Wrapping,
         );
     }
 
     pub fn move_first(&mut self) {
-        self.selected_feed_index = 0;
+        self.selected_row_index = 0;
     }
 
     pub fn move_last(&mut self) {
-        if !self.feeds.is_empty() {
-            self.selected_feed_index = self.effective_feeds.len() - 1;
+        if !self.rows.is_empty() {
+            self.selected_row_index = self.rows.len() - 1;
         }
     }
 
@@ -181,7 +405,7 @@ impl Subscription {
 
         let mut feeds_state = TableState::new()
             .with_offset(0)
-            .with_selected(self.selected_feed_index);
+            .with_selected(self.selected_row_index);
 
         let (header, widths, rows) = self.feed_rows(cx);
 
@@ -210,8 +434,8 @@ impl Subscription {
         // https://github.com/ratatui-org/ratatui/pull/911
         // passing None to track_symbol cause incorrect rendering
         let mut scrollbar_state = ScrollbarState::default()
-            .content_length(self.feeds.len())
-            .position(self.selected_feed_index);
+            .content_length(self.rows.len())
+            .position(self.selected_row_index);
         Scrollbar::default()
             .orientation(ScrollbarOrientation::VerticalRight)
             .begin_symbol(None)
@@ -222,6 +446,7 @@ impl Subscription {
             .render(scrollbar_area, buf, &mut scrollbar_state);
     }
 
+    #[allow(clippy::too_many_lines)]
     fn feed_rows<'a>(
         &'a self,
         cx: &'a Context<'_>,
@@ -231,12 +456,12 @@ impl Subscription {
         impl IntoIterator<Item = Row<'a>>,
     ) {
         let (n, m) = {
-            if self.effective_feeds.is_empty() {
+            if self.rows.is_empty() {
                 (Cow::Borrowed("-"), Cow::Borrowed("-"))
             } else {
                 (
-                    Cow::Owned((self.selected_feed_index + 1).to_string()),
-                    Cow::Owned(self.effective_feeds.len().to_string()),
+                    Cow::Owned((self.selected_row_index + 1).to_string()),
+                    Cow::Owned(self.rows.len().to_string()),
                 )
             }
         };
@@ -246,6 +471,11 @@ impl Subscription {
             Cell::from("URL"),
             Cell::from("Description"),
             Cell::from("Req"),
+            Cell::from(if self.sort_by_health {
+                "Health↓"
+            } else {
+                "Health"
+            }),
         ]);
 
         let constraints = [
@@ -254,10 +484,11 @@ impl Subscription {
             Constraint::Fill(1),
             Constraint::Fill(2),
             Constraint::Length(4),
+            Constraint::Length(7),
         ];
 
-        let row = |feed_meta: &'a Feed| {
-            let title = feed_meta.title.as_deref().unwrap_or(ui::UNKNOWN_SYMBOL);
+        let row = |feed_meta: &'a Feed, depth: usize| {
+            let title = feed_meta.display_title().unwrap_or(ui::UNKNOWN_SYMBOL);
             let updated = feed_meta
                 .updated
                 .as_ref()
@@ -281,6 +512,7 @@ impl Subscription {
             Row::new([
                 Cell::from(Span::from(updated)),
                 Cell::from(Line::from(vec![
+                    Span::from("  ".repeat(depth)),
                     Span::from(icon.symbol()).fg(icon.color().unwrap_or(cx.theme.default_icon_fg)),
                     Span::from(" "),
                     Span::from(title),
@@ -293,15 +525,43 @@ impl Subscription {
                 )),
                 Cell::from(Span::from(desc)),
                 Cell::from(Line::from(vec![requirement, Span::from(" ")])),
+                Cell::from(match feed_meta.health.as_ref() {
+                    Some(health) if health.is_failing() => Span::styled(
+                        format!(" {}", health.consecutive_failures),
+                        cx.theme.subscription.health_warning,
+                    ),
+                    _ => Span::from(""),
+                }),
             ])
         };
 
         (
             header,
             constraints,
-            self.effective_feeds
-                .iter()
-                .map(move |&idx| row(self.feeds.get(idx).unwrap())),
+            self.rows.iter().map(move |&tree_row| match tree_row {
+                TreeRow::Feed { index, depth } => row(self.feeds.get(index).unwrap(), depth),
+                TreeRow::Folder { id, depth, feeds } => {
+                    let name = self.folder(id).map_or("", |folder| folder.name.as_str());
+                    let symbol = if self.collapsed_folders.contains(&id) {
+                        "▸ "
+                    } else {
+                        "▾ "
+                    };
+                    Row::new([
+                        Cell::from(""),
+                        Cell::from(Line::from(vec![
+                            Span::from("  ".repeat(depth)),
+                            Span::from(symbol),
+                            Span::from(name).bold(),
+                            Span::from(format!(" ({feeds})")).dim(),
+                        ])),
+                        Cell::from(""),
+                        Cell::from(""),
+                        Cell::from(""),
+                        Cell::from(""),
+                    ])
+                }
+            }),
         )
     }
 
@@ -324,17 +584,13 @@ impl Subscription {
             return;
         };
 
-        let vertical = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]);
-        let [meta_area, entries_area] = vertical.areas(inner);
-        let entries_area = Block::new().padding(Padding::top(1)).inner(entries_area);
-
         let widths = [
             Constraint::Length(11),
             Constraint::Fill(1),
             Constraint::Fill(2),
         ];
 
-        let meta_rows = vec![
+        let mut meta_rows = vec![
             Row::new([
                 Cell::new(Span::styled(
                     "󰚼 Authors",
@@ -382,6 +638,66 @@ impl Subscription {
                 ])),
             ]),
         ];
+        if let Some(health) = feed.health.as_ref() {
+            let status = if health.is_failing() {
+                let mut status = format!(
+                    "{} failures{}: {}",
+                    health.consecutive_failures,
+                    // health is reset when synd-api restarts
+                    if health.last_success.is_none() {
+                        " since api started"
+                    } else {
+                        ""
+                    },
+                    health.last_error.as_deref().unwrap_or(ui::UNKNOWN_SYMBOL),
+                );
+                if let Some(code) = health.last_http_status {
+                    write!(status, " ({code})").ok();
+                }
+                Span::styled(status, cx.theme.subscription.health_warning)
+            } else {
+                Span::from("ok")
+            };
+            meta_rows.push(Row::new([
+                Cell::new(Span::styled(
+                    "󰓙 Health",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Cell::new(status),
+                Cell::new(Line::from(vec![
+                    Span::styled("󰔟 Latency ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::from(health.average_latency_ms.map_or_else(
+                        || Cow::Borrowed(ui::UNKNOWN_SYMBOL),
+                        |ms| Cow::Owned(format!("{ms}ms")),
+                    )),
+                ])),
+            ]));
+        }
+        if feed.note.is_some() || !feed.tags.is_empty() {
+            meta_rows.push(Row::new([
+                Cell::new(Span::styled(
+                    "󰓹 Tags",
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Cell::new(Span::from(if feed.tags.is_empty() {
+                    Cow::Borrowed(ui::UNKNOWN_SYMBOL)
+                } else {
+                    Cow::Owned(feed.tags.iter().map(Category::as_str).join(" "))
+                })),
+                Cell::new(Line::from(vec![
+                    Span::styled("󰎞 Note ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::from(feed.note.as_deref().unwrap_or(ui::UNKNOWN_SYMBOL)),
+                ])),
+            ]));
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let vertical = Layout::vertical([
+            Constraint::Length(meta_rows.len() as u16),
+            Constraint::Min(0),
+        ]);
+        let [meta_area, entries_area] = vertical.areas(inner);
+        let entries_area = Block::new().padding(Padding::top(1)).inner(entries_area);
 
         let table = Table::new(meta_rows, widths)
             .column_spacing(1)
@@ -458,7 +774,7 @@ impl Subscription {
         block.render(area, buf);
 
         // for align line
-        let feed_n = "Feed: ".len() + feed.title.as_deref().unwrap_or("-").len();
+        let feed_n = "Feed: ".len() + feed.display_title().unwrap_or("-").len();
         let url_n = "URL : ".len() + feed.url.as_str().len();
 
         Paragraph::new(vec![
@@ -466,7 +782,7 @@ impl Subscription {
             Line::from(""),
             Line::from(vec![
                 Span::from("Feed: "),
-                Span::from(feed.title.as_deref().unwrap_or("-")).bold(),
+                Span::from(feed.display_title().unwrap_or("-")).bold(),
                 Span::from(" ".repeat(url_n.saturating_sub(feed_n))),
             ]),
             Line::from(vec![
@@ -501,3 +817,110 @@ impl Subscription {
             .render(selection_area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use fake::{Fake, Faker};
+
+    use super::*;
+
+    fn feed(url: &str, folder_id: Option<FolderId>) -> Feed {
+        let mut feed: Feed = Faker.fake();
+        feed.url = FeedUrl::try_from(url).unwrap();
+        feed.folder_id = folder_id;
+        feed
+    }
+
+    fn folder(id: FolderId, name: &str, parent_id: Option<FolderId>) -> types::Folder {
+        types::Folder {
+            id,
+            name: name.into(),
+            parent_id,
+        }
+    }
+
+    #[test]
+    fn organize_feeds_in_folders() {
+        let mut subscription = Subscription::new();
+        subscription.feeds = vec![
+            feed("https://a.ymgyt.io", Some(2)),
+            feed("https://b.ymgyt.io", None),
+            feed("https://c.ymgyt.io", Some(1)),
+            feed("https://d.ymgyt.io", Some(1)),
+        ];
+        subscription.folders = vec![folder(1, "blogs", None), folder(2, "rust", Some(1))];
+        subscription.apply_filter();
+
+        assert_eq!(
+            subscription.rows,
+            vec![
+                TreeRow::Folder {
+                    id: 1,
+                    depth: 0,
+                    feeds: 3
+                },
+                TreeRow::Folder {
+                    id: 2,
+                    depth: 1,
+                    feeds: 1
+                },
+                TreeRow::Feed { index: 0, depth: 2 },
+                TreeRow::Feed { index: 2, depth: 1 },
+                TreeRow::Feed { index: 3, depth: 1 },
+                TreeRow::Feed { index: 1, depth: 0 },
+            ]
+        );
+        assert_eq!(subscription.folder_path(2).as_deref(), Some("blogs/rust"));
+        assert_eq!(
+            subscription.folder_by_path(&["blogs".into(), "rust".into()]),
+            Some(2)
+        );
+        assert_eq!(subscription.folder_by_path(&["rust".into()]), None);
+
+        // collapse blogs
+        assert!(subscription.toggle_selected_folder());
+        assert_eq!(subscription.rows.len(), 2);
+        assert!(subscription.toggle_selected_folder());
+
+        // move d above c
+        subscription.selected_row_index = 4;
+        let (url, folder_id, position) = subscription.reorder_selected_feed(Direction::Up).unwrap();
+        assert_eq!((folder_id, position), (Some(1), 0));
+        subscription.move_feed(&url, folder_id, Some(position));
+        assert_eq!(subscription.selected_feed().unwrap().url, url);
+        assert_eq!(subscription.selected_row_index, 3);
+        assert!(subscription.reorder_selected_feed(Direction::Up).is_none());
+
+        // move a to the top level
+        let a = FeedUrl::try_from("https://a.ymgyt.io").unwrap();
+        subscription.move_feed(&a, None, None);
+        // empty folders remain visible
+        assert_eq!(subscription.rows.len(), 6);
+        assert_eq!(subscription.selected_feed().unwrap().url, a);
+    }
+
+    #[test]
+    fn sort_feeds_by_health() {
+        let mut subscription = Subscription::new();
+        subscription.feeds = ["https://a.ymgyt.io", "https://b.ymgyt.io"]
+            .into_iter()
+            .map(|url| feed(url, None))
+            .collect();
+        subscription.feeds[0].health = None;
+        subscription.feeds[1].health = Some(types::FeedHealth {
+            last_success: None,
+            last_error: Some("network error".into()),
+            consecutive_failures: 3,
+            last_http_status: None,
+            average_latency_ms: None,
+        });
+        subscription.apply_filter();
+        assert_eq!(subscription.rows[0], TreeRow::Feed { index: 0, depth: 0 });
+
+        subscription.toggle_sort_by_health();
+        assert_eq!(subscription.rows[0], TreeRow::Feed { index: 1, depth: 0 });
+        assert!(subscription
+            .reorder_selected_feed(Direction::Down)
+            .is_none());
+    }
+}
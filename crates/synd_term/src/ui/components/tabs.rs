@@ -13,6 +13,7 @@ use crate::{
 pub enum Tab {
     Entries,
     Feeds,
+    Starred,
 }
 
 pub struct Tabs {
@@ -24,7 +25,7 @@ impl Tabs {
     pub fn new() -> Self {
         Self {
             selected: 0,
-            tabs: vec!["󱉯 Entries", "󰑫 Feeds"],
+            tabs: vec!["󱉯 Entries", "󰑫 Feeds", "󰓎 Starred"],
         }
     }
 
@@ -32,6 +33,7 @@ impl Tabs {
         match self.selected {
             0 => Tab::Entries,
             1 => Tab::Feeds,
+            2 => Tab::Starred,
             _ => unreachable!(),
         }
     }
@@ -50,7 +52,7 @@ impl Tabs {
             ..area
         };
 
-        let horizontal = Layout::horizontal([Constraint::Min(0), Constraint::Length(24)]);
+        let horizontal = Layout::horizontal([Constraint::Min(0), Constraint::Length(37)]);
         let [title, tabs] = horizontal.areas(area);
 
         Paragraph::new(Span::styled("Syndicationd", cx.theme.application_title)).render(title, buf);
@@ -40,6 +40,7 @@ pub struct SubscriptionTheme {
     pub background: Style,
     pub header: Style,
     pub selected_feed: Style,
+    pub health_warning: Style,
 }
 
 #[derive(Clone)]
@@ -136,6 +137,7 @@ impl Theme {
                 background: Style::new().bg(bg),
                 header: Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
                 selected_feed: Style::new().fg(fg_focus).add_modifier(Modifier::BOLD),
+                health_warning: Style::new().fg(error),
             },
             entries: EntriesTheme {
                 header: Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
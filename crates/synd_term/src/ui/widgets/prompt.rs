@@ -6,7 +6,7 @@ use ratatui::{
     text::{Line, Span},
     widgets::Widget,
 };
-use unicode_segmentation::GraphemeCursor;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 use crate::{application::event::KeyEventResult, command::Command};
 
@@ -19,6 +19,7 @@ enum Move {
 pub(crate) struct Prompt {
     line: String,
     cursor: usize,
+    masked: bool,
 }
 
 impl Prompt {
@@ -26,6 +27,15 @@ impl Prompt {
         Self {
             line: String::new(),
             cursor: 0,
+            masked: false,
+        }
+    }
+
+    /// Construct prompt which renders each character as `*`(e.g. password)
+    pub fn masked() -> Self {
+        Self {
+            masked: true,
+            ..Self::new()
         }
     }
 
@@ -89,7 +99,11 @@ impl Prompt {
     }
 
     pub fn render(&self, area: Rect, buf: &mut Buffer, cursor: RenderCursor) {
-        let mut spans = vec![Span::from(&self.line)];
+        let mut spans = if self.masked {
+            vec![Span::from("*".repeat(self.line.graphemes(true).count()))]
+        } else {
+            vec![Span::from(&self.line)]
+        };
 
         if cursor == RenderCursor::Enable {
             spans.push(Span::from(" ").reversed());
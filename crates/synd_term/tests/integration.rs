@@ -14,7 +14,6 @@ mod test {
         let test_case = TestCase {
             mock_port: 6000,
             synd_api_port: 6001,
-            kvsd_port: 47379,
             terminal_col_row: (120, 30),
             device_flow_case: "case1",
             cache_dir: helper::temp_dir().into_path(),
@@ -60,6 +59,7 @@ mod test {
         {
             check_command_test(test_case.synd_api_port);
             export_command_test(test_case.synd_api_port, &test_case.cache_dir);
+            token_command_test(test_case.synd_api_port, &test_case.cache_dir).await;
             clean_command_test(&test_case.cache_dir);
         }
 
@@ -96,6 +96,136 @@ mod test {
         cmd.arg("--print-schema").assert().success();
     }
 
+    async fn token_command_test(api_port: u16, cache_dir: &Path) {
+        let endpoint = format!("https://localhost:{api_port}");
+        let cache_dir = cache_dir.display().to_string();
+        let synd = |args: &[&str]| {
+            let mut cmd = assert_cmd::Command::cargo_bin("synd").unwrap();
+            cmd.arg("token")
+                .args(["--endpoint", &endpoint, "--cache-dir", &cache_dir])
+                .args(args);
+            let output = cmd.assert().success().get_output().stdout.clone();
+            String::from_utf8(output).unwrap()
+        };
+
+        let token = synd(&[
+            "create",
+            "--name",
+            "ci",
+            "--read-only",
+            "--expires-in",
+            "30d",
+        ]);
+        let token = token.trim();
+
+        let tokens: serde_json::Value = serde_json::from_str(&synd(&["list"])).unwrap();
+        assert_eq!(tokens[0]["name"], "ci");
+        assert_eq!(tokens[0]["read_only"], true);
+        let id = tokens[0]["id"].as_str().unwrap().to_owned();
+
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let graphql = |query: &'static str| {
+            client
+                .post(format!("{endpoint}/graphql"))
+                .header("authorization", format!("synd {token}"))
+                .json(&serde_json::json!({ "query": query }))
+                .send()
+        };
+
+        // Read only token can query but can not mutate
+        let response: serde_json::Value = graphql("query { subscription { apiTokens { name } } }")
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(
+            response["data"]["subscription"]["apiTokens"][0]["name"],
+            "ci"
+        );
+        let response: serde_json::Value =
+            graphql(r#"mutation { createFolder(input: { name: "ci" }) { __typename } }"#)
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+        assert_eq!(response["errors"][0]["extensions"]["code"], "UNAUTHORIZED");
+
+        reader_api_test(&client, &endpoint, token).await;
+
+        synd(&["revoke", &id]);
+        let response = graphql("query { subscription { apiTokens { name } } }")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    }
+
+    /// Google Reader compatible api authenticated by the personal access token
+    async fn reader_api_test(client: &reqwest::Client, endpoint: &str, token: &str) {
+        let response = client
+            .post(format!("{endpoint}/accounts/ClientLogin"))
+            .form(&[("Email", "ci"), ("Passwd", "invalid")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let login = client
+            .post(format!("{endpoint}/accounts/ClientLogin"))
+            .form(&[("Email", "ci"), ("Passwd", token)])
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        let auth = login
+            .lines()
+            .find_map(|line| line.strip_prefix("Auth="))
+            .unwrap();
+        let authorization = format!("GoogleLogin auth={auth}");
+
+        let subscriptions: serde_json::Value = client
+            .get(format!(
+                "{endpoint}/reader/api/0/subscription/list?output=json"
+            ))
+            .header("authorization", &authorization)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(subscriptions["subscriptions"].is_array());
+
+        let ids: serde_json::Value = client
+            .get(format!(
+                "{endpoint}/reader/api/0/stream/items/ids?s=user/-/state/com.google/reading-list&n=5"
+            ))
+            .header("authorization", &authorization)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert!(ids["itemRefs"].as_array().unwrap().len() <= 5);
+
+        // Read only token can not modify the state
+        let response = client
+            .post(format!("{endpoint}/reader/api/0/edit-tag"))
+            .header("authorization", &authorization)
+            .form(&[("i", "1"), ("a", "user/-/state/com.google/read")])
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    }
+
     fn clean_command_test(cache_dir: &Path) {
         let mut cmd = assert_cmd::Command::cargo_bin("synd").unwrap();
 
@@ -111,7 +241,6 @@ mod test {
         let test_case = TestCase {
             mock_port: 6010,
             synd_api_port: 6011,
-            kvsd_port: 47389,
             terminal_col_row: (120, 30),
             interactor_buffer: Some("should rust http://localhost:6010/feed/twir_atom".into()),
             ..Default::default()
@@ -175,4 +304,137 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn star_entry() -> anyhow::Result<()> {
+        helper::init_tracing();
+
+        let test_case = TestCase {
+            mock_port: 6020,
+            synd_api_port: 6021,
+            terminal_col_row: (120, 30),
+            interactor_buffer: Some("should rust http://localhost:6020/feed/twir_atom".into()),
+            ..Default::default()
+        }
+        .already_logined();
+
+        let mut application = test_case.init_app().await?;
+        let (tx, mut event_stream) = helper::event_stream();
+
+        {
+            // Subscribe on feeds tab then move to entries tab
+            tx.send(key!(tab));
+            tx.send(key!('a'));
+            tx.send(key!(tab));
+            tx.send(key!(tab));
+            application
+                .wait_until_jobs_completed(&mut event_stream)
+                .await;
+        }
+
+        {
+            // Star the selected entry then move to starred tab
+            tx.send(key!('s'));
+            tx.send(key!(tab));
+            tx.send(key!(tab));
+            application
+                .wait_until_jobs_completed(&mut event_stream)
+                .await;
+            insta::with_settings!({
+                description => "starred entry",
+            },{
+                insta::assert_debug_snapshot!("star_entry_starred", application.buffer());
+            });
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn login_local() -> anyhow::Result<()> {
+        helper::init_tracing();
+
+        let test_case = TestCase {
+            mock_port: 6030,
+            synd_api_port: 6031,
+            terminal_col_row: (120, 30),
+            ..Default::default()
+        };
+        let mut application = test_case.init_app().await?;
+        let (tx, mut event_stream) = helper::event_stream();
+
+        {
+            // Select local provider(last one) then input username and password
+            tx.send(key!('k'));
+            tx.send(key!(enter));
+            helper::LOCAL_USERNAME
+                .chars()
+                .for_each(|c| tx.send(helper::char_key(c)));
+            tx.send(key!(enter));
+            helper::LOCAL_PASSWORD
+                .chars()
+                .for_each(|c| tx.send(helper::char_key(c)));
+            application.event_loop_until_idle(&mut event_stream).await;
+            insta::with_settings!({
+                description => "local login form with masked password",
+            },{
+                insta::assert_debug_snapshot!("local_login_form", application.buffer());
+            });
+        }
+
+        {
+            tx.send(key!(enter));
+            application
+                .wait_until_jobs_completed(&mut event_stream)
+                .await;
+            insta::with_settings!({
+                description => "landing entries after local login",
+            },{
+                insta::assert_debug_snapshot!("local_login_landing_entries", application.buffer());
+            });
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn login_oidc() -> anyhow::Result<()> {
+        helper::init_tracing();
+
+        let test_case = TestCase {
+            mock_port: 6040,
+            synd_api_port: 6041,
+            terminal_col_row: (120, 30),
+            ..Default::default()
+        };
+        let mut application = test_case.init_app().await?;
+        let (tx, mut event_stream) = helper::event_stream();
+
+        {
+            // Select oidc provider then start device flow with the discovered endpoint
+            tx.send(key!('j'));
+            tx.send(key!('j'));
+            tx.send(key!(enter));
+            application.event_loop_until_idle(&mut event_stream).await;
+            insta::with_settings!({
+                description => "show oidc device flow code",
+            },{
+                insta::assert_debug_snapshot!("oidc_device_flow_prompt", application.buffer());
+            });
+        }
+
+        {
+            // api server validates the id token with the jwks of the issuer
+            application
+                .wait_until_jobs_completed(&mut event_stream)
+                .await;
+            insta::with_settings!({
+                description => "landing entries after oidc login",
+            },{
+                insta::assert_debug_snapshot!("oidc_login_landing_entries", application.buffer());
+            });
+        }
+
+        Ok(())
+    }
 }
@@ -1,15 +1,20 @@
-use std::{future::pending, io, path::PathBuf, sync::Once, time::Duration};
+use std::{io, path::PathBuf, sync::Once, time::Duration};
 
-use futures_util::TryFutureExt;
 use ratatui::backend::TestBackend;
 use synd_api::{
-    args::{CacheOptions, KvsdOptions, ServeOptions, TlsOptions},
+    args::{
+        AuthOptions, CacheOptions, FeedOptions, KvsdOptions, QuotaOptions, Repository,
+        RepositoryOptions, ServeOptions, SyndicationOptions, TlsOptions,
+    },
     client::github::GithubClient,
     dependency::Dependency,
-    repository::kvsd::KvsdClient,
+    serve::auth::{hash_password, LocalBackend, LocalUser, LocalUsers},
     shutdown::Shutdown,
 };
-use synd_auth::device_flow::{provider, DeviceFlow};
+use synd_auth::{
+    device_flow::{provider, DeviceFlow},
+    jwt,
+};
 use synd_term::{
     application::{Application, Authenticator, Cache, Config, DeviceFlows},
     auth::Credential,
@@ -19,18 +24,25 @@ use synd_term::{
     terminal::Terminal,
     ui::theme::Theme,
 };
-use tokio::{
-    net::{TcpListener, TcpStream},
-    sync::mpsc::UnboundedSender,
-};
+use tokio::{net::TcpListener, sync::mpsc::UnboundedSender};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tracing_subscriber::EnvFilter;
 
+/// Local user registered to the api server
+pub const LOCAL_USERNAME: &str = "synd";
+pub const LOCAL_PASSWORD: &str = "synd password";
+/// Client id registered to the mock oidc issuer
+pub const OIDC_CLIENT_ID: &str = "synd-test-client";
+
+/// Mock oidc issuer served by `synd_test`
+pub fn oidc_issuer(mock_port: u16) -> String {
+    format!("http://localhost:{mock_port}/oidc")
+}
+
 #[derive(Clone)]
 pub struct TestCase {
     pub mock_port: u16,
     pub synd_api_port: u16,
-    pub kvsd_port: u16,
     pub terminal_col_row: (u16, u16),
     pub device_flow_case: &'static str,
     pub cache_dir: PathBuf,
@@ -44,7 +56,6 @@ impl Default for TestCase {
         Self {
             mock_port: 0,
             synd_api_port: 0,
-            kvsd_port: 0,
             terminal_col_row: (120, 30),
             device_flow_case: "case1",
             cache_dir: temp_dir().into_path(),
@@ -68,7 +79,6 @@ impl TestCase {
         let TestCase {
             mock_port,
             synd_api_port,
-            kvsd_port,
             terminal_col_row: (term_col, term_row),
             device_flow_case,
             cache_dir,
@@ -85,7 +95,7 @@ impl TestCase {
 
         // Start synd api server
         {
-            serve_api(mock_port, synd_api_port, kvsd_port).await?;
+            serve_api(mock_port, synd_api_port).await?;
         }
 
         // Configure application
@@ -107,7 +117,12 @@ impl TestCase {
                 ),
                 google: DeviceFlow::new(provider::Google::new("dummy", "dummy")),
             };
-            let authenticator = Authenticator::new().with_device_flows(device_flows);
+            let authenticator = Authenticator::new()
+                .with_device_flows(device_flows)
+                .with_oidc(jwt::oidc::JwtService::new(
+                    oidc_issuer(mock_port),
+                    OIDC_CLIENT_ID,
+                ));
             let config = Config {
                 idle_timer_interval: Duration::from_millis(1000),
                 throbber_timer_interval: Duration::from_secs(3600), // disable throbber
@@ -168,16 +183,16 @@ pub fn new_test_terminal(width: u16, height: u16) -> Terminal {
     Terminal::with(terminal)
 }
 
-pub async fn serve_api(
-    oauth_provider_port: u16,
-    api_port: u16,
-    kvsd_port: u16,
-) -> anyhow::Result<()> {
+pub async fn serve_api(oauth_provider_port: u16, api_port: u16) -> anyhow::Result<()> {
+    let repository_options = RepositoryOptions {
+        repository: Repository::Memory,
+    };
     let kvsd_options = KvsdOptions {
-        kvsd_host: "localhost".into(),
-        kvsd_port,
-        kvsd_username: "test".into(),
-        kvsd_password: "test".into(),
+        kvsd_host: None,
+        kvsd_port: None,
+        kvsd_username: None,
+        kvsd_password: None,
+        kvsd_pool_size: 1,
     };
     let tls_options = TlsOptions {
         certificate: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -195,8 +210,10 @@ pub async fn serve_api(
     };
     let serve_options = ServeOptions {
         timeout: Duration::from_secs(10),
-        body_limit_bytes: 1024 * 2,
+        body_limit_bytes: 1024 * 4,
         concurrency_limit: 100,
+        rate_limit_burst: 100,
+        rate_limit_per_second: 10,
     };
     let cache_options = CacheOptions {
         feed_cache_size_mb: 1,
@@ -204,11 +221,39 @@ pub async fn serve_api(
         feed_cache_refresh_interval: Duration::from_secs(3600),
     };
 
-    let _kvsd_client = run_kvsd(kvsd_options.clone()).await.map(KvsdClient::new)?;
+    let syndication_options = SyndicationOptions {
+        syndication: false,
+        reader_api: true,
+    };
+
+    let auth_options = AuthOptions {
+        auth_local_users: None,
+        auth_oidc_issuer: Some(oidc_issuer(oauth_provider_port)),
+        auth_oidc_client_id: Some(OIDC_CLIENT_ID.into()),
+        auth_admins: Vec::new(),
+    };
+
+    let quota_options = QuotaOptions {
+        max_subscriptions_per_user: 100,
+    };
+
+    let feed_options = FeedOptions {
+        feed_respect_robots_txt: false,
+    };
 
-    let mut dep = Dependency::new(kvsd_options, tls_options, serve_options, cache_options)
-        .await
-        .unwrap();
+    let mut dep = Dependency::new(
+        repository_options,
+        kvsd_options,
+        tls_options,
+        serve_options,
+        cache_options,
+        syndication_options,
+        auth_options,
+        quota_options,
+        feed_options,
+    )
+    .await
+    .unwrap();
 
     {
         let github_endpoint: &'static str =
@@ -217,6 +262,17 @@ pub async fn serve_api(
 
         dep.authenticator = dep.authenticator.with_client(github_client);
     }
+    {
+        let local = LocalBackend::new(LocalUsers {
+            users: vec![LocalUser {
+                username: LOCAL_USERNAME.into(),
+                password_hash: hash_password(LOCAL_PASSWORD)?,
+                email: None,
+            }],
+        })?;
+
+        dep.authenticator = dep.authenticator.with_backend(local);
+    }
 
     let listener = TcpListener::bind(("localhost", api_port)).await?;
 
@@ -229,53 +285,6 @@ pub async fn serve_api(
     Ok(())
 }
 
-pub async fn run_kvsd(
-    KvsdOptions {
-        kvsd_host,
-        kvsd_port,
-        kvsd_username,
-        kvsd_password,
-    }: KvsdOptions,
-) -> anyhow::Result<kvsd::client::tcp::Client<TcpStream>> {
-    let root_dir = temp_dir();
-    let mut config = kvsd::config::Config::default();
-
-    // Setup user credential.
-    config.kvsd.users = vec![kvsd::core::UserEntry {
-        username: kvsd_username,
-        password: kvsd_password,
-    }];
-    config.server.set_disable_tls(&mut Some(true));
-
-    // Test Server listen addr
-    let addr = (kvsd_host, kvsd_port);
-
-    let mut initializer = kvsd::config::Initializer::from_config(config);
-
-    initializer.set_root_dir(root_dir.path());
-    initializer.set_listener(TcpListener::bind(addr.clone()).await.unwrap());
-
-    initializer.init_dir().await.unwrap();
-
-    let _server_handler = tokio::spawn(initializer.run_kvsd(pending::<()>()));
-
-    let handshake = async {
-        loop {
-            match kvsd::client::tcp::UnauthenticatedClient::insecure_from_addr(&addr.0, addr.1)
-                .and_then(|client| client.authenticate("test", "test"))
-                .await
-            {
-                Ok(client) => break client,
-                Err(_) => tokio::time::sleep(Duration::from_millis(500)).await,
-            }
-        }
-    };
-
-    let client = tokio::time::timeout(Duration::from_secs(5), handshake).await?;
-
-    Ok(client)
-}
-
 pub fn temp_dir() -> tempfile::TempDir {
     tempfile::TempDir::new().unwrap()
 }
@@ -290,6 +299,12 @@ pub fn event_stream() -> (
     (tx, event_stream)
 }
 
+pub fn char_key(c: char) -> crossterm::event::Event {
+    crossterm::event::Event::Key(crossterm::event::KeyEvent::from(
+        crossterm::event::KeyCode::Char(c),
+    ))
+}
+
 pub struct UnboundedSenderWrapper {
     inner: UnboundedSender<io::Result<crossterm::event::Event>>,
 }
@@ -13,6 +13,10 @@ use synd_auth::device_flow::{
 use tokio::net::TcpListener;
 
 mod feed;
+mod oidc;
+pub use oidc::DUMMY_EMAIL as OIDC_DUMMY_EMAIL;
+mod webhook;
+pub use webhook::WEBHOOK_SECRET;
 
 async fn device_authorization(
     Form(DeviceAuthorizationRequest { scope, .. }): Form<DeviceAuthorizationRequest<'static>>,
@@ -86,8 +90,19 @@ pub async fn serve(listener: TcpListener) -> anyhow::Result<()> {
             "/github/login/oauth/access_token",
             post(device_access_token),
         );
+    let oidc = Router::new()
+        .route("/.well-known/openid-configuration", get(oidc::discovery))
+        .route("/jwks", get(oidc::jwks))
+        .route("/device/code", post(oidc::device_authorization))
+        .route("/token", post(oidc::token));
+    // POST verifies and records deliveries, GET returns recorded payloads
+    let webhook = Router::new()
+        .route("/:receiver", post(webhook::receive).get(webhook::received))
+        .with_state(webhook::Receivers::default());
     let router = Router::new()
         .nest("/case1", case_1)
+        .nest("/oidc", oidc)
+        .nest("/webhook", webhook)
         .route("/github/graphql", post(github_graphql_viewer))
         .route("/feed/:feed", get(feed::feed));
 
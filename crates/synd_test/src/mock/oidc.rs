@@ -0,0 +1,149 @@
+//! Mock `OpenID` Connect issuer which is served under `/oidc`
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::{
+    http::{header::HOST, HeaderMap, StatusCode},
+    Form, Json,
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use synd_auth::{
+    device_flow::{
+        DeviceAccessTokenResponse, DeviceAuthorizationRequest, DeviceAuthorizationResponse,
+    },
+    oidc::ProviderMetadata,
+};
+
+pub const DUMMY_EMAIL: &str = "synd@example.com";
+
+const PRIVATE_KEY: &str = include_str!("oidcdata/private_key.pem");
+const KID: &str = "synd-test";
+/// Base64url encoded modulus of `PRIVATE_KEY`
+const MODULUS: &str = "r57iwlwsIbr69aLPt7AunrgYQWiWlTD9xzC3D3kLFUoVwll_o1XLpTLf_KkQjsQAZ_evmB5ab036RXUnJ7eSE_JfbANLmrwqv_426NewdG_8VUv_VgI4okjayAUazNPRB1AGTy9eA8CVT_1JUbIfq-1ypvA1y8IpKMH_-8JN3fkO6usBH_kPt7mRjI_BKdt7gY3XRHp88TohO3L-cWiG9pZ3wmGfl6TwZ2TrMA0jxc9V8PKM8YitewxXJxaxjmaYGcUphacfayljH9cjuzxCScdWmrxMNsfa0UafBquhqd8z0vHRtjB1ZcC36m6_eV4-uUoR-vNEbeJaYYVFwbGzjQ";
+const EXPONENT: &str = "AQAB";
+const DEVICE_CODE: &str = "OIDC_DC001";
+const REFRESH_TOKEN: &str = "oidc_dummy_refresh_token";
+
+/// Issuer is derived from the host so that the tests can choose the port
+fn issuer(headers: &HeaderMap) -> String {
+    let host = headers
+        .get(HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("localhost");
+    format!("http://{host}/oidc")
+}
+
+pub(super) async fn discovery(headers: HeaderMap) -> Json<ProviderMetadata> {
+    let issuer = issuer(&headers);
+    Json(ProviderMetadata {
+        jwks_uri: format!("{issuer}/jwks"),
+        token_endpoint: format!("{issuer}/token"),
+        device_authorization_endpoint: Some(format!("{issuer}/device/code")),
+        issuer,
+    })
+}
+
+pub(super) async fn jwks() -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "keys": [{
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": KID,
+            "n": MODULUS,
+            "e": EXPONENT,
+        }]
+    }))
+}
+
+pub(super) async fn device_authorization(
+    Form(DeviceAuthorizationRequest { scope, .. }): Form<DeviceAuthorizationRequest<'static>>,
+) -> Result<Json<DeviceAuthorizationResponse>, StatusCode> {
+    tracing::debug!(%scope, "Handle oidc device authorization request");
+
+    if !scope.split(' ').any(|scope| scope == "openid") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let res = DeviceAuthorizationResponse {
+        device_code: DEVICE_CODE.into(),
+        user_code: "UC654321".into(),
+        verification_uri: Some("https://syndicationd.ymgyt.io/test/oidc".parse().unwrap()),
+        verification_url: None,
+        verification_uri_complete: None,
+        expires_in: 3600,
+        interval: Some(1),
+    };
+
+    Ok(Json(res))
+}
+
+#[derive(Deserialize)]
+pub(super) struct TokenRequest {
+    grant_type: String,
+    client_id: String,
+    device_code: Option<String>,
+    refresh_token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    sub: &'a str,
+    aud: &'a str,
+    email: &'a str,
+    email_verified: bool,
+    iat: u64,
+    exp: u64,
+}
+
+pub(super) async fn token(
+    headers: HeaderMap,
+    Form(req): Form<TokenRequest>,
+) -> Result<Json<DeviceAccessTokenResponse>, StatusCode> {
+    tracing::debug!(grant_type = %req.grant_type, "Handle oidc token request");
+
+    let valid = match req.grant_type.as_str() {
+        "urn:ietf:params:oauth:grant-type:device_code" => {
+            req.device_code.as_deref() == Some(DEVICE_CODE)
+        }
+        "refresh_token" => req.refresh_token.as_deref() == Some(REFRESH_TOKEN),
+        _ => false,
+    };
+    if !valid {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if req.device_code.is_some() {
+        // mock user input duration
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let issuer = issuer(&headers);
+    let claims = Claims {
+        iss: &issuer,
+        sub: "synd-test-user",
+        aud: &req.client_id,
+        email: DUMMY_EMAIL,
+        email_verified: true,
+        iat: now,
+        exp: now + 3600,
+    };
+    let header = Header {
+        kid: Some(KID.into()),
+        ..Header::new(Algorithm::RS256)
+    };
+    let key = EncodingKey::from_rsa_pem(PRIVATE_KEY.as_bytes()).unwrap();
+    let id_token = jsonwebtoken::encode(&header, &claims, &key).unwrap();
+
+    Ok(Json(DeviceAccessTokenResponse {
+        access_token: "oidc_dummy_access_token".into(),
+        token_type: "Bearer".into(),
+        expires_in: Some(3600),
+        refresh_token: Some(REFRESH_TOKEN.into()),
+        id_token: Some(id_token),
+    }))
+}
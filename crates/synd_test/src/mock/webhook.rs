@@ -0,0 +1,89 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    Json,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Secret which webhooks delivering to the receiver should be registered with
+pub const WEBHOOK_SECRET: &str = "synd-test-webhook-secret";
+
+/// Receiver which fails the first attempt of each delivery
+const FLAKY_RECEIVER: &str = "flaky";
+
+/// Payloads received by each receiver
+#[derive(Clone, Default)]
+pub(super) struct Receivers {
+    payloads: Arc<Mutex<HashMap<String, Vec<serde_json::Value>>>>,
+    /// Delivery ids which were attempted
+    attempted: Arc<Mutex<HashSet<String>>>,
+}
+
+fn verify(headers: &HeaderMap, body: &[u8]) -> bool {
+    let Some(signature) = headers
+        .get("x-synd-signature-256")
+        .and_then(|signature| signature.to_str().ok())
+        .and_then(|signature| signature.strip_prefix("sha256="))
+        .and_then(|signature| hex::decode(signature).ok())
+    else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(WEBHOOK_SECRET.as_bytes()).unwrap();
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+pub(super) async fn receive(
+    State(receivers): State<Receivers>,
+    Path(receiver): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if !verify(&headers, &body) {
+        tracing::warn!(receiver, "Invalid webhook signature");
+        return StatusCode::UNAUTHORIZED;
+    }
+    let delivery_id = headers
+        .get("x-synd-delivery")
+        .and_then(|id| id.to_str().ok())
+        .unwrap_or_default()
+        .to_owned();
+    if receiver == FLAKY_RECEIVER && receivers.attempted.lock().unwrap().insert(delivery_id) {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    }
+    let Ok(payload) = serde_json::from_slice(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    tracing::debug!(receiver, "Webhook received");
+    receivers
+        .payloads
+        .lock()
+        .unwrap()
+        .entry(receiver)
+        .or_default()
+        .push(payload);
+    StatusCode::OK
+}
+
+/// Return payloads received by the receiver in the order of arrival
+pub(super) async fn received(
+    State(receivers): State<Receivers>,
+    Path(receiver): Path<String>,
+) -> Json<Vec<serde_json::Value>> {
+    let payloads = receivers
+        .payloads
+        .lock()
+        .unwrap()
+        .get(&receiver)
+        .cloned()
+        .unwrap_or_default();
+    Json(payloads)
+}